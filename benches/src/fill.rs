@@ -17,6 +17,40 @@ fn rect_tiny_skia(bencher: &mut Bencher) {
     });
 }
 
+#[bench]
+fn rect_opaque_tiny_skia(bencher: &mut Bencher) {
+    use tiny_skia::*;
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+    paint.anti_alias = false;
+
+    let rect = Rect::from_xywh(50.0, 20.0, 812.0, 777.0).unwrap();
+
+    let mut pixmap = Pixmap::new(1000, 1000).unwrap();
+
+    bencher.iter(|| {
+        pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+    });
+}
+
+#[bench]
+fn rect_opaque_fast_tiny_skia(bencher: &mut Bencher) {
+    use tiny_skia::*;
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+    paint.anti_alias = false;
+
+    let rect = Rect::from_xywh(50.0, 20.0, 812.0, 777.0).unwrap();
+
+    let mut pixmap = Pixmap::new(1000, 1000).unwrap();
+
+    bencher.iter(|| {
+        pixmap.fill_rect_fast(rect, &paint, Transform::identity(), None);
+    });
+}
+
 #[cfg(feature = "skia-rs")]
 #[bench]
 fn rect_skia(bencher: &mut Bencher) {
@@ -0,0 +1,345 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+// Based on "An Algorithm for Automatically Fitting Digitized Curves"
+// by Philip J. Schneider, Graphics Gems, 1990.
+//
+// Each input piece between corners is approximated by a single cubic
+// using least squares with chord-length parameterization.
+// When the result is not precise enough, parameters are refined via Newton-Raphson
+// and, if it still doesn't help, the piece is split at the point of the maximum error.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::scalar::Scalar;
+use crate::{Path, PathBuilder, Point};
+
+// Polyline turns sharper than this are preserved as corners. ~60 degrees.
+const CORNER_COS: f32 = 0.5;
+
+// How many times parameters are refined before splitting.
+const MAX_ITERATIONS: usize = 4;
+
+impl PathBuilder {
+    /// Creates a new `Path` by fitting cubic Béziers to a polyline.
+    ///
+    /// See [`PathBuilder::push_fit_cubic`] for details.
+    pub fn fit_cubic(points: &[Point], error: f32) -> Option<Path> {
+        let mut b = PathBuilder::new();
+        b.push_fit_cubic(points, error);
+        b.finish()
+    }
+
+    /// Adds an open contour of cubic Béziers approximating a polyline.
+    ///
+    /// Useful for converting dense and noisy points, like pen input or GPS tracks,
+    /// into a compact smooth path.
+    ///
+    /// `error` is the maximum allowed distance between input points and the curve,
+    /// in path units.
+    ///
+    /// Points where the polyline turns by more than 60 degrees are treated as corners
+    /// and are never smoothed over. Consecutive duplicated points are ignored.
+    ///
+    /// Does nothing when there are less than two distinct points
+    /// or when `error` is negative or not finite.
+    /// Two points produce a straight line.
+    pub fn push_fit_cubic(&mut self, points: &[Point], error: f32) {
+        if !(error.is_finite() && error >= 0.0) {
+            return;
+        }
+
+        if !points.iter().all(|p| p.is_finite()) {
+            return;
+        }
+
+        let mut points = points.to_vec();
+        points.dedup();
+        if points.len() < 2 {
+            return;
+        }
+
+        self.move_to(points[0].x, points[0].y);
+
+        let mut first = 0;
+        for i in 1..points.len() {
+            if i == points.len() - 1 || is_corner(&points, i) {
+                fit_piece(&points[first..=i], error, self);
+                first = i;
+            }
+        }
+    }
+}
+
+/// Simplifies a polyline using the Ramer-Douglas-Peucker algorithm.
+///
+/// Removes points that are within `tolerance` of the simplified polyline.
+/// The first and the last points are always preserved.
+///
+/// Returns a copy of the input when `tolerance` is zero, negative or not finite,
+/// or when any point is not finite.
+pub fn simplify_polyline(points: &[Point], tolerance: f32) -> Vec<Point> {
+    if points.len() < 3
+        || !(tolerance.is_finite() && tolerance > 0.0)
+        || !points.iter().all(|p| p.is_finite())
+    {
+        return points.to_vec();
+    }
+
+    let tolerance = tolerance * tolerance;
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    // An explicit stack instead of recursion, which depth can be as big as the number of points.
+    let mut stack = Vec::new();
+    stack.push((0, points.len() - 1));
+    while let Some((first, last)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut index = first;
+        for i in first + 1..last {
+            let dist = distance_to_segment_sqd(points[i], points[first], points[last]);
+            if dist > max_dist {
+                max_dist = dist;
+                index = i;
+            }
+        }
+
+        if max_dist > tolerance {
+            keep[index] = true;
+            stack.push((index, last));
+            stack.push((first, index));
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(p, keep)| if keep { Some(*p) } else { None })
+        .collect()
+}
+
+// A segment and not a line, so closed polylines, where the first and the last points
+// are the same, are handled as well.
+fn distance_to_segment_sqd(p: Point, a: Point, b: Point) -> f32 {
+    let ab = b - a;
+    let len_sqd = ab.length_sqd();
+    if len_sqd == 0.0 {
+        return p.distance_to_sqd(a);
+    }
+
+    let t = ((p - a).dot(ab) / len_sqd).bound(0.0, 1.0);
+    p.distance_to_sqd(a + ab.scaled(t))
+}
+
+fn is_corner(points: &[Point], i: usize) -> bool {
+    let mut v1 = points[i] - points[i - 1];
+    let mut v2 = points[i + 1] - points[i];
+    v1.normalize();
+    v2.normalize();
+    v1.dot(v2) < CORNER_COS
+}
+
+// Fits a piece of a polyline without corners. The first point must be already added.
+fn fit_piece(points: &[Point], error: f32, pb: &mut PathBuilder) {
+    let last = points.len() - 1;
+    if last == 1 {
+        pb.line_to(points[1].x, points[1].y);
+        return;
+    }
+
+    // Tangents point inside the piece.
+    let tangent1 = unit_vector(points[1] - points[0]);
+    let tangent2 = unit_vector(points[last - 1] - points[last]);
+
+    // The error is checked using a squared distance.
+    let error = error * error;
+
+    // Recursion depth can be as big as the number of points, so use a stack.
+    // The right half is pushed first, so the left one will be emitted first.
+    let mut stack = Vec::new();
+    stack.push((0, last, tangent1, tangent2));
+    let mut u = Vec::new();
+    while let Some((first, last, tangent1, tangent2)) = stack.pop() {
+        let span = &points[first..=last];
+        if span.len() == 2 {
+            let dist = span[0].distance(span[1]) / 3.0;
+            let curve = [
+                span[0],
+                span[0] + tangent1.scaled(dist),
+                span[1] + tangent2.scaled(dist),
+                span[1],
+            ];
+            push_cubic(&curve, pb);
+            continue;
+        }
+
+        chord_length_parameterize(span, &mut u);
+        let mut curve = generate_bezier(span, &u, tangent1, tangent2);
+        let (mut max_error, mut split) = compute_max_error(span, &curve, &u);
+        if max_error > error && max_error < error * 4.0 {
+            // Close enough, try to improve parameters first.
+            for _ in 0..MAX_ITERATIONS {
+                reparameterize(span, &curve, &mut u);
+                curve = generate_bezier(span, &u, tangent1, tangent2);
+                let (e, s) = compute_max_error(span, &curve, &u);
+                max_error = e;
+                split = s;
+                if max_error <= error {
+                    break;
+                }
+            }
+        }
+
+        if max_error <= error {
+            push_cubic(&curve, pb);
+            continue;
+        }
+
+        // A tangent at the split point, pointing backwards.
+        let mut center = span[split - 1] - span[split + 1];
+        if !center.normalize() {
+            center = unit_vector(span[split - 1] - span[split]);
+        }
+
+        stack.push((first + split, last, -center, tangent2));
+        stack.push((first, first + split, tangent1, center));
+    }
+}
+
+fn push_cubic(curve: &[Point; 4], pb: &mut PathBuilder) {
+    pb.cubic_to(
+        curve[1].x, curve[1].y, curve[2].x, curve[2].y, curve[3].x, curve[3].y,
+    );
+}
+
+fn unit_vector(mut v: Point) -> Point {
+    v.normalize();
+    v
+}
+
+fn chord_length_parameterize(points: &[Point], u: &mut Vec<f32>) {
+    u.clear();
+    u.push(0.0);
+    let mut total = 0.0;
+    for pair in points.windows(2) {
+        total += pair[0].distance(pair[1]);
+        u.push(total);
+    }
+
+    // Points are distinct, so the total length is never zero.
+    for v in u.iter_mut() {
+        *v /= total;
+    }
+}
+
+// Finds control points lengths using least squares, while keeping tangent directions.
+fn generate_bezier(points: &[Point], u: &[f32], tangent1: Point, tangent2: Point) -> [Point; 4] {
+    let first = points[0];
+    let last = points[points.len() - 1];
+
+    let mut c = [[0.0f64; 2]; 2];
+    let mut x = [0.0f64; 2];
+    for (p, &t) in points.iter().zip(u) {
+        let (b0, b1, b2, b3) = bernstein(t);
+        let a1 = tangent1.scaled(b1);
+        let a2 = tangent2.scaled(b2);
+
+        c[0][0] += a1.dot(a1) as f64;
+        c[0][1] += a1.dot(a2) as f64;
+        c[1][1] += a2.dot(a2) as f64;
+
+        let tmp = *p - (first.scaled(b0 + b1) + last.scaled(b2 + b3));
+        x[0] += a1.dot(tmp) as f64;
+        x[1] += a2.dot(tmp) as f64;
+    }
+    c[1][0] = c[0][1];
+
+    let det_c0_c1 = c[0][0] * c[1][1] - c[1][0] * c[0][1];
+    let det_c0_x = c[0][0] * x[1] - c[1][0] * x[0];
+    let det_x_c1 = x[0] * c[1][1] - x[1] * c[0][1];
+    let (alpha1, alpha2) = if det_c0_c1 != 0.0 {
+        ((det_x_c1 / det_c0_c1) as f32, (det_c0_x / det_c0_c1) as f32)
+    } else {
+        (0.0, 0.0)
+    };
+
+    // Fallback to a heuristic when the solution is degenerate or goes backwards.
+    let seg_len = first.distance(last);
+    let epsilon = 1.0e-6 * seg_len;
+    let is_valid = |alpha: f32| alpha >= epsilon && alpha.is_finite();
+    let (alpha1, alpha2) = if is_valid(alpha1) && is_valid(alpha2) {
+        (alpha1, alpha2)
+    } else {
+        (seg_len / 3.0, seg_len / 3.0)
+    };
+
+    [
+        first,
+        first + tangent1.scaled(alpha1),
+        last + tangent2.scaled(alpha2),
+        last,
+    ]
+}
+
+// Refines parameters via a single Newton-Raphson iteration.
+fn reparameterize(points: &[Point], curve: &[Point; 4], u: &mut [f32]) {
+    let d1 = [
+        (curve[1] - curve[0]).scaled(3.0),
+        (curve[2] - curve[1]).scaled(3.0),
+        (curve[3] - curve[2]).scaled(3.0),
+    ];
+    let d2 = [(d1[1] - d1[0]).scaled(2.0), (d1[2] - d1[1]).scaled(2.0)];
+
+    for (p, t) in points.iter().zip(u.iter_mut()) {
+        let q = eval_cubic(curve, *t);
+        let q1 = eval_quad(&d1, *t);
+        let q2 = d2[0].scaled(1.0 - *t) + d2[1].scaled(*t);
+
+        let diff = q - *p;
+        let numerator = diff.dot(q1);
+        let denominator = q1.dot(q1) + diff.dot(q2);
+        if denominator != 0.0 {
+            let new_t = *t - numerator / denominator;
+            if new_t.is_finite() {
+                *t = new_t.bound(0.0, 1.0);
+            }
+        }
+    }
+}
+
+// Returns the maximum squared distance and the index of the farthest point.
+fn compute_max_error(points: &[Point], curve: &[Point; 4], u: &[f32]) -> (f32, usize) {
+    let mut max_dist = 0.0;
+    let mut split = points.len() / 2;
+    for i in 1..points.len() - 1 {
+        let dist = eval_cubic(curve, u[i]).distance_to_sqd(points[i]);
+        if dist >= max_dist {
+            max_dist = dist;
+            split = i;
+        }
+    }
+
+    (max_dist, split)
+}
+
+fn bernstein(t: f32) -> (f32, f32, f32, f32) {
+    let mt = 1.0 - t;
+    (mt * mt * mt, 3.0 * t * mt * mt, 3.0 * t * t * mt, t * t * t)
+}
+
+fn eval_cubic(curve: &[Point; 4], t: f32) -> Point {
+    let (b0, b1, b2, b3) = bernstein(t);
+    curve[0].scaled(b0) + curve[1].scaled(b1) + curve[2].scaled(b2) + curve[3].scaled(b3)
+}
+
+fn eval_quad(curve: &[Point; 3], t: f32) -> Point {
+    let mt = 1.0 - t;
+    curve[0].scaled(mt * mt) + curve[1].scaled(2.0 * t * mt) + curve[2].scaled(t * t)
+}
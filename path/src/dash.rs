@@ -10,7 +10,7 @@ use alloc::vec::Vec;
 
 use arrayref::array_ref;
 
-use crate::{Path, Point};
+use crate::{Path, Point, Transform};
 
 use crate::floating_point::{FiniteF32, NonZeroPositiveF32, NormalizedF32, NormalizedF32Exclusive};
 use crate::path::{PathSegment, PathSegmentsIter, PathVerb};
@@ -36,8 +36,8 @@ use crate::NoStdFloat;
 /// - Dash offset is finite.
 #[derive(Clone, PartialEq, Debug)]
 pub struct StrokeDash {
-    array: Vec<f32>,
-    offset: f32,
+    pub(crate) array: Vec<f32>,
+    pub(crate) offset: f32,
     interval_len: NonZeroPositiveF32,
     first_len: f32, // TODO: PositiveF32
     first_index: usize,
@@ -75,6 +75,20 @@ impl StrokeDash {
             first_index,
         })
     }
+
+    /// Converts a path into a dashed one.
+    ///
+    /// Each "on" interval becomes a separate open contour that follows the original
+    /// curves exactly. The result can be stroked, filled or measured as any other path.
+    ///
+    /// The same as [`Path::dash`] with a resolution scale of 1,
+    /// which is what a stroke with this dash would use without a transform.
+    ///
+    /// Returns `None` when more than 1_000_000 dashes had to be produced
+    /// or when the final path has an invalid bounding box.
+    pub fn apply(&self, src: &Path) -> Option<Path> {
+        dash_impl(src, self, 1.0)
+    }
 }
 
 #[cfg(test)]
@@ -160,14 +174,77 @@ impl Path {
     /// `resolution_scale` can be obtained via
     /// [`compute_resolution_scale`](crate::PathStroker::compute_resolution_scale).
     ///
-    /// Returns `None` when more than 1_000_000 dashes had to be produced
+    /// Returns `None` when `resolution_scale` is not finite or not positive,
+    /// when more than 1_000_000 dashes had to be produced
     /// or when the final path has an invalid bounding box.
     pub fn dash(&self, dash: &StrokeDash, resolution_scale: f32) -> Option<Path> {
         dash_impl(self, dash, resolution_scale)
     }
+
+    /// Returns a part of the path between two normalized distances.
+    ///
+    /// `start` and `end` are fractions of the total length of all contours
+    /// and will be clamped to a 0..=1 range.
+    /// `offset` shifts the trimmed window cyclically, also as a fraction.
+    ///
+    /// When `start` is bigger than `end`, the window wraps around the end of the path.
+    /// A window that wraps will be split into two pieces, unless the path consists of
+    /// a single closed contour, in which case it will continue across the close.
+    ///
+    /// Trimmed contours are never closed. A full window returns the original path.
+    ///
+    /// Returns `None` for an empty window or NaN values.
+    pub fn trim(&self, start: f32, end: f32, offset: f32) -> Option<Path> {
+        trim_impl(self, start, end, offset)
+    }
+}
+
+fn trim_impl(src: &Path, start: f32, end: f32, offset: f32) -> Option<Path> {
+    if !(start.is_finite() && end.is_finite() && offset.is_finite()) {
+        return None;
+    }
+
+    let start = start.bound(0.0, 1.0);
+    let end = end.bound(0.0, 1.0);
+    let window = if start <= end {
+        end - start
+    } else {
+        end - start + 1.0
+    };
+
+    if window >= 1.0 {
+        return Some(src.clone());
+    }
+
+    if window <= 0.0 {
+        return None;
+    }
+
+    let measure = PathMeasure::new(src, 1.0);
+    let length = measure.length;
+
+    let mut start = start + offset;
+    start -= start.floor();
+    let start_d = start * length;
+    let stop_d = start_d + window * length;
+
+    let mut pb = PathBuilder::new();
+    if stop_d <= length {
+        measure.push_segment(start_d, stop_d, true, &mut pb);
+    } else {
+        measure.push_segment(start_d, length, true, &mut pb);
+        let continue_contour = measure.contours.len() == 1 && measure.contours[0].is_closed;
+        measure.push_segment(0.0, stop_d - length, !continue_contour, &mut pb);
+    }
+
+    pb.finish()
 }
 
 fn dash_impl(src: &Path, dash: &StrokeDash, res_scale: f32) -> Option<Path> {
+    if !(res_scale.is_finite() && res_scale > 0.0) {
+        return None;
+    }
+
     // We do not support the `cull_path` branch here.
     // Skia has a lot of code for cases when a path contains only a single zero-length line
     // or when a path is a rect. Not sure why.
@@ -188,6 +265,18 @@ fn dash_impl(src: &Path, dash: &StrokeDash, res_scale: f32) -> Option<Path> {
         let length = contour.length;
         let mut index = dash.first_index;
 
+        // Just like in SVG, a zero-length subpath is stroked as a dot by round and square caps,
+        // but only when the dash pattern starts with a dash.
+        if contour.is_zero_length {
+            if is_even(index) {
+                let p = contour.points[0];
+                pb.move_to(p.x, p.y);
+                pb.line_to(p.x, p.y);
+            }
+
+            continue;
+        }
+
         // Since the path length / dash length ratio may be arbitrarily large, we can exert
         // significant memory pressure while attempting to build the filtered path. To avoid this,
         // we simply give up dashing beyond a certain threshold.
@@ -240,6 +329,112 @@ fn dash_impl(src: &Path, dash: &StrokeDash, res_scale: f32) -> Option<Path> {
     pb.finish()
 }
 
+/// A path measuring utility.
+///
+/// All contours are treated as a single curve,
+/// so distances are measured from the start of the first contour.
+/// Zero-length contours are ignored.
+#[derive(Debug)]
+pub struct PathMeasure {
+    contours: Vec<ContourMeasure>,
+    length: f32,
+}
+
+impl PathMeasure {
+    /// Measures a path.
+    ///
+    /// `res_scale` has the same meaning as in [`Path::dash`].
+    /// A non-finite or non-positive `res_scale` is treated as 1.
+    pub fn new(path: &Path, mut res_scale: f32) -> Self {
+        if !(res_scale.is_finite() && res_scale > 0.0) {
+            res_scale = 1.0;
+        }
+
+        let contours: Vec<_> = ContourMeasureIter::new(path, res_scale)
+            .filter(|c| !c.segments.is_empty())
+            .collect();
+        let length = contours.iter().map(|c| c.length).sum();
+        PathMeasure { contours, length }
+    }
+
+    /// Returns the total length of all contours.
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// Returns a position and a normalized tangent at the specified distance.
+    ///
+    /// Returns `None` when `distance` is negative, NaN or past the end of the path.
+    pub fn pos_tan(&self, mut distance: f32) -> Option<(Point, Point)> {
+        if !(distance >= 0.0 && distance <= self.length) {
+            return None;
+        }
+
+        for (i, contour) in self.contours.iter().enumerate() {
+            // Accumulated rounding errors can make the distance slightly bigger
+            // than the last contour's length.
+            if distance <= contour.length || i + 1 == self.contours.len() {
+                return contour.pos_tan(distance.min(contour.length));
+            }
+
+            distance -= contour.length;
+        }
+
+        None
+    }
+
+    /// Returns a transform that places an object along the path.
+    ///
+    /// Object's origin will be moved to the point at `distance`
+    /// and its X axis will be aligned with the path's tangent.
+    /// Which is what text on a path layout requires.
+    ///
+    /// `offset` moves the object along the normal. Positive values move it to the left
+    /// of the path direction, which is up for a left-to-right path,
+    /// since Y axis points down.
+    ///
+    /// Returns `None` when `distance` is negative, NaN or past the end of the path.
+    pub fn matrix_at(&self, distance: f32, offset: f32) -> Option<Transform> {
+        let (pos, tan) = self.pos_tan(distance)?;
+        Some(Transform::from_row(
+            tan.x,
+            tan.y,
+            -tan.y,
+            tan.x,
+            pos.x + tan.y * offset,
+            pos.y - tan.x * offset,
+        ))
+    }
+
+    // Appends a part of the path between two distances.
+    // Each contour within the range starts with a MoveTo.
+    fn push_segment(
+        &self,
+        start_d: f32,
+        stop_d: f32,
+        mut start_with_move_to: bool,
+        pb: &mut PathBuilder,
+    ) {
+        let mut contour_start_d = 0.0;
+        for contour in &self.contours {
+            let contour_stop_d = contour_start_d + contour.length;
+            let from = start_d.max(contour_start_d);
+            let to = stop_d.min(contour_stop_d);
+            if from < to {
+                contour.push_segment(
+                    from - contour_start_d,
+                    to - contour_start_d,
+                    start_with_move_to,
+                    pb,
+                );
+                start_with_move_to = true;
+            }
+
+            contour_start_d = contour_stop_d;
+        }
+    }
+}
+
 const MAX_T_VALUE: u32 = 0x3FFFFFFF;
 
 struct ContourMeasureIter<'a> {
@@ -278,8 +473,13 @@ impl Iterator for ContourMeasureIter<'_> {
         let mut point_index = 0;
         let mut distance = 0.0;
         let mut have_seen_close = false;
+        let mut have_seen_segment = false;
         let mut prev_p = Point::zero();
         while let Some(seg) = self.iter.next() {
+            if !matches!(seg, PathSegment::MoveTo(_)) {
+                have_seen_segment = true;
+            }
+
             match seg {
                 PathSegment::MoveTo(p0) => {
                     contour.points.push(p0);
@@ -372,6 +572,7 @@ impl Iterator for ContourMeasureIter<'_> {
 
         contour.length = distance;
         contour.is_closed = have_seen_close;
+        contour.is_zero_length = have_seen_segment && contour.segments.is_empty();
 
         if contour.points.is_empty() {
             None
@@ -411,6 +612,8 @@ struct ContourMeasure {
     points: Vec<Point>,
     length: f32,
     is_closed: bool,
+    // A contour with segments, but without length. Like a move to followed by a close.
+    is_zero_length: bool,
 }
 
 impl ContourMeasure {
@@ -508,6 +711,21 @@ impl ContourMeasure {
         }
     }
 
+    fn pos_tan(&self, distance: f32) -> Option<(Point, Point)> {
+        let (seg_index, t) = self.distance_to_segment(distance)?;
+        let seg = self.segments[seg_index];
+        let mut pos = Point::zero();
+        let mut tangent = Point::zero();
+        compute_pos_tan(
+            &self.points[seg.point_index..],
+            seg.kind,
+            t,
+            Some(&mut pos),
+            Some(&mut tangent),
+        );
+        Some((pos, tangent))
+    }
+
     fn distance_to_segment(&self, distance: f32) -> Option<(usize, NormalizedF32)> {
         debug_assert!(distance >= 0.0 && distance <= self.length);
 
@@ -0,0 +1,59 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+/// An error returned by `try_*` constructors.
+///
+/// Each variant describes a single failure reason,
+/// so it can be reported to a user as is.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TinySkiaError {
+    /// Width or height is zero.
+    ZeroSize,
+    /// Size is too large to be allocated or addressed.
+    SizeOverflow,
+    /// An input value is NaN or infinite, or a computed one overflowed.
+    NonFinite,
+    /// A transform cannot be inverted. For example, it has a zero scale
+    /// or source points are collinear.
+    NotInvertible,
+    /// A gradient has less than two stops.
+    TooFewGradientStops,
+    /// A gradient stop position is NaN.
+    InvalidGradientStopPosition,
+    /// A gradient radius is zero or negative.
+    InvalidGradientRadius,
+    /// A path has no segments.
+    EmptyPath,
+    /// A path has more points or segments than allowed.
+    TooComplex,
+}
+
+impl core::fmt::Display for TinySkiaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TinySkiaError::ZeroSize => write!(f, "width or height is zero"),
+            TinySkiaError::SizeOverflow => write!(f, "size is too large"),
+            TinySkiaError::NonFinite => write!(f, "a value is not finite"),
+            TinySkiaError::NotInvertible => write!(f, "a transform is not invertible"),
+            TinySkiaError::TooFewGradientStops => {
+                write!(f, "a gradient must have at least two stops")
+            }
+            TinySkiaError::InvalidGradientStopPosition => {
+                write!(f, "gradient stop positions must not be NaN")
+            }
+            TinySkiaError::InvalidGradientRadius => {
+                write!(f, "a gradient radius must be positive")
+            }
+            TinySkiaError::EmptyPath => write!(f, "a path is empty"),
+            TinySkiaError::TooComplex => write!(f, "a path is too complex"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TinySkiaError {}
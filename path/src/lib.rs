@@ -35,20 +35,40 @@ extern crate std;
 
 extern crate alloc;
 
+// A `debug_assert!` for cheap numeric invariants checked per pixel or per point.
+//
+// Can be disabled via the `no-hot-asserts` feature to keep debug builds fast.
+// Structural and bounds checks must use the regular `debug_assert!`.
+macro_rules! hot_debug_assert {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "no-hot-asserts"))]
+        debug_assert!($($arg)*);
+    };
+}
+
+mod curve_fit;
 mod dash;
+mod error;
 mod f32x2_t;
 mod f32x4_t;
 mod floating_point;
 mod path;
 mod path_builder;
 pub mod path_geometry;
+mod path_ops;
+mod pdf;
 mod rect;
 mod scalar;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod size;
 mod stroker;
+mod svg;
 mod transform;
 
-pub use dash::StrokeDash;
+pub use curve_fit::simplify_polyline;
+pub use dash::{PathMeasure, StrokeDash};
+pub use error::TinySkiaError;
 pub use f32x2_t::f32x2;
 pub use floating_point::*;
 pub use path::*;
@@ -68,6 +88,7 @@ type LengthU32 = core::num::NonZeroU32;
 #[allow(missing_docs)]
 #[repr(C)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -127,8 +148,8 @@ impl Point {
     /// Scales (fX, fY) so that length() returns one, while preserving ratio of fX to fY,
     /// if possible.
     ///
-    /// If prior length is nearly zero, sets vector to (0, 0) and returns
-    /// false; otherwise returns true.
+    /// If prior length is nearly zero or not finite, sets vector to (0, 0) and returns
+    /// false; otherwise returns true. Therefore it never produces NaN.
     pub fn normalize(&mut self) -> bool {
         self.set_length_from(self.x, self.y, 1.0)
     }
@@ -174,11 +195,49 @@ impl Point {
         set_point_length(self, x, y, length, &mut None)
     }
 
-    /// Returns the Euclidean distance from origin.
+    /// Returns the Euclidean distance between two points.
     pub fn distance(&self, other: Point) -> f32 {
         (*self - other).length()
     }
 
+    /// Returns the Euclidean distance between two points.
+    ///
+    /// The same as [`distance`](Point::distance).
+    pub fn distance_to(&self, other: Point) -> f32 {
+        self.distance(other)
+    }
+
+    /// Returns a linear interpolation between two points.
+    ///
+    /// `t` is not clamped, so values outside the 0..=1 range will extrapolate.
+    pub fn lerp(&self, other: Point, t: f32) -> Point {
+        Point::from_xy(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    /// Rotates vector in-place around the origin.
+    ///
+    /// `angle` in degrees. Just like in `Transform::from_rotate`,
+    /// a positive angle rotates clockwise in a Y-down coordinate system.
+    pub fn rotate(&mut self, angle: f32) {
+        let v = angle.to_radians();
+        let (sin, cos) = (v.sin(), v.cos());
+        let x = self.x * cos - self.y * sin;
+        let y = self.x * sin + self.y * cos;
+        self.x = x;
+        self.y = y;
+    }
+
+    /// Returns a signed angle between two vectors in degrees.
+    ///
+    /// The result is in a -180..=180 range and has the same direction as in `rotate`.
+    /// Returns 0 when any of the vectors is zero.
+    pub fn angle_to(&self, other: Point) -> f32 {
+        self.cross(other).atan2(self.dot(other)).to_degrees()
+    }
+
     /// Returns the dot product of two points.
     pub fn dot(&self, other: Point) -> f32 {
         self.x * other.x + self.y * other.y
@@ -326,3 +385,69 @@ impl core::ops::MulAssign for Point {
         self.y *= other.y;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_zero() {
+        let mut p = Point::zero();
+        assert!(!p.normalize());
+        assert_eq!(p, Point::zero());
+
+        let mut p = Point::from_xy(f32::NAN, 1.0);
+        assert!(!p.normalize());
+        assert_eq!(p, Point::zero());
+
+        let mut p = Point::from_xy(3.0, 4.0);
+        assert!(p.normalize());
+        assert_eq!(p, Point::from_xy(0.6, 0.8));
+    }
+
+    #[test]
+    fn distance_to() {
+        let p1 = Point::from_xy(1.0, 2.0);
+        let p2 = Point::from_xy(4.0, 6.0);
+        assert_eq!(p1.distance_to(p2), 5.0);
+        assert_eq!(p2.distance_to(p1), 5.0);
+    }
+
+    #[test]
+    fn lerp() {
+        let p1 = Point::from_xy(0.0, 10.0);
+        let p2 = Point::from_xy(10.0, 20.0);
+        assert_eq!(p1.lerp(p2, 0.0), p1);
+        assert_eq!(p1.lerp(p2, 1.0), p2);
+        assert_eq!(p1.lerp(p2, 0.5), Point::from_xy(5.0, 15.0));
+        assert_eq!(p1.lerp(p2, 2.0), Point::from_xy(20.0, 30.0));
+    }
+
+    #[test]
+    fn rotate() {
+        let mut p = Point::from_xy(10.0, 0.0);
+        p.rotate(90.0);
+        assert!(p.equals_within_tolerance(Point::from_xy(0.0, 10.0), 0.0001));
+
+        // Must match `Transform::from_rotate`.
+        let mut p1 = Point::from_xy(3.0, 7.0);
+        let mut p2 = p1;
+        p1.rotate(33.0);
+        Transform::from_rotate(33.0).map_point(&mut p2);
+        assert!(p1.equals_within_tolerance(p2, 0.0001));
+    }
+
+    #[test]
+    fn angle_to() {
+        let p1 = Point::from_xy(1.0, 0.0);
+        assert_eq!(p1.angle_to(Point::from_xy(0.0, 1.0)), 90.0);
+        assert_eq!(p1.angle_to(Point::from_xy(0.0, -1.0)), -90.0);
+        assert_eq!(p1.angle_to(Point::from_xy(-1.0, 0.0)), 180.0);
+        assert_eq!(p1.angle_to(Point::zero()), 0.0);
+
+        let mut p2 = Point::from_xy(5.0, 2.0);
+        let p3 = p2;
+        p2.rotate(45.0);
+        assert!((p3.angle_to(p2) - 45.0).abs() < 0.001);
+    }
+}
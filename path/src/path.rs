@@ -6,13 +6,16 @@
 
 use alloc::vec::Vec;
 
-use crate::path_builder::PathBuilder;
+use crate::path_builder::{PathBuilder, PathDirection};
 use crate::transform::Transform;
 use crate::{Point, Rect};
 
+use crate::scalar::Scalar;
+
 /// A path verb.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PathVerb {
     Move,
     Line,
@@ -21,6 +24,22 @@ pub enum PathVerb {
     Close,
 }
 
+/// A path filling rule.
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FillRule {
+    /// Specifies that "inside" is computed by a non-zero sum of signed edge crossings.
+    Winding,
+    /// Specifies that "inside" is computed by an odd number of edge crossings.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    fn default() -> Self {
+        FillRule::Winding
+    }
+}
+
 /// A Bezier path.
 ///
 /// Can be created via [`PathBuilder`].
@@ -47,6 +66,7 @@ pub struct Path {
     pub(crate) verbs: Vec<PathVerb>,
     pub(crate) points: Vec<Point>,
     pub(crate) bounds: Rect,
+    pub(crate) fill_rule: Option<FillRule>,
 }
 
 impl Path {
@@ -62,6 +82,29 @@ impl Path {
         self.bounds
     }
 
+    /// Returns the fill rule stored with the path.
+    ///
+    /// `None` by default.
+    pub fn fill_rule(&self) -> Option<FillRule> {
+        self.fill_rule
+    }
+
+    /// Stores a fill rule with the path.
+    ///
+    /// Allows keeping the intended fill rule together with the path data.
+    /// It's preserved by [`transform`](Path::transform) and serialization.
+    ///
+    /// Filling methods still take a fill rule explicitly, which always takes precedence.
+    /// To fill using the stored rule, pass [`fill_rule_or`](Path::fill_rule_or) instead.
+    pub fn set_fill_rule(&mut self, fill_rule: Option<FillRule>) {
+        self.fill_rule = fill_rule;
+    }
+
+    /// Returns the fill rule stored with the path or `default` when it's not set.
+    pub fn fill_rule_or(&self, default: FillRule) -> FillRule {
+        self.fill_rule.unwrap_or(default)
+    }
+
     /// Returns an internal vector of verbs.
     pub fn verbs(&self) -> &[PathVerb] {
         &self.verbs
@@ -100,6 +143,374 @@ impl Path {
         }
     }
 
+    /// Checks that the path is convex.
+    ///
+    /// The check is performed on the path's control polygon, which makes it conservative:
+    /// curves with a concave control polygon are treated as concave,
+    /// even if the curve itself is convex.
+    ///
+    /// Paths with multiple contours are never convex.
+    /// Paths with all points on a single line are convex.
+    pub fn is_convex(&self) -> bool {
+        // A trailing MoveTo doesn't form a contour.
+        let contours = self.verbs[..self.verbs.len() - 1]
+            .iter()
+            .filter(|v| **v == PathVerb::Move)
+            .count();
+        if contours > 1 {
+            return false;
+        }
+
+        let mut points: Vec<Point> = Vec::with_capacity(self.points.len());
+        for p in &self.points {
+            if points.last() != Some(p) {
+                points.push(*p);
+            }
+        }
+
+        // Contours are always closed for filling.
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        if points.len() < 3 {
+            return true;
+        }
+
+        let len = points.len();
+        let mut cross_sign = 0.0;
+        let mut dx_sign = 0.0;
+        let mut dy_sign = 0.0;
+        let mut dx_flips = 0;
+        let mut dy_flips = 0;
+        // Iterate over all edges, including the closing one.
+        for i in 0..len {
+            let p0 = points[i];
+            let p1 = points[(i + 1) % len];
+            let p2 = points[(i + 2) % len];
+            let e0 = p1 - p0;
+            let e1 = p2 - p1;
+
+            // Ignore rounding errors on nearly collinear edges,
+            // like the ones at curve joins.
+            let cross = e0.cross(e1);
+            if !(cross / (e0.length() * e1.length())).is_nearly_zero() {
+                if cross_sign != 0.0 && cross.signum() != cross_sign {
+                    return false;
+                }
+
+                cross_sign = cross.signum();
+            } else if e0.dot(e1) < 0.0 {
+                // The contour goes backward.
+                return false;
+            }
+
+            // Count direction changes to reject self-intersecting contours,
+            // since they can have a consistent turn direction as well.
+            let (dx, dy) = direction_signs(e0);
+            if dx != 0.0 {
+                if dx_sign != 0.0 && dx != dx_sign {
+                    dx_flips += 1;
+                }
+                dx_sign = dx;
+            }
+
+            if dy != 0.0 {
+                if dy_sign != 0.0 && dy != dy_sign {
+                    dy_flips += 1;
+                }
+                dy_sign = dy;
+            }
+        }
+
+        // Account for the wrap around between the last and the first edge.
+        let (dx, dy) = direction_signs(points[1] - points[0]);
+        if dx != 0.0 && dx != dx_sign {
+            dx_flips += 1;
+        }
+        if dy != 0.0 && dy != dy_sign {
+            dy_flips += 1;
+        }
+
+        // A convex polygon changes each direction exactly twice.
+        dx_flips <= 2 && dy_flips <= 2
+    }
+
+    /// Returns a `Rect` when the path is exactly an axis-aligned rectangle.
+    ///
+    /// The path must consist of a single contour with a MoveTo and three or four LineTo,
+    /// with an optional Close. Which is also the form [`PathBuilder::push_rect`] produces.
+    ///
+    /// Coordinates are compared exactly.
+    /// Use [`as_rect_within_tolerance`](Path::as_rect_within_tolerance)
+    /// to allow for floating point errors.
+    pub fn as_rect(&self) -> Option<Rect> {
+        self.as_rect_within_tolerance(0.0)
+    }
+
+    /// Returns a `Rect` when the path is an axis-aligned rectangle within the specified tolerance.
+    ///
+    /// Two coordinates are treated as equal when the difference between them
+    /// is less than or equal to `tolerance`.
+    ///
+    /// The returned `Rect` is the path's bounds.
+    ///
+    /// See [`as_rect`](Path::as_rect) for details.
+    pub fn as_rect_within_tolerance(&self, tolerance: f32) -> Option<Rect> {
+        let verbs = match self.verbs.last() {
+            Some(PathVerb::Close) => &self.verbs[..self.verbs.len() - 1],
+            _ => &self.verbs[..],
+        };
+
+        let lines = match verbs.split_first() {
+            Some((PathVerb::Move, lines)) => lines,
+            _ => return None,
+        };
+
+        if !(lines.len() == 3 || lines.len() == 4) || lines.iter().any(|v| *v != PathVerb::Line) {
+            return None;
+        }
+
+        let eq = |a: f32, b: f32| (a - b).abs() <= tolerance;
+
+        let p = &self.points;
+        if p.len() == 5 && !(eq(p[4].x, p[0].x) && eq(p[4].y, p[0].y)) {
+            return None;
+        }
+
+        // Edges must alternate between horizontal and vertical,
+        // and the opposite edges must have opposite directions.
+        let edges = [p[1] - p[0], p[2] - p[1], p[3] - p[2], p[0] - p[3]];
+        let is_horizontal = |e: Point| eq(e.y, 0.0) && !eq(e.x, 0.0);
+        let is_vertical = |e: Point| eq(e.x, 0.0) && !eq(e.y, 0.0);
+
+        let axis_aligned = if is_horizontal(edges[0]) {
+            is_vertical(edges[1]) && is_horizontal(edges[2]) && is_vertical(edges[3])
+        } else {
+            is_vertical(edges[0])
+                && is_horizontal(edges[1])
+                && is_vertical(edges[2])
+                && is_horizontal(edges[3])
+        };
+
+        if !axis_aligned {
+            return None;
+        }
+
+        if edges[0].dot(edges[2]) >= 0.0 || edges[1].dot(edges[3]) >= 0.0 {
+            return None;
+        }
+
+        Some(self.bounds)
+    }
+
+    /// Returns path's signed area.
+    ///
+    /// Calculated using the shoelace formula over contours flattened with a 0.25 tolerance.
+    /// All contours are implicitly closed.
+    ///
+    /// The area is positive for clockwise contours, in a coordinate system where Y axis
+    /// points down, and negative for counterclockwise ones.
+    /// Therefore, holes with an opposite direction will be subtracted.
+    /// Self-intersecting and overlapping contours are not resolved.
+    pub fn area(&self) -> f32 {
+        let mut area = 0.0f64;
+        crate::path_ops::flatten_lines(self, |p0, p1| {
+            area += cross_f64(p0, p1);
+        });
+
+        (area * 0.5) as f32
+    }
+
+    /// Returns path's centroid, aka the center of mass.
+    ///
+    /// Uses the same contours as [`Path::area`], therefore holes with an opposite direction
+    /// will be subtracted.
+    ///
+    /// Returns `None` when the area is zero.
+    pub fn centroid(&self) -> Option<Point> {
+        let mut area = 0.0f64;
+        let mut x = 0.0f64;
+        let mut y = 0.0f64;
+        crate::path_ops::flatten_lines(self, |p0, p1| {
+            let cross = cross_f64(p0, p1);
+            area += cross;
+            x += (p0.x as f64 + p1.x as f64) * cross;
+            y += (p0.y as f64 + p1.y as f64) * cross;
+        });
+
+        if area == 0.0 || !area.is_finite() {
+            return None;
+        }
+
+        let p = Point::from_xy((x / (3.0 * area)) as f32, (y / (3.0 * area)) as f32);
+        if p.is_finite() {
+            Some(p)
+        } else {
+            None
+        }
+    }
+
+    /// Returns path's winding number at a point.
+    ///
+    /// Each contour crossing a horizontal ray from the point to the right adds 1
+    /// when it goes down, aka clockwise in a coordinate system where Y axis points down,
+    /// and subtracts 1 otherwise.
+    /// Contours are flattened with a 0.25 tolerance and implicitly closed.
+    ///
+    /// Uses the same half-open rules as the rasterizer: a point on a left or top edge
+    /// is inside, while a point on a right or bottom edge is outside.
+    /// Therefore, a non-zero winding number means that a pixel with a center at this point
+    /// would be filled using `FillRule::Winding`, up to the rasterizer's fixed-point precision.
+    pub fn winding_number(&self, p: Point) -> i32 {
+        if !(p.y >= self.bounds.top() && p.y < self.bounds.bottom()) {
+            return 0;
+        }
+
+        let mut winding = 0;
+        crate::path_ops::flatten_lines(self, |p0, p1| {
+            let (top, bottom, dir) = if p0.y <= p1.y {
+                (p0, p1, 1)
+            } else {
+                (p1, p0, -1)
+            };
+
+            // Horizontal lines are skipped as well.
+            if p.y < top.y || p.y >= bottom.y {
+                return;
+            }
+
+            let x = top.x + (p.y - top.y) / (bottom.y - top.y) * (bottom.x - top.x);
+            if x > p.x {
+                winding += dir;
+            }
+        });
+
+        winding
+    }
+
+    /// Checks that a point is inside the path using the specified fill rule.
+    ///
+    /// See [`Path::winding_number`] for details.
+    pub fn contains(&self, p: Point, fill_rule: FillRule) -> bool {
+        let winding = self.winding_number(p);
+        match fill_rule {
+            FillRule::Winding => winding != 0,
+            FillRule::EvenOdd => winding % 2 != 0,
+        }
+    }
+
+    /// Returns the direction of each contour.
+    ///
+    /// The direction is determined by the sign of the contour's control polygon area,
+    /// in a coordinate system where Y axis points down.
+    /// Contours with a zero area, like lines or a lone MoveTo, are reported as clockwise.
+    pub fn contour_directions(&self) -> Vec<PathDirection> {
+        self.contours()
+            .map(|c| contour_direction(&self.points[c.points.clone()]))
+            .collect()
+    }
+
+    /// Returns a copy of the path with contours reoriented.
+    ///
+    /// Outer contours will have the `outer` direction and holes will have the opposite one.
+    /// A contour is treated as a hole when its first point is inside an odd number
+    /// of other contours' control polygons.
+    ///
+    /// Contours with a zero area are preserved as is.
+    pub fn oriented(&self, outer: PathDirection) -> Path {
+        let contours: Vec<Contour> = self.contours().collect();
+
+        let mut verbs = Vec::with_capacity(self.verbs.len());
+        let mut points = Vec::with_capacity(self.points.len());
+        for (i, c) in contours.iter().enumerate() {
+            let c_points = &self.points[c.points.clone()];
+            let area = signed_area(c_points);
+            if area == 0.0 {
+                verbs.extend_from_slice(&self.verbs[c.verbs.clone()]);
+                points.extend_from_slice(c_points);
+                continue;
+            }
+
+            let depth = contours
+                .iter()
+                .enumerate()
+                .filter(|(j, other)| {
+                    *j != i && polygon_contains(&self.points[other.points.clone()], c_points[0])
+                })
+                .count();
+
+            let expected = if depth % 2 == 0 {
+                outer
+            } else {
+                outer.opposite()
+            };
+
+            if contour_direction(c_points) == expected {
+                verbs.extend_from_slice(&self.verbs[c.verbs.clone()]);
+                points.extend_from_slice(c_points);
+                continue;
+            }
+
+            // Reversing all points reverses segments as well,
+            // since each segment ends at the point the next one starts from.
+            let c_verbs = &self.verbs[c.verbs.clone()];
+            let closed = c_verbs.last() == Some(&PathVerb::Close);
+            let segments = if closed {
+                &c_verbs[1..c_verbs.len() - 1]
+            } else {
+                &c_verbs[1..]
+            };
+
+            verbs.push(PathVerb::Move);
+            verbs.extend(segments.iter().rev());
+            if closed {
+                verbs.push(PathVerb::Close);
+            }
+
+            points.extend(c_points.iter().rev());
+        }
+
+        Path {
+            verbs,
+            points,
+            bounds: self.bounds,
+            fill_rule: self.fill_rule,
+        }
+    }
+
+    fn contours(&self) -> impl Iterator<Item = Contour> + '_ {
+        let mut verb_idx = 0;
+        let mut point_idx = 0;
+        core::iter::from_fn(move || {
+            if verb_idx == self.verbs.len() {
+                return None;
+            }
+
+            let verbs_start = verb_idx;
+            let points_start = point_idx;
+            loop {
+                match self.verbs[verb_idx] {
+                    PathVerb::Move if verb_idx != verbs_start => break,
+                    PathVerb::Move | PathVerb::Line => point_idx += 1,
+                    PathVerb::Quad => point_idx += 2,
+                    PathVerb::Cubic => point_idx += 3,
+                    PathVerb::Close => {}
+                }
+
+                verb_idx += 1;
+                if verb_idx == self.verbs.len() {
+                    break;
+                }
+            }
+
+            Some(Contour {
+                verbs: verbs_start..verb_idx,
+                points: points_start..point_idx,
+            })
+        })
+    }
+
     /// Clears the path and returns a `PathBuilder` that will reuse an allocated memory.
     pub fn clear(mut self) -> PathBuilder {
         self.verbs.clear();
@@ -110,8 +521,72 @@ impl Path {
             points: self.points,
             last_move_to_index: 0,
             move_to_required: true,
+            bounds: None,
+            bounds_is_dirty: false,
+            tessellation_transform: Transform::identity(),
+            max_points: None,
+        }
+    }
+}
+
+struct Contour {
+    verbs: core::ops::Range<usize>,
+    points: core::ops::Range<usize>,
+}
+
+// Returns a doubled signed area of a polygon.
+// Positive when the polygon is clockwise in a Y-down coordinate system.
+fn signed_area(points: &[Point]) -> f32 {
+    let mut area = 0.0;
+    for (i, p0) in points.iter().enumerate() {
+        let p1 = points[(i + 1) % points.len()];
+        area += p0.cross(p1);
+    }
+
+    area
+}
+
+fn cross_f64(p0: Point, p1: Point) -> f64 {
+    p0.x as f64 * p1.y as f64 - p0.y as f64 * p1.x as f64
+}
+
+fn contour_direction(points: &[Point]) -> PathDirection {
+    if signed_area(points) < 0.0 {
+        PathDirection::CCW
+    } else {
+        PathDirection::CW
+    }
+}
+
+// Even-odd point in polygon test.
+fn polygon_contains(points: &[Point], p: Point) -> bool {
+    let mut inside = false;
+    for (i, p0) in points.iter().enumerate() {
+        let p1 = points[(i + 1) % points.len()];
+        if (p0.y > p.y) != (p1.y > p.y) {
+            let x = p0.x + (p.y - p0.y) / (p1.y - p0.y) * (p1.x - p0.x);
+            if p.x < x {
+                inside = !inside;
+            }
         }
     }
+
+    inside
+}
+
+// Returns signs of the edge direction components,
+// treating components that are nearly zero relative to the edge length as zero.
+fn direction_signs(v: Point) -> (f32, f32) {
+    let len = v.length();
+    let sign = |n: f32| {
+        if (n / len).is_nearly_zero() {
+            0.0
+        } else {
+            n.signum()
+        }
+    };
+
+    (sign(v.x), sign(v.y))
 }
 
 impl core::fmt::Debug for Path {
@@ -187,27 +662,34 @@ impl<'a> PathSegmentsIter<'a> {
 
     pub(crate) fn has_valid_tangent(&self) -> bool {
         let mut iter = self.clone();
-        while let Some(segment) = iter.next() {
+        loop {
+            // `last_point` must be checked before `next()`, since it will be updated.
+            let p0 = iter.last_point;
+            let segment = match iter.next() {
+                Some(v) => v,
+                None => break,
+            };
+
             match segment {
                 PathSegment::MoveTo(_) => {
                     return false;
                 }
                 PathSegment::LineTo(p) => {
-                    if iter.last_point == p {
+                    if p0 == p {
                         continue;
                     }
 
                     return true;
                 }
                 PathSegment::QuadTo(p1, p2) => {
-                    if iter.last_point == p1 && iter.last_point == p2 {
+                    if p0 == p1 && p0 == p2 {
                         continue;
                     }
 
                     return true;
                 }
                 PathSegment::CubicTo(p1, p2, p3) => {
-                    if iter.last_point == p1 && iter.last_point == p2 && iter.last_point == p3 {
+                    if p0 == p1 && p0 == p2 && p0 == p3 {
                         continue;
                     }
 
@@ -222,6 +704,27 @@ impl<'a> PathSegmentsIter<'a> {
         false
     }
 
+    /// Returns the current pen position.
+    ///
+    /// This is the end point of the last returned segment.
+    /// Calling it before `next()` returns the implicit start point of the upcoming segment.
+    ///
+    /// After `PathSegment::Close`, returns the start point of the closed contour,
+    /// which is also the end point of the implicit closing line.
+    ///
+    /// Returns (0, 0) when no segments were returned yet.
+    pub fn last_point(&self) -> Point {
+        self.last_point
+    }
+
+    /// Returns the start point of the current contour,
+    /// aka the point of the last `PathSegment::MoveTo`.
+    ///
+    /// Returns (0, 0) when no segments were returned yet.
+    pub fn last_move_to(&self) -> Point {
+        self.last_move_to
+    }
+
     /// Returns the current verb.
     pub fn curr_verb(&self) -> PathVerb {
         self.path.verbs[self.verb_index - 1]
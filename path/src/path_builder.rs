@@ -9,20 +9,36 @@
 use alloc::vec;
 use alloc::vec::Vec;
 
-use crate::{Path, Point, Rect};
+use crate::{Path, Point, Rect, TinySkiaError, Transform};
 
 use crate::path::PathVerb;
 use crate::path_geometry;
 use crate::scalar::{Scalar, SCALAR_ROOT_2_OVER_2};
 
-#[derive(Copy, Clone, PartialEq, Debug)]
-pub(crate) enum PathDirection {
-    /// Clockwise direction for adding closed contours.
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use crate::NoStdFloat;
+
+/// A contour direction.
+///
+/// Assumes a coordinate system where Y axis points down.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PathDirection {
+    /// Clockwise direction.
     CW,
-    /// Counter-clockwise direction for adding closed contours.
+    /// Counter-clockwise direction.
     CCW,
 }
 
+impl PathDirection {
+    /// Returns the opposite direction.
+    pub fn opposite(self) -> Self {
+        match self {
+            PathDirection::CW => PathDirection::CCW,
+            PathDirection::CCW => PathDirection::CW,
+        }
+    }
+}
+
 /// A path builder.
 #[derive(Clone, Default, Debug)]
 pub struct PathBuilder {
@@ -30,6 +46,15 @@ pub struct PathBuilder {
     pub(crate) points: Vec<Point>,
     pub(crate) last_move_to_index: usize,
     pub(crate) move_to_required: bool,
+    // Bounds of all points added so far, as min and max points.
+    pub(crate) bounds: Option<(Point, Point)>,
+    // Set when a point was overwritten or is not finite,
+    // therefore `bounds` cannot be used and must be recalculated.
+    pub(crate) bounds_is_dirty: bool,
+    // A transform the path is expected to be rendered with.
+    // Used only to choose the number of quads a conic is converted into.
+    pub(crate) tessellation_transform: Transform,
+    pub(crate) max_points: Option<usize>,
 }
 
 impl PathBuilder {
@@ -40,6 +65,10 @@ impl PathBuilder {
             points: Vec::new(),
             last_move_to_index: 0,
             move_to_required: true,
+            bounds: None,
+            bounds_is_dirty: false,
+            tessellation_transform: Transform::identity(),
+            max_points: None,
         }
     }
 
@@ -58,6 +87,10 @@ impl PathBuilder {
             points: Vec::with_capacity(points_capacity),
             last_move_to_index: 0,
             move_to_required: true,
+            bounds: None,
+            bounds_is_dirty: false,
+            tessellation_transform: Transform::identity(),
+            max_points: None,
         }
     }
 
@@ -88,6 +121,7 @@ impl PathBuilder {
             bounds: rect,
             verbs,
             points,
+            fill_rule: None,
         }
     }
 
@@ -109,11 +143,50 @@ impl PathBuilder {
         b.finish()
     }
 
-    pub(crate) fn reserve(&mut self, additional_verbs: usize, additional_points: usize) {
+    /// Reserves capacity for at least the specified number of additional verbs and points.
+    ///
+    /// See [`with_capacity`](PathBuilder::with_capacity) for the number of points per verb.
+    pub fn reserve(&mut self, additional_verbs: usize, additional_points: usize) {
         self.verbs.reserve(additional_verbs);
         self.points.reserve(additional_points);
     }
 
+    /// Sets a transform the path is expected to be rendered with.
+    ///
+    /// Ovals, circles and arcs are approximated with quadratic curves when added.
+    /// The number of curves is chosen based on the shape size after this transform,
+    /// so a scaled up shape stays smooth and a scaled down one is not over-tessellated.
+    ///
+    /// Affects only segments added after this call and is preserved by
+    /// [`clear`](PathBuilder::clear). Identity by default.
+    pub fn set_tessellation_transform(&mut self, ts: Transform) {
+        self.tessellation_transform = ts;
+    }
+
+    /// Returns the current tessellation transform.
+    ///
+    /// See [`set_tessellation_transform`](PathBuilder::set_tessellation_transform) for details.
+    pub fn tessellation_transform(&self) -> Transform {
+        self.tessellation_transform
+    }
+
+    /// Sets the maximum number of points a finished path can have.
+    ///
+    /// Useful when building paths from untrusted input.
+    /// When exceeded, [`finish`](PathBuilder::finish) will fail.
+    ///
+    /// Preserved by [`clear`](PathBuilder::clear). Unlimited by default.
+    pub fn set_max_points(&mut self, max_points: Option<usize>) {
+        self.max_points = max_points;
+    }
+
+    /// Returns the maximum number of points.
+    ///
+    /// See [`set_max_points`](PathBuilder::set_max_points) for details.
+    pub fn max_points(&self) -> Option<usize> {
+        self.max_points
+    }
+
     /// Returns the current number of segments in the builder.
     pub fn len(&self) -> usize {
         self.verbs.len()
@@ -132,12 +205,13 @@ impl PathBuilder {
         if let Some(PathVerb::Move) = self.verbs.last() {
             let last_idx = self.points.len() - 1;
             self.points[last_idx] = Point::from_xy(x, y);
+            self.bounds_is_dirty = true;
         } else {
             self.last_move_to_index = self.points.len();
             self.move_to_required = false;
 
             self.verbs.push(PathVerb::Move);
-            self.points.push(Point::from_xy(x, y));
+            self.push_point(Point::from_xy(x, y));
         }
     }
 
@@ -153,24 +227,24 @@ impl PathBuilder {
     /// Adds a line from the last point.
     ///
     /// - If `Path` is empty - adds Move(0, 0) first.
-    /// - If `Path` ends with Close - adds Move(last_x, last_y) first.
+    /// - If `Path` ends with Close - adds a Move to the start of the last contour first.
     pub fn line_to(&mut self, x: f32, y: f32) {
         self.inject_move_to_if_needed();
 
         self.verbs.push(PathVerb::Line);
-        self.points.push(Point::from_xy(x, y));
+        self.push_point(Point::from_xy(x, y));
     }
 
     /// Adds a quad curve from the last point to `x`, `y`.
     ///
     /// - If `Path` is empty - adds Move(0, 0) first.
-    /// - If `Path` ends with Close - adds Move(last_x, last_y) first.
+    /// - If `Path` ends with Close - adds a Move to the start of the last contour first.
     pub fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
         self.inject_move_to_if_needed();
 
         self.verbs.push(PathVerb::Quad);
-        self.points.push(Point::from_xy(x1, y1));
-        self.points.push(Point::from_xy(x, y));
+        self.push_point(Point::from_xy(x1, y1));
+        self.push_point(Point::from_xy(x, y));
     }
 
     pub(crate) fn quad_to_pt(&mut self, p1: Point, p: Point) {
@@ -197,6 +271,7 @@ impl PathBuilder {
                 Point::from_xy(x1, y1),
                 Point::from_xy(x, y),
                 weight,
+                &self.tessellation_transform,
             );
             if let Some(quadder) = quadder {
                 // Points are ordered as: 0 - 1 2 - 3 4 - 5 6 - ..
@@ -219,20 +294,135 @@ impl PathBuilder {
     /// Adds a cubic curve from the last point to `x`, `y`.
     ///
     /// - If `Path` is empty - adds Move(0, 0) first.
-    /// - If `Path` ends with Close - adds Move(last_x, last_y) first.
+    /// - If `Path` ends with Close - adds a Move to the start of the last contour first.
     pub fn cubic_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
         self.inject_move_to_if_needed();
 
         self.verbs.push(PathVerb::Cubic);
-        self.points.push(Point::from_xy(x1, y1));
-        self.points.push(Point::from_xy(x2, y2));
-        self.points.push(Point::from_xy(x, y));
+        self.push_point(Point::from_xy(x1, y1));
+        self.push_point(Point::from_xy(x2, y2));
+        self.push_point(Point::from_xy(x, y));
     }
 
     pub(crate) fn cubic_to_pt(&mut self, p1: Point, p2: Point, p: Point) {
         self.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
     }
 
+    /// Adds an elliptical arc from the last point to `x`, `y`.
+    ///
+    /// Follows the SVG `A` command semantics: `x_axis_rotation` is in degrees,
+    /// too small radii are scaled up, zero radii produce a line
+    /// and an arc to the last point is ignored.
+    ///
+    /// The arc is approximated by cubic curves, one per each 90° or less.
+    ///
+    /// - If `Path` is empty - adds Move(0, 0) first.
+    /// - If `Path` ends with Close - adds a Move to the start of the last contour first.
+    pub fn svg_arc_to(
+        &mut self,
+        rx: f32,
+        ry: f32,
+        x_axis_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        x: f32,
+        y: f32,
+    ) {
+        self.inject_move_to_if_needed();
+
+        // Cannot fail, since we've just injected a move to.
+        let p0 = match self.last_point() {
+            Some(v) => v,
+            None => return,
+        };
+
+        if p0 == Point::from_xy(x, y) {
+            return;
+        }
+
+        let mut rx = rx.abs();
+        let mut ry = ry.abs();
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(x, y);
+            return;
+        }
+
+        // A conversion from the endpoint to the center parameterization.
+        // See https://www.w3.org/TR/SVG11/implnote.html#ArcConversionEndpointToCenter
+        let angle = x_axis_rotation.to_radians();
+        let (sin, cos) = (angle.sin(), angle.cos());
+        let hdx = (p0.x - x) * 0.5;
+        let hdy = (p0.y - y) * 0.5;
+        let x1 = cos * hdx + sin * hdy;
+        let y1 = -sin * hdx + cos * hdy;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        if lambda > 1.0 {
+            let s = lambda.sqrt();
+            rx *= s;
+            ry *= s;
+        }
+
+        let rx2 = rx * rx;
+        let ry2 = ry * ry;
+        let den = rx2 * y1 * y1 + ry2 * x1 * x1;
+        let mut coef = ((rx2 * ry2 - den) / den).max(0.0).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+        let cx = cos * cx1 - sin * cy1 + (p0.x + x) * 0.5;
+        let cy = sin * cx1 + cos * cy1 + (p0.y + y) * 0.5;
+
+        let vector_angle =
+            |ux: f32, uy: f32, vx: f32, vy: f32| (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+
+        let ux = (x1 - cx1) / rx;
+        let uy = (y1 - cy1) / ry;
+        let vx = (-x1 - cx1) / rx;
+        let vy = (-y1 - cy1) / ry;
+        let start_angle = vector_angle(1.0, 0.0, ux, uy);
+        let mut sweep_angle = vector_angle(ux, uy, vx, vy);
+        if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * core::f32::consts::PI;
+        } else if sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * core::f32::consts::PI;
+        }
+
+        // Maps a point on a unit circle onto the ellipse.
+        let map = |px: f32, py: f32| {
+            Point::from_xy(
+                cx + rx * cos * px - ry * sin * py,
+                cy + rx * sin * px + ry * cos * py,
+            )
+        };
+
+        let count = (sweep_angle.abs() / core::f32::consts::FRAC_PI_2)
+            .ceil()
+            .max(1.0) as u32;
+        let delta = sweep_angle / count as f32;
+        let k = 4.0 / 3.0 * (delta / 4.0).sin() / (delta / 4.0).cos();
+        for i in 0..count {
+            let a1 = start_angle + delta * i as f32;
+            let a2 = a1 + delta;
+            let (s1, c1) = (a1.sin(), a1.cos());
+            let (s2, c2) = (a2.sin(), a2.cos());
+
+            let p1 = map(c1 - k * s1, s1 + k * c1);
+            let p2 = map(c2 + k * s2, s2 - k * c2);
+            // End exactly at the requested point.
+            let p3 = if i + 1 == count {
+                Point::from_xy(x, y)
+            } else {
+                map(c2, s2)
+            };
+
+            self.cubic_to_pt(p1, p2, p3);
+        }
+    }
+
     /// Closes the current contour.
     ///
     /// A closed contour connects the first and the last Point
@@ -259,9 +449,47 @@ impl PathBuilder {
         self.points.last().cloned()
     }
 
+    /// Returns bounds of all points added so far, including control points.
+    ///
+    /// Bounds are updated as points are added, therefore this method is usually cheap.
+    /// Unless a point was overwritten, like by consecutive `move_to` calls,
+    /// in which case all points are checked.
+    ///
+    /// Returns `None` when the builder is empty or any point is not finite.
+    pub fn current_bounds(&self) -> Option<Rect> {
+        if self.bounds_is_dirty {
+            return Rect::from_points(&self.points);
+        }
+
+        let (min, max) = self.bounds?;
+        Rect::from_ltrb(min.x, min.y, max.x, max.y)
+    }
+
+    fn push_point(&mut self, p: Point) {
+        if !p.is_finite() {
+            self.bounds_is_dirty = true;
+        }
+
+        self.extend_bounds(p, p);
+        self.points.push(p);
+    }
+
+    fn extend_bounds(&mut self, min: Point, max: Point) {
+        self.bounds = Some(match self.bounds {
+            Some((old_min, old_max)) => (
+                Point::from_xy(old_min.x.min(min.x), old_min.y.min(min.y)),
+                Point::from_xy(old_max.x.max(max.x), old_max.y.max(max.y)),
+            ),
+            None => (min, max),
+        });
+    }
+
     pub(crate) fn set_last_point(&mut self, pt: Point) {
         match self.points.last_mut() {
-            Some(last) => *last = pt,
+            Some(last) => {
+                *last = pt;
+                self.bounds_is_dirty = true;
+            }
             None => self.move_to(pt.x, pt.y),
         }
     }
@@ -335,10 +563,168 @@ impl PathBuilder {
         }
     }
 
+    /// Adds a regular polygon contour.
+    ///
+    /// The first vertex is placed at the `rotation` angle in degrees,
+    /// measured from the positive X axis. Just like in `Transform::from_rotate`,
+    /// a positive angle rotates clockwise in a Y-down coordinate system.
+    ///
+    /// The contour is closed and has a clock-wise direction.
+    ///
+    /// Does nothing when:
+    /// - `sides` < 3
+    /// - `radius` <= 0
+    /// - any value is not finite
+    pub fn push_polygon(&mut self, x: f32, y: f32, radius: f32, sides: u32, rotation: f32) {
+        if sides < 3 || !(radius > 0.0 && radius.is_finite()) {
+            return;
+        }
+
+        self.push_radial_contour(x, y, sides, rotation, |_| radius);
+    }
+
+    /// Adds a star contour.
+    ///
+    /// Vertices alternate between `outer_radius` and `inner_radius`,
+    /// starting with an outer one placed at the `rotation` angle in degrees.
+    /// See [`PathBuilder::push_polygon`] for details.
+    ///
+    /// The contour is closed and has a clock-wise direction.
+    ///
+    /// Does nothing when:
+    /// - `points` < 3
+    /// - `outer_radius` <= 0
+    /// - `inner_radius` < 0
+    /// - any value is not finite
+    pub fn push_star(
+        &mut self,
+        x: f32,
+        y: f32,
+        outer_radius: f32,
+        inner_radius: f32,
+        points: u32,
+        rotation: f32,
+    ) {
+        if points < 3
+            || !(outer_radius > 0.0 && outer_radius.is_finite())
+            || !(inner_radius >= 0.0 && inner_radius.is_finite())
+        {
+            return;
+        }
+
+        self.push_radial_contour(x, y, points * 2, rotation, |i| {
+            if i % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            }
+        });
+    }
+
+    /// Adds a Catmull-Rom spline contour that passes through all `points`.
+    ///
+    /// Each spline segment is converted into a cubic Bézier.
+    ///
+    /// `tension` will be clamped to the 0..=1 range. 0 produces a regular
+    /// Catmull-Rom spline and 1 produces straight lines between points.
+    ///
+    /// When `closed` is set, tangents wrap around the first and the last points
+    /// and the contour is closed.
+    /// Otherwise, end tangents are computed using the nearest point only.
+    ///
+    /// Does nothing when there are less than two points.
+    /// Two points produce a straight line.
+    pub fn push_catmull_rom(&mut self, points: &[Point], tension: f32, closed: bool) {
+        if points.len() < 2 {
+            return;
+        }
+
+        let first = points[0];
+        self.move_to(first.x, first.y);
+
+        if points.len() == 2 {
+            let last = points[1];
+            self.line_to(last.x, last.y);
+            if closed {
+                self.close();
+            }
+
+            return;
+        }
+
+        // A cardinal spline tangent divided by 3, which is a Bézier control point offset.
+        let scale = (1.0 - tension.bound(0.0, 1.0)) / 6.0;
+        let len = points.len();
+        let tangent = |i: usize| -> Point {
+            let (prev, next) = if closed {
+                (points[(i + len - 1) % len], points[(i + 1) % len])
+            } else {
+                (points[i.saturating_sub(1)], points[(i + 1).min(len - 1)])
+            };
+
+            Point::from_xy((next.x - prev.x) * scale, (next.y - prev.y) * scale)
+        };
+
+        let count = if closed { len } else { len - 1 };
+        for i in 0..count {
+            let j = (i + 1) % len;
+            let p0 = points[i];
+            let p1 = points[j];
+            let t0 = tangent(i);
+            let t1 = tangent(j);
+            self.cubic_to(
+                p0.x + t0.x,
+                p0.y + t0.y,
+                p1.x - t1.x,
+                p1.y - t1.y,
+                p1.x,
+                p1.y,
+            );
+        }
+
+        if closed {
+            self.close();
+        }
+    }
+
+    fn push_radial_contour(
+        &mut self,
+        x: f32,
+        y: f32,
+        count: u32,
+        rotation: f32,
+        radius: impl Fn(u32) -> f32,
+    ) {
+        if !(x.is_finite() && y.is_finite() && rotation.is_finite()) {
+            return;
+        }
+
+        let step = core::f32::consts::PI * 2.0 / count as f32;
+        let start = rotation.to_radians();
+        for i in 0..count {
+            let angle = start + step * i as f32;
+            let r = radius(i);
+            let px = x + r * angle.cos();
+            let py = y + r * angle.sin();
+            if i == 0 {
+                self.move_to(px, py);
+            } else {
+                self.line_to(px, py);
+            }
+        }
+        self.close();
+    }
+
     /// Adds a path.
     pub fn push_path(&mut self, other: &Path) {
         self.last_move_to_index = self.points.len();
 
+        let bounds = other.bounds();
+        self.extend_bounds(
+            Point::from_xy(bounds.left(), bounds.top()),
+            Point::from_xy(bounds.right(), bounds.bottom()),
+        );
+
         self.verbs.extend_from_slice(&other.verbs);
         self.points.extend_from_slice(&other.points);
     }
@@ -352,6 +738,11 @@ impl PathBuilder {
             self.last_move_to_index = self.points.len() + other.last_move_to_index;
         }
 
+        if let Some((min, max)) = other.bounds {
+            self.extend_bounds(min, max);
+        }
+        self.bounds_is_dirty |= other.bounds_is_dirty;
+
         self.verbs.extend_from_slice(&other.verbs);
         self.points.extend_from_slice(&other.points);
     }
@@ -395,6 +786,12 @@ impl PathBuilder {
         }
     }
 
+    /// Shrinks the capacity of the builder as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.verbs.shrink_to_fit();
+        self.points.shrink_to_fit();
+    }
+
     /// Reset the builder.
     ///
     /// Memory is not deallocated.
@@ -403,27 +800,63 @@ impl PathBuilder {
         self.points.clear();
         self.last_move_to_index = 0;
         self.move_to_required = true;
+        self.bounds = None;
+        self.bounds_is_dirty = false;
+    }
+
+    /// Checks that the builder contains a valid path.
+    ///
+    /// A valid path has at least one segment after a move to, all its points are finite
+    /// and there are no more of them than [`max_points`](Self::max_points).
+    /// When `true`, [`finish`](Self::finish) will return a `Path`.
+    ///
+    /// Segments added without a preceding move to are always valid,
+    /// because a move to is inserted implicitly, just like in Skia.
+    pub fn is_valid(&self) -> bool {
+        // Just a move to is not a path.
+        self.verbs.len() > 1
+            && !matches!(self.max_points, Some(max) if self.points.len() > max)
+            && self.current_bounds().is_some()
     }
 
     /// Finishes the builder and returns a `Path`.
     ///
-    /// Returns `None` when `Path` is empty or has invalid bounds.
+    /// Returns `None` when `Path` is empty, contains only a move to,
+    /// has non-finite points or has more points than allowed.
+    /// See [`is_valid`](Self::is_valid) and [`try_finish`](Self::try_finish).
     pub fn finish(self) -> Option<Path> {
-        if self.is_empty() {
-            return None;
-        }
+        self.try_finish().ok()
+    }
 
+    /// Finishes the builder and returns a `Path`.
+    ///
+    /// The same as [`PathBuilder::finish`], but reports why the path is invalid.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::EmptyPath`] when `Path` is empty or contains only a move to.
+    /// - [`TinySkiaError::TooComplex`] when `Path` has more points than
+    ///   [`max_points`](Self::max_points).
+    /// - [`TinySkiaError::NonFinite`] when any point is not finite.
+    pub fn try_finish(self) -> Result<Path, TinySkiaError> {
         // Just a move to? Bail.
-        if self.verbs.len() == 1 {
-            return None;
+        if self.verbs.len() < 2 {
+            return Err(TinySkiaError::EmptyPath);
+        }
+
+        if let Some(max) = self.max_points {
+            if self.points.len() > max {
+                return Err(TinySkiaError::TooComplex);
+            }
         }
 
-        let bounds = Rect::from_points(&self.points)?;
+        let bounds = self.current_bounds().ok_or(TinySkiaError::NonFinite)?;
 
-        Some(Path {
+        Ok(Path {
             bounds,
             verbs: self.verbs,
             points: self.points,
+            fill_rule: None,
         })
     }
 }
@@ -10,7 +10,7 @@
 
 #![allow(missing_docs)]
 
-use crate::{Point, Transform};
+use crate::{Point, Rect, Transform};
 
 use crate::f32x2_t::f32x2;
 use crate::floating_point::FLOAT_PI;
@@ -183,6 +183,120 @@ pub fn chop_cubic_at2(src: &[Point; 4], t: NormalizedF32Exclusive, dst: &mut [Po
     dst[6] = Point::from_f32x2(p3);
 }
 
+/// Chops a cubic at the specified arc length from its start.
+///
+/// The length is computed by integrating the derivative and the chop point
+/// is found using a binary search, until the length of the first half
+/// is within `tolerance` from the requested one.
+///
+/// Returns `false` when `length` is negative or exceeds the curve length.
+pub fn chop_cubic_at_length(
+    src: &[Point; 4],
+    length: f32,
+    tolerance: f32,
+    dst: &mut [Point; 7],
+) -> bool {
+    // Also handles NaN.
+    if !(length >= 0.0 && tolerance > 0.0) {
+        return false;
+    }
+
+    let coeff = cubic_derivative_coeff(src);
+    let integral_tolerance = tolerance * 0.1;
+    let total_length = cubic_length(&coeff, 0.0, 1.0, integral_tolerance);
+    if !(length <= total_length + tolerance) {
+        return false;
+    }
+
+    let mut t = 0.0;
+    if length >= total_length - tolerance {
+        t = 1.0;
+    } else if length > tolerance {
+        let mut lo = 0.0;
+        let mut lo_length = 0.0;
+        let mut hi = 1.0;
+        // f32 precision is exhausted long before that.
+        for _ in 0..32 {
+            t = (lo + hi) * 0.5;
+            let len = lo_length + cubic_length(&coeff, lo, t, integral_tolerance);
+            if (len - length).abs() <= tolerance {
+                break;
+            }
+
+            if len < length {
+                lo = t;
+                lo_length = len;
+            } else {
+                hi = t;
+            }
+        }
+    }
+
+    // Chopping at the end points produces degenerate halves.
+    match NormalizedF32Exclusive::new(t) {
+        Some(t) => chop_cubic_at2(src, t, dst),
+        None if t < 0.5 => {
+            dst[..4].fill(src[0]);
+            dst[4..].copy_from_slice(&src[1..]);
+        }
+        None => {
+            dst[..4].copy_from_slice(src);
+            dst[4..].fill(src[3]);
+        }
+    }
+
+    true
+}
+
+// Gauss-Legendre quadrature abscissas and weights for 5 points.
+const GAUSS_LEGENDRE_5: [(f32, f32); 5] = [
+    (0.0, 0.568_888_9),
+    (-0.538_469_3, 0.478_628_67),
+    (0.538_469_3, 0.478_628_67),
+    (-0.906_179_8, 0.236_926_88),
+    (0.906_179_8, 0.236_926_88),
+];
+
+// Returns the arc length of a cubic between `t0` and `t1`,
+// using its derivative coefficients.
+fn cubic_length(derivative: &QuadCoeff, t0: f32, t1: f32, tolerance: f32) -> f32 {
+    let whole = gauss_legendre_length(derivative, t0, t1);
+    cubic_length_adaptive(derivative, t0, t1, whole, tolerance, 10)
+}
+
+fn cubic_length_adaptive(
+    derivative: &QuadCoeff,
+    t0: f32,
+    t1: f32,
+    whole: f32,
+    tolerance: f32,
+    depth: u8,
+) -> f32 {
+    let mid = (t0 + t1) * 0.5;
+    let left = gauss_legendre_length(derivative, t0, mid);
+    let right = gauss_legendre_length(derivative, mid, t1);
+    if depth == 0 || (left + right - whole).abs() <= tolerance {
+        return left + right;
+    }
+
+    let tolerance = tolerance * 0.5;
+    cubic_length_adaptive(derivative, t0, mid, left, tolerance, depth - 1)
+        + cubic_length_adaptive(derivative, mid, t1, right, tolerance, depth - 1)
+}
+
+fn gauss_legendre_length(derivative: &QuadCoeff, t0: f32, t1: f32) -> f32 {
+    let half = (t1 - t0) * 0.5;
+    let mid = (t0 + t1) * 0.5;
+    let mut sum = 0.0;
+    for (x, w) in GAUSS_LEGENDRE_5.iter() {
+        let d = Point::from_f32x2(derivative.eval(f32x2::splat(mid + half * x)));
+        sum += w * d.length();
+    }
+
+    // The derivative coefficients are scaled by 1/3.
+    sum * half * 3.0
+}
+
 pub fn valid_unit_divide(mut numer: f32, mut denom: f32) -> Option<NormalizedF32Exclusive> {
     if numer < 0.0 {
         numer = -numer;
@@ -256,15 +370,23 @@ pub(crate) fn eval_quad_tangent_at(src: &[Point; 3], tol: NormalizedF32) -> Poin
         return src[2] - src[0];
     }
 
+    let (a, b) = quad_derivative_coeff(src);
+    Point::from_f32x2(a.to_f32x2() * f32x2::splat(tol.get()) + b.to_f32x2())
+}
+
+/// Returns the `(A, B)` coefficients of a quad derivative: `F'(t) == A * t + B`.
+///
+/// Matches the formula used by the renderer to compute quad tangents.
+pub fn quad_derivative_coeff(src: &[Point; 3]) -> (Point, Point) {
     let p0 = src[0].to_f32x2();
     let p1 = src[1].to_f32x2();
     let p2 = src[2].to_f32x2();
 
     let b = p1 - p0;
     let a = p2 - p1 - b;
-    let t = a * f32x2::splat(tol.get()) + b;
 
-    Point::from_f32x2(t + t)
+    // Multiplication by 2 is exact, so `2(at + b) == 2a * t + 2b`.
+    (Point::from_f32x2(times_2(a)), Point::from_f32x2(times_2(b)))
 }
 
 // Looking for F' dot F'' == 0
@@ -435,18 +557,24 @@ pub(crate) fn eval_cubic_tangent_at(src: &[Point; 4], t: NormalizedF32) -> Point
 }
 
 fn eval_cubic_derivative(src: &[Point; 4], t: NormalizedF32) -> Point {
+    Point::from_f32x2(cubic_derivative_coeff(src).eval(f32x2::splat(t.get())))
+}
+
+/// Returns the coefficients of a cubic derivative.
+///
+/// Just like in Skia, the result is scaled by 1/3, aka `F'(t) == 3 * coeff.eval(t)`.
+/// Matches the formula used by the renderer to compute cubic tangents.
+pub fn cubic_derivative_coeff(src: &[Point; 4]) -> QuadCoeff {
     let p0 = src[0].to_f32x2();
     let p1 = src[1].to_f32x2();
     let p2 = src[2].to_f32x2();
     let p3 = src[3].to_f32x2();
 
-    let coeff = QuadCoeff {
+    QuadCoeff {
         a: p3 + f32x2::splat(3.0) * (p1 - p2) - p0,
         b: times_2(p2 - times_2(p1) + p0),
         c: p1 - p0,
-    };
-
-    Point::from_f32x2(coeff.eval(f32x2::splat(t.get())))
+    }
 }
 
 // http://www.faculty.idc.ac.il/arik/quality/appendixA.html
@@ -551,8 +679,157 @@ fn calc_cubic_precision(src: &[Point; 4]) -> f32 {
         * 1e-8
 }
 
+/// A cubic Bezier curve type.
+///
+/// See [`classify_cubic`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CubicType {
+    /// A curve with two distinct inflection points, like an S-shape.
+    Serpentine,
+    /// A self-intersecting curve. Has no real inflection points.
+    Loop,
+    /// A curve with a cusp.
+    ///
+    /// Also includes curves with a cusp at infinity,
+    /// which have a single inflection point.
+    Cusp,
+    /// A quadratic curve represented as a cubic.
+    Quadratic,
+    /// Collinear control points.
+    Line,
+    /// All control points are coincident.
+    Point,
+}
+
+/// Classifies a cubic Bezier curve.
+///
+/// Uses the inflection function discriminant from "Resolution Independent Curve Rendering
+/// using Programmable Graphics Hardware" by Loop and Blinn.
+/// The whole curve is classified, not just the `0..1` range,
+/// therefore a loop or a cusp can be outside of the drawn segment.
+///
+/// Like `SkClassifyCubic`, the math is done in `f64`
+/// and the discriminant is compared with zero exactly.
+/// The result is unspecified for non-finite points.
+pub fn classify_cubic(src: &[Point; 4]) -> CubicType {
+    // The inflection function is I = [T^3  -3T^2  3T  -1] dot D.
+    // D0 is always zero for integral cubics.
+    let a1 = dot_cross_cubic(src[0], src[3], src[2]);
+    let a2 = dot_cross_cubic(src[1], src[0], src[3]);
+    let a3 = dot_cross_cubic(src[2], src[1], src[0]);
+
+    let mut d3 = 3.0 * a3;
+    let mut d2 = d3 - a2;
+    let mut d1 = d2 - a2 + a1;
+
+    // Shift the exponents so the largest magnitude falls somewhere in 1..2
+    // to protect us from overflow.
+    let norm = previous_inverse_pow2(d1.abs().max(d2.abs()).max(d3.abs()));
+    d1 *= norm;
+    d2 *= norm;
+    d3 *= norm;
+
+    if d1 != 0.0 {
+        let discr = 3.0 * d2 * d2 - 4.0 * d1 * d3;
+        if discr > 0.0 {
+            CubicType::Serpentine
+        } else if discr < 0.0 {
+            CubicType::Loop
+        } else {
+            CubicType::Cusp
+        }
+    } else if d2 != 0.0 {
+        CubicType::Cusp
+    } else if d3 != 0.0 {
+        CubicType::Quadratic
+    } else if src[1..].iter().all(|p| *p == src[0]) {
+        CubicType::Point
+    } else {
+        CubicType::Line
+    }
+}
+
+fn dot_cross_cubic(p0: Point, p1: Point, p2: Point) -> f64 {
+    let x_comp = p0.x as f64 * (p1.y as f64 - p2.y as f64);
+    let y_comp = p0.y as f64 * (p2.x as f64 - p1.x as f64);
+    let w_comp = p1.x as f64 * p2.y as f64 - p1.y as f64 * p2.x as f64;
+    x_comp + y_comp + w_comp
+}
+
+// Returns a power of two that, when multiplied by `n`, produces a value
+// with a binary exponent of 1. Zero and infinity are not special-cased.
+fn previous_inverse_pow2(n: f64) -> f64 {
+    let mut bits = n.to_bits();
+    bits = (((1023u64 * 2) << 52) + ((1u64 << 52) - 1)).wrapping_sub(bits); // exp = -exp
+    bits &= 0x7ffu64 << 52; // mantissa = 1.0, sign = 0
+    f64::from_bits(bits)
+}
+
+/// Approximates an elliptical arc with cubic Béziers.
+///
+/// The arc is inscribed into `oval` and starts at `start_deg` degrees, going clockwise
+/// for a positive `sweep_deg` and counter-clockwise for a negative one.
+/// Angles are measured from the positive X axis, with the Y axis pointing down.
+///
+/// The sweep is split into equal parts of at most 90 degrees,
+/// each approximated by a single cubic. For a circle, the error is less than 3e-4 of the radius.
+/// Sweeps outside of the -360..360 range are clamped.
+///
+/// Writes the start point followed by three points per cubic into `dst`
+/// and returns the number of cubics. A full circle requires 13 points.
+///
+/// Returns zero when the sweep is zero, any argument is not finite
+/// or `dst` is too small.
+pub fn arc_to_cubics(oval: Rect, start_deg: f32, sweep_deg: f32, dst: &mut [Point]) -> usize {
+    if !(start_deg.is_finite() && sweep_deg.is_finite()) || sweep_deg == 0.0 {
+        return 0;
+    }
+
+    let sweep_deg = sweep_deg.bound(-360.0, 360.0);
+    // Allow a bit of slack, so 90.00001 degrees will not produce two cubics.
+    let count = (sweep_deg.abs() / 90.0 - 1e-4).ceil().max(1.0) as usize;
+    if dst.len() < 1 + count * 3 {
+        return 0;
+    }
+
+    let cx = (oval.left() as f64 + oval.right() as f64) * 0.5;
+    let cy = (oval.top() as f64 + oval.bottom() as f64) * 0.5;
+    let rx = oval.width() as f64 * 0.5;
+    let ry = oval.height() as f64 * 0.5;
+
+    let start = (start_deg as f64).to_radians();
+    let step = (sweep_deg as f64).to_radians() / count as f64;
+    // The standard control points length. 0.5522847 for 90 degrees.
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+
+    let point = |x: f64, y: f64| Point::from_xy(x as f32, y as f32);
+
+    let (mut sin, mut cos) = (start.sin(), start.cos());
+    dst[0] = point(cx + rx * cos, cy + ry * sin);
+    for i in 0..count {
+        let angle = start + step * (i + 1) as f64;
+        let (sin2, cos2) = (angle.sin(), angle.cos());
+        dst[i * 3 + 1] = point(cx + rx * (cos - k * sin), cy + ry * (sin + k * cos));
+        dst[i * 3 + 2] = point(cx + rx * (cos2 + k * sin2), cy + ry * (sin2 - k * cos2));
+        dst[i * 3 + 3] = point(cx + rx * cos2, cy + ry * sin2);
+        sin = sin2;
+        cos = cos2;
+    }
+
+    count
+}
+
+// Limit the number of suggested quads to approximate a conic.
+pub(crate) const MAX_CONIC_TO_QUAD_POW2: u8 = 5;
+
+// The number of points needed to store the maximum number of quads.
+const MAX_CONIC_TO_QUAD_POINTS: usize = 1 + 2 * (1 << MAX_CONIC_TO_QUAD_POW2);
+
+/// A rational quadratic Bezier curve.
+///
+/// Circular and elliptical arcs can be represented exactly.
 #[derive(Copy, Clone, Default, Debug)]
-pub(crate) struct Conic {
+pub struct Conic {
     pub points: [Point; 3],
     pub weight: f32,
 }
@@ -572,7 +849,71 @@ impl Conic {
         }
     }
 
-    fn compute_quad_pow2(&self, tolerance: f32) -> Option<u8> {
+    /// Evaluates a point on the conic at `t`.
+    ///
+    /// Uses the exact rational formula, not a quadratic approximation.
+    /// `t` of 0 and 1 return the exact end points.
+    pub fn eval(&self, t: NormalizedF32) -> Point {
+        if t == NormalizedF32::ZERO {
+            return self.points[0];
+        } else if t == NormalizedF32::ONE {
+            return self.points[2];
+        }
+
+        let p0 = self.points[0].to_f32x2();
+        let p1 = self.points[1].to_f32x2();
+        let p2 = self.points[2].to_f32x2();
+        let ww = f32x2::splat(self.weight);
+        let p1w = p1 * ww;
+
+        let numer = QuadCoeff {
+            a: p2 - times_2(p1w) + p0,
+            b: times_2(p1w - p0),
+            c: p0,
+        };
+
+        let denom_b = times_2(ww - f32x2::splat(1.0));
+        let denom = QuadCoeff {
+            a: f32x2::default() - denom_b,
+            b: denom_b,
+            c: f32x2::splat(1.0),
+        };
+
+        let t = f32x2::splat(t.get());
+        Point::from_f32x2(numer.eval(t) / denom.eval(t))
+    }
+
+    /// Evaluates a tangent vector on the conic at `t`.
+    ///
+    /// The vector has the direction of the conic derivative, but not its length.
+    pub fn eval_tangent(&self, t: NormalizedF32) -> Point {
+        // The derivative equation returns a zero tangent vector when t is 0 or 1,
+        // and the control point is equal to the end point.
+        // In this case, use the conic end points to compute the tangent.
+        if (t == NormalizedF32::ZERO && self.points[0] == self.points[1])
+            || (t == NormalizedF32::ONE && self.points[1] == self.points[2])
+        {
+            return self.points[2] - self.points[0];
+        }
+
+        let p0 = self.points[0].to_f32x2();
+        let p1 = self.points[1].to_f32x2();
+        let p2 = self.points[2].to_f32x2();
+        let ww = f32x2::splat(self.weight);
+
+        let p20 = p2 - p0;
+        let p10 = p1 - p0;
+        let c = ww * p10;
+        let a = ww * p20 - p20;
+        let b = p20 - c - c;
+
+        Point::from_f32x2(QuadCoeff { a, b, c }.eval(f32x2::splat(t.get())))
+    }
+
+    // Returns the subdivision level needed to approximate this conic with quads,
+    // so the error is less than or equal to `tolerance`.
+    // The number of quads is `1 << pow2`, at most `1 << MAX_CONIC_TO_QUAD_POW2`.
+    pub fn compute_quad_pow2(&self, tolerance: f32) -> Option<u8> {
         if tolerance < 0.0 || !tolerance.is_finite() {
             return None;
         }
@@ -582,9 +923,6 @@ impl Conic {
             return None;
         }
 
-        // Limit the number of suggested quads to approximate a conic
-        const MAX_CONIC_TO_QUAD_POW2: usize = 4;
-
         // "High order approximation of conic sections by quadratic splines"
         // by Michael Floater, 1993
         let a = self.weight - 1.0;
@@ -611,7 +949,8 @@ impl Conic {
     // Chop this conic into N quads, stored continuously in pts[], where
     // N = 1 << pow2. The amount of storage needed is (1 + 2 * N)
     pub fn chop_into_quads_pow2(&self, pow2: u8, points: &mut [Point]) -> u8 {
-        debug_assert!(pow2 < 5);
+        debug_assert!(pow2 <= MAX_CONIC_TO_QUAD_POW2);
+        debug_assert!(points.len() > 2 * (1 << pow2));
 
         points[0] = self.points[0];
         subdivide(self, &mut points[1..], pow2);
@@ -839,16 +1178,42 @@ fn between(a: f32, b: f32, c: f32) -> bool {
     (a - b) * (c - b) <= 0.0
 }
 
+// Returns the subdivision level needed to approximate a conic with quads,
+// so the error after `transform` is less than or equal to `tolerance`.
+//
+// An affine transform maps a conic onto a conic with the same weight,
+// therefore the error is estimated using the mapped points directly
+// and not an averaged scale, which would underestimate it for a non-uniform scale or a skew.
+// Falls back to the maximum level when the estimation is not possible.
+pub(crate) fn compute_quad_pow2(conic: &Conic, transform: &Transform, tolerance: f32) -> u8 {
+    let mut mapped = *conic;
+    transform.map_points(&mut mapped.points);
+    mapped
+        .compute_quad_pow2(tolerance)
+        .unwrap_or(MAX_CONIC_TO_QUAD_POW2)
+}
+
 pub(crate) struct AutoConicToQuads {
-    pub points: [Point; 64],
+    pub points: [Point; MAX_CONIC_TO_QUAD_POINTS],
     pub len: u8, // the number of quads
 }
 
 impl AutoConicToQuads {
-    pub fn compute(pt0: Point, pt1: Point, pt2: Point, weight: f32) -> Option<Self> {
+    // `transform` is the one the quads are expected to be rendered with.
+    pub fn compute(
+        pt0: Point,
+        pt1: Point,
+        pt2: Point,
+        weight: f32,
+        transform: &Transform,
+    ) -> Option<Self> {
         let conic = Conic::new(pt0, pt1, pt2, weight);
-        let pow2 = conic.compute_quad_pow2(0.25)?;
-        let mut points = [Point::zero(); 64];
+        if !pt0.is_finite() || !pt1.is_finite() || !pt2.is_finite() {
+            return None;
+        }
+
+        let pow2 = compute_quad_pow2(&conic, transform, 0.25);
+        let mut points = [Point::zero(); MAX_CONIC_TO_QUAD_POINTS];
         let len = conic.chop_into_quads_pow2(pow2, &mut points);
         Some(AutoConicToQuads { points, len })
     }
@@ -858,6 +1223,52 @@ impl AutoConicToQuads {
 mod tests {
     use super::*;
 
+    #[test]
+    fn eval_conic() {
+        // A quarter of a unit circle.
+        let conic = Conic::new(
+            Point::from_xy(1.0, 0.0),
+            Point::from_xy(1.0, 1.0),
+            Point::from_xy(0.0, 1.0),
+            SCALAR_ROOT_2_OVER_2,
+        );
+
+        assert_eq!(conic.eval(NormalizedF32::ZERO), Point::from_xy(1.0, 0.0));
+        assert_eq!(conic.eval(NormalizedF32::ONE), Point::from_xy(0.0, 1.0));
+
+        // All points are on the circle.
+        for i in 1..10 {
+            let p = conic.eval(NormalizedF32::new(i as f32 / 10.0).unwrap());
+            assert!((p.length() - 1.0).abs() < 1e-6);
+        }
+
+        // The middle point is on the diagonal.
+        let p = conic.eval(NormalizedF32::new(0.5).unwrap());
+        assert!((p.x - SCALAR_ROOT_2_OVER_2).abs() < 1e-6);
+        assert!((p.y - SCALAR_ROOT_2_OVER_2).abs() < 1e-6);
+
+        // Tangents are perpendicular to the radius.
+        for i in 0..=10 {
+            let t = NormalizedF32::new(i as f32 / 10.0).unwrap();
+            let p = conic.eval(t);
+            let tangent = conic.eval_tangent(t);
+            assert!(p.dot(tangent).abs() < 1e-6);
+            assert!(p.cross(tangent) > 0.0);
+        }
+
+        // A weight of 1 is a quad.
+        let points = [
+            Point::from_xy(10.0, 20.0),
+            Point::from_xy(50.0, 80.0),
+            Point::from_xy(90.0, 30.0),
+        ];
+        let conic = Conic::from_points(&points, 1.0);
+        let t = NormalizedF32::new(0.3).unwrap();
+        let p = conic.eval(t);
+        let expected = eval_quad_at(&points, t);
+        assert!((p.x - expected.x).abs() < 1e-4 && (p.y - expected.y).abs() < 1e-4);
+    }
+
     #[test]
     fn eval_cubic_at_1() {
         let src = [
@@ -877,6 +1288,106 @@ mod tests {
         );
     }
 
+    #[test]
+    fn derivative_coeff() {
+        let quad = [
+            Point::from_xy(10.0, 20.0),
+            Point::from_xy(50.0, 80.0),
+            Point::from_xy(90.0, 30.0),
+        ];
+        let (a, b) = quad_derivative_coeff(&quad);
+        let t = NormalizedF32::new_clamped(0.3);
+        let p = a.to_f32x2() * f32x2::splat(t.get()) + b.to_f32x2();
+        assert_eq!(Point::from_f32x2(p), eval_quad_tangent_at(&quad, t));
+
+        let cubic = [
+            Point::from_xy(30.0, 40.0),
+            Point::from_xy(60.0, 10.0),
+            Point::from_xy(171.0, 45.0),
+            Point::from_xy(180.0, 155.0),
+        ];
+        let coeff = cubic_derivative_coeff(&cubic);
+        assert_eq!(
+            Point::from_f32x2(coeff.eval(f32x2::splat(t.get()))),
+            eval_cubic_tangent_at(&cubic, t)
+        );
+        assert_eq!(
+            Point::from_f32x2(coeff.eval(f32x2::splat(0.0))),
+            Point::from_xy(30.0, -30.0)
+        );
+    }
+
+    #[test]
+    fn chop_cubic_at_length_line() {
+        let src = [
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(30.0, 0.0),
+            Point::from_xy(60.0, 0.0),
+            Point::from_xy(90.0, 0.0),
+        ];
+
+        let mut dst = [Point::zero(); 7];
+        assert!(chop_cubic_at_length(&src, 30.0, 0.01, &mut dst));
+        assert!((dst[3].x - 30.0).abs() <= 0.01);
+        assert_eq!(dst[0], src[0]);
+        assert_eq!(dst[6], src[3]);
+
+        assert!(chop_cubic_at_length(&src, 0.0, 0.01, &mut dst));
+        assert_eq!(
+            dst,
+            [src[0], src[0], src[0], src[0], src[1], src[2], src[3]]
+        );
+
+        assert!(chop_cubic_at_length(&src, 90.0, 0.01, &mut dst));
+        assert_eq!(
+            dst,
+            [src[0], src[1], src[2], src[3], src[3], src[3], src[3]]
+        );
+
+        assert!(!chop_cubic_at_length(&src, 91.0, 0.01, &mut dst));
+        assert!(!chop_cubic_at_length(&src, -1.0, 0.01, &mut dst));
+        assert!(!chop_cubic_at_length(&src, f32::NAN, 0.01, &mut dst));
+    }
+
+    #[test]
+    fn chop_cubic_at_length_curve() {
+        // A quarter of a circle with a radius of 100.
+        let k = 55.228_475;
+        let src = [
+            Point::from_xy(100.0, 0.0),
+            Point::from_xy(100.0, k),
+            Point::from_xy(k, 100.0),
+            Point::from_xy(0.0, 100.0),
+        ];
+
+        let total = cubic_length(&cubic_derivative_coeff(&src), 0.0, 1.0, 0.001);
+        assert!((total - 157.08).abs() < 0.1);
+
+        // Symmetric, so the middle is at t=0.5.
+        let mut dst = [Point::zero(); 7];
+        assert!(chop_cubic_at_length(&src, total * 0.5, 0.001, &mut dst));
+        assert!((dst[3].x - dst[3].y).abs() < 0.01);
+
+        assert!(chop_cubic_at_length(&src, 40.0, 0.001, &mut dst));
+        let first = [dst[0], dst[1], dst[2], dst[3]];
+        let len = cubic_length(&cubic_derivative_coeff(&first), 0.0, 1.0, 0.0001);
+        assert!((len - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn chop_cubic_at_length_degenerate() {
+        let p = Point::from_xy(10.0, 10.0);
+        let src = [p, p, p, Point::from_xy(10.0, 10.001)];
+
+        let mut dst = [Point::zero(); 7];
+        assert!(chop_cubic_at_length(&src, 0.0, 0.01, &mut dst));
+        assert!(!chop_cubic_at_length(&src, 1.0, 0.01, &mut dst));
+
+        let src = [p; 4];
+        assert!(chop_cubic_at_length(&src, 0.0, 0.01, &mut dst));
+        assert_eq!(dst, [p; 7]);
+    }
+
     #[test]
     fn find_cubic_max_curvature_1() {
         let src = [
@@ -898,4 +1409,225 @@ mod tests {
             ]
         );
     }
+
+    fn quarter_circle(radius: f32) -> Conic {
+        Conic::new(
+            Point::from_xy(radius, 0.0),
+            Point::from_xy(radius, radius),
+            Point::from_xy(0.0, radius),
+            SCALAR_ROOT_2_OVER_2,
+        )
+    }
+
+    #[test]
+    fn conic_quad_pow2() {
+        // At least one subdivision is always used.
+        assert_eq!(quarter_circle(1.0).compute_quad_pow2(0.25), Some(1));
+        assert_eq!(quarter_circle(100.0).compute_quad_pow2(0.25), Some(3));
+        assert_eq!(quarter_circle(1000.0).compute_quad_pow2(0.25), Some(4));
+        assert_eq!(
+            quarter_circle(1_000_000.0).compute_quad_pow2(0.25),
+            Some(MAX_CONIC_TO_QUAD_POW2)
+        );
+
+        assert_eq!(quarter_circle(100.0).compute_quad_pow2(-1.0), None);
+        assert_eq!(quarter_circle(f32::INFINITY).compute_quad_pow2(0.25), None);
+    }
+
+    #[test]
+    fn chop_conic_into_quads() {
+        let conic = quarter_circle(100.0);
+        for pow2 in 0..=MAX_CONIC_TO_QUAD_POW2 {
+            let unset = Point::from_xy(-1.0, -1.0);
+            let mut points = [unset; MAX_CONIC_TO_QUAD_POINTS];
+            let count = conic.chop_into_quads_pow2(pow2, &mut points);
+            assert_eq!(count, 1 << pow2);
+
+            let len = 1 + 2 * count as usize;
+            assert_eq!(points[0], conic.points[0]);
+            assert_eq!(points[len - 1], conic.points[2]);
+            // All quad end points are on the circle.
+            for p in points[..len].iter().step_by(2) {
+                assert!((p.length() - 100.0).abs() < 0.01);
+            }
+            // Nothing is written past the needed storage.
+            assert!(points[len..].iter().all(|p| *p == unset));
+        }
+    }
+
+    #[test]
+    fn chop_conic_into_quads_non_finite() {
+        let conic = Conic::new(
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(1e30, 1e30),
+            Point::from_xy(2e30, 0.0),
+            1e10,
+        );
+
+        let mut points = [Point::zero(); MAX_CONIC_TO_QUAD_POINTS];
+        let count = conic.chop_into_quads_pow2(MAX_CONIC_TO_QUAD_POW2, &mut points);
+        let len = 1 + 2 * count as usize;
+
+        // Inner points are pinned to the middle of the hull.
+        assert_eq!(points[0], conic.points[0]);
+        assert_eq!(points[len - 1], conic.points[2]);
+        assert!(points[1..len - 1].iter().all(|p| *p == conic.points[1]));
+    }
+
+    #[test]
+    fn conic_quad_pow2_with_transform() {
+        let conic = quarter_circle(100.0);
+        let pow2 = |ts: Transform| compute_quad_pow2(&conic, &ts, 0.25);
+
+        assert_eq!(pow2(Transform::identity()), 3);
+        // Scaling up requires more quads and scaling down less.
+        assert_eq!(pow2(Transform::from_scale(10.0, 10.0)), 4);
+        assert_eq!(pow2(Transform::from_scale(0.01, 0.01)), 1);
+        // Translation and rotation do not affect the error.
+        assert_eq!(pow2(Transform::from_translate(1000.0, -1000.0)), 3);
+        assert_eq!(pow2(Transform::from_rotate(30.0)), 3);
+        // The largest axis is used for a non-uniform scale.
+        assert_eq!(pow2(Transform::from_scale(10.0, 0.1)), 4);
+        assert_eq!(pow2(Transform::from_scale(0.1, 10.0)), 4);
+        // Falls back to the maximum level.
+        assert_eq!(
+            pow2(Transform::from_scale(1e38, 1e38)),
+            MAX_CONIC_TO_QUAD_POW2
+        );
+        assert_eq!(
+            compute_quad_pow2(&conic, &Transform::identity(), -1.0),
+            MAX_CONIC_TO_QUAD_POW2
+        );
+    }
+
+    fn cubic(points: [(f32, f32); 4]) -> [Point; 4] {
+        [
+            Point::from_xy(points[0].0, points[0].1),
+            Point::from_xy(points[1].0, points[1].1),
+            Point::from_xy(points[2].0, points[2].1),
+            Point::from_xy(points[3].0, points[3].1),
+        ]
+    }
+
+    #[test]
+    fn classify_cubic_types() {
+        let src = cubic([(0.0, 0.0), (1.0, 2.0), (3.0, -2.0), (3.0, 0.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Serpentine);
+
+        let src = cubic([(0.0, 0.0), (3.0, 2.0), (-1.0, 2.0), (2.0, 0.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Loop);
+
+        let src = cubic([(0.0, 0.0), (1.0, 1.0), (0.0, 1.0), (1.0, 0.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Cusp);
+        assert!(find_cubic_cusp(&src).is_some());
+
+        // y = x^3, with a cusp at infinity.
+        let src = cubic([(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (3.0, 1.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Cusp);
+        assert!(find_cubic_cusp(&src).is_none());
+
+        // An elevated (0 0) (3 6) (6 0) quad.
+        let src = cubic([(0.0, 0.0), (2.0, 4.0), (4.0, 4.0), (6.0, 0.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Quadratic);
+
+        let src = cubic([(0.0, 0.0), (1.0, 1.0), (5.0, 5.0), (2.0, 2.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Line);
+
+        let src = cubic([(1.0, 1.0), (1.0, 1.0), (2.0, 2.0), (2.0, 2.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Line);
+
+        let src = cubic([(1.0, 1.0), (1.0, 1.0), (1.0, 1.0), (1.0, 1.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Point);
+
+        // Huge coordinates must not overflow.
+        let src = cubic([(0.0, 0.0), (1e30, 2e30), (3e30, -2e30), (3e30, 0.0)]);
+        assert_eq!(classify_cubic(&src), CubicType::Serpentine);
+
+        // Must not panic.
+        let inf = f32::INFINITY;
+        let src = cubic([(0.0, 0.0), (inf, 2.0), (f32::NAN, 2.0), (3.0, 0.0)]);
+        let _ = classify_cubic(&src);
+    }
+
+    #[test]
+    fn classify_cubic_matches_inflections() {
+        let coords = [-2.0, 0.0, 1.0, 3.0];
+        for &x1 in &coords {
+            for &y1 in &coords {
+                for &x2 in &coords {
+                    for &y2 in &coords {
+                        let src = cubic([(0.0, 0.0), (x1, y1), (x2, y2), (1.0, 0.0)]);
+                        let mut t_values = [NormalizedF32Exclusive::HALF; 3];
+                        let inflections = find_cubic_inflections(&src, &mut t_values).len();
+                        match classify_cubic(&src) {
+                            CubicType::Loop => assert_eq!(inflections, 0),
+                            CubicType::Cusp => assert!(inflections < 2),
+                            CubicType::Serpentine => {}
+                            _ => assert_eq!(inflections, 0),
+                        }
+
+                        if inflections == 2 {
+                            assert_eq!(classify_cubic(&src), CubicType::Serpentine);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn arc_to_cubics_split() {
+        let oval = Rect::from_ltrb(0.0, 0.0, 100.0, 100.0).unwrap();
+        let mut dst = [Point::zero(); 13];
+        assert_eq!(arc_to_cubics(oval, 0.0, 90.0, &mut dst), 1);
+        assert_eq!(arc_to_cubics(oval, 0.0, 90.00001, &mut dst), 1);
+        assert_eq!(arc_to_cubics(oval, 0.0, 91.0, &mut dst), 2);
+        assert_eq!(arc_to_cubics(oval, 0.0, -180.0, &mut dst), 2);
+        assert_eq!(arc_to_cubics(oval, 0.0, 360.0, &mut dst), 4);
+        assert_eq!(arc_to_cubics(oval, 0.0, 1000.0, &mut dst), 4);
+        assert_eq!(arc_to_cubics(oval, 0.0, 0.0, &mut dst), 0);
+        assert_eq!(arc_to_cubics(oval, f32::NAN, 90.0, &mut dst), 0);
+        assert_eq!(arc_to_cubics(oval, 0.0, f32::INFINITY, &mut dst), 0);
+        assert_eq!(arc_to_cubics(oval, 0.0, 360.0, &mut dst[..10]), 0);
+
+        // A quarter starting at the right-most point, going clockwise, i.e. down.
+        assert_eq!(arc_to_cubics(oval, 0.0, 90.0, &mut dst), 1);
+        let k = 50.0 * 0.5522847;
+        let expected = [
+            Point::from_xy(100.0, 50.0),
+            Point::from_xy(100.0, 50.0 + k),
+            Point::from_xy(50.0 + k, 100.0),
+            Point::from_xy(50.0, 100.0),
+        ];
+        for (p, e) in dst.iter().zip(expected.iter()) {
+            assert!(p.distance(*e) < 1e-3, "{:?}", p);
+        }
+
+        // Counter-clockwise goes up.
+        assert_eq!(arc_to_cubics(oval, 0.0, -90.0, &mut dst), 1);
+        assert!(dst[3].x.is_nearly_equal(50.0) && dst[3].y.is_nearly_zero());
+    }
+
+    #[test]
+    fn arc_to_cubics_error() {
+        let radius = 100.0;
+        let oval = Rect::from_ltrb(-radius, -radius, radius, radius).unwrap();
+        for &(start, sweep) in &[(0.0, 360.0), (30.0, 250.0), (-45.0, -300.0), (10.0, 45.0)] {
+            let mut dst = [Point::zero(); 13];
+            let count = arc_to_cubics(oval, start, sweep, &mut dst);
+
+            let end: f32 = start + sweep;
+            let end = Point::from_xy(end.to_radians().cos(), end.to_radians().sin());
+            assert!(dst[count * 3].distance(end.scaled(radius)) < 1e-3);
+
+            for i in 0..count {
+                let cubic = [dst[i * 3], dst[i * 3 + 1], dst[i * 3 + 2], dst[i * 3 + 3]];
+                for j in 0..=20 {
+                    let t = NormalizedF32::new_clamped(j as f32 / 20.0);
+                    let p = eval_cubic_pos_at(&cubic, t);
+                    assert!((p.length() - radius).abs() < 1e-3 * radius);
+                }
+            }
+        }
+    }
 }
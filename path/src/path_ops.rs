@@ -0,0 +1,284 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+// Overlaps are resolved by splitting a flattened path into horizontal bands
+// at each vertex and edge intersection. Edges cannot cross inside a band,
+// so filled spans can be found by simply walking edges from left to right
+// while accumulating the winding. Each span is then emitted as a trapezoid.
+//
+// The result contains only non-overlapping, clockwise contours,
+// therefore it fills identically using both fill rules.
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::{f32x2, Path, PathBuilder, PathSegment, Point};
+
+use crate::path_geometry::{CubicCoeff, QuadCoeff};
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use crate::NoStdFloat;
+
+// The maximum distance between a curve and its flattened approximation.
+const FLATTENING_TOLERANCE: f32 = 0.25;
+const MAX_CURVE_SEGMENTS: u32 = 256;
+
+impl Path {
+    /// Converts a path filled using the nonzero rule into one
+    /// that fills identically using the even-odd rule.
+    ///
+    /// Overlapping areas are merged, so the result has no self-intersections.
+    /// Curves are flattened into lines with a 0.25 tolerance.
+    ///
+    /// Returns `None` when the path doesn't fill anything
+    /// or when flattening produces non-finite coordinates.
+    pub fn to_even_odd_equivalent(&self) -> Option<Path> {
+        resolve_overlaps(self, |winding| winding != 0)
+    }
+
+    /// Converts a path filled using the even-odd rule into one
+    /// that fills identically using the nonzero rule.
+    ///
+    /// Overlapping areas are resolved, so the result has no self-intersections.
+    /// Curves are flattened into lines with a 0.25 tolerance.
+    ///
+    /// Returns `None` when the path doesn't fill anything
+    /// or when flattening produces non-finite coordinates.
+    pub fn to_nonzero_equivalent(&self) -> Option<Path> {
+        resolve_overlaps(self, |winding| winding % 2 != 0)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    // Always top to bottom.
+    p0: Point,
+    p1: Point,
+    winding: i32,
+}
+
+impl Edge {
+    fn new(p0: Point, p1: Point) -> Option<Self> {
+        // Horizontal edges do not affect the winding.
+        if p0.y == p1.y {
+            return None;
+        }
+
+        Some(if p0.y < p1.y {
+            Edge { p0, p1, winding: 1 }
+        } else {
+            Edge {
+                p0: p1,
+                p1: p0,
+                winding: -1,
+            }
+        })
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        if y <= self.p0.y {
+            self.p0.x
+        } else if y >= self.p1.y {
+            self.p1.x
+        } else {
+            let t = (y - self.p0.y) / (self.p1.y - self.p0.y);
+            self.p0.x + (self.p1.x - self.p0.x) * t
+        }
+    }
+
+    // Returns the Y coordinate of an intersection with another edge.
+    fn intersection_y(&self, other: &Edge) -> Option<f32> {
+        let top = self.p0.y.max(other.p0.y);
+        let bottom = self.p1.y.min(other.p1.y);
+        if top >= bottom {
+            return None;
+        }
+
+        let d_top = self.x_at(top) - other.x_at(top);
+        let d_bottom = self.x_at(bottom) - other.x_at(bottom);
+        if (d_top < 0.0 && d_bottom > 0.0) || (d_top > 0.0 && d_bottom < 0.0) {
+            let t = d_top / (d_top - d_bottom);
+            let y = top + (bottom - top) * t;
+            // Rounding errors can move the point outside the range.
+            if y > top && y < bottom {
+                return Some(y);
+            }
+        }
+
+        None
+    }
+}
+
+fn resolve_overlaps(path: &Path, is_filled: impl Fn(i32) -> bool) -> Option<Path> {
+    let edges = flatten(path)?;
+
+    let mut ys = Vec::with_capacity(edges.len() * 2);
+    for (i, edge) in edges.iter().enumerate() {
+        ys.push(edge.p0.y);
+        ys.push(edge.p1.y);
+
+        for other in &edges[i + 1..] {
+            if let Some(y) = edge.intersection_y(other) {
+                ys.push(y);
+            }
+        }
+    }
+
+    ys.sort_by(cmp_f32);
+    ys.dedup();
+
+    let mut pb = PathBuilder::new();
+    let mut band = Vec::new();
+    for pair in ys.windows(2) {
+        let (top, bottom) = (pair[0], pair[1]);
+        let middle = top + (bottom - top) * 0.5;
+
+        band.clear();
+        band.extend(
+            edges
+                .iter()
+                .filter(|e| e.p0.y <= top && e.p1.y >= bottom)
+                .map(|e| (e.x_at(middle), e)),
+        );
+        // Interpolation can still overflow.
+        if band.iter().any(|(x, _)| !x.is_finite()) {
+            return None;
+        }
+        band.sort_by(|a, b| cmp_f32(&a.0, &b.0));
+
+        let mut winding = 0;
+        let mut left: Option<&Edge> = None;
+        for (_, edge) in &band {
+            winding += edge.winding;
+            match (left, is_filled(winding)) {
+                (None, true) => left = Some(edge),
+                (Some(l), false) => {
+                    let (x0, x1) = (l.x_at(top), edge.x_at(top));
+                    let (x2, x3) = (edge.x_at(bottom), l.x_at(bottom));
+                    // Skip spans between coincident edges.
+                    if x0 < x1 || x3 < x2 {
+                        pb.move_to(x0, top);
+                        pb.line_to(x1, top);
+                        pb.line_to(x2, bottom);
+                        pb.line_to(x3, bottom);
+                        pb.close();
+                    }
+
+                    left = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pb.finish()
+}
+
+// Returns `None` when curves flattening overflows.
+fn flatten(path: &Path) -> Option<Vec<Edge>> {
+    let mut edges = Vec::new();
+    let mut is_finite = true;
+    flatten_lines(path, |p0, p1| {
+        // `Point::is_finite` would reject large, but finite coordinates.
+        is_finite &= [p0.x, p0.y, p1.x, p1.y].iter().all(|v| v.is_finite());
+        if let Some(edge) = Edge::new(p0, p1) {
+            edges.push(edge);
+        }
+    });
+
+    if is_finite {
+        Some(edges)
+    } else {
+        None
+    }
+}
+
+// Coordinates are finite, but unlike `partial_cmp().unwrap()` this never panics.
+fn cmp_f32(a: &f32, b: &f32) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Flattens a path into lines with a 0.25 tolerance.
+///
+/// All contours are implicitly closed.
+pub(crate) fn flatten_lines(path: &Path, mut push_line: impl FnMut(Point, Point)) {
+    let mut last_move_to = Point::zero();
+    let mut last = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                // All contours are implicitly closed for filling.
+                push_line(last, last_move_to);
+                last_move_to = p;
+                last = p;
+            }
+            PathSegment::LineTo(p) => {
+                push_line(last, p);
+                last = p;
+            }
+            PathSegment::QuadTo(p1, p2) => {
+                let points = [last, p1, p2];
+                let dev = second_difference(points[0], points[1], points[2]) * 0.25;
+                let coeff = QuadCoeff::from_points(&points);
+                let n = segments_count(dev);
+                let mut prev = last;
+                for i in 1..=n {
+                    let t = f32x2::splat(i as f32 / n as f32);
+                    let p = if i == n {
+                        p2
+                    } else {
+                        Point::from_f32x2(coeff.eval(t))
+                    };
+                    push_line(prev, p);
+                    prev = p;
+                }
+
+                last = p2;
+            }
+            PathSegment::CubicTo(p1, p2, p3) => {
+                let points = [last, p1, p2, p3];
+                let dev = second_difference(points[0], points[1], points[2])
+                    .max(second_difference(points[1], points[2], points[3]))
+                    * 0.75;
+                let coeff = CubicCoeff::from_points(&points);
+                let n = segments_count(dev);
+                let mut prev = last;
+                for i in 1..=n {
+                    let t = f32x2::splat(i as f32 / n as f32);
+                    let p = if i == n {
+                        p3
+                    } else {
+                        Point::from_f32x2(coeff.eval(t))
+                    };
+                    push_line(prev, p);
+                    prev = p;
+                }
+
+                last = p3;
+            }
+            PathSegment::Close => {
+                push_line(last, last_move_to);
+                last = last_move_to;
+            }
+        }
+    }
+
+    push_line(last, last_move_to);
+}
+
+fn second_difference(p0: Point, p1: Point, p2: Point) -> f32 {
+    (p0 - p1 - p1 + p2).length()
+}
+
+fn segments_count(deviation: f32) -> u32 {
+    let n = (deviation / FLATTENING_TOLERANCE).sqrt().ceil();
+    if n.is_finite() {
+        (n as u32).clamp(1, MAX_CURVE_SEGMENTS)
+    } else {
+        MAX_CURVE_SEGMENTS
+    }
+}
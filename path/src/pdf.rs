@@ -0,0 +1,97 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{Path, PathSegment, PathVerb, Point};
+
+impl Path {
+    /// Converts a path into PDF content stream path construction operators.
+    ///
+    /// Uses only `m`, `l`, `c`, `re` and `h` operators, one per line.
+    /// Quadratic curves are converted into cubic ones exactly.
+    /// A closed axis-aligned rectangle is written as a single `re` operator.
+    ///
+    /// Coordinates are written as is. Since PDF uses a y-up coordinate system,
+    /// use [`to_pdf_ops_flipped`](Path::to_pdf_ops_flipped) for y-down content.
+    ///
+    /// The output doesn't include a painting operator, like `f` or `S`.
+    pub fn to_pdf_ops(&self) -> String {
+        self.write_pdf_ops(None)
+    }
+
+    /// Converts a path into PDF content stream path construction operators
+    /// with the y axis flipped.
+    ///
+    /// Each point is written as `(x, height - y)`, which maps y-down content
+    /// of the specified height onto a y-up PDF page.
+    ///
+    /// See [`to_pdf_ops`](Path::to_pdf_ops) for details.
+    pub fn to_pdf_ops_flipped(&self, height: f32) -> String {
+        self.write_pdf_ops(Some(height))
+    }
+
+    fn write_pdf_ops(&self, flip_height: Option<f32>) -> String {
+        let map = |p: Point| match flip_height {
+            Some(h) => Point::from_xy(p.x, h - p.y),
+            None => p,
+        };
+
+        let mut s = String::new();
+
+        // `re` always produces a closed contour, therefore an open rect must be written as lines.
+        if self.verbs.last() == Some(&PathVerb::Close) {
+            if let Some(rect) = self.as_rect() {
+                let y = match flip_height {
+                    Some(h) => h - rect.bottom(),
+                    None => rect.y(),
+                };
+
+                let (x, w, h) = (rect.x(), rect.width(), rect.height());
+                let _ = writeln!(s, "{} {} {} {} re", x, y, w, h);
+                return s;
+            }
+        }
+
+        let mut iter = self.segments();
+        loop {
+            let p0 = iter.last_point();
+            let segment = match iter.next() {
+                Some(v) => v,
+                None => break,
+            };
+
+            // Writing into a `String` cannot fail.
+            let _ = match segment {
+                PathSegment::MoveTo(p) => {
+                    let p = map(p);
+                    writeln!(s, "{} {} m", p.x, p.y)
+                }
+                PathSegment::LineTo(p) => {
+                    let p = map(p);
+                    writeln!(s, "{} {} l", p.x, p.y)
+                }
+                PathSegment::QuadTo(p1, p2) => {
+                    // Degree elevation: the cubic control points are 2/3 of the way
+                    // from the end points to the quad control point.
+                    let c1 = p0 + (p1 - p0).scaled(2.0 / 3.0);
+                    let c2 = p2 + (p1 - p2).scaled(2.0 / 3.0);
+                    let (c1, c2, p) = (map(c1), map(c2), map(p2));
+                    writeln!(s, "{} {} {} {} {} {} c", c1.x, c1.y, c2.x, c2.y, p.x, p.y)
+                }
+                PathSegment::CubicTo(p1, p2, p) => {
+                    let (p1, p2, p) = (map(p1), map(p2), map(p));
+                    writeln!(s, "{} {} {} {} {} {} c", p1.x, p1.y, p2.x, p2.y, p.x, p.y)
+                }
+                PathSegment::Close => writeln!(s, "h"),
+            };
+        }
+
+        s
+    }
+}
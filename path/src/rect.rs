@@ -203,6 +203,22 @@ mod int_rect_tests {
     }
 }
 
+/// A rounding mode used to convert float coordinates into pixel ones.
+///
+/// Used by [`Transform::map_rect_to_int`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Rounding {
+    /// Each edge is rounded to the nearest integer, with halves rounded up.
+    ///
+    /// Unlike rounding the position and the size separately, adjacent rects
+    /// stay adjacent after rounding.
+    Round,
+    /// Rounds outwards. The result contains all pixels touched by the rect.
+    Out,
+    /// Rounds inwards. The result contains only pixels fully covered by the rect.
+    In,
+}
+
 /// A rectangle defined by left, top, right and bottom edges.
 ///
 /// Can have zero width and/or height. But not a negative one.
@@ -262,6 +278,21 @@ impl Rect {
         Rect::from_ltrb(x, y, w + x, h + y)
     }
 
+    /// Creates a new `Rect` from two opposite corners in any order.
+    ///
+    /// Useful when corners come from user input, like a drag selection.
+    /// Equal corners produce an empty rect.
+    ///
+    /// Returns `None` when any coordinate is not finite.
+    pub fn from_corners(a: Point, b: Point) -> Option<Self> {
+        // `min` and `max` ignore NaN.
+        if !(a.is_finite() && b.is_finite()) {
+            return None;
+        }
+
+        Rect::from_ltrb(a.x.min(b.x), a.y.min(b.y), a.x.max(b.x), a.y.max(b.y))
+    }
+
     /// Returns the left edge.
     pub fn left(&self) -> f32 {
         self.left.get()
@@ -307,7 +338,13 @@ impl Rect {
     /// Converts into an `IntRect` by adding 0.5 and discarding the fractional portion.
     ///
     /// Width and height are guarantee to be >= 1.
+    ///
+    /// Returns `None` when the rect has a zero width or height.
     pub fn round(&self) -> Option<IntRect> {
+        if self.is_empty() {
+            return None;
+        }
+
         IntRect::from_xywh(
             i32::saturate_round(self.x()),
             i32::saturate_round(self.y()),
@@ -318,19 +355,63 @@ impl Rect {
 
     /// Converts into an `IntRect` rounding outwards.
     ///
-    /// Width and height are guarantee to be >= 1.
+    /// The resulting rect always contains the original one.
+    ///
+    /// Returns `None` when the rect has a zero width or height
+    /// or when the result doesn't fit into `IntRect`.
     pub fn round_out(&self) -> Option<IntRect> {
-        IntRect::from_xywh(
-            i32::saturate_floor(self.x()),
-            i32::saturate_floor(self.y()),
-            core::cmp::max(1, i32::saturate_ceil(self.width()) as u32),
-            core::cmp::max(1, i32::saturate_ceil(self.height()) as u32),
+        if self.is_empty() {
+            return None;
+        }
+
+        IntRect::from_ltrb(
+            f32_to_i32(self.left().floor())?,
+            f32_to_i32(self.top().floor())?,
+            f32_to_i32(self.right().ceil())?,
+            f32_to_i32(self.bottom().ceil())?,
+        )
+    }
+
+    /// Converts into an `IntRect` rounding inwards.
+    ///
+    /// The resulting rect is always contained by the original one.
+    ///
+    /// Returns `None` when the rect doesn't contain any whole pixel
+    /// or when the result doesn't fit into `IntRect`.
+    pub fn round_in(&self) -> Option<IntRect> {
+        if self.is_empty() {
+            return None;
+        }
+
+        IntRect::from_ltrb(
+            f32_to_i32(self.left().ceil())?,
+            f32_to_i32(self.top().ceil())?,
+            f32_to_i32(self.right().floor())?,
+            f32_to_i32(self.bottom().floor())?,
         )
     }
 
+    // Rounds each edge to the nearest integer, with halves rounded up.
+    pub(crate) fn round_edges(&self) -> Option<IntRect> {
+        let round = |n: f32| f32_to_i32((n + 0.5).floor());
+        IntRect::from_ltrb(
+            round(self.left())?,
+            round(self.top())?,
+            round(self.right())?,
+            round(self.bottom())?,
+        )
+    }
+
+    /// Checks that the rect has a zero width or height.
+    pub fn is_empty(&self) -> bool {
+        self.left == self.right || self.top == self.bottom
+    }
+
     /// Returns an intersection of two rectangles.
     ///
-    /// Returns `None` otherwise.
+    /// Returns `None` when rectangles do not overlap.
+    /// Rectangles that only touch each other, aka have a zero area intersection,
+    /// are treated as non-overlapping.
     pub fn intersect(&self, other: &Self) -> Option<Self> {
         let left = self.x().max(other.x());
         let top = self.y().max(other.y());
@@ -338,7 +419,43 @@ impl Rect {
         let right = self.right().min(other.right());
         let bottom = self.bottom().min(other.bottom());
 
-        Rect::from_ltrb(left, top, right, bottom)
+        if left < right && top < bottom {
+            Rect::from_ltrb(left, top, right, bottom)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest rectangle that contains both rectangles.
+    ///
+    /// Returns `None` when the resulting size overflows `f32`.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        Rect::from_ltrb(
+            self.left().min(other.left()),
+            self.top().min(other.top()),
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        )
+    }
+
+    /// Checks that the rect contains the other one.
+    ///
+    /// Edges are inclusive.
+    pub fn contains(&self, other: &Self) -> bool {
+        self.left() <= other.left()
+            && self.top() <= other.top()
+            && self.right() >= other.right()
+            && self.bottom() >= other.bottom()
+    }
+
+    /// Checks that the rect contains a point.
+    ///
+    /// Left and top edges are inclusive, right and bottom are exclusive.
+    /// Therefore an empty rect never contains a point.
+    ///
+    /// Non-finite points are never contained.
+    pub fn contains_point(&self, p: Point) -> bool {
+        p.x >= self.left() && p.x < self.right() && p.y >= self.top() && p.y < self.bottom()
     }
 
     /// Creates a Rect from Point array.
@@ -367,7 +484,8 @@ impl Rect {
             offset += 2;
         }
 
-        let mut accum = f32x4::default();
+        // Initial points must be checked as well.
+        let mut accum = min * f32x4::default();
         while offset != points.len() {
             let pt0 = points[offset + 0];
             let pt1 = points[offset + 1];
@@ -411,16 +529,9 @@ impl Rect {
 
     /// Transforms the rect using the provided `Transform`.
     ///
-    /// This method is expensive.
+    /// Returns bounds of the transformed rect. Same as [`Transform::map_rect`].
     pub fn transform(&self, ts: Transform) -> Option<Self> {
-        if !ts.is_identity() {
-            // TODO: remove allocation
-            let mut path = PathBuilder::from_rect(*self);
-            path = path.transform(ts)?;
-            Some(path.bounds())
-        } else {
-            Some(*self)
-        }
+        ts.map_rect(*self)
     }
 
     /// Applies a bounding box transform.
@@ -438,6 +549,16 @@ impl Rect {
     }
 }
 
+/// Converts an integral `f32` into `i32`, without saturation.
+fn f32_to_i32(n: f32) -> Option<i32> {
+    // `i32::MAX as f32` is 2^31, which is already out of range.
+    if n >= i32::MIN as f32 && n < i32::MAX as f32 {
+        Some(n as i32)
+    } else {
+        None
+    }
+}
+
 fn checked_f32_sub(a: f32, b: f32) -> Option<f32> {
     debug_assert!(a.is_finite());
     debug_assert!(b.is_finite());
@@ -478,6 +599,47 @@ mod rect_tests {
         assert_eq!(rect.height(), 20.0);
     }
 
+    #[test]
+    fn intersect() {
+        let r1 = Rect::from_ltrb(0.0, 0.0, 10.0, 10.0).unwrap();
+        let r2 = Rect::from_ltrb(5.0, 5.0, 15.0, 15.0).unwrap();
+        assert_eq!(r1.intersect(&r2), Rect::from_ltrb(5.0, 5.0, 10.0, 10.0));
+
+        // Disjoint.
+        let r2 = Rect::from_ltrb(20.0, 20.0, 30.0, 30.0).unwrap();
+        assert_eq!(r1.intersect(&r2), None);
+
+        // Touching.
+        let r2 = Rect::from_ltrb(10.0, 0.0, 20.0, 10.0).unwrap();
+        assert_eq!(r1.intersect(&r2), None);
+        assert_eq!(r2.intersect(&r1), None);
+    }
+
+    #[test]
+    fn union() {
+        let r1 = Rect::from_ltrb(0.0, 0.0, 10.0, 10.0).unwrap();
+        let r2 = Rect::from_ltrb(20.0, -5.0, 30.0, 5.0).unwrap();
+        assert_eq!(r1.union(&r2), Rect::from_ltrb(0.0, -5.0, 30.0, 10.0));
+
+        let r1 = Rect::from_ltrb(-2.0e38, 0.0, 0.0, 1.0).unwrap();
+        let r2 = Rect::from_ltrb(0.0, 0.0, 2.0e38, 1.0).unwrap();
+        assert_eq!(r1.union(&r2), None);
+    }
+
+    #[test]
+    fn contains() {
+        let r1 = Rect::from_ltrb(0.0, 0.0, 10.0, 10.0).unwrap();
+        assert!(r1.contains(&r1));
+        assert!(r1.contains(&Rect::from_ltrb(2.0, 2.0, 8.0, 8.0).unwrap()));
+        assert!(!r1.contains(&Rect::from_ltrb(2.0, 2.0, 18.0, 8.0).unwrap()));
+
+        assert!(r1.contains_point(Point::from_xy(0.0, 0.0)));
+        assert!(r1.contains_point(Point::from_xy(5.0, 9.9)));
+        assert!(!r1.contains_point(Point::from_xy(10.0, 5.0)));
+        assert!(!r1.contains_point(Point::from_xy(f32::NAN, 5.0)));
+        assert!(!r1.contains_point(Point::from_xy(f32::INFINITY, 5.0)));
+    }
+
     #[test]
     fn round_overflow() {
         // minimum value that cause overflow
@@ -489,6 +651,85 @@ mod rect_tests {
         let rect = Rect::from_xywh(x, 0.0, width, 1.0).unwrap();
         assert_eq!(rect.round(), None);
         assert_eq!(rect.round_out(), None);
+        assert_eq!(rect.round_in(), None);
+    }
+
+    #[test]
+    fn round_out() {
+        let rect = Rect::from_ltrb(0.5, 0.2, 1.5, 10.7).unwrap();
+        assert_eq!(rect.round_out(), IntRect::from_ltrb(0, 0, 2, 11));
+
+        let rect = Rect::from_ltrb(-1.5, -0.2, -0.5, 0.3).unwrap();
+        assert_eq!(rect.round_out(), IntRect::from_ltrb(-2, -1, 0, 1));
+
+        let rect = Rect::from_ltrb(1.0, 2.0, 3.0, 4.0).unwrap();
+        assert_eq!(rect.round_out(), IntRect::from_ltrb(1, 2, 3, 4));
+    }
+
+    #[test]
+    fn round_in() {
+        let rect = Rect::from_ltrb(0.5, 0.2, 2.5, 10.7).unwrap();
+        assert_eq!(rect.round_in(), IntRect::from_ltrb(1, 1, 2, 10));
+
+        let rect = Rect::from_ltrb(1.0, 2.0, 3.0, 4.0).unwrap();
+        assert_eq!(rect.round_in(), IntRect::from_ltrb(1, 2, 3, 4));
+
+        // No whole pixels inside.
+        let rect = Rect::from_ltrb(0.2, 0.2, 0.8, 0.8).unwrap();
+        assert_eq!(rect.round_in(), None);
+    }
+
+    #[test]
+    fn round_empty() {
+        let rect = Rect::from_ltrb(1.0, 2.0, 1.0, 4.0).unwrap();
+        assert!(rect.is_empty());
+        assert_eq!(rect.round(), None);
+        assert_eq!(rect.round_out(), None);
+        assert_eq!(rect.round_in(), None);
+    }
+
+    #[test]
+    fn from_points() {
+        let p = |x, y| Point::from_xy(x, y);
+        assert_eq!(
+            Rect::from_points(&[p(10.0, 20.0), p(30.0, 5.0), p(15.0, 40.0)]),
+            Rect::from_ltrb(10.0, 5.0, 30.0, 40.0)
+        );
+
+        // Non-finite values must be detected in any position.
+        let nan = core::f32::NAN;
+        assert_eq!(Rect::from_points(&[p(nan, 20.0)]), None);
+        assert_eq!(Rect::from_points(&[p(10.0, 20.0), p(nan, 40.0)]), None);
+        assert_eq!(
+            Rect::from_points(&[p(10.0, 20.0), p(30.0, 40.0), p(nan, 40.0)]),
+            None
+        );
+    }
+
+    #[test]
+    fn from_corners() {
+        let p = |x, y| Point::from_xy(x, y);
+        let rect = Rect::from_ltrb(10.0, 5.0, 30.0, 40.0);
+        assert_eq!(Rect::from_corners(p(10.0, 5.0), p(30.0, 40.0)), rect);
+        assert_eq!(Rect::from_corners(p(30.0, 40.0), p(10.0, 5.0)), rect);
+        assert_eq!(Rect::from_corners(p(30.0, 5.0), p(10.0, 40.0)), rect);
+        assert_eq!(Rect::from_corners(p(10.0, 40.0), p(30.0, 5.0)), rect);
+
+        let rect = Rect::from_corners(p(10.0, 5.0), p(10.0, 5.0)).unwrap();
+        assert!(rect.is_empty());
+
+        let nan = core::f32::NAN;
+        assert_eq!(Rect::from_corners(p(nan, 5.0), p(10.0, 5.0)), None);
+        assert_eq!(Rect::from_corners(p(10.0, 5.0), p(10.0, nan)), None);
+    }
+
+    #[test]
+    fn int_rect_to_rect() {
+        let rect = IntRect::from_xywh(-1, 2, 3, 4).unwrap();
+        assert_eq!(
+            rect.to_rect(),
+            Rect::from_ltrb(-1.0, 2.0, 2.0, 6.0).unwrap()
+        );
     }
 }
 
@@ -625,6 +866,24 @@ impl NonZeroRect {
         Self::from_xywh(x, y, w, h).unwrap()
     }
 
+    /// Returns an intersection of two rectangles.
+    ///
+    /// Returns `None` when rectangles do not overlap or only touch each other.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        self.to_rect()
+            .intersect(&other.to_rect())
+            .and_then(|r| r.to_non_zero_rect())
+    }
+
+    /// Returns the smallest rectangle that contains both rectangles.
+    ///
+    /// Returns `None` when the resulting size overflows `f32`.
+    pub fn union(&self, other: &Self) -> Option<Self> {
+        self.to_rect()
+            .union(&other.to_rect())
+            .and_then(|r| r.to_non_zero_rect())
+    }
+
     /// Converts into [`Rect`].
     pub fn to_rect(&self) -> Rect {
         Rect::from_xywh(self.x(), self.y(), self.width(), self.height()).unwrap()
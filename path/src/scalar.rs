@@ -83,11 +83,13 @@ pub trait NoStdFloat {
     fn abs(self) -> Self;
     fn sin(self) -> Self;
     fn cos(self) -> Self;
+    fn tan(self) -> Self;
     fn ceil(self) -> Self;
     fn floor(self) -> Self;
     fn round(self) -> Self;
     fn powf(self, y: Self) -> Self;
     fn acos(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
 }
 
 #[cfg(all(not(feature = "std"), feature = "no-std-float"))]
@@ -107,6 +109,9 @@ impl NoStdFloat for f32 {
     fn cos(self) -> Self {
         libm::cosf(self)
     }
+    fn tan(self) -> Self {
+        libm::tanf(self)
+    }
     fn ceil(self) -> Self {
         libm::ceilf(self)
     }
@@ -122,6 +127,9 @@ impl NoStdFloat for f32 {
     fn acos(self) -> Self {
         libm::acosf(self)
     }
+    fn atan2(self, x: Self) -> Self {
+        libm::atan2f(self, x)
+    }
 }
 
 #[cfg(all(not(feature = "std"), feature = "no-std-float"))]
@@ -141,6 +149,9 @@ impl NoStdFloat for f64 {
     fn cos(self) -> Self {
         libm::cos(self)
     }
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
     fn ceil(self) -> Self {
         libm::ceil(self)
     }
@@ -156,6 +167,9 @@ impl NoStdFloat for f64 {
     fn acos(self) -> Self {
         libm::acos(self)
     }
+    fn atan2(self, x: Self) -> Self {
+        libm::atan2(self, x)
+    }
 }
 
 #[cfg(test)]
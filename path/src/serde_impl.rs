@@ -0,0 +1,338 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+//! `serde` support for types with invariants.
+//!
+//! Such types are serialized via plain data structs and validated during deserialization,
+//! therefore a malformed input produces an error instead of an inconsistent object.
+//! Plain types simply derive `Serialize` and `Deserialize`.
+
+use alloc::vec::Vec;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{FillRule, Path, PathVerb, Point, Rect, StrokeDash};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Rect")]
+struct RectData {
+    left: f32,
+    top: f32,
+    right: f32,
+    bottom: f32,
+}
+
+impl Serialize for Rect {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RectData {
+            left: self.left(),
+            top: self.top(),
+            right: self.right(),
+            bottom: self.bottom(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rect {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = RectData::deserialize(deserializer)?;
+        Rect::from_ltrb(data.left, data.top, data.right, data.bottom)
+            .ok_or_else(|| D::Error::custom("invalid rect"))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "StrokeDash")]
+struct StrokeDashData {
+    array: Vec<f32>,
+    offset: f32,
+}
+
+impl Serialize for StrokeDash {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StrokeDashData {
+            array: self.array.clone(),
+            offset: self.offset,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StrokeDash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = StrokeDashData::deserialize(deserializer)?;
+        StrokeDash::new(data.array, data.offset).ok_or_else(|| D::Error::custom("invalid dash"))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename = "Path")]
+struct PathDataRef<'a> {
+    verbs: &'a [PathVerb],
+    points: &'a [Point],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fill_rule: Option<FillRule>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Path")]
+struct PathData {
+    verbs: Vec<PathVerb>,
+    points: Vec<Point>,
+    #[serde(default)]
+    fill_rule: Option<FillRule>,
+}
+
+impl Serialize for Path {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        PathDataRef {
+            verbs: &self.verbs,
+            points: &self.points,
+            fill_rule: self.fill_rule,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Path {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = PathData::deserialize(deserializer)?;
+        validate_path(&data.verbs, &data.points).map_err(D::Error::custom)?;
+
+        let bounds = Rect::from_points(&data.points)
+            .ok_or_else(|| D::Error::custom("path bounds are too big"))?;
+
+        Ok(Path {
+            verbs: data.verbs,
+            points: data.points,
+            bounds,
+            fill_rule: data.fill_rule,
+        })
+    }
+}
+
+// Checks the same invariants that `PathBuilder` guarantees.
+fn validate_path(verbs: &[PathVerb], points: &[Point]) -> Result<(), &'static str> {
+    // Just like `PathBuilder::finish`, a single move to is not a path.
+    if verbs.len() < 2 {
+        return Err("a path must have at least two verbs");
+    }
+
+    if verbs[0] != PathVerb::Move {
+        return Err("a path must start with a move to");
+    }
+
+    let mut points_count = 0;
+    for (i, verb) in verbs.iter().enumerate() {
+        points_count += match verb {
+            PathVerb::Move => 1,
+            PathVerb::Line => 1,
+            PathVerb::Quad => 2,
+            PathVerb::Cubic => 3,
+            PathVerb::Close => 0,
+        };
+
+        // `PathBuilder` always injects a move to after a close.
+        if i > 0 && verbs[i - 1] == PathVerb::Close && *verb != PathVerb::Move {
+            return Err("a close must be followed by a move to");
+        }
+
+        // `PathBuilder` replaces the previous move to instead.
+        if i > 0 && verbs[i - 1] == PathVerb::Move && *verb == PathVerb::Move {
+            return Err("a move to must not be followed by a move to");
+        }
+    }
+
+    if points_count != points.len() {
+        return Err("verbs and points count mismatch");
+    }
+
+    if !points.iter().all(|p| p.is_finite()) {
+        return Err("path points must be finite");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathBuilder;
+    use alloc::vec;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    fn path_tokens(verbs: &[&'static str], points: &[(f32, f32)]) -> Vec<Token> {
+        let mut tokens = vec![
+            Token::Struct {
+                name: "Path",
+                len: 2,
+            },
+            Token::Str("verbs"),
+            Token::Seq {
+                len: Some(verbs.len()),
+            },
+        ];
+        for verb in verbs {
+            tokens.push(Token::UnitVariant {
+                name: "PathVerb",
+                variant: verb,
+            });
+        }
+        tokens.push(Token::SeqEnd);
+        tokens.push(Token::Str("points"));
+        tokens.push(Token::Seq {
+            len: Some(points.len()),
+        });
+        for (x, y) in points {
+            tokens.extend_from_slice(&[
+                Token::Struct {
+                    name: "Point",
+                    len: 2,
+                },
+                Token::Str("x"),
+                Token::F32(*x),
+                Token::Str("y"),
+                Token::F32(*y),
+                Token::StructEnd,
+            ]);
+        }
+        tokens.push(Token::SeqEnd);
+        tokens.push(Token::StructEnd);
+        tokens
+    }
+
+    #[test]
+    fn path() {
+        let mut pb = PathBuilder::new();
+        pb.move_to(10.0, 20.0);
+        pb.quad_to(30.0, 40.0, 50.0, 60.0);
+        pb.close();
+        let path = pb.finish().unwrap();
+
+        let tokens = path_tokens(
+            &["Move", "Quad", "Close"],
+            &[(10.0, 20.0), (30.0, 40.0), (50.0, 60.0)],
+        );
+        assert_tokens(&path, &tokens);
+    }
+
+    #[test]
+    fn path_with_fill_rule() {
+        let mut pb = PathBuilder::new();
+        pb.move_to(10.0, 20.0);
+        pb.line_to(30.0, 40.0);
+        let mut path = pb.finish().unwrap();
+        path.set_fill_rule(Some(FillRule::EvenOdd));
+
+        let mut tokens = path_tokens(&["Move", "Line"], &[(10.0, 20.0), (30.0, 40.0)]);
+        tokens[0] = Token::Struct {
+            name: "Path",
+            len: 3,
+        };
+        let end = tokens.len() - 1;
+        tokens.splice(
+            end..end,
+            [
+                Token::Str("fill_rule"),
+                Token::Some,
+                Token::UnitVariant {
+                    name: "FillRule",
+                    variant: "EvenOdd",
+                },
+            ],
+        );
+        assert_tokens(&path, &tokens);
+    }
+
+    #[test]
+    fn invalid_path() {
+        assert_de_tokens_error::<Path>(
+            &path_tokens(&["Move"], &[(10.0, 20.0)]),
+            "a path must have at least two verbs",
+        );
+        assert_de_tokens_error::<Path>(
+            &path_tokens(&["Line", "Line"], &[(10.0, 20.0), (30.0, 40.0)]),
+            "a path must start with a move to",
+        );
+        assert_de_tokens_error::<Path>(
+            &path_tokens(&["Move", "Cubic"], &[(10.0, 20.0), (30.0, 40.0)]),
+            "verbs and points count mismatch",
+        );
+        assert_de_tokens_error::<Path>(
+            &path_tokens(
+                &["Move", "Line", "Close", "Line"],
+                &[(10.0, 20.0), (30.0, 40.0), (50.0, 60.0)],
+            ),
+            "a close must be followed by a move to",
+        );
+        assert_de_tokens_error::<Path>(
+            &path_tokens(
+                &["Move", "Move", "Line"],
+                &[(10.0, 20.0), (30.0, 40.0), (50.0, 60.0)],
+            ),
+            "a move to must not be followed by a move to",
+        );
+        assert_de_tokens_error::<Path>(
+            &path_tokens(&["Move", "Line"], &[(10.0, 20.0), (f32::NAN, 40.0)]),
+            "path points must be finite",
+        );
+    }
+
+    #[test]
+    fn rect() {
+        let rect = Rect::from_ltrb(10.0, 20.0, 30.0, 40.0).unwrap();
+        let tokens = [
+            Token::Struct {
+                name: "Rect",
+                len: 4,
+            },
+            Token::Str("left"),
+            Token::F32(10.0),
+            Token::Str("top"),
+            Token::F32(20.0),
+            Token::Str("right"),
+            Token::F32(30.0),
+            Token::Str("bottom"),
+            Token::F32(40.0),
+            Token::StructEnd,
+        ];
+        assert_tokens(&rect, &tokens);
+
+        // Right is less than left.
+        let mut tokens = tokens;
+        tokens[6] = Token::F32(0.0);
+        assert_de_tokens_error::<Rect>(&tokens, "invalid rect");
+    }
+
+    #[test]
+    fn dash() {
+        let dash = StrokeDash::new(vec![5.0, 10.0], 2.0).unwrap();
+        let tokens = [
+            Token::Struct {
+                name: "StrokeDash",
+                len: 2,
+            },
+            Token::Str("array"),
+            Token::Seq { len: Some(2) },
+            Token::F32(5.0),
+            Token::F32(10.0),
+            Token::SeqEnd,
+            Token::Str("offset"),
+            Token::F32(2.0),
+            Token::StructEnd,
+        ];
+        assert_tokens(&dash, &tokens);
+
+        // An odd number of values.
+        let mut tokens = tokens.to_vec();
+        tokens.remove(4);
+        tokens[2] = Token::Seq { len: Some(1) };
+        assert_de_tokens_error::<StrokeDash>(&tokens, "invalid dash");
+    }
+}
@@ -87,6 +87,11 @@ impl IntSize {
     pub fn to_int_rect(&self, x: i32, y: i32) -> IntRect {
         IntRect::from_xywh(x, y, self.width(), self.height()).unwrap()
     }
+
+    /// Converts into [`Rect`] at the provided position.
+    pub fn to_rect(&self, x: f32, y: f32) -> Option<Rect> {
+        Rect::from_xywh(x, y, self.width() as f32, self.height() as f32)
+    }
 }
 
 fn size_scale(s1: IntSize, s2: IntSize, expand: bool) -> IntSize {
@@ -120,6 +125,7 @@ mod tests {
             size.to_int_rect(1, 2),
             IntRect::from_xywh(1, 2, 3, 4).unwrap()
         );
+        assert_eq!(size.to_rect(1.0, 2.0), Rect::from_xywh(1.0, 2.0, 3.0, 4.0));
     }
 }
 
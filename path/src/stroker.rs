@@ -36,6 +36,7 @@ impl<'a> SwappableBuilders<'a> {
 
 /// Stroke properties.
 #[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stroke {
     /// A stroke thickness.
     ///
@@ -81,6 +82,7 @@ impl Default for Stroke {
 
 /// Draws at the beginning and end of an open path contour.
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineCap {
     /// No stroke extension.
     Butt,
@@ -109,6 +111,7 @@ impl Default for LineCap {
 /// not contain the actual join. For instance, a fill path constructed with round joins does
 /// not necessarily include circles at each connected segment.
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineJoin {
     /// Extends to miter limit.
     Miter,
@@ -124,6 +127,21 @@ impl Default for LineJoin {
     }
 }
 
+/// Stroking statistics.
+///
+/// Useful for debugging and tuning stroke properties.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct StrokeStats {
+    /// Number of joins between segments, including nearly straight ones.
+    pub joins: u32,
+    /// Number of miter joins that were drawn beveled because of the miter limit.
+    ///
+    /// With a miter limit <= 1, all miter joins are beveled.
+    pub miter_fallbacks: u32,
+    /// Number of caps, aka two per each open contour.
+    pub caps: u32,
+}
+
 const QUAD_RECURSIVE_LIMIT: usize = 3;
 
 // quads with extreme widths (e.g. (0,1) (1,6) (0,3) width=5e7) recurse to point of failure
@@ -148,7 +166,7 @@ type JoinProc = fn(
     prev_is_line: bool,
     curr_is_line: bool,
     builders: SwappableBuilders,
-);
+) -> bool; // `true` when a miter join was beveled
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
 enum ReductionType {
@@ -230,6 +248,8 @@ pub struct PathStroker {
     recursion_depth: i32, // track stack depth to abort if numerics run amok
     found_tangents: bool, // do less work until tangents meet (cubic)
     join_completed: bool, // previous join was not degenerate
+
+    stats: StrokeStats,
 }
 
 impl Default for PathStroker {
@@ -273,6 +293,8 @@ impl PathStroker {
             recursion_depth: 0,
             found_tangents: false,
             join_completed: false,
+
+            stats: StrokeStats::default(),
         }
     }
 
@@ -304,8 +326,20 @@ impl PathStroker {
     ///
     /// `resolution_scale` can be obtained via
     /// [`compute_resolution_scale`](Self::compute_resolution_scale).
+    ///
+    /// Returns `None` when the stroke width is not finite or not positive,
+    /// the miter limit is NaN or `resolution_scale` is not finite or not positive.
     pub fn stroke(&mut self, path: &Path, stroke: &Stroke, resolution_scale: f32) -> Option<Path> {
+        self.stats = StrokeStats::default();
         let width = NonZeroPositiveF32::new(stroke.width)?;
+        if stroke.miter_limit.is_nan() {
+            return None;
+        }
+
+        if !(resolution_scale.is_finite() && resolution_scale > 0.0) {
+            return None;
+        }
+
         self.stroke_inner(
             path,
             width,
@@ -316,6 +350,11 @@ impl PathStroker {
         )
     }
 
+    /// Returns statistics of the last [`stroke`](Self::stroke) call.
+    pub fn stats(&self) -> StrokeStats {
+        self.stats
+    }
+
     fn stroke_inner(
         &mut self,
         path: &Path,
@@ -328,10 +367,12 @@ impl PathStroker {
         // TODO: stroke_rect optimization
 
         let mut inv_miter_limit = 0.0;
+        let mut miter_as_bevel = false;
 
         if line_join == LineJoin::Miter {
             if miter_limit <= 1.0 {
                 line_join = LineJoin::Bevel;
+                miter_as_bevel = true;
             } else {
                 inv_miter_limit = miter_limit.invert();
             }
@@ -359,7 +400,11 @@ impl PathStroker {
         self.prev_is_line = false;
 
         self.capper = cap_factory(line_cap);
-        self.joiner = join_factory(line_join);
+        self.joiner = if miter_as_bevel {
+            miter_fallback_joiner
+        } else {
+            join_factory(line_join)
+        };
 
         // Need some estimate of how large our final result (fOuter)
         // and our per-contour temp (fInner) will be, so we don't spend
@@ -376,6 +421,12 @@ impl PathStroker {
 
         self.cusper.clear();
 
+        // Round joins and caps are added as conics, which must be smooth at the output resolution.
+        let ts = Transform::from_scale(res_scale, res_scale);
+        self.inner.set_tessellation_transform(ts);
+        self.outer.set_tessellation_transform(ts);
+        self.cusper.set_tessellation_transform(ts);
+
         self.stroke_type = StrokeType::Outer;
 
         self.recursion_depth = 0;
@@ -847,7 +898,7 @@ impl PathStroker {
     fn finish_contour(&mut self, close: bool, curr_is_line: bool) {
         if self.segment_count > 0 {
             if close {
-                (self.joiner)(
+                let beveled = (self.joiner)(
                     self.prev_unit_normal,
                     self.prev_pt,
                     self.first_unit_normal,
@@ -857,6 +908,7 @@ impl PathStroker {
                     curr_is_line,
                     self.builders(),
                 );
+                self.count_join(beveled);
                 self.outer.close();
 
                 // now add inner as its own contour
@@ -897,6 +949,7 @@ impl PathStroker {
                     &mut self.outer,
                 );
                 self.outer.close();
+                self.stats.caps += 2;
             }
 
             if !self.cusper.is_empty() {
@@ -954,7 +1007,7 @@ impl PathStroker {
             self.inner.move_to(prev_x - normal.x, prev_y - normal.y);
         } else {
             // we have a previous segment
-            (self.joiner)(
+            let beveled = (self.joiner)(
                 self.prev_unit_normal,
                 self.prev_pt,
                 *unit_normal,
@@ -964,11 +1017,19 @@ impl PathStroker {
                 curr_is_line,
                 self.builders(),
             );
+            self.count_join(beveled);
         }
         self.prev_is_line = curr_is_line;
         true
     }
 
+    fn count_join(&mut self, beveled: bool) {
+        self.stats.joins += 1;
+        if beveled {
+            self.stats.miter_fallbacks += 1;
+        }
+    }
+
     fn post_join_to(&mut self, p: Point, normal: Point, unit_normal: Point) {
         self.join_completed = true;
         self.prev_pt = p;
@@ -1390,7 +1451,7 @@ fn bevel_joiner(
     _: bool,
     _: bool,
     mut builders: SwappableBuilders,
-) {
+) -> bool {
     let mut after = after_unit_normal.scaled(radius);
 
     if !is_clockwise(before_unit_normal, after_unit_normal) {
@@ -1400,6 +1461,31 @@ fn bevel_joiner(
 
     builders.outer.line_to(pivot.x + after.x, pivot.y + after.y);
     handle_inner_join(pivot, after, builders.inner);
+    false
+}
+
+// Used for miter joins with a miter limit <= 1, which are always beveled.
+fn miter_fallback_joiner(
+    before_unit_normal: Point,
+    pivot: Point,
+    after_unit_normal: Point,
+    radius: f32,
+    inv_miter_limit: f32,
+    prev_is_line: bool,
+    curr_is_line: bool,
+    builders: SwappableBuilders,
+) -> bool {
+    bevel_joiner(
+        before_unit_normal,
+        pivot,
+        after_unit_normal,
+        radius,
+        inv_miter_limit,
+        prev_is_line,
+        curr_is_line,
+        builders,
+    );
+    true
 }
 
 fn round_joiner(
@@ -1411,12 +1497,12 @@ fn round_joiner(
     _: bool,
     _: bool,
     mut builders: SwappableBuilders,
-) {
+) -> bool {
     let dot_prod = before_unit_normal.dot(after_unit_normal);
     let angle_type = dot_to_angle_type(dot_prod);
 
     if angle_type == AngleType::NearlyLine {
-        return;
+        return false;
     }
 
     let mut before = before_unit_normal;
@@ -1444,6 +1530,8 @@ fn round_joiner(
         after.scale(radius);
         handle_inner_join(pivot, after, builders.inner);
     }
+
+    false
 }
 
 fn miter_joiner(
@@ -1455,7 +1543,7 @@ fn miter_joiner(
     prev_is_line: bool,
     mut curr_is_line: bool,
     mut builders: SwappableBuilders,
-) {
+) -> bool {
     fn do_blunt(
         builders: SwappableBuilders,
         pivot: Point,
@@ -1499,13 +1587,13 @@ fn miter_joiner(
     let mut mid;
 
     if angle_type == AngleType::NearlyLine {
-        return;
+        return false;
     }
 
     if angle_type == AngleType::Nearly180 {
         curr_is_line = false;
         do_blunt(builders, pivot, radius, curr_is_line, after);
-        return;
+        return true;
     }
 
     let ccw = !is_clockwise(before, after);
@@ -1531,7 +1619,7 @@ fn miter_joiner(
             mid,
             after,
         );
-        return;
+        return false;
     }
 
     // midLength = radius / sinHalfAngle
@@ -1545,7 +1633,7 @@ fn miter_joiner(
     if sin_half_angle < inv_miter_limit {
         curr_is_line = false;
         do_blunt(builders, pivot, radius, curr_is_line, after);
-        return;
+        return true;
     }
 
     // choose the most accurate way to form the initial mid-vector
@@ -1568,6 +1656,7 @@ fn miter_joiner(
         mid,
         after,
     );
+    false
 }
 
 fn set_normal_unit_normal(
@@ -1928,6 +2017,7 @@ fn cubic_in_line(cubic: &[Point; 4]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Rect;
 
     impl PathSegment {
         fn new_move_to(x: f32, y: f32) -> Self {
@@ -2079,4 +2169,59 @@ mod tests {
 
         assert!(PathStroker::new().stroke(&path, &stroke, 1.0).is_some());
     }
+
+    #[test]
+    fn stats() {
+        // Two sharp joins.
+        let mut pb = PathBuilder::new();
+        pb.move_to(0.0, 0.0);
+        pb.line_to(100.0, 0.0);
+        pb.line_to(0.0, 10.0);
+        pb.line_to(100.0, 20.0);
+        let zigzag = pb.finish().unwrap();
+
+        let rect = PathBuilder::from_rect(Rect::from_xywh(10.0, 10.0, 50.0, 50.0).unwrap());
+
+        let mut stroker = PathStroker::new();
+        let mut stroke = Stroke::default();
+        stroke.width = 4.0;
+
+        stroker.stroke(&zigzag, &stroke, 1.0).unwrap();
+        assert_eq!(
+            stroker.stats(),
+            StrokeStats { joins: 2, miter_fallbacks: 2, caps: 2 }
+        );
+
+        // Right angles are within the default miter limit.
+        stroker.stroke(&rect, &stroke, 1.0).unwrap();
+        assert_eq!(
+            stroker.stats(),
+            StrokeStats { joins: 4, miter_fallbacks: 0, caps: 0 }
+        );
+
+        // Miter length of a right angle is sqrt(2).
+        stroke.miter_limit = 1.5;
+        stroker.stroke(&rect, &stroke, 1.0).unwrap();
+        assert_eq!(stroker.stats().miter_fallbacks, 0);
+        stroke.miter_limit = 1.4;
+        stroker.stroke(&rect, &stroke, 1.0).unwrap();
+        assert_eq!(stroker.stats().miter_fallbacks, 4);
+
+        stroke.miter_limit = 1.0;
+        stroker.stroke(&rect, &stroke, 1.0).unwrap();
+        assert_eq!(stroker.stats().miter_fallbacks, 4);
+
+        // Only miter joins can fall back.
+        stroke.line_join = LineJoin::Round;
+        stroker.stroke(&zigzag, &stroke, 1.0).unwrap();
+        assert_eq!(
+            stroker.stats(),
+            StrokeStats { joins: 2, miter_fallbacks: 0, caps: 2 }
+        );
+
+        // Stats are reset even when stroking fails.
+        stroke.width = 0.0;
+        assert!(stroker.stroke(&zigzag, &stroke, 1.0).is_none());
+        assert_eq!(stroker.stats(), StrokeStats::default());
+    }
 }
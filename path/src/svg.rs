@@ -0,0 +1,284 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{Path, PathBuilder, PathSegment, Point};
+
+impl Path {
+    /// Parses a path from an SVG path data string.
+    ///
+    /// Supports all SVG commands, both absolute and relative, and implicitly
+    /// repeated commands. Arcs are added via [`PathBuilder::svg_arc_to`].
+    ///
+    /// Returns `None` when the data is malformed or the resulting path is empty.
+    pub fn from_svg(data: &str) -> Option<Path> {
+        let mut s = Stream::new(data);
+        let mut pb = PathBuilder::new();
+
+        let mut command = 0;
+        // The current point and the start of the current contour.
+        let mut current = Point::zero();
+        let mut start = Point::zero();
+        // The last control point of a previous cubic or quad, for smooth curves.
+        let mut prev_cubic: Option<Point> = None;
+        let mut prev_quad: Option<Point> = None;
+
+        s.skip_spaces();
+        while !s.at_end() {
+            let c = s.curr_byte();
+            if c.is_ascii_alphabetic() {
+                // Path data must start with a move to.
+                if command == 0 && c != b'M' && c != b'm' {
+                    return None;
+                }
+
+                s.advance(1);
+                command = c;
+            } else if command == 0 || command == b'Z' || command == b'z' || !s.starts_number() {
+                // Only a command can be repeated implicitly, unless it's a close path.
+                return None;
+            } else if command == b'M' {
+                // Subsequent pairs after a move to are treated as line to.
+                command = b'L';
+            } else if command == b'm' {
+                command = b'l';
+            }
+
+            let relative = command.is_ascii_lowercase();
+            let base = if relative { current } else { Point::zero() };
+            let mut next_cubic = None;
+            let mut next_quad = None;
+            match command.to_ascii_uppercase() {
+                b'M' => {
+                    let p = s.parse_point(base)?;
+                    pb.move_to(p.x, p.y);
+                    current = p;
+                    start = p;
+                }
+                b'L' => {
+                    let p = s.parse_point(base)?;
+                    pb.line_to(p.x, p.y);
+                    current = p;
+                }
+                b'H' => {
+                    current.x = s.parse_list_number()? + base.x;
+                    pb.line_to(current.x, current.y);
+                }
+                b'V' => {
+                    current.y = s.parse_list_number()? + base.y;
+                    pb.line_to(current.x, current.y);
+                }
+                b'C' => {
+                    let p1 = s.parse_point(base)?;
+                    let p2 = s.parse_point(base)?;
+                    let p = s.parse_point(base)?;
+                    pb.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                    next_cubic = Some(p2);
+                    current = p;
+                }
+                b'S' => {
+                    let p1 = reflect(prev_cubic, current);
+                    let p2 = s.parse_point(base)?;
+                    let p = s.parse_point(base)?;
+                    pb.cubic_to(p1.x, p1.y, p2.x, p2.y, p.x, p.y);
+                    next_cubic = Some(p2);
+                    current = p;
+                }
+                b'Q' => {
+                    let p1 = s.parse_point(base)?;
+                    let p = s.parse_point(base)?;
+                    pb.quad_to(p1.x, p1.y, p.x, p.y);
+                    next_quad = Some(p1);
+                    current = p;
+                }
+                b'T' => {
+                    let p1 = reflect(prev_quad, current);
+                    let p = s.parse_point(base)?;
+                    pb.quad_to(p1.x, p1.y, p.x, p.y);
+                    next_quad = Some(p1);
+                    current = p;
+                }
+                b'A' => {
+                    let rx = s.parse_list_number()?;
+                    let ry = s.parse_list_number()?;
+                    let angle = s.parse_list_number()?;
+                    let large_arc = s.parse_flag()?;
+                    let sweep = s.parse_flag()?;
+                    let p = s.parse_point(base)?;
+                    pb.svg_arc_to(rx, ry, angle, large_arc, sweep, p.x, p.y);
+                    current = p;
+                }
+                b'Z' => {
+                    pb.close();
+                    current = start;
+                }
+                _ => return None,
+            }
+
+            prev_cubic = next_cubic;
+            prev_quad = next_quad;
+            s.skip_spaces();
+        }
+
+        pb.finish()
+    }
+
+    /// Converts a path into an SVG path data string.
+    ///
+    /// Uses only absolute `M`, `L`, `Q`, `C` and `Z` commands.
+    /// Numbers are written with the shortest precision that parses back
+    /// to the same `f32`, therefore `Path::from_svg` produces an identical path.
+    pub fn to_svg(&self) -> String {
+        let mut s = String::new();
+        for segment in self.segments() {
+            // Writing into a `String` cannot fail.
+            let _ = match segment {
+                PathSegment::MoveTo(p) => write!(s, "M {} {} ", p.x, p.y),
+                PathSegment::LineTo(p) => write!(s, "L {} {} ", p.x, p.y),
+                PathSegment::QuadTo(p1, p) => write!(s, "Q {} {} {} {} ", p1.x, p1.y, p.x, p.y),
+                PathSegment::CubicTo(p1, p2, p) => {
+                    write!(s, "C {} {} {} {} {} {} ", p1.x, p1.y, p2.x, p2.y, p.x, p.y)
+                }
+                PathSegment::Close => write!(s, "Z "),
+            };
+        }
+
+        s.pop(); // trailing space
+        s
+    }
+}
+
+// Reflects a previous control point around the current point.
+// Without a previous curve, the current point is used.
+fn reflect(ctrl: Option<Point>, current: Point) -> Point {
+    match ctrl {
+        Some(p) => Point::from_xy(2.0 * current.x - p.x, 2.0 * current.y - p.y),
+        None => current,
+    }
+}
+
+struct Stream<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Stream<'a> {
+    fn new(text: &'a str) -> Self {
+        Stream {
+            data: text.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn curr_byte(&self) -> u8 {
+        self.data.get(self.pos).cloned().unwrap_or(0)
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.pos += n;
+    }
+
+    fn skip_spaces(&mut self) {
+        while !self.at_end() && matches!(self.curr_byte(), b' ' | b'\t' | b'\n' | b'\r' | b'\x0C') {
+            self.advance(1);
+        }
+    }
+
+    fn skip_spaces_and_comma(&mut self) {
+        self.skip_spaces();
+        if self.curr_byte() == b',' {
+            self.advance(1);
+            self.skip_spaces();
+        }
+    }
+
+    fn starts_number(&self) -> bool {
+        matches!(self.curr_byte(), b'0'..=b'9' | b'.' | b'-' | b'+')
+    }
+
+    fn skip_digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.curr_byte().is_ascii_digit() {
+            self.advance(1);
+        }
+
+        self.pos - start
+    }
+
+    // Parses a number using the SVG grammar, where "-1.5.5" is three numbers: -1.5 and .5.
+    fn parse_number(&mut self) -> Option<f32> {
+        self.skip_spaces();
+        let start = self.pos;
+
+        if matches!(self.curr_byte(), b'-' | b'+') {
+            self.advance(1);
+        }
+
+        let mut digits = self.skip_digits();
+        if self.curr_byte() == b'.' {
+            self.advance(1);
+            digits += self.skip_digits();
+        }
+
+        if digits == 0 {
+            return None;
+        }
+
+        // An exponent must be followed by digits, otherwise it's not a part of the number.
+        if matches!(self.curr_byte(), b'e' | b'E') {
+            let mantissa_end = self.pos;
+            self.advance(1);
+            if matches!(self.curr_byte(), b'-' | b'+') {
+                self.advance(1);
+            }
+
+            if self.skip_digits() == 0 {
+                self.pos = mantissa_end;
+            }
+        }
+
+        let text = core::str::from_utf8(&self.data[start..self.pos]).ok()?;
+        let n: f32 = text.parse().ok()?;
+        if n.is_finite() {
+            Some(n)
+        } else {
+            None
+        }
+    }
+
+    fn parse_list_number(&mut self) -> Option<f32> {
+        let n = self.parse_number()?;
+        self.skip_spaces_and_comma();
+        Some(n)
+    }
+
+    fn parse_point(&mut self, base: Point) -> Option<Point> {
+        let x = self.parse_list_number()?;
+        let y = self.parse_list_number()?;
+        Some(Point::from_xy(base.x + x, base.y + y))
+    }
+
+    // Flags are single characters and can be written without separators, like "a1 1 0 00 1 1".
+    fn parse_flag(&mut self) -> Option<bool> {
+        self.skip_spaces();
+        let flag = match self.curr_byte() {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+
+        self.advance(1);
+        self.skip_spaces_and_comma();
+        Some(flag)
+    }
+}
@@ -4,7 +4,7 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
-use crate::{NonZeroRect, Point};
+use crate::{IntRect, NonZeroRect, Point, Rect, Rounding, TinySkiaError};
 
 use crate::scalar::{Scalar, SCALAR_NEARLY_ZERO};
 
@@ -15,9 +15,11 @@ use crate::NoStdFloat;
 ///
 /// Unlike other types, doesn't guarantee to be valid. This is Skia quirk.
 /// Meaning Transform(0, 0, 0, 0, 0, 0) is ok, while it's technically not.
-/// Non-finite values are also not an error.
+/// Non-finite values are also not an error, but such transforms cannot be inverted
+/// and all drawing and path methods will reject them.
 #[allow(missing_docs)]
 #[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     pub sx: f32,
     pub kx: f32,
@@ -98,12 +100,72 @@ impl Transform {
         ts
     }
 
+    /// Creates a new `Transform` flipping the Y axis of an area with the specified height.
+    ///
+    /// Converts from a y-up coordinate system, like in OpenGL or PDF,
+    /// with the origin at the bottom-left corner, into the y-down one used by tiny-skia.
+    /// And vice versa, since the transform is its own inverse.
+    ///
+    /// Usually, `height` is the height of a pixmap or a page.
+    pub fn from_flip_y(height: f32) -> Self {
+        Transform::from_row(1.0, 0.0, 0.0, -1.0, 0.0, height)
+    }
+
     /// Converts `Rect` into a bounding box `Transform`.
     #[inline]
     pub fn from_bbox(bbox: NonZeroRect) -> Self {
         Transform::from_row(bbox.width(), 0.0, 0.0, bbox.height(), bbox.x(), bbox.y())
     }
 
+    /// Creates a transform that maps `src` points onto `dst` points.
+    ///
+    /// Since `Transform` is affine, only a triangle-to-triangle mapping is possible.
+    ///
+    /// Returns `None` when `src` points are collinear.
+    ///
+    /// See [`try_from_poly_to_poly`](Transform::try_from_poly_to_poly) for error details.
+    pub fn from_poly_to_poly(src: &[Point; 3], dst: &[Point; 3]) -> Option<Self> {
+        Transform::try_from_poly_to_poly(src, dst).ok()
+    }
+
+    /// Creates a transform that maps `src` points onto `dst` points.
+    ///
+    /// Since `Transform` is affine, only a triangle-to-triangle mapping is possible.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::NonFinite`] when any point is not finite
+    ///   or the resulting transform overflows.
+    /// - [`TinySkiaError::NotInvertible`] when `src` points are collinear.
+    pub fn try_from_poly_to_poly(
+        src: &[Point; 3],
+        dst: &[Point; 3],
+    ) -> Result<Self, TinySkiaError> {
+        // Maps a unit triangle onto the specified one.
+        fn from_triangle(p: &[Point; 3]) -> Transform {
+            Transform::from_row(
+                p[1].x - p[0].x,
+                p[1].y - p[0].y,
+                p[2].x - p[0].x,
+                p[2].y - p[0].y,
+                p[0].x,
+                p[0].y,
+            )
+        }
+
+        if !src.iter().chain(dst.iter()).all(|p| p.is_finite()) {
+            return Err(TinySkiaError::NonFinite);
+        }
+
+        let src_inv = from_triangle(src).try_invert()?;
+        let ts = from_triangle(dst).pre_concat(src_inv);
+        if ts.is_finite() {
+            Ok(ts)
+        } else {
+            Err(TinySkiaError::NonFinite)
+        }
+    }
+
     /// Checks that transform is finite.
     pub fn is_finite(&self) -> bool {
         self.sx.is_finite()
@@ -265,6 +327,52 @@ impl Transform {
         }
     }
 
+    /// Returns a point transformed by the current transform.
+    pub fn map_xy(&self, x: f32, y: f32) -> Point {
+        let mut point = Point::from_xy(x, y);
+        self.map_point(&mut point);
+        point
+    }
+
+    /// Returns bounds of a transformed rectangle.
+    ///
+    /// All four corners are transformed, therefore a rotated rectangle
+    /// produces an axis-aligned rectangle that encloses it.
+    ///
+    /// Returns `None` when the result is not finite.
+    pub fn map_rect(&self, rect: Rect) -> Option<Rect> {
+        if self.is_identity() {
+            return Some(rect);
+        }
+
+        let mut points = [
+            Point::from_xy(rect.left(), rect.top()),
+            Point::from_xy(rect.right(), rect.top()),
+            Point::from_xy(rect.right(), rect.bottom()),
+            Point::from_xy(rect.left(), rect.bottom()),
+        ];
+        self.map_points(&mut points);
+        Rect::from_points(&points)
+    }
+
+    /// Returns pixel bounds of a transformed rectangle.
+    ///
+    /// The rectangle is mapped using [`map_rect`](Transform::map_rect) first
+    /// and then its edges are rounded using `rounding`.
+    /// With [`Rounding::Out`], the result contains all pixels the transformed rectangle touches,
+    /// which makes it suitable for allocating a pixmap.
+    ///
+    /// Returns `None` when the result is not finite, doesn't fit into `IntRect`
+    /// or has a zero width or height after rounding.
+    pub fn map_rect_to_int(&self, rect: Rect, rounding: Rounding) -> Option<IntRect> {
+        let rect = self.map_rect(rect)?;
+        match rounding {
+            Rounding::Round => rect.round_edges(),
+            Rounding::Out => rect.round_out(),
+            Rounding::In => rect.round_in(),
+        }
+    }
+
     /// Transforms a slice of points using the current transform.
     pub fn map_points(&self, points: &mut [Point]) {
         if points.is_empty() {
@@ -296,14 +404,35 @@ impl Transform {
     }
 
     /// Returns an inverted transform.
+    ///
+    /// See [`try_invert`](Transform::try_invert) for error details.
     pub fn invert(&self) -> Option<Self> {
         // Allow the trivial case to be inlined.
         if self.is_identity() {
             return Some(*self);
         }
 
+        if !self.is_finite() {
+            return None;
+        }
+
         invert(self)
     }
+
+    /// Returns an inverted transform.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::NonFinite`] when the transform is not finite.
+    /// - [`TinySkiaError::NotInvertible`] when the transform has a zero scale
+    ///   or is too close to a singular one.
+    pub fn try_invert(&self) -> Result<Self, TinySkiaError> {
+        if !self.is_finite() {
+            return Err(TinySkiaError::NonFinite);
+        }
+
+        self.invert().ok_or(TinySkiaError::NotInvertible)
+    }
 }
 
 fn invert(ts: &Transform) -> Option<Transform> {
@@ -313,6 +442,11 @@ fn invert(ts: &Transform) -> Option<Transform> {
         if ts.has_scale() {
             let inv_x = ts.sx.invert();
             let inv_y = ts.sy.invert();
+            // A zero scale.
+            if !inv_x.is_finite() || !inv_y.is_finite() {
+                return None;
+            }
+
             Some(Transform::from_row(
                 inv_x,
                 0.0,
@@ -497,4 +631,209 @@ mod tests {
         ts = ts.post_scale(2.0, -4.0);
         assert_eq!(ts, Transform::from_row(2.4, -13.6, -11.2, 31.2, 2.4, -13.6));
     }
+
+    #[test]
+    fn from_poly_to_poly() {
+        let src = [
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(10.0, 0.0),
+            Point::from_xy(0.0, 20.0),
+        ];
+        let dst = [
+            Point::from_xy(5.0, 5.0),
+            Point::from_xy(25.0, 5.0),
+            Point::from_xy(5.0, 15.0),
+        ];
+        let ts = Transform::from_poly_to_poly(&src, &dst).unwrap();
+        assert_eq!(ts, Transform::from_row(2.0, 0.0, 0.0, 0.5, 5.0, 5.0));
+
+        let src = [
+            Point::from_xy(1.0, 2.0),
+            Point::from_xy(7.0, -3.0),
+            Point::from_xy(4.0, 9.0),
+        ];
+        let dst = [
+            Point::from_xy(-2.0, 3.5),
+            Point::from_xy(10.0, 12.0),
+            Point::from_xy(0.5, -8.0),
+        ];
+        let ts = Transform::from_poly_to_poly(&src, &dst).unwrap();
+        for (s, d) in src.iter().zip(dst.iter()) {
+            let mut p = *s;
+            ts.map_point(&mut p);
+            assert!((p.x - d.x).abs() < 1e-4 && (p.y - d.y).abs() < 1e-4);
+        }
+
+        // Collinear.
+        let src = [
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(10.0, 10.0),
+            Point::from_xy(20.0, 20.0),
+        ];
+        assert_eq!(Transform::from_poly_to_poly(&src, &dst), None);
+
+        // Coincident.
+        let src = [Point::from_xy(3.0, 4.0); 3];
+        assert_eq!(Transform::from_poly_to_poly(&src, &dst), None);
+    }
+
+    #[test]
+    fn try_from_poly_to_poly() {
+        let src = [
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(10.0, 0.0),
+            Point::from_xy(0.0, 20.0),
+        ];
+        let dst = [
+            Point::from_xy(5.0, 5.0),
+            Point::from_xy(25.0, 5.0),
+            Point::from_xy(5.0, 15.0),
+        ];
+        assert_eq!(
+            Transform::try_from_poly_to_poly(&src, &dst),
+            Ok(Transform::from_row(2.0, 0.0, 0.0, 0.5, 5.0, 5.0))
+        );
+
+        let collinear = [
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(10.0, 10.0),
+            Point::from_xy(20.0, 20.0),
+        ];
+        assert_eq!(
+            Transform::try_from_poly_to_poly(&collinear, &dst),
+            Err(TinySkiaError::NotInvertible)
+        );
+
+        let mut non_finite = dst;
+        non_finite[1].x = f32::NAN;
+        assert_eq!(
+            Transform::try_from_poly_to_poly(&src, &non_finite),
+            Err(TinySkiaError::NonFinite)
+        );
+
+        let mut huge = dst;
+        huge[1].x = f32::MAX;
+        huge[2].x = -f32::MAX;
+        assert_eq!(
+            Transform::try_from_poly_to_poly(&src, &huge),
+            Err(TinySkiaError::NonFinite)
+        );
+    }
+
+    #[test]
+    fn try_invert() {
+        let ts = Transform::from_row(2.0, 0.0, 0.0, 4.0, 10.0, 20.0);
+        assert_eq!(
+            ts.try_invert(),
+            Ok(Transform::from_row(0.5, 0.0, 0.0, 0.25, -5.0, -5.0))
+        );
+
+        assert_eq!(
+            Transform::from_scale(0.0, 1.0).try_invert(),
+            Err(TinySkiaError::NotInvertible)
+        );
+        assert_eq!(
+            Transform::from_row(1.0, 2.0, 2.0, 4.0, 0.0, 0.0).try_invert(),
+            Err(TinySkiaError::NotInvertible)
+        );
+        assert_eq!(
+            Transform::from_translate(f32::INFINITY, 0.0).try_invert(),
+            Err(TinySkiaError::NonFinite)
+        );
+
+        // `invert` rejects a zero scale as well.
+        assert_eq!(Transform::from_scale(1.0, 0.0).invert(), None);
+    }
+
+    #[test]
+    fn map_xy() {
+        let ts = Transform::from_row(2.0, 0.0, 0.0, 3.0, 10.0, 20.0);
+        assert_eq!(ts.map_xy(1.0, 2.0), Point::from_xy(12.0, 26.0));
+
+        let ts = Transform::from_rotate(90.0);
+        let p = ts.map_xy(1.0, 0.0);
+        assert!(p.x.is_nearly_zero() && (p.y - 1.0).is_nearly_zero());
+    }
+
+    #[test]
+    fn map_rect() {
+        let rect = Rect::from_ltrb(10.0, 20.0, 30.0, 60.0).unwrap();
+
+        assert_eq!(Transform::identity().map_rect(rect), Some(rect));
+
+        assert_eq!(
+            Transform::from_row(2.0, 0.0, 0.0, -1.0, 5.0, 5.0).map_rect(rect),
+            Rect::from_ltrb(25.0, -55.0, 65.0, -15.0)
+        );
+
+        // A 45 degrees rotation around the center produces an enclosing square.
+        let ts = Transform::from_rotate_at(45.0, 20.0, 40.0);
+        let bounds = ts.map_rect(rect).unwrap();
+        let half = 30.0 * core::f32::consts::FRAC_1_SQRT_2;
+        assert!((bounds.left() - (20.0 - half)).abs() < 1e-4);
+        assert!((bounds.top() - (40.0 - half)).abs() < 1e-4);
+        assert!((bounds.right() - (20.0 + half)).abs() < 1e-4);
+        assert!((bounds.bottom() - (40.0 + half)).abs() < 1e-4);
+
+        assert_eq!(Transform::from_scale(f32::MAX, 1.0).map_rect(rect), None);
+    }
+
+    #[test]
+    fn from_flip_y() {
+        let ts = Transform::from_flip_y(100.0);
+        assert_eq!(ts.map_xy(10.0, 0.0), Point::from_xy(10.0, 100.0));
+        assert_eq!(ts.map_xy(10.0, 100.0), Point::from_xy(10.0, 0.0));
+        assert_eq!(ts.map_xy(10.0, 30.0), Point::from_xy(10.0, 70.0));
+        assert_eq!(ts.pre_concat(ts), Transform::identity());
+    }
+
+    #[test]
+    fn map_rect_to_int() {
+        let rect = Rect::from_ltrb(0.5, 1.5, 10.25, 20.75).unwrap();
+        let ts = Transform::identity();
+        assert_eq!(
+            ts.map_rect_to_int(rect, Rounding::Round),
+            IntRect::from_ltrb(1, 2, 10, 21)
+        );
+        assert_eq!(
+            ts.map_rect_to_int(rect, Rounding::Out),
+            IntRect::from_ltrb(0, 1, 11, 21)
+        );
+        assert_eq!(
+            ts.map_rect_to_int(rect, Rounding::In),
+            IntRect::from_ltrb(1, 2, 10, 20)
+        );
+
+        // Halves are always rounded up, so adjacent rects stay adjacent.
+        let ts = Transform::from_translate(-1.0, -1.0);
+        let r1 = Rect::from_ltrb(0.0, 0.0, 1.5, 1.5).unwrap();
+        let r2 = Rect::from_ltrb(1.5, 0.0, 3.0, 1.5).unwrap();
+        assert_eq!(
+            ts.map_rect_to_int(r1, Rounding::Round),
+            IntRect::from_ltrb(-1, -1, 1, 1)
+        );
+        assert_eq!(
+            ts.map_rect_to_int(r2, Rounding::Round),
+            IntRect::from_ltrb(1, -1, 2, 1)
+        );
+
+        // A rotated rect touches more pixels than its size.
+        let rect = Rect::from_ltrb(0.0, 0.0, 10.0, 10.0).unwrap();
+        let ts = Transform::from_rotate_at(45.0, 5.0, 5.0);
+        let bounds = ts.map_rect_to_int(rect, Rounding::Out).unwrap();
+        assert_eq!(bounds, IntRect::from_ltrb(-3, -3, 13, 13).unwrap());
+
+        // Too small to cover anything.
+        let rect = Rect::from_ltrb(0.2, 0.2, 0.4, 0.4).unwrap();
+        let ts = Transform::identity();
+        assert_eq!(ts.map_rect_to_int(rect, Rounding::Round), None);
+        assert_eq!(ts.map_rect_to_int(rect, Rounding::In), None);
+        assert_eq!(
+            ts.map_rect_to_int(rect, Rounding::Out),
+            IntRect::from_ltrb(0, 0, 1, 1)
+        );
+
+        let ts = Transform::from_scale(f32::MAX, 1.0);
+        assert_eq!(ts.map_rect_to_int(rect, Rounding::Out), None);
+    }
 }
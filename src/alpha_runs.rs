@@ -35,7 +35,7 @@ impl AlphaRuns {
 
     /// Returns 0-255 given 0-256.
     pub fn catch_overflow(alpha: u16) -> AlphaU8 {
-        debug_assert!(alpha <= 256);
+        hot_debug_assert!(alpha <= 256);
         (alpha - (alpha >> 8)) as u8
     }
 
@@ -97,7 +97,7 @@ impl AlphaRuns {
             // edge of the current span round to the same super-sampled x value,
             // I might overflow to 256 with this add, hence the funny subtract (crud).
             let tmp = u16::from(self.alpha[alpha_offset + x]) + u16::from(start_alpha);
-            debug_assert!(tmp <= 256);
+            hot_debug_assert!(tmp <= 256);
             // was (tmp >> 7), but that seems wrong if we're trying to catch 256
             self.alpha[alpha_offset + x] = (tmp - (tmp >> 8)) as u8;
 
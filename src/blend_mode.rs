@@ -70,6 +70,55 @@ impl Default for BlendMode {
 }
 
 impl BlendMode {
+    /// Checks that the blend mode reads the destination.
+    ///
+    /// Only `Clear` and `Source` ignore the destination,
+    /// assuming a full coverage.
+    /// A partial coverage always mixes the result with the destination.
+    pub fn uses_dst(self) -> bool {
+        !matches!(self, BlendMode::Clear | BlendMode::Source)
+    }
+
+    /// Checks that the result is guaranteed to be opaque.
+    ///
+    /// `src_opaque` and `dst_opaque` indicate that the source
+    /// and the destination are known to be opaque. Assumes a full coverage.
+    pub fn is_opaque_preserving(self, src_opaque: bool, dst_opaque: bool) -> bool {
+        match self {
+            // 0
+            BlendMode::Clear => false,
+            // sa
+            BlendMode::Source | BlendMode::DestinationAtop => src_opaque,
+            // da
+            BlendMode::Destination | BlendMode::SourceAtop => dst_opaque,
+            // sa * da
+            BlendMode::SourceIn | BlendMode::DestinationIn | BlendMode::Modulate => {
+                src_opaque && dst_opaque
+            }
+            // sa * inv(da), da * inv(sa) or both. Can be opaque, but not guaranteed to.
+            BlendMode::SourceOut | BlendMode::DestinationOut | BlendMode::Xor => false,
+            // sa + da - sa * da or min(sa + da, 1)
+            BlendMode::SourceOver
+            | BlendMode::DestinationOver
+            | BlendMode::Plus
+            | BlendMode::Screen
+            | BlendMode::Overlay
+            | BlendMode::Darken
+            | BlendMode::Lighten
+            | BlendMode::ColorDodge
+            | BlendMode::ColorBurn
+            | BlendMode::HardLight
+            | BlendMode::SoftLight
+            | BlendMode::Difference
+            | BlendMode::Exclusion
+            | BlendMode::Multiply
+            | BlendMode::Hue
+            | BlendMode::Saturation
+            | BlendMode::Color
+            | BlendMode::Luminosity => src_opaque || dst_opaque,
+        }
+    }
+
     pub(crate) fn should_pre_scale_coverage(self) -> bool {
         // The most important things we do here are:
         //   1) never pre-scale with rgb coverage if the blend mode involves a source-alpha term;
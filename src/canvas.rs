@@ -0,0 +1,469 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec::Vec;
+
+use tiny_skia_path::{IntSize, PathBuilder};
+
+use crate::{FillRule, IntRect, Mask, Paint, Path, Pixmap, PixmapMut, PixmapPaint, PixmapRef};
+use crate::{Rect, Stroke, Transform};
+
+/// A drawing surface with a transform and clip stack.
+///
+/// Wraps [`PixmapMut`] and applies the current transform and clip to each drawing call,
+/// similar to Skia's `SkCanvas`.
+///
+/// The state can be saved via [`save`](Canvas::save) and restored
+/// via [`restore`](Canvas::restore). Clips can only be narrowed,
+/// therefore restore is the only way to remove a clip.
+///
+/// Drawing can be redirected into an offscreen layer via [`save_layer`](Canvas::save_layer).
+///
+/// A canvas can use a y-up [`CoordinateSystem`] via
+/// [`with_coordinate_system`](Canvas::with_coordinate_system).
+#[derive(Debug)]
+pub struct Canvas<'a> {
+    pixmap: PixmapMut<'a>,
+    coordinate_system: CoordinateSystem,
+    // Maps the coordinate system onto the pixmap. Not affected by save/restore.
+    base_transform: Transform,
+    transform: Transform,
+    // Each clip is already intersected with all the previous ones,
+    // therefore only the last one is used for drawing.
+    clips: Vec<Clip>,
+    layers: Vec<Layer>,
+    saves: Vec<SavedState>,
+}
+
+/// A canvas coordinate system.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CoordinateSystem {
+    /// The origin is at the top-left corner and the Y axis points down.
+    ///
+    /// The native tiny-skia coordinate system.
+    YDown,
+    /// The origin is at the bottom-left corner and the Y axis points up.
+    ///
+    /// Like in OpenGL, PDF or font outlines.
+    YUp,
+}
+
+impl Default for CoordinateSystem {
+    fn default() -> Self {
+        CoordinateSystem::YDown
+    }
+}
+
+// Clips are stored in the coordinates of the layer they were created in.
+// Since a layer is always removed together with all clips created after it,
+// the last clip always matches the current drawing target.
+#[derive(Clone, Debug)]
+struct Clip {
+    mask: Mask,
+    // `None` when the clip is empty.
+    bounds: Option<IntRect>,
+}
+
+#[derive(Debug)]
+struct Layer {
+    // `None` when the layer is empty and all drawing should be skipped.
+    pixmap: Option<Pixmap>,
+    // Layer origin in the canvas coordinates.
+    x: i32,
+    y: i32,
+    paint: PixmapPaint,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SavedState {
+    transform: Transform,
+    clips_len: usize,
+    layers_len: usize,
+}
+
+impl<'a> Canvas<'a> {
+    /// Creates a new canvas with an identity transform and no clip.
+    pub fn new(pixmap: PixmapMut<'a>) -> Self {
+        Canvas::with_coordinate_system(pixmap, CoordinateSystem::YDown)
+    }
+
+    /// Creates a new canvas using the specified coordinate system.
+    ///
+    /// With [`CoordinateSystem::YUp`], all drawing is flipped using
+    /// [`Transform::from_flip_y`] with the pixmap height.
+    /// The flip is applied after the canvas transform and is not a part of it,
+    /// therefore [`set_transform`](Canvas::set_transform) will not remove it.
+    ///
+    /// Pixmaps are drawn upright, with `x` and `y` being the bottom-left corner.
+    pub fn with_coordinate_system(
+        pixmap: PixmapMut<'a>,
+        coordinate_system: CoordinateSystem,
+    ) -> Self {
+        let base_transform = match coordinate_system {
+            CoordinateSystem::YDown => Transform::identity(),
+            CoordinateSystem::YUp => Transform::from_flip_y(pixmap.height() as f32),
+        };
+
+        Canvas {
+            pixmap,
+            coordinate_system,
+            base_transform,
+            transform: Transform::identity(),
+            clips: Vec::new(),
+            layers: Vec::new(),
+            saves: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying pixmap.
+    ///
+    /// Can be used for drawing operations not supported by the canvas.
+    /// The current transform, clip and layers will not be applied in this case.
+    pub fn pixmap(&mut self) -> &mut PixmapMut<'a> {
+        &mut self.pixmap
+    }
+
+    /// Returns the coordinate system.
+    pub fn coordinate_system(&self) -> CoordinateSystem {
+        self.coordinate_system
+    }
+
+    /// Saves the current transform and clip.
+    ///
+    /// Must be balanced by a [`restore`](Canvas::restore) call.
+    pub fn save(&mut self) {
+        self.saves.push(SavedState {
+            transform: self.transform,
+            clips_len: self.clips.len(),
+            layers_len: self.layers.len(),
+        });
+    }
+
+    /// Saves the current transform and clip and redirects drawing into a new layer.
+    ///
+    /// The layer is a transparent offscreen pixmap, which will be drawn
+    /// onto the previous layer or the canvas on the matching [`restore`](Canvas::restore)
+    /// using `paint`. Which allows applying opacity or a blend mode to a group of drawings.
+    ///
+    /// The layer is sized to `bounds`, which are affected by the current transform,
+    /// and to the current clip. Anything outside of them will not be drawn.
+    ///
+    /// Must be balanced by a [`restore`](Canvas::restore) call.
+    pub fn save_layer(&mut self, bounds: Option<Rect>, paint: &PixmapPaint) {
+        self.save();
+
+        if self.is_target_empty() {
+            return self.push_empty_layer(paint);
+        }
+
+        let (x, y) = self.target_origin();
+        // Clip bounds are already limited to the target size.
+        let mut rect = match self.clips.last() {
+            Some(clip) => match clip.bounds {
+                Some(bounds) => bounds,
+                None => return self.push_empty_layer(paint),
+            },
+            None => self.target_size().to_int_rect(0, 0),
+        };
+
+        if let Some(bounds) = bounds {
+            let ts = self.target_transform();
+            rect = match bounds.transform(ts).and_then(|r| r.round_out()) {
+                Some(bounds) => match rect.intersect(&bounds) {
+                    Some(v) => v,
+                    None => return self.push_empty_layer(paint),
+                },
+                None => return self.push_empty_layer(paint),
+            };
+        }
+
+        let pixmap = match Pixmap::new(rect.width(), rect.height()) {
+            Some(v) => v,
+            None => return self.push_empty_layer(paint),
+        };
+
+        // Crop the current clip to the layer.
+        if let Some(clip) = self.clips.last() {
+            let mut mask = match Mask::new(rect.width(), rect.height()) {
+                Some(v) => v,
+                None => return self.push_empty_layer(paint), // technically unreachable
+            };
+
+            let src_stride = clip.mask.width() as usize;
+            let dst_stride = rect.width() as usize;
+            let src = clip.mask.data();
+            for (row, dst) in mask.data_mut().chunks_exact_mut(dst_stride).enumerate() {
+                let start = (rect.y() as usize + row) * src_stride + rect.x() as usize;
+                dst.copy_from_slice(&src[start..start + dst_stride]);
+            }
+
+            let bounds = Some(mask.size().to_int_rect(0, 0));
+            self.clips.push(Clip { mask, bounds });
+        }
+
+        self.layers.push(Layer {
+            pixmap: Some(pixmap),
+            x: x + rect.x(),
+            y: y + rect.y(),
+            paint: *paint,
+        });
+    }
+
+    fn push_empty_layer(&mut self, paint: &PixmapPaint) {
+        self.layers.push(Layer {
+            pixmap: None,
+            x: 0,
+            y: 0,
+            paint: *paint,
+        });
+    }
+
+    /// Restores the transform and clip to the state of the matching
+    /// [`save`](Canvas::save) or [`save_layer`](Canvas::save_layer) call.
+    ///
+    /// When the matching call was `save_layer`, the layer is drawn
+    /// onto the previous layer or the canvas.
+    ///
+    /// Does nothing and triggers a debug assertion when there is no matching `save`.
+    pub fn restore(&mut self) {
+        let state = match self.saves.pop() {
+            Some(v) => v,
+            None => {
+                debug_assert!(false, "restore without a matching save");
+                return;
+            }
+        };
+
+        self.transform = state.transform;
+        self.clips.truncate(state.clips_len);
+
+        if self.layers.len() > state.layers_len {
+            let layer = match self.layers.pop() {
+                Some(v) => v,
+                None => return, // technically unreachable
+            };
+
+            if let Some(ref pixmap) = layer.pixmap {
+                let (x, y) = self.target_origin();
+                self.with_target(|target, _, mask| {
+                    target.draw_pixmap(
+                        layer.x - x,
+                        layer.y - y,
+                        pixmap.as_ref(),
+                        &layer.paint,
+                        Transform::identity(),
+                        mask,
+                    );
+                });
+            }
+        }
+    }
+
+    /// Returns the number of saved states, including layers.
+    pub fn save_count(&self) -> usize {
+        self.saves.len()
+    }
+
+    /// Returns the current transform.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Replaces the current transform.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    /// Pre-translates the current transform.
+    pub fn translate(&mut self, tx: f32, ty: f32) {
+        self.transform = self.transform.pre_translate(tx, ty);
+    }
+
+    /// Pre-scales the current transform.
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.transform = self.transform.pre_scale(sx, sy);
+    }
+
+    /// Pre-rotates the current transform.
+    ///
+    /// Angle is in degrees.
+    pub fn rotate(&mut self, angle: f32) {
+        self.transform = self.transform.pre_concat(Transform::from_rotate(angle));
+    }
+
+    /// Pre-concats the current transform with the provided one.
+    pub fn concat(&mut self, transform: Transform) {
+        self.transform = self.transform.pre_concat(transform);
+    }
+
+    /// Returns the current clip mask.
+    ///
+    /// Returns `None` when there is no clip.
+    ///
+    /// Inside a layer, the mask has the layer size.
+    pub fn clip_mask(&self) -> Option<&Mask> {
+        self.clips.last().map(|clip| &clip.mask)
+    }
+
+    /// Intersects the current clip with a rectangle.
+    ///
+    /// The rectangle is affected by the current transform.
+    pub fn clip_rect(&mut self, rect: Rect, anti_alias: bool) {
+        let path = PathBuilder::from_rect(rect);
+        self.clip_path(&path, FillRule::Winding, anti_alias);
+    }
+
+    /// Intersects the current clip with a path.
+    ///
+    /// The path is affected by the current transform.
+    pub fn clip_path(&mut self, path: &Path, fill_rule: FillRule, anti_alias: bool) {
+        if self.is_target_empty() {
+            return;
+        }
+
+        let ts = self.target_transform();
+        let size = self.target_size();
+
+        let mut bounds = path
+            .bounds()
+            .transform(ts)
+            .and_then(|r| r.round_out())
+            .and_then(|r| r.intersect(&size.to_int_rect(0, 0)));
+
+        let mask = match self.clips.last() {
+            Some(last) => {
+                bounds = match (bounds, last.bounds) {
+                    (Some(a), Some(b)) => a.intersect(&b),
+                    _ => None,
+                };
+
+                let mut mask = last.mask.clone();
+                mask.intersect_path(path, fill_rule, anti_alias, ts);
+                mask
+            }
+            None => {
+                let mut mask = match Mask::from_size(size) {
+                    Some(v) => v,
+                    None => return, // technically unreachable
+                };
+
+                mask.fill_path(path, fill_rule, anti_alias, ts);
+                mask
+            }
+        };
+
+        self.clips.push(Clip { mask, bounds });
+    }
+
+    /// Draws a filled rectangle using the current transform and clip.
+    ///
+    /// See [`PixmapMut::fill_rect`] for details.
+    pub fn fill_rect(&mut self, rect: Rect, paint: &Paint) {
+        self.with_target(|target, ts, mask| target.fill_rect(rect, paint, ts, mask));
+    }
+
+    /// Draws a filled path using the current transform and clip.
+    ///
+    /// See [`PixmapMut::fill_path`] for details.
+    pub fn fill_path(&mut self, path: &Path, paint: &Paint, fill_rule: FillRule) {
+        self.with_target(|target, ts, mask| target.fill_path(path, paint, fill_rule, ts, mask));
+    }
+
+    /// Strokes a path using the current transform and clip.
+    ///
+    /// See [`PixmapMut::stroke_path`] for details.
+    pub fn stroke_path(&mut self, path: &Path, paint: &Paint, stroke: &Stroke) {
+        self.with_target(|target, ts, mask| target.stroke_path(path, paint, stroke, ts, mask));
+    }
+
+    /// Draws a `Pixmap` using the current transform and clip.
+    ///
+    /// With [`CoordinateSystem::YUp`], `x` and `y` are the bottom-left corner
+    /// and the pixmap is not flipped.
+    ///
+    /// See [`PixmapMut::draw_pixmap`] for details.
+    pub fn draw_pixmap(&mut self, x: i32, y: i32, pixmap: PixmapRef, paint: &PixmapPaint) {
+        match self.coordinate_system {
+            CoordinateSystem::YDown => {
+                self.with_target(|target, ts, mask| {
+                    target.draw_pixmap(x, y, pixmap, paint, ts, mask)
+                });
+            }
+            CoordinateSystem::YUp => {
+                // Flip the pixmap back, so it will be upright after the base flip.
+                let h = pixmap.height() as f32;
+                let flip = Transform::from_row(1.0, 0.0, 0.0, -1.0, x as f32, y as f32 + h);
+                self.with_target(|target, ts, mask| {
+                    target.draw_pixmap(0, 0, pixmap, paint, ts.pre_concat(flip), mask)
+                });
+            }
+        }
+    }
+
+    // Calls `f` with the current drawing target, a transform in its coordinates and a clip.
+    // Not called at all when the current layer is empty.
+    fn with_target<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut PixmapMut, Transform, Option<&Mask>),
+    {
+        let ts = self.device_transform();
+        let mask = self.clips.last().map(|clip| &clip.mask);
+        match self.layers.last_mut() {
+            Some(layer) => {
+                if let Some(ref mut pixmap) = layer.pixmap {
+                    let (x, y) = (-layer.x as f32, -layer.y as f32);
+                    f(&mut pixmap.as_mut(), ts.post_translate(x, y), mask);
+                }
+            }
+            None => f(&mut self.pixmap, ts, mask),
+        }
+    }
+
+    fn target_origin(&self) -> (i32, i32) {
+        match self.layers.last() {
+            Some(layer) => (layer.x, layer.y),
+            None => (0, 0),
+        }
+    }
+
+    fn is_target_empty(&self) -> bool {
+        match self.layers.last() {
+            Some(layer) => layer.pixmap.is_none(),
+            None => false,
+        }
+    }
+
+    // Must not be called when the current layer is empty.
+    fn target_size(&self) -> IntSize {
+        match self.layers.last().and_then(|layer| layer.pixmap.as_ref()) {
+            Some(pixmap) => pixmap.size(),
+            None => self.pixmap.size(),
+        }
+    }
+
+    fn target_transform(&self) -> Transform {
+        let (x, y) = self.target_origin();
+        self.device_transform().post_translate(-x as f32, -y as f32)
+    }
+
+    // The current transform in the canvas pixmap coordinates.
+    fn device_transform(&self) -> Transform {
+        self.transform.post_concat(self.base_transform)
+    }
+}
+
+impl Drop for Canvas<'_> {
+    fn drop(&mut self) {
+        // Do not panic while already panicking, since this will abort.
+        #[cfg(feature = "std")]
+        {
+            if !std::thread::panicking() {
+                debug_assert!(self.saves.is_empty(), "save without a matching restore");
+            }
+        }
+    }
+}
@@ -6,6 +6,9 @@
 
 use tiny_skia_path::{NormalizedF32, Scalar};
 
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
 /// 8-bit type for an alpha value. 255 is 100% opaque, zero is 100% transparent.
 pub type AlphaU8 = u8;
 
@@ -115,10 +118,24 @@ impl PremultipliedColorU8 {
     }
 
     /// Creates a new color.
+    ///
+    /// RGB components must be <= alpha, which is checked only in debug builds.
     pub(crate) const fn from_rgba_unchecked(r: u8, g: u8, b: u8, a: u8) -> Self {
+        hot_debug_assert!(r <= a && g <= a && b <= a);
         PremultipliedColorU8([r, g, b, a])
     }
 
+    /// Clamps color components to alpha.
+    ///
+    /// Colors created via public constructors are always valid,
+    /// but data accessed via `bytemuck` or `Pixmap::data_mut` can be not.
+    /// Just like in Skia, over-bright components are clamped to alpha,
+    /// therefore the result can be safely demultiplied.
+    pub fn clamp(self) -> Self {
+        let a = self.alpha();
+        PremultipliedColorU8([self.0[0].min(a), self.0[1].min(a), self.0[2].min(a), a])
+    }
+
     /// Returns color's red component.
     ///
     /// The value is <= alpha.
@@ -410,6 +427,290 @@ impl PremultipliedColor {
     }
 }
 
+/// A premultiplied RGBA color value, holding four half-float components.
+///
+/// Unlike [`PremultipliedColor`], RGB components are not limited by alpha,
+/// which allows storing high dynamic range values.
+///
+/// # Guarantees
+///
+/// - Alpha is in 0..=1 range.
+/// - RGB components are finite and >= 0.
+/// - RGB components are zero when alpha is zero.
+#[repr(transparent)]
+#[derive(Copy, Clone, Default, PartialEq)]
+pub struct PremultipliedColorF16([u16; 4]);
+
+impl PremultipliedColorF16 {
+    /// A transparent color.
+    pub const TRANSPARENT: Self = PremultipliedColorF16([0; 4]);
+
+    /// Creates a new premultiplied color.
+    ///
+    /// Components are rounded to the nearest half-float.
+    ///
+    /// Returns `None` when alpha is not in 0..=1 range, when RGB components are negative,
+    /// NaN or larger than the largest half-float (65504), or when RGB components
+    /// are not zero while alpha is.
+    pub fn from_rgba(r: f32, g: f32, b: f32, a: f32) -> Option<Self> {
+        if !(0.0..=1.0).contains(&a) {
+            return None;
+        }
+
+        for c in [r, g, b] {
+            if !(0.0..=F16_MAX).contains(&c) || (a == 0.0 && c != 0.0) {
+                return None;
+            }
+        }
+
+        Some(Self::from_rgba_clamped(r, g, b, a))
+    }
+
+    /// Creates a new color, clamping components to the valid range.
+    ///
+    /// NaN components are treated as zero.
+    pub(crate) fn from_rgba_clamped(r: f32, g: f32, b: f32, a: f32) -> Self {
+        // Negative zero and NaN will fail the `v > 0.0` check.
+        let clamp = |v: f32, max: f32| f32_to_f16(if v > 0.0 { v.min(max) } else { 0.0 });
+
+        let a = clamp(a, 1.0);
+        if a == 0 {
+            // Alpha can be rounded to zero.
+            return Self::TRANSPARENT;
+        }
+
+        PremultipliedColorF16([clamp(r, F16_MAX), clamp(g, F16_MAX), clamp(b, F16_MAX), a])
+    }
+
+    /// Returns color's red component.
+    ///
+    /// The value is >= 0, but can be larger than alpha.
+    pub fn red(&self) -> f32 {
+        f16_to_f32(self.0[0])
+    }
+
+    /// Returns color's green component.
+    ///
+    /// The value is >= 0, but can be larger than alpha.
+    pub fn green(&self) -> f32 {
+        f16_to_f32(self.0[1])
+    }
+
+    /// Returns color's blue component.
+    ///
+    /// The value is >= 0, but can be larger than alpha.
+    pub fn blue(&self) -> f32 {
+        f16_to_f32(self.0[2])
+    }
+
+    /// Returns color's alpha component.
+    ///
+    /// The value is guarantee to be in a 0..=1 range.
+    pub fn alpha(&self) -> f32 {
+        f16_to_f32(self.0[3])
+    }
+
+    /// Converts into `PremultipliedColorU8`.
+    ///
+    /// RGB components larger than alpha are clamped to it.
+    pub fn to_color_u8(&self) -> PremultipliedColorU8 {
+        let a = self.alpha();
+        let c = |v: f32| (v.min(a) * 255.0 + 0.5) as u8;
+        PremultipliedColorU8::from_rgba_unchecked(
+            c(self.red()),
+            c(self.green()),
+            c(self.blue()),
+            c(a),
+        )
+    }
+}
+
+impl From<PremultipliedColorU8> for PremultipliedColorF16 {
+    /// Converts an 8-bit color.
+    ///
+    /// The conversion is lossless, aka `to_color_u8` will return the original color.
+    fn from(c: PremultipliedColorU8) -> Self {
+        let f = |v: u8| f32_to_f16(v as f32 / 255.0);
+        PremultipliedColorF16([f(c.red()), f(c.green()), f(c.blue()), f(c.alpha())])
+    }
+}
+
+impl From<PremultipliedColor> for PremultipliedColorF16 {
+    fn from(c: PremultipliedColor) -> Self {
+        PremultipliedColorF16::from_rgba_clamped(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl core::fmt::Debug for PremultipliedColorF16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PremultipliedColorF16")
+            .field("r", &self.red())
+            .field("g", &self.green())
+            .field("b", &self.blue())
+            .field("a", &self.alpha())
+            .finish()
+    }
+}
+
+/// A premultiplied RGBA color value for arithmetic, holding four `f32` components.
+///
+/// Unlike [`PremultipliedColor`], components are not validated,
+/// therefore intermediate values can be out of range and even negative.
+/// Which allows writing filters and compositing math directly, like `a * 0.5 + b * 0.5`.
+/// Values are clamped only during conversion into other color types.
+///
+/// All operations are component-wise, alpha included.
+/// Since colors are premultiplied, mixing them doesn't require any alpha handling.
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct PremultipliedColorF32 {
+    /// Red component.
+    pub r: f32,
+    /// Green component.
+    pub g: f32,
+    /// Blue component.
+    pub b: f32,
+    /// Alpha component.
+    pub a: f32,
+}
+
+impl PremultipliedColorF32 {
+    /// A transparent color.
+    pub const TRANSPARENT: Self = PremultipliedColorF32::new(0.0, 0.0, 0.0, 0.0);
+
+    /// Creates a new color.
+    ///
+    /// Components are expected to be premultiplied already.
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        PremultipliedColorF32 { r, g, b, a }
+    }
+
+    /// Converts into `PremultipliedColor`.
+    ///
+    /// Alpha is clamped to the 0..=1 range and RGB components to the 0..=alpha range.
+    /// NaN is treated as zero.
+    pub fn to_premultiplied_color(&self) -> PremultipliedColor {
+        let a = clamp_component(self.a, 1.0);
+        PremultipliedColor {
+            r: NormalizedF32::new_clamped(clamp_component(self.r, a)),
+            g: NormalizedF32::new_clamped(clamp_component(self.g, a)),
+            b: NormalizedF32::new_clamped(clamp_component(self.b, a)),
+            a: NormalizedF32::new_clamped(a),
+        }
+    }
+
+    /// Converts into `PremultipliedColorU8`.
+    ///
+    /// Components are clamped the same way as in
+    /// [`to_premultiplied_color`](Self::to_premultiplied_color) and rounded to nearest.
+    pub fn to_color_u8(&self) -> PremultipliedColorU8 {
+        self.to_premultiplied_color().to_color_u8()
+    }
+}
+
+// Also maps NaN to zero.
+fn clamp_component(v: f32, max: f32) -> f32 {
+    if v > 0.0 {
+        v.min(max)
+    } else {
+        0.0
+    }
+}
+
+impl From<PremultipliedColorU8> for PremultipliedColorF32 {
+    /// Converts an 8-bit color.
+    ///
+    /// The conversion is lossless, aka `to_color_u8` will return the original color.
+    fn from(c: PremultipliedColorU8) -> Self {
+        let f = |v: u8| v as f32 / 255.0;
+        PremultipliedColorF32::new(f(c.red()), f(c.green()), f(c.blue()), f(c.alpha()))
+    }
+}
+
+impl From<PremultipliedColor> for PremultipliedColorF32 {
+    fn from(c: PremultipliedColor) -> Self {
+        PremultipliedColorF32::new(c.red(), c.green(), c.blue(), c.alpha())
+    }
+}
+
+impl From<Color> for PremultipliedColorF32 {
+    fn from(c: Color) -> Self {
+        c.premultiply().into()
+    }
+}
+
+impl core::ops::Add for PremultipliedColorF32 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        PremultipliedColorF32::new(
+            self.r + rhs.r,
+            self.g + rhs.g,
+            self.b + rhs.b,
+            self.a + rhs.a,
+        )
+    }
+}
+
+impl core::ops::AddAssign for PremultipliedColorF32 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl core::ops::Sub for PremultipliedColorF32 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        PremultipliedColorF32::new(
+            self.r - rhs.r,
+            self.g - rhs.g,
+            self.b - rhs.b,
+            self.a - rhs.a,
+        )
+    }
+}
+
+impl core::ops::SubAssign for PremultipliedColorF32 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl core::ops::Mul for PremultipliedColorF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        PremultipliedColorF32::new(
+            self.r * rhs.r,
+            self.g * rhs.g,
+            self.b * rhs.b,
+            self.a * rhs.a,
+        )
+    }
+}
+
+impl core::ops::Mul<f32> for PremultipliedColorF32 {
+    type Output = Self;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        PremultipliedColorF32::new(self.r * rhs, self.g * rhs, self.b * rhs, self.a * rhs)
+    }
+}
+
+impl core::ops::MulAssign<f32> for PremultipliedColorF32 {
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl core::ops::Div<f32> for PremultipliedColorF32 {
+    type Output = Self;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        PremultipliedColorF32::new(self.r / rhs, self.g / rhs, self.b / rhs, self.a / rhs)
+    }
+}
+
 /// Return a*b/255, rounding any fractional bits.
 pub fn premultiply_u8(c: u8, a: u8) -> u8 {
     let prod = u32::from(c) * u32::from(a) + 128;
@@ -430,10 +731,269 @@ fn color_f32_to_u8(
     ]
 }
 
+/// The largest finite half-float value.
+const F16_MAX: f32 = 65504.0;
+
+/// Converts `f32` into an IEEE 754 half-float, rounding to the nearest even.
+///
+/// Values that are too large become infinity.
+fn f32_to_f16(v: f32) -> u16 {
+    let x = v.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = (x >> 23) & 0xFF;
+    let man = x & 0x007F_FFFF;
+
+    // NaN and infinity.
+    if exp == 0xFF {
+        let nan_bit = if man != 0 { 0x0200 } else { 0 };
+        return sign | 0x7C00 | nan_bit | (man >> 13) as u16;
+    }
+
+    let half_exp = exp as i32 - 127 + 15;
+
+    // Overflow.
+    if half_exp >= 0x1F {
+        return sign | 0x7C00;
+    }
+
+    // Subnormal or zero.
+    if half_exp <= 0 {
+        // Too small even for a subnormal.
+        if half_exp < -10 {
+            return sign;
+        }
+
+        let man = man | 0x0080_0000;
+        let shift = (14 - half_exp) as u32;
+        let half_man = man >> shift;
+        // Round to nearest, ties to even.
+        let round_bit = 1 << (shift - 1);
+        if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+            return sign | (half_man + 1) as u16;
+        }
+
+        return sign | half_man as u16;
+    }
+
+    let half = ((half_exp as u32) << 10) | (man >> 13);
+    // Round to nearest, ties to even. A carry into the exponent is correct,
+    // including the one that produces infinity.
+    let round_bit = 0x1000;
+    if man & round_bit != 0 && man & (3 * round_bit - 1) != 0 {
+        return sign | (half + 1) as u16;
+    }
+
+    sign | half as u16
+}
+
+/// Converts an IEEE 754 half-float into `f32`.
+///
+/// The conversion is exact.
+fn f16_to_f32(h: u16) -> f32 {
+    let sign = u32::from(h & 0x8000) << 16;
+    let exp = u32::from((h >> 10) & 0x1F);
+    let man = u32::from(h & 0x03FF);
+
+    if exp == 0 {
+        // Zero or subnormal, aka man * 2^-24.
+        let v = man as f32 * (1.0 / 16_777_216.0);
+        return if sign != 0 { -v } else { v };
+    }
+
+    if exp == 0x1F {
+        // NaN and infinity.
+        return f32::from_bits(sign | 0x7F80_0000 | (man << 13));
+    }
+
+    f32::from_bits(sign | ((exp + 127 - 15) << 23) | (man << 13))
+}
+
+/// Converts an sRGB encoded value into a linear one.
+///
+/// Uses the standard sRGB transfer function.
+pub(crate) fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a linear value into an sRGB encoded one.
+///
+/// Uses the standard sRGB transfer function.
+pub(crate) fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Like [`srgb_to_linear`], but produces the same result on all platforms.
+pub(crate) fn srgb_to_linear_portable(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        approx_powf((c + 0.055) / 1.055, 2.4)
+    }
+}
+
+/// Like [`linear_to_srgb`], but produces the same result on all platforms.
+pub(crate) fn linear_to_srgb_portable(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * approx_powf(c, 1.0 / 2.4) - 0.055
+    }
+}
+
+// `f32::powf` is implemented by the platform's libm, which can round differently.
+// This one relies only on basic arithmetic, which is exact everywhere.
+//
+// Based on Skia's `approx_powf`. Precise enough for 8-bit colors.
+fn approx_powf(x: f32, y: f32) -> f32 {
+    if x == 0.0 || x == 1.0 {
+        return x;
+    }
+
+    approx_pow2(approx_log2(x) * y)
+}
+
+fn approx_log2(x: f32) -> f32 {
+    // The exponent bits are the integer part of log2(x).
+    // The mantissa is approximated by a rational function.
+    let bits = x.to_bits();
+    let e = bits as f32 * (1.0 / (1 << 23) as f32);
+    let m = f32::from_bits((bits & 0x007f_ffff) | 0x3f00_0000);
+    e - 124.225514990 - 1.498030302 * m - 1.725879990 / (0.3520887068 + m)
+}
+
+fn approx_pow2(x: f32) -> f32 {
+    let f = x - x.floor();
+    let v =
+        (1 << 23) as f32 * (x + 121.274057500 - 1.490129070 * f + 27.728023300 / (4.84252568 - f));
+    // Negative values are saturated to zero.
+    f32::from_bits((v + 0.5) as u32)
+}
+
+/// A precomputed `srgb_to_linear` for all 8-bit values.
+#[rustfmt::skip]
+pub(crate) static SRGB_TO_LINEAR_LUT: [f32; 256] = [
+    0.000000000, 0.000303527, 0.000607054, 0.000910581,
+    0.001214108, 0.001517635, 0.001821162, 0.002124689,
+    0.002428216, 0.002731743, 0.003035270, 0.003346536,
+    0.003676507, 0.004024717, 0.004391442, 0.004776953,
+    0.005181517, 0.005605392, 0.006048833, 0.006512091,
+    0.006995410, 0.007499032, 0.008023193, 0.008568126,
+    0.009134059, 0.009721217, 0.010329823, 0.010960094,
+    0.011612245, 0.012286488, 0.012983032, 0.013702083,
+    0.014443844, 0.015208514, 0.015996293, 0.016807376,
+    0.017641954, 0.018500220, 0.019382361, 0.020288563,
+    0.021219010, 0.022173885, 0.023153366, 0.024157632,
+    0.025186860, 0.026241222, 0.027320892, 0.028426040,
+    0.029556834, 0.030713444, 0.031896033, 0.033104767,
+    0.034339807, 0.035601315, 0.036889450, 0.038204372,
+    0.039546235, 0.040915197, 0.042311411, 0.043735029,
+    0.045186204, 0.046665086, 0.048171824, 0.049706566,
+    0.051269458, 0.052860647, 0.054480276, 0.056128490,
+    0.057805430, 0.059511238, 0.061246054, 0.063010018,
+    0.064803267, 0.066625939, 0.068478170, 0.070360096,
+    0.072271851, 0.074213568, 0.076185381, 0.078187422,
+    0.080219820, 0.082282707, 0.084376212, 0.086500462,
+    0.088655586, 0.090841711, 0.093058963, 0.095307467,
+    0.097587347, 0.099898728, 0.102241733, 0.104616484,
+    0.107023103, 0.109461711, 0.111932428, 0.114435374,
+    0.116970668, 0.119538428, 0.122138772, 0.124771818,
+    0.127437680, 0.130136477, 0.132868322, 0.135633330,
+    0.138431615, 0.141263291, 0.144128471, 0.147027266,
+    0.149959790, 0.152926152, 0.155926464, 0.158960835,
+    0.162029376, 0.165132195, 0.168269400, 0.171441101,
+    0.174647404, 0.177888416, 0.181164244, 0.184474995,
+    0.187820772, 0.191201683, 0.194617830, 0.198069320,
+    0.201556254, 0.205078736, 0.208636870, 0.212230757,
+    0.215860500, 0.219526200, 0.223227957, 0.226965874,
+    0.230740049, 0.234550582, 0.238397574, 0.242281122,
+    0.246201327, 0.250158285, 0.254152094, 0.258182853,
+    0.262250658, 0.266355605, 0.270497791, 0.274677312,
+    0.278894263, 0.283148740, 0.287440838, 0.291770650,
+    0.296138271, 0.300543794, 0.304987314, 0.309468923,
+    0.313988713, 0.318546778, 0.323143209, 0.327778098,
+    0.332451536, 0.337163615, 0.341914425, 0.346704056,
+    0.351532600, 0.356400144, 0.361306780, 0.366252596,
+    0.371237680, 0.376262123, 0.381326011, 0.386429434,
+    0.391572478, 0.396755231, 0.401977780, 0.407240212,
+    0.412542613, 0.417885071, 0.423267670, 0.428690497,
+    0.434153636, 0.439657174, 0.445201195, 0.450785783,
+    0.456411023, 0.462077000, 0.467783796, 0.473531496,
+    0.479320183, 0.485149940, 0.491020850, 0.496932995,
+    0.502886458, 0.508881321, 0.514917665, 0.520995573,
+    0.527115126, 0.533276404, 0.539479489, 0.545724461,
+    0.552011402, 0.558340390, 0.564711506, 0.571124829,
+    0.577580440, 0.584078418, 0.590618841, 0.597201788,
+    0.603827339, 0.610495571, 0.617206562, 0.623960392,
+    0.630757136, 0.637596874, 0.644479682, 0.651405637,
+    0.658374817, 0.665387298, 0.672443157, 0.679542470,
+    0.686685312, 0.693871761, 0.701101892, 0.708375780,
+    0.715693501, 0.723055129, 0.730460740, 0.737910409,
+    0.745404210, 0.752942217, 0.760524505, 0.768151147,
+    0.775822218, 0.783537792, 0.791297940, 0.799102738,
+    0.806952258, 0.814846572, 0.822785754, 0.830769877,
+    0.838799012, 0.846873232, 0.854992608, 0.863157213,
+    0.871367119, 0.879622397, 0.887923118, 0.896269353,
+    0.904661174, 0.913098652, 0.921581856, 0.930110858,
+    0.938685728, 0.947306537, 0.955973353, 0.964686248,
+    0.973445290, 0.982250550, 0.991102097, 1.000000000,
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn premultiplied_color_f32_math() {
+        let a =
+            PremultipliedColorF32::from(PremultipliedColorU8::from_rgba(200, 100, 0, 200).unwrap());
+        let b =
+            PremultipliedColorF32::from(PremultipliedColorU8::from_rgba(0, 0, 50, 100).unwrap());
+
+        let c = a * 0.5 + b * 0.5;
+        assert_eq!(
+            c.to_color_u8(),
+            PremultipliedColorU8::from_rgba(100, 50, 25, 150).unwrap()
+        );
+
+        let mut c = a;
+        c += b;
+        c -= b;
+        c *= 2.0;
+        assert_eq!((c / 2.0).to_color_u8(), a.to_color_u8());
+        assert_eq!(a * PremultipliedColorF32::new(1.0, 1.0, 1.0, 1.0), a);
+    }
+
+    #[test]
+    fn premultiplied_color_f32_conversion() {
+        // Lossless.
+        for a in 0..=255 {
+            for c in 0..=a {
+                let color = PremultipliedColorU8::from_rgba(c, c / 2, 0, a).unwrap();
+                assert_eq!(PremultipliedColorF32::from(color).to_color_u8(), color);
+            }
+        }
+
+        // Out of range values are clamped.
+        let c = PremultipliedColorF32::new(2.0, -1.0, f32::NAN, 0.5);
+        assert_eq!(
+            c.to_color_u8(),
+            PremultipliedColorU8::from_rgba(128, 0, 0, 128).unwrap()
+        );
+        let c = PremultipliedColorF32::new(0.5, 0.5, 0.5, 2.0);
+        assert_eq!(
+            c.to_color_u8(),
+            PremultipliedColorU8::from_rgba(128, 128, 128, 255).unwrap()
+        );
+    }
+
     #[test]
     fn premultiply_u8() {
         assert_eq!(
@@ -450,6 +1010,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clamp() {
+        let c: PremultipliedColorU8 = bytemuck::cast([10u8, 200, 30, 100]);
+        assert_eq!(
+            c.clamp(),
+            PremultipliedColorU8::from_rgba(10, 100, 30, 100).unwrap()
+        );
+
+        let c = PremultipliedColorU8::from_rgba(10, 20, 30, 40).unwrap();
+        assert_eq!(c.clamp(), c);
+
+        let c: PremultipliedColorU8 = bytemuck::cast([10u8, 20, 30, 0]);
+        assert_eq!(c.clamp(), PremultipliedColorU8::TRANSPARENT);
+    }
+
+    #[test]
+    #[cfg(all(debug_assertions, not(feature = "no-hot-asserts")))]
+    #[should_panic]
+    fn from_rgba_unchecked_overflow() {
+        PremultipliedColorU8::from_rgba_unchecked(10, 20, 30, 15);
+    }
+
     #[test]
     fn demultiply_u8_1() {
         assert_eq!(
@@ -483,4 +1065,87 @@ mod tests {
         let bytes: &[u8] = bytemuck::cast_slice(slice);
         assert_eq!(bytes, &[0, 1, 2, 3, 10, 11, 12, 13]);
     }
+
+    #[test]
+    fn f16_conversion() {
+        assert_eq!(f32_to_f16(0.0), 0x0000);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+        assert_eq!(f32_to_f16(1.0), 0x3C00);
+        assert_eq!(f32_to_f16(-2.0), 0xC000);
+        assert_eq!(f32_to_f16(F16_MAX), 0x7BFF);
+        assert_eq!(f32_to_f16(65520.0), 0x7C00);
+        assert_eq!(f32_to_f16(f32::INFINITY), 0x7C00);
+        assert_eq!(f32_to_f16(f32::NAN) & 0x7C00, 0x7C00);
+        assert_ne!(f32_to_f16(f32::NAN) & 0x03FF, 0);
+        // The smallest subnormal.
+        assert_eq!(f32_to_f16(5.9604645e-8), 0x0001);
+        assert_eq!(f32_to_f16(1e-8), 0x0000);
+
+        // Ties to even.
+        assert_eq!(f32_to_f16(1.0 + 1.0 / 2048.0), 0x3C00);
+        assert_eq!(f32_to_f16(1.0 + 3.0 / 2048.0), 0x3C02);
+
+        // All finite half-floats survive a round trip.
+        for h in 0..=u16::MAX {
+            if h & 0x7C00 != 0x7C00 {
+                assert_eq!(f32_to_f16(f16_to_f32(h)), h);
+            }
+        }
+    }
+
+    #[test]
+    fn f16_u8_round_trip() {
+        for a in 0..=255 {
+            let c = PremultipliedColorU8::from_rgba(a / 2, a / 3, a, a).unwrap();
+            assert_eq!(PremultipliedColorF16::from(c).to_color_u8(), c);
+        }
+    }
+
+    #[test]
+    fn f16_from_rgba() {
+        assert!(PremultipliedColorF16::from_rgba(2.0, 0.5, 0.0, 0.5).is_some());
+        assert!(PremultipliedColorF16::from_rgba(0.0, 0.0, 0.0, 1.5).is_none());
+        assert!(PremultipliedColorF16::from_rgba(-0.1, 0.0, 0.0, 1.0).is_none());
+        assert!(PremultipliedColorF16::from_rgba(f32::NAN, 0.0, 0.0, 1.0).is_none());
+        assert!(PremultipliedColorF16::from_rgba(1e6, 0.0, 0.0, 1.0).is_none());
+        assert!(PremultipliedColorF16::from_rgba(0.5, 0.0, 0.0, 0.0).is_none());
+        assert_eq!(
+            PremultipliedColorF16::from_rgba(-0.0, 0.0, 0.0, -0.0),
+            Some(PremultipliedColorF16::TRANSPARENT)
+        );
+        // Alpha is rounded to zero, therefore color must be zeroed too.
+        assert_eq!(
+            PremultipliedColorF16::from_rgba(0.5, 0.0, 0.0, 1e-8),
+            Some(PremultipliedColorF16::TRANSPARENT)
+        );
+
+        let c = PremultipliedColorF16::from_rgba(2.0, 0.25, 0.0, 0.5).unwrap();
+        assert_eq!(c.red(), 2.0);
+        assert_eq!(c.green(), 0.25);
+        assert_eq!(
+            c.to_color_u8(),
+            PremultipliedColorU8::from_rgba(128, 64, 0, 128).unwrap()
+        );
+    }
+
+    #[test]
+    fn srgb_lut() {
+        for (i, v) in SRGB_TO_LINEAR_LUT.iter().enumerate() {
+            let c = i as f32 / 255.0;
+            assert!((srgb_to_linear(c) - v).abs() < 1e-6);
+            assert_eq!((linear_to_srgb(*v) * 255.0 + 0.5) as usize, i);
+        }
+    }
+
+    #[test]
+    fn portable_transfer_functions() {
+        for i in 0..=1000 {
+            let c = i as f32 / 1000.0;
+            assert!((srgb_to_linear(c) - srgb_to_linear_portable(c)).abs() < 0.1 / 255.0);
+            assert!((linear_to_srgb(c) - linear_to_srgb_portable(c)).abs() < 0.1 / 255.0);
+        }
+
+        assert_eq!(srgb_to_linear_portable(1.0), 1.0);
+        assert_eq!(linear_to_srgb_portable(0.0), 0.0);
+    }
 }
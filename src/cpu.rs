@@ -0,0 +1,226 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+//! CPU features detection and runtime-dispatched span kernels.
+//!
+//! The `wide` module relies on compile-time target features only.
+//! This module complements it with a small set of span kernels
+//! that are selected at runtime, so a single binary can take advantage of
+//! AVX2/SSE2 or Neon when available and fall back to a scalar code otherwise.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::PremultipliedColorU8;
+
+/// A SIMD backend used by the runtime-dispatched span kernels.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SimdBackend {
+    /// Plain scalar code. Always available.
+    Scalar,
+    /// x86 SSE2.
+    Sse2,
+    /// x86 AVX2.
+    Avx2,
+    /// AArch64 Neon.
+    Neon,
+}
+
+/// A list of CPU features detected at runtime.
+///
+/// Can be retrieved via [`cpu_features`].
+#[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
+pub struct CpuFeatures {
+    sse2: bool,
+    avx2: bool,
+    neon: bool,
+}
+
+impl CpuFeatures {
+    /// Checks that x86 SSE2 is supported.
+    pub fn has_sse2(&self) -> bool {
+        self.sse2
+    }
+
+    /// Checks that x86 AVX2 is supported.
+    pub fn has_avx2(&self) -> bool {
+        self.avx2
+    }
+
+    /// Checks that AArch64 Neon is supported.
+    pub fn has_neon(&self) -> bool {
+        self.neon
+    }
+
+    /// Returns a SIMD backend that will be used by span kernels.
+    ///
+    /// Always returns `SimdBackend::Scalar` when the `simd` feature is disabled.
+    pub fn backend(&self) -> SimdBackend {
+        if !cfg!(feature = "simd") {
+            SimdBackend::Scalar
+        } else if self.avx2 {
+            SimdBackend::Avx2
+        } else if self.sse2 {
+            SimdBackend::Sse2
+        } else if self.neon {
+            SimdBackend::Neon
+        } else {
+            SimdBackend::Scalar
+        }
+    }
+
+    fn to_bits(self) -> u8 {
+        (self.sse2 as u8) | (self.avx2 as u8) << 1 | (self.neon as u8) << 2
+    }
+
+    fn from_bits(bits: u8) -> Self {
+        CpuFeatures {
+            sse2: bits & 1 != 0,
+            avx2: bits & 2 != 0,
+            neon: bits & 4 != 0,
+        }
+    }
+}
+
+// The highest bit indicates that the detection was already performed.
+const DETECTED_BIT: u8 = 0x80;
+static FEATURES: AtomicU8 = AtomicU8::new(0);
+
+/// Returns CPU features available at runtime.
+///
+/// Detection is performed only once and then cached.
+///
+/// Runtime detection requires the `std` feature.
+/// Otherwise, only compile-time target features are reported.
+pub fn cpu_features() -> CpuFeatures {
+    let bits = FEATURES.load(Ordering::Relaxed);
+    if bits & DETECTED_BIT != 0 {
+        return CpuFeatures::from_bits(bits);
+    }
+
+    let features = detect();
+    // Multiple threads may race here, but they all will store the same value.
+    FEATURES.store(features.to_bits() | DETECTED_BIT, Ordering::Relaxed);
+    features
+}
+
+fn detect() -> CpuFeatures {
+    cfg_if::cfg_if! {
+        if #[cfg(all(feature = "std", any(target_arch = "x86", target_arch = "x86_64")))] {
+            CpuFeatures {
+                sse2: std::is_x86_feature_detected!("sse2"),
+                avx2: std::is_x86_feature_detected!("avx2"),
+                neon: false,
+            }
+        } else if #[cfg(all(feature = "std", target_arch = "aarch64"))] {
+            CpuFeatures {
+                sse2: false,
+                avx2: false,
+                neon: std::arch::is_aarch64_feature_detected!("neon"),
+            }
+        } else {
+            CpuFeatures {
+                sse2: cfg!(target_feature = "sse2"),
+                avx2: cfg!(target_feature = "avx2"),
+                neon: cfg!(all(target_arch = "aarch64", target_feature = "neon")),
+            }
+        }
+    }
+}
+
+/// Fills a span of pixels with a specified color.
+///
+/// Dispatches to the fastest available kernel.
+pub(crate) fn fill_span(span: &mut [PremultipliedColorU8], color: PremultipliedColorU8) {
+    match cpu_features().backend() {
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        SimdBackend::Avx2 => unsafe { x86::fill_span_avx2(span, color) },
+        #[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+        SimdBackend::Sse2 => unsafe { x86::fill_span_sse2(span, color) },
+        #[cfg(all(feature = "simd", target_arch = "aarch64"))]
+        SimdBackend::Neon => unsafe { aarch64::fill_span_neon(span, color) },
+        _ => fill_span_scalar(span, color),
+    }
+}
+
+fn fill_span_scalar(span: &mut [PremultipliedColorU8], color: PremultipliedColorU8) {
+    for p in span {
+        *p = color;
+    }
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64")))]
+mod x86 {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    use crate::PremultipliedColorU8;
+
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn fill_span_avx2(span: &mut [PremultipliedColorU8], color: PremultipliedColorU8) {
+        let v = _mm256_set1_epi32(bytemuck::cast(color));
+        let mut chunks = span.chunks_exact_mut(8);
+        for chunk in &mut chunks {
+            _mm256_storeu_si256(chunk.as_mut_ptr() as *mut __m256i, v);
+        }
+
+        super::fill_span_scalar(chunks.into_remainder(), color);
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn fill_span_sse2(span: &mut [PremultipliedColorU8], color: PremultipliedColorU8) {
+        let v = _mm_set1_epi32(bytemuck::cast(color));
+        let mut chunks = span.chunks_exact_mut(4);
+        for chunk in &mut chunks {
+            _mm_storeu_si128(chunk.as_mut_ptr() as *mut __m128i, v);
+        }
+
+        super::fill_span_scalar(chunks.into_remainder(), color);
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "aarch64"))]
+mod aarch64 {
+    use core::arch::aarch64::*;
+
+    use crate::PremultipliedColorU8;
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn fill_span_neon(span: &mut [PremultipliedColorU8], color: PremultipliedColorU8) {
+        let v = vdupq_n_u32(bytemuck::cast(color));
+        let mut chunks = span.chunks_exact_mut(4);
+        for chunk in &mut chunks {
+            vst1q_u32(chunk.as_mut_ptr() as *mut u32, v);
+        }
+
+        super::fill_span_scalar(chunks.into_remainder(), color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn detection_is_cached() {
+        assert_eq!(cpu_features(), cpu_features());
+    }
+
+    #[test]
+    fn fill_span_matches_scalar() {
+        let c = PremultipliedColorU8::from_rgba_unchecked(10, 20, 30, 40);
+        for len in 0..20 {
+            let mut a = vec![PremultipliedColorU8::TRANSPARENT; len];
+            let mut b = a.clone();
+            fill_span(&mut a, c);
+            fill_span_scalar(&mut b, c);
+            assert_eq!(a, b);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use crate::{
+    FillRule, IntRect, LineCap, LineJoin, Mask, Paint, Path, PixmapMut, PixmapPaint, PixmapRef,
+    Rect, Stroke, Transform,
+};
+
+/// A `PixmapMut` wrapper that records a region touched by drawing operations.
+///
+/// Can be created via
+/// [`PixmapMut::with_damage_tracking`](struct.PixmapMut.html#method.with_damage_tracking).
+///
+/// The damage is a conservative device-space bounding box of all drawing operations,
+/// clipped by the pixmap bounds. Pixels outside of it are guaranteed to stay unchanged.
+/// Anti-aliased edges are included as well.
+pub struct DamageTracker<'a, 'b> {
+    pixmap: &'a mut PixmapMut<'b>,
+    damage: Option<IntRect>,
+}
+
+impl<'b> PixmapMut<'b> {
+    /// Returns a wrapper that records the region touched by drawing operations.
+    pub fn with_damage_tracking(&mut self) -> DamageTracker<'_, 'b> {
+        DamageTracker {
+            pixmap: self,
+            damage: None,
+        }
+    }
+}
+
+impl DamageTracker<'_, '_> {
+    /// Returns the accumulated damage region.
+    ///
+    /// Returns `None` when nothing was drawn yet.
+    pub fn damage(&self) -> Option<IntRect> {
+        self.damage
+    }
+
+    /// Adds a region to the damage.
+    ///
+    /// Useful when the underlying pixmap was modified directly.
+    pub fn add_damage(&mut self, rect: IntRect) {
+        let rect = match self.pixmap.size().to_int_rect(0, 0).intersect(&rect) {
+            Some(v) => v,
+            None => return,
+        };
+
+        self.damage = match self.damage {
+            Some(damage) => union(&damage, &rect),
+            None => Some(rect),
+        };
+    }
+
+    /// Resets the damage region and returns the old one.
+    pub fn reset_damage(&mut self) -> Option<IntRect> {
+        self.damage.take()
+    }
+
+    /// Draws a filled rectangle onto the pixmap.
+    ///
+    /// See [`PixmapMut::fill_rect`](struct.PixmapMut.html#method.fill_rect) for details.
+    pub fn fill_rect(
+        &mut self,
+        rect: Rect,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.add_device_rect(rect.transform(transform));
+        self.pixmap.fill_rect(rect, paint, transform, mask);
+    }
+
+    /// Draws a filled path onto the pixmap.
+    ///
+    /// See [`PixmapMut::fill_path`](struct.PixmapMut.html#method.fill_path) for details.
+    pub fn fill_path(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        fill_rule: FillRule,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.add_device_rect(path.bounds().transform(transform));
+        self.pixmap
+            .fill_path(path, paint, fill_rule, transform, mask);
+    }
+
+    /// Strokes a path.
+    ///
+    /// See [`PixmapMut::stroke_path`](struct.PixmapMut.html#method.stroke_path) for details.
+    pub fn stroke_path(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        stroke: &Stroke,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        if stroke.width >= 0.0 {
+            let bounds = if crate::painter::treat_as_hairline(paint, stroke, transform).is_some() {
+                // Hairlines are always stroked in device space
+                // and anti-aliased hairlines can touch a neighbour pixel.
+                // Must match the outset in `scan::hairline::stroke_path_impl`.
+                let cap_out = if stroke.line_cap == LineCap::Butt {
+                    1.0
+                } else {
+                    2.0
+                };
+                path.bounds()
+                    .transform(transform)
+                    .and_then(|r| r.outset(cap_out, cap_out))
+            } else {
+                let r = stroke_inflation_radius(stroke);
+                path.bounds()
+                    .outset(r, r)
+                    .and_then(|r| r.transform(transform))
+            };
+
+            self.add_device_rect(bounds);
+        }
+
+        self.pixmap
+            .stroke_path(path, paint, stroke, transform, mask);
+    }
+
+    /// Draws a `Pixmap` on top of the current `Pixmap`.
+    ///
+    /// See [`PixmapMut::draw_pixmap`](struct.PixmapMut.html#method.draw_pixmap) for details.
+    pub fn draw_pixmap(
+        &mut self,
+        x: i32,
+        y: i32,
+        pixmap: PixmapRef,
+        paint: &PixmapPaint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let rect = pixmap.size().to_int_rect(x, y).to_rect();
+        self.add_device_rect(rect.transform(transform));
+        self.pixmap
+            .draw_pixmap(x, y, pixmap, paint, transform, mask);
+    }
+
+    fn add_device_rect(&mut self, rect: Option<Rect>) {
+        // Partially covered pixels are included by `round_out`.
+        if let Some(rect) = rect.and_then(|r| r.round_out()) {
+            self.add_damage(rect);
+        }
+    }
+}
+
+impl core::fmt::Debug for DamageTracker<'_, '_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DamageTracker")
+            .field("pixmap", &self.pixmap)
+            .field("damage", &self.damage)
+            .finish()
+    }
+}
+
+/// Returns how far a stroke can extend past the path bounds.
+///
+/// Based on SkStrokeRec::GetInflationRadius.
+fn stroke_inflation_radius(stroke: &Stroke) -> f32 {
+    let mut multiplier = 1.0;
+    if stroke.line_join == LineJoin::Miter {
+        multiplier = stroke.miter_limit.max(multiplier);
+    }
+
+    if stroke.line_cap == LineCap::Square {
+        multiplier = core::f32::consts::SQRT_2.max(multiplier);
+    }
+
+    stroke.width * 0.5 * multiplier
+}
+
+fn union(a: &IntRect, b: &IntRect) -> Option<IntRect> {
+    IntRect::from_ltrb(
+        a.left().min(b.left()),
+        a.top().min(b.top()),
+        a.right().max(b.right()),
+        a.bottom().max(b.bottom()),
+    )
+}
@@ -16,6 +16,35 @@ use crate::math::left_shift;
 /// larger than i8.
 const MAX_COEFF_SHIFT: i32 = 6;
 
+/// The default curve flattening tolerance in pixels.
+///
+/// Curves are subdivided until they are within ~1/8 of a pixel from their approximation.
+pub const DEFAULT_CURVE_TOLERANCE: f32 = 0.125;
+
+/// The supported curve flattening tolerance range in pixels.
+const MIN_CURVE_TOLERANCE: f32 = 1.0 / 1024.0;
+const MAX_CURVE_TOLERANCE: f32 = 64.0;
+
+/// Returns how many times the curve tolerance is finer than the default one.
+///
+/// Returns exactly 1.0 for the default tolerance.
+pub fn curve_tolerance_scale(tolerance: f32) -> f32 {
+    // NaN will be clamped to the minimum as well.
+    let tolerance = if tolerance > MIN_CURVE_TOLERANCE {
+        tolerance.min(MAX_CURVE_TOLERANCE)
+    } else {
+        MIN_CURVE_TOLERANCE
+    };
+    DEFAULT_CURVE_TOLERANCE / tolerance
+}
+
+/// Converts a curve flattening tolerance into a curve distance scale.
+///
+/// The default tolerance produces `fdot16::ONE`, aka no scaling.
+pub fn curve_scale(tolerance: f32) -> FDot16 {
+    (curve_tolerance_scale(tolerance) * fdot16::ONE as f32 + 0.5) as FDot16
+}
+
 #[derive(Clone, Debug)]
 pub enum Edge {
     Line(LineEdge),
@@ -39,6 +68,36 @@ impl Edge {
             Edge::Cubic(cubic) => &mut cubic.line,
         }
     }
+
+    /// Moves the edge by an integer offset.
+    ///
+    /// The offset is in the edge's coordinates, aka already shifted for anti-aliasing.
+    /// Since all coordinates are fixed-point, the result is exactly the same as building
+    /// an edge from translated points.
+    pub fn translate(&mut self, dx: i32, dy: i32) {
+        let fdx = left_shift(dx, 16);
+        let fdy = left_shift(dy, 16);
+        match self {
+            Edge::Line(_) => {}
+            Edge::Quadratic(quad) => {
+                quad.qx += fdx;
+                quad.qy += fdy;
+                quad.q_last_x += fdx;
+                quad.q_last_y += fdy;
+            }
+            Edge::Cubic(cubic) => {
+                cubic.cx += fdx;
+                cubic.cy += fdy;
+                cubic.c_last_x += fdx;
+                cubic.c_last_y += fdy;
+            }
+        }
+
+        let line = self.as_line_mut();
+        line.x += fdx;
+        line.first_y += dy;
+        line.last_y += dy;
+    }
 }
 
 impl core::ops::Deref for Edge {
@@ -157,8 +216,8 @@ pub struct QuadraticEdge {
 }
 
 impl QuadraticEdge {
-    pub fn new(points: &[Point], shift: i32) -> Option<Self> {
-        let mut quad = Self::new2(points, shift)?;
+    pub fn new(points: &[Point], shift: i32, curve_scale: FDot16) -> Option<Self> {
+        let mut quad = Self::new2(points, shift, curve_scale)?;
         if quad.update() {
             Some(quad)
         } else {
@@ -166,7 +225,8 @@ impl QuadraticEdge {
         }
     }
 
-    fn new2(points: &[Point], mut shift: i32) -> Option<Self> {
+    // Unlike `new`, doesn't produce the first line, so `curve_count` is the total number of lines.
+    pub fn new2(points: &[Point], mut shift: i32, curve_scale: FDot16) -> Option<Self> {
         let scale = (1 << (shift + 6)) as f32;
         let mut x0 = (points[0].x * scale) as i32;
         let mut y0 = (points[0].y * scale) as i32;
@@ -198,7 +258,7 @@ impl QuadraticEdge {
             // This is a little confusing:
             // before this line, shift is the scale up factor for AA;
             // after this line, shift is the fCurveShift.
-            shift = diff_to_shift(dx, dy, shift);
+            shift = diff_to_shift(dx, dy, shift, curve_scale);
             debug_assert!(shift >= 0);
         }
 
@@ -332,8 +392,8 @@ pub struct CubicEdge {
 }
 
 impl CubicEdge {
-    pub fn new(points: &[Point], shift: i32) -> Option<Self> {
-        let mut cubic = Self::new2(points, shift, true)?;
+    pub fn new(points: &[Point], shift: i32, curve_scale: FDot16) -> Option<Self> {
+        let mut cubic = Self::new2(points, shift, curve_scale, true)?;
         if cubic.update() {
             Some(cubic)
         } else {
@@ -341,7 +401,13 @@ impl CubicEdge {
         }
     }
 
-    fn new2(points: &[Point], mut shift: i32, sort_y: bool) -> Option<Self> {
+    // Unlike `new`, doesn't produce the first line, so `curve_count` is the total number of lines.
+    pub fn new2(
+        points: &[Point],
+        mut shift: i32,
+        curve_scale: FDot16,
+        sort_y: bool,
+    ) -> Option<Self> {
         let scale = (1 << (shift + 6)) as f32;
         let mut x0 = (points[0].x * scale) as i32;
         let mut y0 = (points[0].y * scale) as i32;
@@ -377,7 +443,7 @@ impl CubicEdge {
             let dx = cubic_delta_from_line(x0, x1, x2, x3);
             let dy = cubic_delta_from_line(y0, y1, y2, y3);
             // add 1 (by observation)
-            shift = diff_to_shift(dx, dy, 2) + 1;
+            shift = diff_to_shift(dx, dy, 2, curve_scale) + 1;
         }
         // need at least 1 subdivision for our bias trick
         debug_assert!(shift > 0);
@@ -502,10 +568,13 @@ fn compute_dy(top: FDot6, y0: FDot6) -> FDot6 {
     left_shift(top, 6) + 32 - y0
 }
 
-fn diff_to_shift(dx: FDot6, dy: FDot6, shift_aa: i32) -> i32 {
+fn diff_to_shift(dx: FDot6, dy: FDot6, shift_aa: i32, curve_scale: FDot16) -> i32 {
     // cheap calc of distance from center of p0-p2 to the center of the curve
     let mut dist = cheap_distance(dx, dy);
 
+    // Apply a custom tolerance. Doesn't affect the distance when `curve_scale` is 1.0.
+    dist = ((i64::from(dist) * i64::from(curve_scale)) >> 16).min(i64::from(i32::MAX)) as i32;
+
     // shift down dist (it is currently in dot6)
     // down by 3 should give us 1/8 pixel accuracy (assuming our dist is accurate...)
     // this is chosen by heuristic: make it as big as possible (to minimize segments)
@@ -545,7 +614,7 @@ fn fdot6_to_fixed_div2(value: FDot6) -> FDot16 {
 }
 
 fn fdot6_up_shift(x: FDot6, up_shift: i32) -> i32 {
-    debug_assert!((left_shift(x, up_shift) >> up_shift) == x);
+    hot_debug_assert!((left_shift(x, up_shift) >> up_shift) == x);
     left_shift(x, up_shift)
 }
 
@@ -10,8 +10,9 @@ use tiny_skia_path::PathVerb;
 
 use crate::{Path, Point};
 
-use crate::edge::{CubicEdge, Edge, LineEdge, QuadraticEdge};
+use crate::edge::{self, CubicEdge, Edge, LineEdge, QuadraticEdge};
 use crate::edge_clipper::EdgeClipperIter;
+use crate::fixed_point::FDot16;
 use crate::geom::ScreenIntRect;
 use crate::path_geometry;
 
@@ -57,13 +58,15 @@ impl ShiftedIntRect {
 pub struct BasicEdgeBuilder {
     edges: Vec<Edge>,
     clip_shift: i32,
+    curve_scale: FDot16,
 }
 
 impl BasicEdgeBuilder {
-    pub fn new(clip_shift: i32) -> Self {
+    pub fn new(clip_shift: i32, curve_tolerance: f32) -> Self {
         BasicEdgeBuilder {
             edges: Vec::with_capacity(64), // TODO: stack array + fallback
             clip_shift,
+            curve_scale: edge::curve_scale(curve_tolerance),
         }
     }
 
@@ -73,12 +76,13 @@ impl BasicEdgeBuilder {
         path: &Path,
         clip: Option<&ShiftedIntRect>,
         clip_shift: i32,
+        curve_tolerance: f32,
     ) -> Option<Vec<Edge>> {
         // If we're convex, then we need both edges, even if the right edge is past the clip.
         // let can_cull_to_the_right = !path.isConvex();
         let can_cull_to_the_right = false; // TODO: this
 
-        let mut builder = BasicEdgeBuilder::new(clip_shift);
+        let mut builder = BasicEdgeBuilder::new(clip_shift, curve_tolerance);
         if !builder.build(path, clip, can_cull_to_the_right) {
             log::warn!("infinite or NaN segments detected during edges building");
             return None;
@@ -183,18 +187,61 @@ impl BasicEdgeBuilder {
     }
 
     fn push_quad(&mut self, points: &[Point]) {
-        if let Some(edge) = QuadraticEdge::new(points, self.clip_shift) {
+        if let Some(edge) = QuadraticEdge::new(points, self.clip_shift, self.curve_scale) {
             self.edges.push(Edge::Quadratic(edge));
         }
     }
 
     fn push_cubic(&mut self, points: &[Point]) {
-        if let Some(edge) = CubicEdge::new(points, self.clip_shift) {
+        if let Some(edge) = CubicEdge::new(points, self.clip_shift, self.curve_scale) {
             self.edges.push(Edge::Cubic(edge));
         }
     }
 }
 
+/// Returns the number of line segments a path will be flattened into.
+///
+/// Uses the same subdivision as `BasicEdgeBuilder`, but doesn't allocate.
+/// Clipping is ignored, therefore the result is an upper bound.
+pub fn count_segments(path: &Path, shift: i32, curve_tolerance: f32) -> usize {
+    let curve_scale = edge::curve_scale(curve_tolerance);
+    let mut count = 0;
+    for edge in edge_iter(path) {
+        match edge {
+            PathEdge::LineTo(p0, p1) => {
+                if LineEdge::new(p0, p1, shift).is_some() {
+                    count += 1;
+                }
+            }
+            PathEdge::QuadTo(p0, p1, p2) => {
+                let points = [p0, p1, p2];
+                let mut mono_x = [Point::zero(); 5];
+                let n = path_geometry::chop_quad_at_y_extrema(&points, &mut mono_x);
+                for i in 0..=n {
+                    // `new` would have already consumed the first line.
+                    if let Some(edge) = QuadraticEdge::new2(&mono_x[i * 2..], shift, curve_scale) {
+                        count += usize::from(edge.curve_count.unsigned_abs());
+                    }
+                }
+            }
+            PathEdge::CubicTo(p0, p1, p2, p3) => {
+                let points = [p0, p1, p2, p3];
+                let mut mono_y = [Point::zero(); 10];
+                let n = path_geometry::chop_cubic_at_y_extrema(&points, &mut mono_y);
+                for i in 0..=n {
+                    if let Some(edge) = CubicEdge::new2(&mono_y[i * 3..], shift, curve_scale, true)
+                    {
+                        // Cubic edges store a negative count.
+                        count += usize::from(edge.curve_count.unsigned_abs());
+                    }
+                }
+            }
+        }
+    }
+
+    count
+}
+
 fn combine_vertical(edge: &LineEdge, last: &mut LineEdge) -> Combine {
     if last.dx != 0 || edge.x != last.x {
         return Combine::No;
@@ -349,3 +396,50 @@ impl<'a> Iterator for PathEdgeIter<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tiny_skia_path::PathBuilder;
+
+    // Counts lines actually produced by stepping edges.
+    fn produced_lines(edges: &mut [Edge]) -> usize {
+        let mut count = 0;
+        for edge in edges {
+            count += 1;
+            match edge {
+                Edge::Line(_) => {}
+                Edge::Quadratic(quad) => {
+                    while quad.curve_count > 0 {
+                        quad.update();
+                        count += 1;
+                    }
+                }
+                Edge::Cubic(cubic) => {
+                    while cubic.curve_count < 0 {
+                        cubic.update();
+                        count += 1;
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    fn count_segments_is_upper_bound() {
+        let mut pb = PathBuilder::new();
+        pb.push_circle(50.0, 50.0, 40.0);
+        pb.move_to(10.0, 10.0);
+        pb.quad_to(90.0, 20.0, 30.0, 80.0);
+        pb.close();
+        let path = pb.finish().unwrap();
+
+        for shift in [0, 2] {
+            let mut edges = BasicEdgeBuilder::build_edges(&path, None, shift, 0.25).unwrap();
+            let lines = produced_lines(&mut edges);
+            assert!(count_segments(&path, shift, 0.25) >= lines);
+        }
+    }
+}
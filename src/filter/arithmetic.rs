@@ -0,0 +1,137 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use tiny_skia_path::Scalar;
+
+use crate::{Pixmap, PixmapRef, PremultipliedColorU8};
+
+impl Pixmap {
+    /// Composites two pixmaps using an arithmetic formula.
+    ///
+    /// Similar to SVG `feComposite` with `operator="arithmetic"`.
+    ///
+    /// Each channel is computed in premultiplied space as
+    /// `k1 * i1 * i2 + k2 * i1 + k3 * i2 + k4`, where `i1` is the current pixmap
+    /// and `i2` is `other`. Results are clamped to the 0..=1 range
+    /// and color channels are clamped to alpha, so the result is always
+    /// a valid premultiplied color.
+    ///
+    /// Returns `None` when pixmaps have different sizes or any coefficient is not finite.
+    pub fn composite_arithmetic(
+        &self,
+        other: PixmapRef,
+        k1: f32,
+        k2: f32,
+        k3: f32,
+        k4: f32,
+    ) -> Option<Pixmap> {
+        if self.width() != other.width() || self.height() != other.height() {
+            return None;
+        }
+
+        if !(k1.is_finite() && k2.is_finite() && k3.is_finite() && k4.is_finite()) {
+            return None;
+        }
+
+        let mut result = self.clone();
+        let pixels = result.pixels_mut().iter_mut();
+        for (p, o) in pixels.zip(other.rows().flatten()) {
+            let f = |i1: u8, i2: u8| {
+                let i1 = f32::from(i1) / 255.0;
+                let i2 = f32::from(i2) / 255.0;
+                let v = k1 * i1 * i2 + k2 * i1 + k3 * i2 + k4;
+                (v.bound(0.0, 1.0) * 255.0 + 0.5) as u8
+            };
+
+            let a = f(p.alpha(), o.alpha());
+            *p = PremultipliedColorU8::from_rgba_unchecked(
+                f(p.red(), o.red()).min(a),
+                f(p.green(), o.green()).min(a),
+                f(p.blue(), o.blue()).min(a),
+                a,
+            );
+        }
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn solid(r: u8, g: u8, b: u8, a: u8) -> Pixmap {
+        let mut pixmap = Pixmap::new(3, 2).unwrap();
+        pixmap.fill(Color::from_rgba8(r, g, b, a));
+        pixmap
+    }
+
+    #[test]
+    fn source() {
+        let p1 = solid(255, 0, 0, 200);
+        let p2 = solid(0, 255, 0, 255);
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 1.0, 0.0, 0.0)
+            .unwrap();
+        assert_eq!(result, p1);
+
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 0.0, 1.0, 0.0)
+            .unwrap();
+        assert_eq!(result, p2);
+    }
+
+    #[test]
+    fn multiply() {
+        let p1 = solid(255, 255, 255, 255);
+        let p2 = solid(0, 128, 255, 255);
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 1.0, 0.0, 0.0, 0.0)
+            .unwrap();
+        assert_eq!(result, p2);
+    }
+
+    #[test]
+    fn clamped() {
+        let p1 = solid(255, 0, 0, 255);
+        let p2 = solid(255, 0, 0, 255);
+
+        // Would be 2.0 for red and alpha.
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 1.0, 1.0, 0.0)
+            .unwrap();
+        assert_eq!(result, p1);
+
+        // Negative values are clamped to zero.
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 0.0, 0.0, -0.5)
+            .unwrap();
+        assert_eq!(result, solid(0, 0, 0, 0));
+
+        // Colors are clamped to alpha.
+        let result = p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 0.0, 0.0, 0.5)
+            .unwrap();
+        assert_eq!(
+            result.pixel(0, 0).unwrap(),
+            PremultipliedColorU8::from_rgba(128, 128, 128, 128).unwrap()
+        );
+    }
+
+    #[test]
+    fn size_mismatch() {
+        let p1 = solid(255, 0, 0, 255);
+        let p2 = Pixmap::new(3, 3).unwrap();
+        assert!(p1
+            .composite_arithmetic(p2.as_ref(), 0.0, 1.0, 0.0, 0.0)
+            .is_none());
+        assert!(p1
+            .composite_arithmetic(p1.as_ref(), f32::NAN, 1.0, 0.0, 0.0)
+            .is_none());
+    }
+}
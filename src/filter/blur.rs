@@ -0,0 +1,233 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+//! A Gaussian blur approximation using three box blurs.
+//!
+//! Based on the SVG `feGaussianBlur` specification.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+/// Returns a box size for the specified standard deviation.
+///
+/// Zero means that no blur is required.
+pub fn box_size(sigma: f32) -> usize {
+    if !sigma.is_finite() || sigma <= 0.0 {
+        return 0;
+    }
+
+    // d = floor(s * 3 * sqrt(2 * PI) / 4 + 0.5)
+    let d = (sigma * 3.0 * (2.0 * core::f32::consts::PI).sqrt() / 4.0 + 0.5).floor();
+    // Prevent overflows for absurd values. Such a blur will produce an empty image anyway.
+    d.min(1_000_000.0) as usize
+}
+
+/// Returns how far a blurred image can extend in each direction.
+pub fn blur_extent(sigma: f32) -> usize {
+    let d = box_size(sigma);
+    if d == 0 {
+        0
+    } else {
+        // Three boxes, each extends by up to `d / 2` pixels.
+        3 * (d / 2) + 1
+    }
+}
+
+/// Defines how pixels outside the image are treated.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum EdgeMode {
+    /// Pixels outside the image are assumed to be zero.
+    Zero,
+    /// Pixels outside the image are the same as the nearest edge pixel.
+    Clamp,
+}
+
+/// Blurs a single channel image in place.
+pub fn blur(
+    data: &mut [u8],
+    width: usize,
+    height: usize,
+    sigma_x: f32,
+    sigma_y: f32,
+    edge_mode: EdgeMode,
+) {
+    debug_assert_eq!(data.len(), width * height);
+
+    let dx = box_size(sigma_x);
+    let dy = box_size(sigma_y);
+    if dx <= 1 && dy <= 1 {
+        return;
+    }
+
+    let mut buf = vec![0; width.max(height)];
+    let mut line = vec![0; width.max(height)];
+
+    if dx > 1 {
+        for row in data.chunks_exact_mut(width) {
+            box_blur_3(row, &mut buf[..width], dx, edge_mode);
+        }
+    }
+
+    if dy > 1 {
+        let line = &mut line[..height];
+        for x in 0..width {
+            for y in 0..height {
+                line[y] = data[y * width + x];
+            }
+
+            box_blur_3(line, &mut buf[..height], dy, edge_mode);
+
+            for y in 0..height {
+                data[y * width + x] = line[y];
+            }
+        }
+    }
+}
+
+fn box_blur_3(line: &mut [u8], buf: &mut [u8], d: usize, edge_mode: EdgeMode) {
+    if d % 2 == 1 {
+        // Three boxes of size `d` centered on the output pixel.
+        let r = d / 2;
+        box_blur(line, buf, r, r, edge_mode);
+        box_blur(buf, line, r, r, edge_mode);
+        box_blur(line, buf, r, r, edge_mode);
+    } else {
+        // Two boxes of size `d`, centered on the pixel boundaries,
+        // and one of size `d + 1` centered on the output pixel.
+        let r = d / 2;
+        box_blur(line, buf, r, r - 1, edge_mode);
+        box_blur(buf, line, r - 1, r, edge_mode);
+        box_blur(line, buf, r, r, edge_mode);
+    }
+
+    line.copy_from_slice(buf);
+}
+
+/// A single box blur pass.
+///
+/// Each output pixel is an average of `left + 1 + right` input pixels.
+fn box_blur(src: &[u8], dst: &mut [u8], left: usize, right: usize, edge_mode: EdgeMode) {
+    let len = src.len();
+    let size = (left + 1 + right) as u32;
+
+    // Returns an input pixel at `i - left`, which can be outside the line.
+    let get = |i: usize| -> u32 {
+        let n = if i < left {
+            match edge_mode {
+                EdgeMode::Zero => 0,
+                EdgeMode::Clamp => src[0],
+            }
+        } else if i - left >= len {
+            match edge_mode {
+                EdgeMode::Zero => 0,
+                EdgeMode::Clamp => src[len - 1],
+            }
+        } else {
+            src[i - left]
+        };
+
+        u32::from(n)
+    };
+
+    let mut sum: u32 = (0..size as usize).map(get).sum();
+    for i in 0..len {
+        // An average of 8-bit values cannot exceed 255.
+        dst[i] = ((sum + size / 2) / size) as u8;
+        sum += get(i + size as usize);
+        sum -= get(i);
+    }
+}
+
+/// Extracts an alpha channel from premultiplied RGBA pixels.
+pub fn extract_alpha(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(4).map(|p| p[3]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_blur() {
+        assert_eq!(box_size(0.0), 0);
+        assert_eq!(box_size(-1.0), 0);
+        assert_eq!(box_size(f32::NAN), 0);
+
+        let mut data = vec![0, 0, 255, 0, 0];
+        blur(&mut data, 5, 1, 0.0, 0.0, EdgeMode::Zero);
+        assert_eq!(data, vec![0, 0, 255, 0, 0]);
+    }
+
+    #[test]
+    fn symmetric() {
+        let mut data = vec![0; 21 * 21];
+        data[10 * 21 + 10] = 255;
+        data[10 * 21 + 9] = 255;
+        data[10 * 21 + 11] = 255;
+        blur(&mut data, 21, 21, 2.0, 2.0, EdgeMode::Zero);
+
+        for y in 0..21 {
+            for x in 0..21 {
+                assert_eq!(data[y * 21 + x], data[y * 21 + (20 - x)]);
+            }
+        }
+
+        // Peak is in the center and the energy spreads out.
+        let center = data[10 * 21 + 10];
+        assert!(center > 0 && center < 255);
+        assert!(data.iter().all(|n| *n <= center));
+        assert!(data[10 * 21 + 14] > 0);
+        assert_eq!(data[0], 0);
+    }
+
+    #[test]
+    fn solid_stays_solid() {
+        let mut data = vec![255; 40 * 40];
+        blur(&mut data, 40, 40, 1.5, 1.5, EdgeMode::Zero);
+        assert_eq!(data[20 * 40 + 20], 255);
+        // Edges are blended with the transparent outside.
+        assert!(data[0] < 255);
+    }
+
+    #[test]
+    fn even_box_size() {
+        // sigma = 2.0 gives d = 4.
+        assert_eq!(box_size(2.0), 4);
+
+        let mut data = vec![0; 20];
+        data[10] = 255;
+        blur(&mut data, 20, 1, 2.0, 0.0, EdgeMode::Zero);
+        assert!(data.iter().map(|n| *n as u32).sum::<u32>() > 200);
+    }
+
+    #[test]
+    fn clamp_edges() {
+        // A solid image stays solid, since there is nothing to blend with.
+        let mut data = vec![255; 40 * 40];
+        blur(&mut data, 40, 40, 3.0, 3.0, EdgeMode::Clamp);
+        assert!(data.iter().all(|n| *n == 255));
+
+        // A half-filled line keeps its edge values.
+        let mut data = vec![0; 20];
+        data[10..].fill(200);
+        blur(&mut data, 20, 1, 2.0, 0.0, EdgeMode::Clamp);
+        assert_eq!(data[0], 0);
+        assert_eq!(data[19], 200);
+        assert!(data[9] > 0 && data[10] < 200);
+        assert!(data.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn large_sigma_does_not_overflow() {
+        let mut data = vec![255; 8 * 8];
+        blur(&mut data, 8, 8, 1000.0, 1000.0, EdgeMode::Clamp);
+        assert!(data.iter().all(|n| *n == 255));
+    }
+}
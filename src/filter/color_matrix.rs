@@ -0,0 +1,243 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use crate::{Pixmap, PixmapMut};
+
+use crate::wide::f32x4;
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+/// A 4x5 color matrix.
+///
+/// Similar to SVG `feColorMatrix`.
+///
+/// Values are stored in a row-major order, so each color is calculated as:
+///
+/// ```text
+/// R' = m[0]  * R + m[1]  * G + m[2]  * B + m[3]  * A + m[4]
+/// G' = m[5]  * R + m[6]  * G + m[7]  * B + m[8]  * A + m[9]
+/// B' = m[10] * R + m[11] * G + m[12] * B + m[13] * A + m[14]
+/// A' = m[15] * R + m[16] * G + m[17] * B + m[18] * A + m[19]
+/// ```
+///
+/// Colors are unpremultiplied and in a 0..=1 range.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ColorMatrix {
+    values: [f32; 20],
+}
+
+impl ColorMatrix {
+    /// Creates a new matrix from row-major values.
+    pub fn from_row_major(values: [f32; 20]) -> Self {
+        ColorMatrix { values }
+    }
+
+    /// Creates an identity matrix.
+    pub fn identity() -> Self {
+        ColorMatrix::saturate(1.0)
+    }
+
+    /// Creates a saturation matrix.
+    ///
+    /// 0 produces a grayscale image and 1 leaves the colors unchanged.
+    /// Values above 1 will oversaturate the image.
+    ///
+    /// The same as SVG `feColorMatrix` with `type="saturate"`.
+    pub fn saturate(s: f32) -> Self {
+        #[rustfmt::skip]
+        let values = [
+            0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s, 0.0, 0.0,
+            0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s, 0.0, 0.0,
+            0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s, 0.0, 0.0,
+            0.0,               0.0,               0.0,               1.0, 0.0,
+        ];
+
+        ColorMatrix { values }
+    }
+
+    /// Creates a hue rotation matrix.
+    ///
+    /// The angle is in degrees.
+    ///
+    /// The same as SVG `feColorMatrix` with `type="hueRotate"`.
+    pub fn hue_rotate(degrees: f32) -> Self {
+        let rad = degrees.to_radians();
+        let (sin, cos) = (rad.sin(), rad.cos());
+
+        #[rustfmt::skip]
+        let values = [
+            0.213 + cos * 0.787 - sin * 0.213,
+            0.715 - cos * 0.715 - sin * 0.715,
+            0.072 - cos * 0.072 + sin * 0.928,
+            0.0, 0.0,
+            0.213 - cos * 0.213 + sin * 0.143,
+            0.715 + cos * 0.285 + sin * 0.140,
+            0.072 - cos * 0.072 - sin * 0.283,
+            0.0, 0.0,
+            0.213 - cos * 0.213 - sin * 0.787,
+            0.715 - cos * 0.715 + sin * 0.715,
+            0.072 + cos * 0.928 + sin * 0.072,
+            0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ];
+
+        ColorMatrix { values }
+    }
+
+    /// Creates a grayscale matrix.
+    ///
+    /// The same as `saturate(0.0)`.
+    pub fn grayscale() -> Self {
+        ColorMatrix::saturate(0.0)
+    }
+
+    /// Returns matrix values in a row-major order.
+    pub fn values(&self) -> &[f32; 20] {
+        &self.values
+    }
+}
+
+impl Default for ColorMatrix {
+    fn default() -> Self {
+        ColorMatrix::identity()
+    }
+}
+
+impl Pixmap {
+    /// Applies a 4x5 color matrix to each pixel.
+    ///
+    /// See [`PixmapMut::apply_color_matrix`] for details.
+    pub fn apply_color_matrix(&mut self, m: &[f32; 20]) {
+        self.as_mut().apply_color_matrix(m)
+    }
+}
+
+impl PixmapMut<'_> {
+    /// Applies a 4x5 color matrix to each pixel.
+    ///
+    /// Each pixel is unpremultiplied first, then transformed as `M * [R, G, B, A, 1]`,
+    /// clamped to a 0..=1 range and premultiplied back.
+    ///
+    /// See [`ColorMatrix`] for the values layout and predefined matrices.
+    pub fn apply_color_matrix(&mut self, m: &[f32; 20]) {
+        // Columns of the matrix, so each channel can be processed at once.
+        let cr = f32x4::from([m[0], m[5], m[10], m[15]]);
+        let cg = f32x4::from([m[1], m[6], m[11], m[16]]);
+        let cb = f32x4::from([m[2], m[7], m[12], m[17]]);
+        let ca = f32x4::from([m[3], m[8], m[13], m[18]]);
+        // Prescale the offset, since we're operating in a 0..=255 range.
+        let offset = f32x4::from([m[4], m[9], m[14], m[19]]) * f32x4::splat(255.0);
+
+        let zero = f32x4::splat(0.0);
+        let max = f32x4::splat(255.0);
+        let half = f32x4::splat(0.5);
+
+        for p in self.rows_mut().flatten() {
+            let a = p.alpha() as f32;
+            let (r, g, b) = if a == 0.0 {
+                (0.0, 0.0, 0.0)
+            } else {
+                let scale = 255.0 / a;
+                (
+                    p.red() as f32 * scale,
+                    p.green() as f32 * scale,
+                    p.blue() as f32 * scale,
+                )
+            };
+
+            let c = cr * f32x4::splat(r)
+                + cg * f32x4::splat(g)
+                + cb * f32x4::splat(b)
+                + ca * f32x4::splat(a)
+                + offset;
+            let c = c.max(zero).min(max);
+
+            // Premultiply.
+            let c: [f32; 4] = c.into();
+            let a = c[3];
+            let c = f32x4::from([c[0], c[1], c[2], 255.0]) * f32x4::splat(a / 255.0) + half;
+            let c: [f32; 4] = c.into();
+
+            // Colors are guarantee to be <= alpha, since we're scaling by alpha.
+            *p = crate::PremultipliedColorU8::from_rgba_unchecked(
+                c[0] as u8, c[1] as u8, c[2] as u8, c[3] as u8,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Color, PremultipliedColorU8};
+
+    #[test]
+    fn identity() {
+        let mut pixmap = Pixmap::new(4, 1).unwrap();
+        pixmap.pixels_mut()[0] = PremultipliedColorU8::from_rgba(10, 20, 30, 40).unwrap();
+        pixmap.pixels_mut()[1] = PremultipliedColorU8::from_rgba(255, 127, 0, 255).unwrap();
+        pixmap.pixels_mut()[2] = PremultipliedColorU8::from_rgba(1, 2, 3, 3).unwrap();
+        let original = pixmap.clone();
+
+        pixmap.apply_color_matrix(ColorMatrix::identity().values());
+        assert_eq!(pixmap, original);
+    }
+
+    #[test]
+    fn grayscale() {
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(255, 0, 0, 255));
+        pixmap.apply_color_matrix(ColorMatrix::grayscale().values());
+        let c = pixmap.pixel(0, 0).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (54, 54, 54, 255));
+    }
+
+    #[test]
+    fn alpha_scale() {
+        // Scales alpha by 0.5, while preserving straight colors.
+        #[rustfmt::skip]
+        let m = [
+            1.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 1.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 1.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.5, 0.0,
+        ];
+
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(200, 100, 0, 255));
+        pixmap.apply_color_matrix(&m);
+        let c = pixmap.pixel(0, 0).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (100, 50, 0, 128));
+    }
+
+    #[test]
+    fn offset_on_transparent() {
+        // A flood fill.
+        #[rustfmt::skip]
+        let m = [
+            0.0, 0.0, 0.0, 0.0, 1.0,
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 0.0,
+            0.0, 0.0, 0.0, 0.0, 1.0,
+        ];
+
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.apply_color_matrix(&m);
+        let c = pixmap.pixel(0, 0).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (255, 0, 0, 255));
+    }
+
+    #[test]
+    fn hue_rotate_360() {
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(200, 100, 50, 255));
+        let original = pixmap.clone();
+        pixmap.apply_color_matrix(ColorMatrix::hue_rotate(360.0).values());
+        assert_eq!(pixmap, original);
+    }
+}
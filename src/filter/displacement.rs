@@ -0,0 +1,207 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use crate::{ColorU8, Pixmap, PixmapRef, PremultipliedColorU8};
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+/// A color channel.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ColorChannel {
+    /// Red channel.
+    R,
+    /// Green channel.
+    G,
+    /// Blue channel.
+    B,
+    /// Alpha channel.
+    A,
+}
+
+impl ColorChannel {
+    fn get(self, c: ColorU8) -> u8 {
+        match self {
+            ColorChannel::R => c.red(),
+            ColorChannel::G => c.green(),
+            ColorChannel::B => c.blue(),
+            ColorChannel::A => c.alpha(),
+        }
+    }
+}
+
+impl Pixmap {
+    /// Displaces pixels using a displacement map.
+    ///
+    /// Similar to SVG `feDisplacementMap`. Each output pixel is sampled from
+    /// the current pixmap at an offset of `scale * (C / 255 - 0.5)` per axis,
+    /// where `C` is a value of the selected unpremultiplied `map` channel
+    /// at the same position.
+    ///
+    /// The source is sampled using bilinear filtering, with coordinates clamped
+    /// to the pixmap edges. Pixels outside the `map` are not displaced.
+    pub fn displace(
+        &self,
+        map: PixmapRef,
+        scale: f32,
+        x_channel: ColorChannel,
+        y_channel: ColorChannel,
+    ) -> Pixmap {
+        let mut result = self.clone();
+        if !scale.is_finite() || scale == 0.0 {
+            return result;
+        }
+
+        let width = self.width();
+        let height = self.height();
+        let src = self.pixels();
+        let dst = result.pixels_mut();
+        for y in 0..height.min(map.height()) {
+            for x in 0..width.min(map.width()) {
+                // Cannot fail, since we're inside the map.
+                let c = match map.pixel(x, y) {
+                    Some(c) => c.demultiply(),
+                    None => continue,
+                };
+
+                let dx = scale * (f32::from(x_channel.get(c)) / 255.0 - 0.5);
+                let dy = scale * (f32::from(y_channel.get(c)) / 255.0 - 0.5);
+                dst[(y * width + x) as usize] =
+                    sample_bilinear(src, width, height, x as f32 + dx, y as f32 + dy);
+            }
+        }
+
+        result
+    }
+}
+
+// Pixel centers are at integer coordinates here.
+fn sample_bilinear(
+    pixels: &[PremultipliedColorU8],
+    width: u32,
+    height: u32,
+    x: f32,
+    y: f32,
+) -> PremultipliedColorU8 {
+    let max_x = (width - 1) as f32;
+    let max_y = (height - 1) as f32;
+    let x = x.max(0.0).min(max_x);
+    let y = y.max(0.0).min(max_y);
+
+    let x0 = x.floor();
+    let y0 = y.floor();
+    let fx = x - x0;
+    let fy = y - y0;
+    let x0 = x0 as u32;
+    let y0 = y0 as u32;
+    let x1 = (x0 + 1).min(width - 1);
+    let y1 = (y0 + 1).min(height - 1);
+
+    let p = |x: u32, y: u32| {
+        let c = pixels[(y * width + x) as usize];
+        [
+            f32::from(c.red()),
+            f32::from(c.green()),
+            f32::from(c.blue()),
+            f32::from(c.alpha()),
+        ]
+    };
+
+    let (p00, p10, p01, p11) = (p(x0, y0), p(x1, y0), p(x0, y1), p(x1, y1));
+    let mut c = [0u8; 4];
+    for i in 0..4 {
+        let top = p00[i] + (p10[i] - p00[i]) * fx;
+        let bottom = p01[i] + (p11[i] - p01[i]) * fx;
+        c[i] = (top + (bottom - top) * fy + 0.5) as u8;
+    }
+
+    // A convex combination of premultiplied colors is still premultiplied.
+    PremultipliedColorU8::from_rgba_unchecked(c[0], c[1], c[2], c[3])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gradient() -> Pixmap {
+        let mut pixmap = Pixmap::new(4, 3).unwrap();
+        for (i, p) in pixmap.pixels_mut().iter_mut().enumerate() {
+            let v = (i * 20) as u8;
+            *p = PremultipliedColorU8::from_rgba(v, v / 2, 0, 255).unwrap();
+        }
+
+        pixmap
+    }
+
+    fn solid_map(r: u8, g: u8, b: u8, a: u8) -> Pixmap {
+        let mut map = Pixmap::new(4, 3).unwrap();
+        map.fill(crate::Color::from_rgba8(r, g, b, a));
+        map
+    }
+
+    #[test]
+    fn zero_scale() {
+        let pixmap = gradient();
+        let map = solid_map(255, 0, 0, 255);
+        let result = pixmap.displace(map.as_ref(), 0.0, ColorChannel::R, ColorChannel::G);
+        assert_eq!(result, pixmap);
+    }
+
+    #[test]
+    fn channels() {
+        let pixmap = gradient();
+        let map = solid_map(255, 0, 0, 255);
+
+        // Shift by +1 horizontally and by -1 vertically.
+        let result = pixmap.displace(map.as_ref(), 2.0, ColorChannel::R, ColorChannel::G);
+        for y in 0..3 {
+            for x in 0..4 {
+                let sx = (x + 1).min(3);
+                let sy = y.max(1) - 1;
+                assert_eq!(result.pixel(x, y), pixmap.pixel(sx, sy));
+            }
+        }
+
+        // Alpha is 255, blue is 0, therefore the direction is reversed.
+        let result = pixmap.displace(map.as_ref(), 2.0, ColorChannel::B, ColorChannel::A);
+        for y in 0..3 {
+            for x in 0..4 {
+                let sx = x.max(1) - 1;
+                let sy = (y + 1).min(2);
+                assert_eq!(result.pixel(x, y), pixmap.pixel(sx, sy));
+            }
+        }
+    }
+
+    #[test]
+    fn bilinear() {
+        let pixmap = gradient();
+        let map = solid_map(255, 128, 0, 255);
+
+        // Shift by half a pixel horizontally.
+        let result = pixmap.displace(map.as_ref(), 1.0, ColorChannel::R, ColorChannel::G);
+        // Vertical displacement is 128/255 - 0.5, which is negligible.
+        assert_eq!(
+            result.pixel(0, 0).unwrap(),
+            PremultipliedColorU8::from_rgba(10, 5, 0, 255).unwrap()
+        );
+        // Clamped to the right edge.
+        assert_eq!(result.pixel(3, 0), pixmap.pixel(3, 0));
+    }
+
+    #[test]
+    fn smaller_map() {
+        let pixmap = gradient();
+        let mut map = Pixmap::new(2, 2).unwrap();
+        map.fill(crate::Color::from_rgba8(255, 255, 0, 255));
+
+        let result = pixmap.displace(map.as_ref(), 2.0, ColorChannel::R, ColorChannel::G);
+        assert_eq!(result.pixel(0, 0), pixmap.pixel(1, 1));
+        // Outside the map.
+        assert_eq!(result.pixel(3, 2), pixmap.pixel(3, 2));
+    }
+}
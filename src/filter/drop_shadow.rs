@@ -0,0 +1,196 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use tiny_skia_path::IntSize;
+
+use crate::{
+    Color, FilterQuality, Pixmap, PixmapPaint, PremultipliedColorU8, Transform, BYTES_PER_PIXEL,
+};
+
+use crate::pixmap::data_len_for_size_and_bpp;
+
+use super::blur;
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+impl Pixmap {
+    /// Renders a drop shadow below the pixmap.
+    ///
+    /// The shadow is created from the pixmap's alpha, blurred using `sigma` as a standard deviation,
+    /// tinted with `color` and offset by `dx`/`dy`. The original pixmap is drawn on top.
+    /// Similar to SVG `feDropShadow`.
+    ///
+    /// The resulting pixmap is large enough to fit the whole shadow.
+    /// Therefore it can be larger than the current one and the original content
+    /// can be offset. Use [`drop_shadow_origin`](Pixmap::drop_shadow_origin)
+    /// to get the position of the original content.
+    ///
+    /// Returns `None` when offsets are not finite or the resulting pixmap is too big,
+    /// just like [`Pixmap::new`].
+    pub fn drop_shadow(&self, dx: f32, dy: f32, sigma: f32, color: Color) -> Option<Pixmap> {
+        let (left, top, width, height) = self.drop_shadow_layout(dx, dy, sigma)?;
+        let mut shadow = Pixmap::new(width, height)?;
+
+        // Place the source alpha with a subpixel offset.
+        let quality = if dx.trunc() == dx && dy.trunc() == dy {
+            FilterQuality::Nearest
+        } else {
+            FilterQuality::Bilinear
+        };
+        let paint = PixmapPaint {
+            quality,
+            ..PixmapPaint::default()
+        };
+        shadow.draw_pixmap(
+            0,
+            0,
+            self.as_ref(),
+            &paint,
+            Transform::from_translate(dx - left as f32, dy - top as f32),
+            None,
+        );
+
+        let mut alpha = blur::extract_alpha(shadow.data());
+        let sigma = sigma.max(0.0);
+        blur::blur(
+            &mut alpha,
+            width as usize,
+            height as usize,
+            sigma,
+            sigma,
+            blur::EdgeMode::Zero,
+        );
+
+        // Tint.
+        let color = color.premultiply();
+        for (p, a) in shadow.pixels_mut().iter_mut().zip(alpha) {
+            let a = a as f32 / 255.0;
+            let c = |n: f32| (n * a * 255.0 + 0.5) as u8;
+            *p = PremultipliedColorU8::from_rgba_unchecked(
+                c(color.red()),
+                c(color.green()),
+                c(color.blue()),
+                c(color.alpha()),
+            );
+        }
+
+        shadow.draw_pixmap(
+            -left,
+            -top,
+            self.as_ref(),
+            &PixmapPaint::default(),
+            Transform::identity(),
+            None,
+        );
+
+        Some(shadow)
+    }
+
+    /// Returns a position of the original content inside a pixmap
+    /// produced by [`drop_shadow`](Pixmap::drop_shadow) with the same arguments.
+    pub fn drop_shadow_origin(&self, dx: f32, dy: f32, sigma: f32) -> Option<(u32, u32)> {
+        let (left, top, _, _) = self.drop_shadow_layout(dx, dy, sigma)?;
+        Some((-left as u32, -top as u32))
+    }
+
+    /// Returns the shadowed pixmap's bounds in the current pixmap coordinates.
+    fn drop_shadow_layout(&self, dx: f32, dy: f32, sigma: f32) -> Option<(i32, i32, u32, u32)> {
+        if !dx.is_finite() || !dy.is_finite() {
+            return None;
+        }
+
+        let extent = blur::blur_extent(sigma) as f32;
+        let w = self.width() as f64;
+        let h = self.height() as f64;
+
+        // Rounding is done in `f32`, since `f64` methods are not available in `no_std`.
+        let left = ((dx - extent).floor() as f64).min(0.0);
+        let top = ((dy - extent).floor() as f64).min(0.0);
+        let right = ((dx + extent).ceil() as f64 + w).max(w);
+        let bottom = ((dy + extent).ceil() as f64 + h).max(h);
+
+        let width = right - left;
+        let height = bottom - top;
+        if left < i32::MIN as f64 || top < i32::MIN as f64 {
+            return None;
+        }
+
+        // Casts are saturating, therefore a too big size will be rejected below.
+        let size = IntSize::from_wh(width as u32, height as u32)?;
+        data_len_for_size_and_bpp(size, BYTES_PER_PIXEL)?;
+
+        Some((left as i32, top as i32, size.width(), size.height()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square() -> Pixmap {
+        let mut pixmap = Pixmap::new(10, 10).unwrap();
+        pixmap.fill(Color::from_rgba8(255, 0, 0, 255));
+        pixmap
+    }
+
+    #[test]
+    fn bounds_grow() {
+        let pixmap = square();
+
+        let shadow = pixmap.drop_shadow(5.0, 5.0, 0.0, Color::BLACK).unwrap();
+        assert_eq!((shadow.width(), shadow.height()), (15, 15));
+        assert_eq!(pixmap.drop_shadow_origin(5.0, 5.0, 0.0), Some((0, 0)));
+
+        let shadow = pixmap.drop_shadow(-5.0, 0.0, 0.0, Color::BLACK).unwrap();
+        assert_eq!((shadow.width(), shadow.height()), (15, 10));
+        assert_eq!(pixmap.drop_shadow_origin(-5.0, 0.0, 0.0), Some((5, 0)));
+
+        let shadow = pixmap.drop_shadow(0.0, 0.0, 2.0, Color::BLACK).unwrap();
+        let (x, y) = pixmap.drop_shadow_origin(0.0, 0.0, 2.0).unwrap();
+        assert!(x > 0 && y > 0);
+        assert_eq!(shadow.width(), 10 + x * 2);
+
+        // Shadow is not clipped.
+        assert_eq!(shadow.pixel(0, 0).unwrap().alpha(), 0);
+        assert_eq!(shadow.pixel(0, y + 5).unwrap().alpha(), 0);
+        assert!(shadow.pixel(x - 1, y + 5).unwrap().alpha() > 0);
+    }
+
+    #[test]
+    fn hard_shadow() {
+        let pixmap = square();
+        let shadow = pixmap.drop_shadow(5.0, 5.0, 0.0, Color::BLACK).unwrap();
+
+        // Original.
+        let c = shadow.pixel(2, 2).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (255, 0, 0, 255));
+        // Shadow.
+        let c = shadow.pixel(12, 12).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (0, 0, 0, 255));
+        // Nothing.
+        assert_eq!(shadow.pixel(12, 2).unwrap().alpha(), 0);
+    }
+
+    #[test]
+    fn invalid() {
+        let pixmap = square();
+        assert!(pixmap
+            .drop_shadow(f32::NAN, 0.0, 0.0, Color::BLACK)
+            .is_none());
+        assert!(pixmap.drop_shadow(1e20, 0.0, 0.0, Color::BLACK).is_none());
+
+        // Must not try to allocate a pixmap with rows larger than `i32::MAX` bytes.
+        assert!(pixmap.drop_shadow(1e9, 0.0, 0.0, Color::BLACK).is_none());
+        assert!(pixmap.drop_shadow_origin(-1e9, 0.0, 0.0).is_none());
+
+        // An absurd sigma is clamped by the blur.
+        let (x, y) = pixmap.drop_shadow_origin(0.0, 0.0, f32::MAX).unwrap();
+        assert_eq!(x, y);
+        assert_eq!(pixmap.drop_shadow_origin(0.0, 0.0, 1e30), Some((x, y)));
+    }
+}
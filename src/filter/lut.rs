@@ -0,0 +1,149 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use crate::{ColorU8, Pixmap, PixmapMut, PremultipliedColorU8};
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+impl Pixmap {
+    /// Remaps each channel using a lookup table.
+    ///
+    /// See [`PixmapMut::apply_lut`] for details.
+    pub fn apply_lut(
+        &mut self,
+        r: &[u8; 256],
+        g: &[u8; 256],
+        b: &[u8; 256],
+        a: Option<&[u8; 256]>,
+    ) {
+        self.as_mut().apply_lut(r, g, b, a)
+    }
+}
+
+impl PixmapMut<'_> {
+    /// Remaps each channel using a lookup table.
+    ///
+    /// Similar to SVG `feComponentTransfer` with `type="table"`.
+    ///
+    /// Tables are applied to unpremultiplied values
+    /// and a pixel will be premultiplied back afterwards.
+    ///
+    /// When `a` is `None`, alpha is left untouched.
+    pub fn apply_lut(
+        &mut self,
+        r: &[u8; 256],
+        g: &[u8; 256],
+        b: &[u8; 256],
+        a: Option<&[u8; 256]>,
+    ) {
+        for p in self.rows_mut().flatten() {
+            let c = p.demultiply();
+            let alpha = match a {
+                Some(a) => a[usize::from(c.alpha())],
+                None => c.alpha(),
+            };
+
+            let c = ColorU8::from_rgba(
+                r[usize::from(c.red())],
+                g[usize::from(c.green())],
+                b[usize::from(c.blue())],
+                alpha,
+            );
+
+            // `premultiply` guarantees that colors are <= alpha.
+            *p = if alpha == 0 {
+                PremultipliedColorU8::TRANSPARENT
+            } else {
+                c.premultiply()
+            };
+        }
+    }
+}
+
+/// Creates a gamma correction lookup table.
+///
+/// Each value is calculated as `(i / 255) ^ exponent * 255`.
+///
+/// Can be used with [`PixmapMut::apply_lut`].
+pub fn gamma_lut(exponent: f32) -> [u8; 256] {
+    let mut table = [0; 256];
+    for (i, v) in table.iter_mut().enumerate() {
+        let n = (i as f32 / 255.0).powf(exponent) * 255.0 + 0.5;
+        // `as` will saturate NaN to 0 and infinity to 255.
+        *v = n as u8;
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    fn identity_lut() -> [u8; 256] {
+        let mut table = [0; 256];
+        for (i, v) in table.iter_mut().enumerate() {
+            *v = i as u8;
+        }
+
+        table
+    }
+
+    #[test]
+    fn identity() {
+        let lut = identity_lut();
+
+        let mut pixmap = Pixmap::new(2, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(200, 100, 50, 255));
+        let original = pixmap.clone();
+        pixmap.apply_lut(&lut, &lut, &lut, Some(&lut));
+        assert_eq!(pixmap, original);
+    }
+
+    #[test]
+    fn invert() {
+        let lut = identity_lut();
+        let mut inverted = [0; 256];
+        for (i, v) in inverted.iter_mut().enumerate() {
+            *v = 255 - i as u8;
+        }
+
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(255, 0, 100, 128));
+        pixmap.apply_lut(&inverted, &lut, &lut, None);
+
+        let c = pixmap.pixel(0, 0).unwrap();
+        assert_eq!(c.alpha(), 128);
+        assert_eq!(c.red(), 0);
+        assert!(c.blue() <= c.alpha());
+    }
+
+    #[test]
+    fn color_is_not_greater_than_alpha() {
+        let full = [255; 256];
+
+        let mut pixmap = Pixmap::new(1, 1).unwrap();
+        pixmap.fill(Color::from_rgba8(10, 20, 30, 50));
+        pixmap.apply_lut(&full, &full, &full, None);
+
+        let c = pixmap.pixel(0, 0).unwrap();
+        assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (50, 50, 50, 50));
+    }
+
+    #[test]
+    fn gamma() {
+        let table = gamma_lut(1.0);
+        assert_eq!(table, identity_lut());
+
+        let table = gamma_lut(2.0);
+        assert_eq!(table[0], 0);
+        assert_eq!(table[128], 64);
+        assert_eq!(table[255], 255);
+    }
+}
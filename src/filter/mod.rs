@@ -0,0 +1,21 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+//! Pixmap-wide filters.
+//!
+//! Unlike shaders, filters operate on an already rendered pixmap.
+//! Most of them are similar to SVG filter primitives.
+
+mod arithmetic;
+pub(crate) mod blur;
+mod color_matrix;
+mod displacement;
+mod drop_shadow;
+mod lut;
+mod morphology;
+
+pub use color_matrix::ColorMatrix;
+pub use displacement::ColorChannel;
+pub use lut::gamma_lut;
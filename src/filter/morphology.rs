@@ -0,0 +1,318 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::pixmap::BYTES_PER_PIXEL;
+use crate::{Pixmap, PixmapMut};
+
+impl Pixmap {
+    /// Replaces each pixel with a per-channel maximum of its neighborhood.
+    ///
+    /// See [`PixmapMut::dilate`] for details.
+    pub fn dilate(&mut self, radius_x: u32, radius_y: u32) {
+        self.as_mut().dilate(radius_x, radius_y)
+    }
+
+    /// Replaces each pixel with a per-channel minimum of its neighborhood.
+    ///
+    /// See [`PixmapMut::erode`] for details.
+    pub fn erode(&mut self, radius_x: u32, radius_y: u32) {
+        self.as_mut().erode(radius_x, radius_y)
+    }
+}
+
+impl PixmapMut<'_> {
+    /// Replaces each pixel with a per-channel maximum of its neighborhood.
+    ///
+    /// Similar to SVG `feMorphology` with `operator="dilate"`.
+    ///
+    /// The neighborhood is a `2 * radius_x + 1` by `2 * radius_y + 1` rectangle
+    /// centered at the pixel. Pixels outside the pixmap are ignored.
+    /// Channels are processed in premultiplied space, which always produces
+    /// a valid premultiplied color.
+    ///
+    /// Zero radii leave the pixmap untouched.
+    pub fn dilate(&mut self, radius_x: u32, radius_y: u32) {
+        self.morphology(radius_x, radius_y, Operator::Dilate);
+    }
+
+    /// Replaces each pixel with a per-channel minimum of its neighborhood.
+    ///
+    /// Similar to SVG `feMorphology` with `operator="erode"`.
+    ///
+    /// See [`PixmapMut::dilate`] for details.
+    pub fn erode(&mut self, radius_x: u32, radius_y: u32) {
+        self.morphology(radius_x, radius_y, Operator::Erode);
+    }
+
+    fn morphology(&mut self, radius_x: u32, radius_y: u32, op: Operator) {
+        let width = self.width() as usize;
+        let height = self.height() as usize;
+        let stride = self.stride();
+        // Larger radii produce the same result.
+        let radius_x = (radius_x as usize).min(width);
+        let radius_y = (radius_y as usize).min(height);
+
+        let len = width.max(height);
+        let mut line = vec![0; len];
+        let mut buf = LineBuffers::new(len + 2 * radius_x.max(radius_y));
+
+        // A rectangular kernel is separable, therefore we can process
+        // rows and columns independently.
+        let data = self.data_mut();
+        if radius_x > 0 {
+            for y in 0..height {
+                for c in 0..BYTES_PER_PIXEL {
+                    let offset = y * stride + c;
+                    morph_strided(
+                        data,
+                        offset,
+                        BYTES_PER_PIXEL,
+                        &mut line[..width],
+                        &mut buf,
+                        radius_x,
+                        op,
+                    );
+                }
+            }
+        }
+
+        if radius_y > 0 {
+            for x in 0..width {
+                for c in 0..BYTES_PER_PIXEL {
+                    let offset = x * BYTES_PER_PIXEL + c;
+                    morph_strided(
+                        data,
+                        offset,
+                        stride,
+                        &mut line[..height],
+                        &mut buf,
+                        radius_y,
+                        op,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Operator {
+    Dilate,
+    Erode,
+}
+
+impl Operator {
+    #[inline]
+    fn apply(self, a: u8, b: u8) -> u8 {
+        match self {
+            Operator::Dilate => a.max(b),
+            Operator::Erode => a.min(b),
+        }
+    }
+
+    /// A value that doesn't affect the result.
+    #[inline]
+    fn identity(self) -> u8 {
+        match self {
+            Operator::Dilate => 0,
+            Operator::Erode => 255,
+        }
+    }
+}
+
+struct LineBuffers {
+    padded: Vec<u8>,
+    prefix: Vec<u8>,
+    suffix: Vec<u8>,
+}
+
+impl LineBuffers {
+    fn new(len: usize) -> Self {
+        LineBuffers {
+            padded: vec![0; len],
+            prefix: vec![0; len],
+            suffix: vec![0; len],
+        }
+    }
+}
+
+fn morph_strided(
+    data: &mut [u8],
+    offset: usize,
+    step: usize,
+    line: &mut [u8],
+    buf: &mut LineBuffers,
+    radius: usize,
+    op: Operator,
+) {
+    for (i, v) in line.iter_mut().enumerate() {
+        *v = data[offset + i * step];
+    }
+
+    morph_line(line, buf, radius, op);
+
+    for (i, v) in line.iter().enumerate() {
+        data[offset + i * step] = *v;
+    }
+}
+
+// The van Herk/Gil-Werman algorithm.
+//
+// The line is split into blocks of the window size. A window always spans
+// at most two blocks, therefore its result can be combined from a block suffix
+// and a block prefix. This makes the cost independent of the radius.
+fn morph_line(line: &mut [u8], buf: &mut LineBuffers, radius: usize, op: Operator) {
+    let window = 2 * radius + 1;
+    let len = line.len() + 2 * radius;
+
+    // Pad with values that do not affect the result,
+    // which is the same as ignoring pixels outside the line.
+    let padded = &mut buf.padded[..len];
+    padded.fill(op.identity());
+    padded[radius..radius + line.len()].copy_from_slice(line);
+
+    let prefix = &mut buf.prefix[..len];
+    for i in 0..len {
+        prefix[i] = if i % window == 0 {
+            padded[i]
+        } else {
+            op.apply(prefix[i - 1], padded[i])
+        };
+    }
+
+    let suffix = &mut buf.suffix[..len];
+    for i in (0..len).rev() {
+        suffix[i] = if i + 1 == len || (i + 1) % window == 0 {
+            padded[i]
+        } else {
+            op.apply(suffix[i + 1], padded[i])
+        };
+    }
+
+    for (x, v) in line.iter_mut().enumerate() {
+        *v = op.apply(suffix[x], prefix[x + window - 1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{IntRect, PremultipliedColorU8};
+
+    // A direct rectangular kernel implementation.
+    fn morph_reference(pixmap: &Pixmap, rx: u32, ry: u32, op: Operator) -> Pixmap {
+        let w = pixmap.width() as i64;
+        let h = pixmap.height() as i64;
+        let mut result = pixmap.clone();
+        for y in 0..h {
+            for x in 0..w {
+                let mut c = [op.identity(); 4];
+                for ky in (y - ry as i64).max(0)..=(y + ry as i64).min(h - 1) {
+                    for kx in (x - rx as i64).max(0)..=(x + rx as i64).min(w - 1) {
+                        let p = pixmap.pixel(kx as u32, ky as u32).unwrap();
+                        let v = [p.red(), p.green(), p.blue(), p.alpha()];
+                        for i in 0..4 {
+                            c[i] = op.apply(c[i], v[i]);
+                        }
+                    }
+                }
+
+                result.pixels_mut()[(y * w + x) as usize] =
+                    PremultipliedColorU8::from_rgba(c[0], c[1], c[2], c[3]).unwrap();
+            }
+        }
+
+        result
+    }
+
+    fn noise(width: u32, height: u32) -> Pixmap {
+        let mut pixmap = Pixmap::new(width, height).unwrap();
+        let mut state = 7u32;
+        for p in pixmap.pixels_mut() {
+            state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            let a = (state >> 16) as u8;
+            let c = |n: u32| ((n % 256) * u32::from(a) / 255) as u8;
+            *p = PremultipliedColorU8::from_rgba(c(state >> 3), c(state >> 9), c(state >> 13), a)
+                .unwrap();
+        }
+
+        pixmap
+    }
+
+    #[test]
+    fn zero_radius() {
+        let original = noise(13, 7);
+        let mut pixmap = original.clone();
+        pixmap.dilate(0, 0);
+        assert_eq!(pixmap, original);
+        pixmap.erode(0, 0);
+        assert_eq!(pixmap, original);
+    }
+
+    #[test]
+    fn matches_reference() {
+        let original = noise(23, 17);
+        for &(rx, ry) in &[(1, 0), (0, 2), (1, 1), (2, 3), (5, 1), (30, 40)] {
+            let mut pixmap = original.clone();
+            pixmap.dilate(rx, ry);
+            assert_eq!(pixmap, morph_reference(&original, rx, ry, Operator::Dilate));
+
+            let mut pixmap = original.clone();
+            pixmap.erode(rx, ry);
+            assert_eq!(pixmap, morph_reference(&original, rx, ry, Operator::Erode));
+        }
+    }
+
+    #[test]
+    fn dilate_square() {
+        let mut pixmap = Pixmap::new(10, 10).unwrap();
+        pixmap.fill_rect(
+            IntRect::from_xywh(4, 4, 2, 2).unwrap().to_rect(),
+            &crate::Paint::default(),
+            crate::Transform::identity(),
+            None,
+        );
+
+        pixmap.dilate(2, 1);
+        for y in 0..10 {
+            for x in 0..10 {
+                let inside = (2..8).contains(&x) && (3..7).contains(&y);
+                assert_eq!(pixmap.pixel(x, y).unwrap().alpha() != 0, inside);
+            }
+        }
+
+        pixmap.erode(2, 1);
+        for y in 0..10 {
+            for x in 0..10 {
+                let inside = (4..6).contains(&x) && (4..6).contains(&y);
+                assert_eq!(pixmap.pixel(x, y).unwrap().alpha() != 0, inside);
+            }
+        }
+    }
+
+    #[test]
+    fn with_stride() {
+        let original = noise(8, 5);
+        let mut data = vec![0xAA; 12 * 4 * 5];
+        for (y, row) in original.data().chunks(8 * 4).enumerate() {
+            data[y * 48..y * 48 + 32].copy_from_slice(row);
+        }
+
+        let mut pixmap = PixmapMut::from_bytes_with_stride(&mut data, 8, 5, 48).unwrap();
+        pixmap.dilate(1, 2);
+
+        let expected = morph_reference(&original, 1, 2, Operator::Dilate);
+        assert_eq!(pixmap.to_owned(), expected);
+        // Padding is left untouched.
+        assert!(data
+            .chunks(48)
+            .all(|row| row[32..].iter().all(|v| *v == 0xAA)));
+    }
+}
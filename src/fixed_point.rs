@@ -27,7 +27,7 @@ pub mod fdot6 {
     pub const ONE: FDot6 = 64;
 
     pub fn from_i32(n: i32) -> FDot6 {
-        debug_assert!(n as i16 as i32 == n);
+        hot_debug_assert!(n as i16 as i32 == n);
         n << 6
     }
 
@@ -48,7 +48,7 @@ pub mod fdot6 {
     }
 
     pub fn to_fdot16(n: FDot6) -> FDot16 {
-        debug_assert!((left_shift(n, 10) >> 10) == n);
+        hot_debug_assert!((left_shift(n, 10) >> 10) == n);
         left_shift(n, 10)
     }
 
@@ -68,7 +68,7 @@ pub mod fdot6 {
     }
 
     pub fn small_scale(value: u8, dot6: FDot6) -> u8 {
-        debug_assert!(dot6 as u32 <= 64);
+        hot_debug_assert!(dot6 as u32 <= 64);
         ((value as i32 * dot6) >> 6) as u8
     }
 }
@@ -123,7 +123,7 @@ pub mod fdot16 {
     }
 
     pub fn fast_div(a: FDot6, b: FDot6) -> FDot16 {
-        debug_assert!((left_shift(a, 16) >> 16) == a);
+        hot_debug_assert!((left_shift(a, 16) >> 16) == a);
         debug_assert!(b != 0);
         left_shift(a, 16) / b
     }
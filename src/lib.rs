@@ -5,6 +5,7 @@
 It doesn't look like cairo, QPainter (Qt), HTML Canvas or even Skia itself.
 Instead, `tiny-skia` provides a set of low-level drawing APIs
 and a user should manage the world transform, clipping mask and style manually.
+Or use [`Canvas`], which handles the transform and clip stack like `SkCanvas`.
 
 See the `examples/` directory for usage examples.
 */
@@ -34,13 +35,28 @@ extern crate std;
 
 extern crate alloc;
 
+// A `debug_assert!` for cheap numeric invariants checked per pixel or per point.
+//
+// Can be disabled via the `no-hot-asserts` feature to keep debug builds fast.
+// Structural and bounds checks must use the regular `debug_assert!`.
+macro_rules! hot_debug_assert {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "no-hot-asserts"))]
+        debug_assert!($($arg)*);
+    };
+}
+
 mod alpha_runs;
 mod blend_mode;
 mod blitter;
+mod canvas;
 mod color;
+mod cpu;
+mod damage;
 mod edge;
 mod edge_builder;
 mod edge_clipper;
+mod filter;
 mod fixed_point;
 mod geom;
 mod line_clipper;
@@ -50,24 +66,49 @@ mod path64;
 mod path_geometry;
 mod pipeline;
 mod pixmap;
+mod pixmap_f16;
+mod prepared_path;
+mod rasterizer;
+mod region;
 mod scan;
+mod scene;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod shaders;
+mod tiled;
 mod wide;
 
 mod painter; // Keep it under `pixmap` for a better order in the docs.
 
 pub use blend_mode::BlendMode;
+pub use canvas::{Canvas, CoordinateSystem};
 pub use color::{Color, ColorU8, PremultipliedColor, PremultipliedColorU8};
+pub use color::{PremultipliedColorF16, PremultipliedColorF32};
 pub use color::{ALPHA_OPAQUE, ALPHA_TRANSPARENT, ALPHA_U8_OPAQUE, ALPHA_U8_TRANSPARENT};
+pub use cpu::{cpu_features, CpuFeatures, SimdBackend};
+pub use damage::DamageTracker;
+pub use filter::{gamma_lut, ColorChannel, ColorMatrix};
 pub use mask::{Mask, MaskType};
-pub use painter::{FillRule, Paint};
-pub use pixmap::{Pixmap, PixmapMut, PixmapRef, BYTES_PER_PIXEL};
-pub use shaders::{FilterQuality, GradientStop, PixmapPaint, SpreadMode};
+pub use painter::{AntiAliasMode, Paint, RenderMode};
+pub use pixmap::{DiffStats, Pixmap, PixmapMut, PixmapRef, BYTES_PER_PIXEL};
+pub use pixmap_f16::PixmapF16;
+pub use prepared_path::PreparedPath;
+pub use rasterizer::{rasterize_path, CoverageFn};
+pub use region::{Region, RegionRectsIter};
+pub use scene::Scene;
+pub use shaders::{
+    ColorSpace, FilterQuality, GradientInterpolation, GradientStop, PixmapPaint, SpreadMode,
+    TintMode,
+};
 pub use shaders::{LinearGradient, Pattern, RadialGradient, Shader};
+pub use tiled::{DrawCommand, TiledRenderer};
 
-pub use tiny_skia_path::{IntRect, IntSize, NonZeroRect, Point, Rect, Size, Transform};
+pub use tiny_skia_path::simplify_polyline;
+pub use tiny_skia_path::{FillRule, TinySkiaError};
+pub use tiny_skia_path::{IntRect, IntSize, NonZeroRect, Point, Rect, Rounding, Size, Transform};
 pub use tiny_skia_path::{LineCap, LineJoin, Stroke, StrokeDash};
-pub use tiny_skia_path::{Path, PathBuilder, PathSegment, PathSegmentsIter, PathStroker};
+pub use tiny_skia_path::{Path, PathBuilder, PathDirection, PathSegment, PathSegmentsIter};
+pub use tiny_skia_path::{PathMeasure, PathStroker, StrokeStats};
 
 /// An integer length that is guarantee to be > 0
 type LengthU32 = core::num::NonZeroU32;
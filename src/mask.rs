@@ -11,10 +11,12 @@ use alloc::vec::Vec;
 
 use tiny_skia_path::{IntRect, IntSize, Path, Scalar, Transform};
 
+use crate::edge::DEFAULT_CURVE_TOLERANCE;
+use crate::filter::blur;
 use crate::geom::IntSizeExt;
 use crate::painter::DrawTiler;
 use crate::pipeline::RasterPipelineBlitter;
-use crate::pixmap::SubPixmapMut;
+use crate::pixmap::{data_len_for_size_and_bpp, SubPixmapMut};
 use crate::scan;
 use crate::{FillRule, PixmapRef};
 
@@ -48,9 +50,16 @@ impl Mask {
     ///
     /// The size needs to match the data provided.
     pub fn new(width: u32, height: u32) -> Option<Self> {
-        let size = IntSize::from_wh(width, height)?;
+        Mask::from_size(IntSize::from_wh(width, height)?)
+    }
+
+    /// Creates a new mask using an `IntSize`.
+    ///
+    /// The same as [`Mask::new`].
+    pub fn from_size(size: IntSize) -> Option<Self> {
+        let data_len = data_len_for_size_and_bpp(size, 1)?;
         Some(Mask {
-            data: vec![0; width as usize * height as usize],
+            data: vec![0; data_len],
             size,
         })
     }
@@ -66,12 +75,12 @@ impl Mask {
         // TODO: optimize
         match mask_type {
             MaskType::Alpha => {
-                for (p, a) in pixmap.pixels().iter().zip(mask.data.as_mut_slice()) {
+                for (p, a) in pixmap.rows().flatten().zip(mask.data.as_mut_slice()) {
                     *a = p.alpha();
                 }
             }
             MaskType::Luminance => {
-                for (p, ma) in pixmap.pixels().iter().zip(mask.data.as_mut_slice()) {
+                for (p, ma) in pixmap.rows().flatten().zip(mask.data.as_mut_slice()) {
                     // Normalize.
                     let mut r = f32::from(p.red()) / 255.0;
                     let mut g = f32::from(p.green()) / 255.0;
@@ -98,7 +107,7 @@ impl Mask {
     ///
     /// The size needs to match the data provided.
     pub fn from_vec(data: Vec<u8>, size: IntSize) -> Option<Self> {
-        let data_len = size.width() as usize * size.height() as usize;
+        let data_len = data_len_for_size_and_bpp(size, 1)?;
         if data.len() != data_len {
             return None;
         }
@@ -275,6 +284,7 @@ impl Mask {
             // TODO: ignore paths outside the pixmap
 
             if let Some(tiler) = DrawTiler::new(self.width(), self.height()) {
+                let tiler = tiler.with_bounds(path.bounds());
                 let mut path = path.clone(); // TODO: avoid cloning
 
                 for tile in tiler {
@@ -302,9 +312,21 @@ impl Mask {
                     // when rendering a tile that doesn't have a path on it.
                     // Which is not an error in this case.
                     if anti_alias {
-                        scan::path_aa::fill_path(&path, fill_rule, &clip_rect, &mut blitter);
+                        scan::path_aa::fill_path(
+                            &path,
+                            fill_rule,
+                            DEFAULT_CURVE_TOLERANCE,
+                            &clip_rect,
+                            &mut blitter,
+                        );
                     } else {
-                        scan::path::fill_path(&path, fill_rule, &clip_rect, &mut blitter);
+                        scan::path::fill_path(
+                            &path,
+                            fill_rule,
+                            DEFAULT_CURVE_TOLERANCE,
+                            &clip_rect,
+                            &mut blitter,
+                        );
                     }
 
                     let ts = Transform::from_translate(tile.x() as f32, tile.y() as f32);
@@ -322,9 +344,21 @@ impl Mask {
                 };
 
                 if anti_alias {
-                    scan::path_aa::fill_path(path, fill_rule, &clip_rect, &mut blitter);
+                    scan::path_aa::fill_path(
+                        path,
+                        fill_rule,
+                        DEFAULT_CURVE_TOLERANCE,
+                        &clip_rect,
+                        &mut blitter,
+                    );
                 } else {
-                    scan::path::fill_path(path, fill_rule, &clip_rect, &mut blitter);
+                    scan::path::fill_path(
+                        path,
+                        fill_rule,
+                        DEFAULT_CURVE_TOLERANCE,
+                        &clip_rect,
+                        &mut blitter,
+                    );
                 }
             }
         } else {
@@ -352,8 +386,22 @@ impl Mask {
     ) {
         let mut submask = Mask::new(self.width(), self.height()).unwrap();
         submask.fill_path(path, fill_rule, anti_alias, transform);
+        self.intersect_mask(&submask);
+    }
+
+    /// Intersects the provided mask with the current one.
+    ///
+    /// Coverage values are multiplied, the same way a mask is applied during drawing.
+    /// Allows combining multiple clips into one, which can then be passed to drawing methods.
+    ///
+    /// Masks must have the same size.
+    pub fn intersect_mask(&mut self, mask: &Mask) {
+        if self.size != mask.size {
+            log::warn!("Masks are expected to have the same size");
+            return;
+        }
 
-        for (a, b) in self.data.iter_mut().zip(submask.data.iter()) {
+        for (a, b) in self.data.iter_mut().zip(mask.data.iter()) {
             *a = crate::color::premultiply_u8(*a, *b);
         }
     }
@@ -369,6 +417,26 @@ impl Mask {
     pub fn clear(&mut self) {
         self.data.fill(0);
     }
+
+    /// Applies a Gaussian blur to the mask.
+    ///
+    /// Allows creating soft-edged masks, like feathered selections and vignettes.
+    ///
+    /// Coverage outside the mask is the same as the nearest edge pixel,
+    /// so a fully covered edge stays fully covered.
+    ///
+    /// Does nothing when `sigma` is not finite, negative or too small
+    /// to affect a single pixel.
+    pub fn blur(&mut self, sigma: f32) {
+        blur::blur(
+            &mut self.data,
+            self.size.width() as usize,
+            self.size.height() as usize,
+            sigma,
+            sigma,
+            blur::EdgeMode::Clamp,
+        );
+    }
 }
 
 impl core::fmt::Debug for Mask {
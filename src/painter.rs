@@ -8,6 +8,7 @@ use crate::*;
 
 use tiny_skia_path::{PathStroker, Scalar, SCALAR_MAX};
 
+use crate::edge::{self, DEFAULT_CURVE_TOLERANCE};
 use crate::geom::ScreenIntRect;
 use crate::mask::SubMaskRef;
 use crate::pipeline::{RasterPipelineBlitter, RasterPipelineBuilder};
@@ -18,18 +19,57 @@ use crate::geom::IntSizeExt;
 #[cfg(all(not(feature = "std"), feature = "no-std-float"))]
 use tiny_skia_path::NoStdFloat;
 
-/// A path filling rule.
+/// An anti-aliasing mode.
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum FillRule {
-    /// Specifies that "inside" is computed by a non-zero sum of signed edge crossings.
-    Winding,
-    /// Specifies that "inside" is computed by an odd number of edge crossings.
-    EvenOdd,
+pub enum AntiAliasMode {
+    /// No anti-aliasing.
+    None,
+    /// An analytic coverage-based anti-aliasing.
+    ///
+    /// Fast and good enough for most cases.
+    Analytic,
+    /// Rasterizes a shape at `factor x factor` resolution and then averages the coverage.
+    ///
+    /// This is very slow, but can be useful for reference images and complex overlapping
+    /// geometry, where the analytic coverage is not precise enough.
+    ///
+    /// Applies only to filling and thick stroking. Hairlines are not affected.
+    ///
+    /// `factor` is limited by 16. A `factor` of 1 is the same as `Analytic`.
+    Supersample {
+        /// A number of samples per pixel side.
+        factor: u8,
+    },
 }
 
-impl Default for FillRule {
+impl Default for AntiAliasMode {
     fn default() -> Self {
-        FillRule::Winding
+        AntiAliasMode::Analytic
+    }
+}
+
+/// A rendering mode.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum RenderMode {
+    /// The fastest rendering available on the current platform.
+    ///
+    /// Some stages rely on approximate SIMD instructions and on the platform's `powf`,
+    /// therefore results can differ by 1 LSB between platforms.
+    Fast,
+    /// A bit-identical rendering across all platforms.
+    ///
+    /// Stages that rely on approximate instructions or on the platform's `powf`
+    /// are replaced with portable ones, which use only basic IEEE 754 arithmetic.
+    /// Since basic arithmetic is exact on all targets and `tiny-skia` never uses FMA,
+    /// SIMD code is still used for everything else.
+    ///
+    /// Slower and can slightly differ from `Fast`. Useful for reference images.
+    Deterministic,
+}
+
+impl Default for RenderMode {
+    fn default() -> Self {
+        RenderMode::Fast
     }
 }
 
@@ -51,6 +91,14 @@ pub struct Paint<'a> {
     /// Default: true
     pub anti_alias: bool,
 
+    /// An anti-aliasing mode.
+    ///
+    /// Used only when `anti_alias` is set.
+    /// Use [`set_anti_alias_mode`](Paint::set_anti_alias_mode) to set both at once.
+    ///
+    /// Default: Analytic
+    pub anti_alias_mode: AntiAliasMode,
+
     /// Forces the high quality/precision rendering pipeline.
     ///
     /// `tiny-skia`, just like Skia, has two rendering pipelines:
@@ -68,6 +116,57 @@ pub struct Paint<'a> {
     ///
     /// Default: false
     pub force_hq_pipeline: bool,
+
+    /// Enables blending in a linear color space.
+    ///
+    /// By default, blending is done directly on sRGB encoded values,
+    /// which leads to darker anti-aliased edges, especially for light-on-dark content.
+    /// When enabled, both source and destination will be converted into linear light
+    /// before blending and converted back into sRGB during store.
+    ///
+    /// This is a fairly expensive operation and it forces the high quality pipeline.
+    /// Fully opaque fills without any blending are not affected.
+    ///
+    /// Default: false
+    pub blend_in_linear_space: bool,
+
+    /// Enables an ordered dithering to reduce gradients banding.
+    ///
+    /// Each pixel will be perturbed by up to ±0.5 LSB, depending on its position.
+    /// Which makes the result deterministic.
+    ///
+    /// Affects only shaders. Solid colors are never dithered.
+    ///
+    /// Default: false
+    pub dither: bool,
+
+    /// A rendering mode.
+    ///
+    /// Default: Fast
+    pub render_mode: RenderMode,
+
+    /// A maximum distance in pixels between a curve and its flattened approximation.
+    ///
+    /// Smaller values produce smoother large curves at the cost of performance,
+    /// while larger values are faster, but make curves visibly faceted.
+    /// A curve is split into at most 64 lines, therefore very small values have no effect.
+    /// Values outside of the 1/1024..=64 range are clamped.
+    ///
+    /// Affects filling and thick stroking. Hairlines are not affected.
+    /// With `AntiAliasMode::Supersample`, the tolerance is applied at the supersampled resolution.
+    ///
+    /// Default: 0.125
+    pub curve_tolerance: f32,
+
+    /// A maximum number of line segments a path can be flattened into.
+    ///
+    /// Useful when rendering untrusted content. Paths exceeding the limit are not drawn
+    /// and [`PixmapMut::try_fill_path`] will return [`TinySkiaError::TooComplex`].
+    ///
+    /// Affects filling and thick stroking. Hairlines are not affected.
+    ///
+    /// Default: None
+    pub max_segments: Option<usize>,
 }
 
 impl Default for Paint<'_> {
@@ -76,7 +175,13 @@ impl Default for Paint<'_> {
             shader: Shader::SolidColor(Color::BLACK),
             blend_mode: BlendMode::default(),
             anti_alias: true,
+            anti_alias_mode: AntiAliasMode::default(),
             force_hq_pipeline: false,
+            blend_in_linear_space: false,
+            dither: false,
+            render_mode: RenderMode::default(),
+            curve_tolerance: DEFAULT_CURVE_TOLERANCE,
+            max_segments: None,
         }
     }
 }
@@ -94,6 +199,14 @@ impl<'a> Paint<'a> {
         self.set_color(Color::from_rgba8(r, g, b, a))
     }
 
+    /// Sets an anti-aliasing mode.
+    ///
+    /// Also updates `anti_alias` accordingly.
+    pub fn set_anti_alias_mode(&mut self, mode: AntiAliasMode) {
+        self.anti_alias = mode != AntiAliasMode::None;
+        self.anti_alias_mode = mode;
+    }
+
     /// Checks that the paint source is a solid color.
     pub fn is_solid_color(&self) -> bool {
         matches!(self.shader, Shader::SolidColor(_))
@@ -114,6 +227,47 @@ impl Pixmap {
         self.as_mut().fill_rect(rect, paint, transform, mask);
     }
 
+    /// Draws a filled rectangle onto the pixmap using a fast path when possible.
+    ///
+    /// See [`PixmapMut::fill_rect_fast`](struct.PixmapMut.html#method.fill_rect_fast) for details.
+    pub fn fill_rect_fast(
+        &mut self,
+        rect: Rect,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_rect_fast(rect, paint, transform, mask);
+    }
+
+    /// Draws a filled circle onto the pixmap.
+    ///
+    /// See [`PixmapMut::fill_circle`](struct.PixmapMut.html#method.fill_circle) for details.
+    pub fn fill_circle(
+        &mut self,
+        center: Point,
+        radius: f32,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut()
+            .fill_circle(center, radius, paint, transform, mask);
+    }
+
+    /// Draws a filled ellipse onto the pixmap.
+    ///
+    /// See [`PixmapMut::fill_ellipse`](struct.PixmapMut.html#method.fill_ellipse) for details.
+    pub fn fill_ellipse(
+        &mut self,
+        rect: Rect,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_ellipse(rect, paint, transform, mask);
+    }
+
     /// Draws a filled path onto the pixmap.
     ///
     /// See [`PixmapMut::fill_path`](struct.PixmapMut.html#method.fill_path) for details.
@@ -129,6 +283,21 @@ impl Pixmap {
             .fill_path(path, paint, fill_rule, transform, mask);
     }
 
+    /// Draws a filled path onto the pixmap and reports why it wasn't drawn.
+    ///
+    /// See [`PixmapMut::try_fill_path`](struct.PixmapMut.html#method.try_fill_path) for details.
+    pub fn try_fill_path(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        fill_rule: FillRule,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) -> Result<(), TinySkiaError> {
+        self.as_mut()
+            .try_fill_path(path, paint, fill_rule, transform, mask)
+    }
+
     /// Strokes a path.
     ///
     /// See [`PixmapMut::stroke_path`](struct.PixmapMut.html#method.stroke_path) for details.
@@ -144,6 +313,108 @@ impl Pixmap {
             .stroke_path(path, paint, stroke, transform, mask);
     }
 
+    /// Strokes a path and returns stroking statistics.
+    ///
+    /// See [`PixmapMut::stroke_path_with_stats`](struct.PixmapMut.html#method.stroke_path_with_stats)
+    /// for details.
+    pub fn stroke_path_with_stats(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        stroke: &Stroke,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) -> StrokeStats {
+        self.as_mut()
+            .stroke_path_with_stats(path, paint, stroke, transform, mask)
+    }
+
+    /// Fills and then strokes a path.
+    ///
+    /// See [`PixmapMut::fill_and_stroke_path`](struct.PixmapMut.html#method.fill_and_stroke_path)
+    /// for details.
+    pub fn fill_and_stroke_path(
+        &mut self,
+        path: &Path,
+        fill_paint: &Paint,
+        fill_rule: FillRule,
+        stroke: &Stroke,
+        stroke_paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_and_stroke_path(
+            path,
+            fill_paint,
+            fill_rule,
+            stroke,
+            stroke_paint,
+            transform,
+            mask,
+        );
+    }
+
+    /// Fills a prepared path at an integer offset.
+    ///
+    /// See [`PixmapMut::fill_prepared_path`](struct.PixmapMut.html#method.fill_prepared_path)
+    /// for details.
+    pub fn fill_prepared_path(
+        &mut self,
+        path: &PreparedPath,
+        paint: &Paint,
+        x: i32,
+        y: i32,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_prepared_path(path, paint, x, y, mask);
+    }
+
+    /// Builds a path using a closure and fills it.
+    ///
+    /// See [`PixmapMut::fill_with`](struct.PixmapMut.html#method.fill_with)
+    /// for details.
+    pub fn fill_with<F: FnOnce(&mut PathBuilder)>(
+        &mut self,
+        paint: &Paint,
+        fill_rule: FillRule,
+        transform: Transform,
+        mask: Option<&Mask>,
+        f: F,
+    ) {
+        self.as_mut()
+            .fill_with(paint, fill_rule, transform, mask, f);
+    }
+
+    /// Fills a batch of glyph outlines.
+    ///
+    /// See [`PixmapMut::fill_glyphs`](struct.PixmapMut.html#method.fill_glyphs)
+    /// for details.
+    pub fn fill_glyphs(
+        &mut self,
+        glyphs: &[(&Path, Transform)],
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_glyphs(glyphs, paint, transform, mask);
+    }
+
+    /// Draws a line segment.
+    ///
+    /// See [`PixmapMut::draw_line`](struct.PixmapMut.html#method.draw_line) for details.
+    pub fn draw_line(
+        &mut self,
+        p0: Point,
+        p1: Point,
+        paint: &Paint,
+        width: f32,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut()
+            .draw_line(p0, p1, paint, width, transform, mask);
+    }
+
     /// Draws a `Pixmap` on top of the current `Pixmap`.
     ///
     /// See [`PixmapMut::draw_pixmap`](struct.PixmapMut.html#method.draw_pixmap) for details.
@@ -160,12 +431,44 @@ impl Pixmap {
             .draw_pixmap(x, y, pixmap, paint, transform, mask);
     }
 
+    /// Draws a `Pixmap` recolored by a `tint` color.
+    ///
+    /// See [`PixmapMut::draw_pixmap_tinted`](struct.PixmapMut.html#method.draw_pixmap_tinted)
+    /// for details.
+    pub fn draw_pixmap_tinted(
+        &mut self,
+        x: i32,
+        y: i32,
+        pixmap: PixmapRef,
+        tint: Color,
+        tint_mode: TintMode,
+        paint: &PixmapPaint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut()
+            .draw_pixmap_tinted(x, y, pixmap, tint, tint_mode, paint, transform, mask);
+    }
+
     /// Applies a masks.
     ///
     /// See [`PixmapMut::apply_mask`](struct.PixmapMut.html#method.apply_mask) for details.
     pub fn apply_mask(&mut self, mask: &Mask) {
         self.as_mut().apply_mask(mask);
     }
+
+    /// Fills the area covered by a mask using the paint.
+    ///
+    /// See [`PixmapMut::fill_mask`](struct.PixmapMut.html#method.fill_mask) for details.
+    pub fn fill_mask(
+        &mut self,
+        coverage: &Mask,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.as_mut().fill_mask(coverage, paint, transform, mask);
+    }
 }
 
 impl PixmapMut<'_> {
@@ -178,7 +481,8 @@ impl PixmapMut<'_> {
     ///
     /// Used mainly to render a pixmap onto a pixmap.
     ///
-    /// Returns `None` when there is nothing to fill or in case of a numeric overflow.
+    /// Empty rects, like the ones created by [`Rect::from_corners`] with equal corners,
+    /// are silently skipped.
     pub fn fill_rect(
         &mut self,
         rect: Rect,
@@ -186,6 +490,10 @@ impl PixmapMut<'_> {
         transform: Transform,
         mask: Option<&Mask>,
     ) {
+        if rect.is_empty() {
+            return;
+        }
+
         // TODO: we probably can use tiler for rect too
         if transform.is_identity() && !DrawTiler::required(self.width(), self.height()) {
             // TODO: ignore rects outside the pixmap
@@ -210,7 +518,135 @@ impl PixmapMut<'_> {
         }
     }
 
+    /// Draws a filled rectangle onto the pixmap using a fast path when possible.
+    ///
+    /// When the rectangle is integer-aligned after applying a translate-only `transform`,
+    /// the paint is an opaque solid color with the `SourceOver` blend mode
+    /// (or any solid color with the `Source` blend mode) and there is no mask,
+    /// the pixmap rows will be filled directly, without any coverage computation.
+    ///
+    /// Otherwise, falls back to [`fill_rect`](struct.PixmapMut.html#method.fill_rect).
+    pub fn fill_rect_fast(
+        &mut self,
+        rect: Rect,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        if mask.is_none() && !transform.has_scale() && !transform.has_skew() {
+            if let Some(color) = fast_fill_color(paint) {
+                if let Some(rect) = rect
+                    .transform(transform)
+                    .and_then(|r| to_exact_int_rect(&r))
+                {
+                    self.fill_int_rect(rect, color);
+                    return;
+                }
+            }
+        }
+
+        self.fill_rect(rect, paint, transform, mask);
+    }
+
+    /// Fills an integer rectangle with a solid color, bypassing the raster pipeline.
+    fn fill_int_rect(&mut self, rect: IntRect, color: PremultipliedColorU8) {
+        let rect = match self.size().to_int_rect(0, 0).intersect(&rect) {
+            Some(v) => v,
+            None => return, // clipped out, nothing to do
+        };
+
+        let stride = self.as_ref().real_width();
+        let pixels = self.pixels_mut();
+        for y in rect.top()..rect.bottom() {
+            let start = y as usize * stride + rect.left() as usize;
+            let end = start + rect.width() as usize;
+            crate::cpu::fill_span(&mut pixels[start..end], color);
+        }
+    }
+
+    /// Draws a filled circle onto the pixmap.
+    ///
+    /// See [`fill_ellipse`](struct.PixmapMut.html#method.fill_ellipse) for details.
+    pub fn fill_circle(
+        &mut self,
+        center: Point,
+        radius: f32,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let rect = match Rect::from_xywh(
+            center.x - radius,
+            center.y - radius,
+            radius * 2.0,
+            radius * 2.0,
+        ) {
+            Some(v) => v,
+            None => return,
+        };
+
+        self.fill_ellipse(rect, paint, transform, mask);
+    }
+
+    /// Draws a filled ellipse inscribed into `rect` onto the pixmap.
+    ///
+    /// Unlike filling an oval path, coverage is computed analytically,
+    /// without building and rasterizing a path. Which is much faster for small shapes.
+    ///
+    /// Falls back to [`fill_path`](struct.PixmapMut.html#method.fill_path)
+    /// when `transform` has a skew or a rotation,
+    /// or when the `AntiAliasMode::Supersample` mode is requested.
+    pub fn fill_ellipse(
+        &mut self,
+        rect: Rect,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let anti_alias = match (paint.anti_alias, paint.anti_alias_mode) {
+            (false, _) | (true, AntiAliasMode::None) => Some(false),
+            (true, AntiAliasMode::Analytic) => Some(true),
+            (true, AntiAliasMode::Supersample { .. }) => None,
+        };
+
+        let is_simple = !transform.has_skew() && !DrawTiler::required(self.width(), self.height());
+        if let (Some(anti_alias), true) = (anti_alias, is_simple) {
+            let mut points = [
+                Point::from_xy(rect.left(), rect.top()),
+                Point::from_xy(rect.right(), rect.bottom()),
+            ];
+            transform.map_points(&mut points);
+
+            if let Some(device_rect) = Rect::from_points(&points) {
+                if device_rect.width().is_nearly_zero() || device_rect.height().is_nearly_zero() {
+                    return;
+                }
+
+                let mut paint = paint.clone();
+                paint.shader.transform(transform);
+
+                let clip = self.size().to_screen_int_rect(0, 0);
+                let mask = mask.map(|mask| mask.as_submask());
+                let mut subpix = self.as_subpixmap();
+                let mut blitter = match RasterPipelineBlitter::new(&paint, mask, &mut subpix) {
+                    Some(v) => v,
+                    None => return, // nothing to do, all good
+                };
+
+                scan::ellipse::fill_ellipse(&device_rect, anti_alias, &clip, &mut blitter);
+                return;
+            }
+        }
+
+        if let Some(path) = PathBuilder::from_oval(rect) {
+            self.fill_path(&path, paint, FillRule::Winding, transform, mask);
+        }
+    }
+
     /// Draws a filled path onto the pixmap.
+    ///
+    /// `fill_rule` always takes precedence over the one stored in the path.
+    /// Use [`Path::fill_rule_or`] to fill using the stored one.
     pub fn fill_path(
         &mut self,
         path: &Path,
@@ -219,6 +655,34 @@ impl PixmapMut<'_> {
         transform: Transform,
         mask: Option<&Mask>,
     ) {
+        // Errors are already logged.
+        let _ = self.try_fill_path(path, paint, fill_rule, transform, mask);
+    }
+
+    /// Draws a filled path onto the pixmap.
+    ///
+    /// The same as [`PixmapMut::fill_path`], but reports why the path wasn't drawn.
+    /// Paths that are simply not visible, like empty ones, are not an error.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::TooComplex`] when the path has more segments than
+    ///   [`Paint::max_segments`].
+    /// - [`TinySkiaError::NonFinite`] when the transform is not finite
+    ///   or the path transformation failed.
+    pub fn try_fill_path(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        fill_rule: FillRule,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) -> Result<(), TinySkiaError> {
+        if !transform.is_finite() {
+            log::warn!("transform must be finite");
+            return Err(TinySkiaError::NonFinite);
+        }
+
         if transform.is_identity() {
             // This is sort of similar to SkDraw::drawPath
 
@@ -226,17 +690,25 @@ impl PixmapMut<'_> {
             let path_bounds = path.bounds();
             if path_bounds.width().is_nearly_zero() || path_bounds.height().is_nearly_zero() {
                 log::warn!("empty paths and horizontal/vertical lines cannot be filled");
-                return;
+                return Ok(());
             }
 
             if is_too_big_for_math(path) {
                 log::warn!("path coordinates are too big");
-                return;
+                return Ok(());
+            }
+
+            if let Some(max) = paint.max_segments {
+                if flattened_segments_count(path, paint) > max {
+                    log::warn!("path has more than {} segments", max);
+                    return Err(TinySkiaError::TooComplex);
+                }
             }
 
             // TODO: ignore paths outside the pixmap
 
             if let Some(tiler) = DrawTiler::new(self.width(), self.height()) {
+                let tiler = tiler.with_bounds(path.bounds());
                 let mut path = path.clone(); // TODO: avoid cloning
                 let mut paint = paint.clone();
 
@@ -246,7 +718,7 @@ impl PixmapMut<'_> {
                         Some(v) => v,
                         None => {
                             log::warn!("path transformation failed");
-                            return;
+                            return Err(TinySkiaError::NonFinite);
                         }
                     };
                     paint.shader.transform(ts);
@@ -267,16 +739,12 @@ impl PixmapMut<'_> {
                     // We're ignoring "errors" here, because `fill_path` will return `None`
                     // when rendering a tile that doesn't have a path on it.
                     // Which is not an error in this case.
-                    if paint.anti_alias {
-                        scan::path_aa::fill_path(&path, fill_rule, &clip_rect, &mut blitter);
-                    } else {
-                        scan::path::fill_path(&path, fill_rule, &clip_rect, &mut blitter);
-                    }
+                    fill_path_impl(&path, fill_rule, &paint, &clip_rect, &mut blitter);
 
                     let ts = Transform::from_translate(tile.x() as f32, tile.y() as f32);
                     path = match path.transform(ts) {
                         Some(v) => v,
-                        None => return, // technically unreachable
+                        None => return Ok(()), // technically unreachable
                     };
                     paint.shader.transform(ts);
                 }
@@ -286,54 +754,300 @@ impl PixmapMut<'_> {
                 let mut subpix = self.as_subpixmap();
                 let mut blitter = match RasterPipelineBlitter::new(paint, submask, &mut subpix) {
                     Some(v) => v,
-                    None => return, // nothing to do, all good
+                    None => return Ok(()), // nothing to do, all good
                 };
 
-                if paint.anti_alias {
-                    scan::path_aa::fill_path(path, fill_rule, &clip_rect, &mut blitter);
-                } else {
-                    scan::path::fill_path(path, fill_rule, &clip_rect, &mut blitter);
-                }
-            }
-        } else {
-            let path = match path.clone().transform(transform) {
+                fill_path_impl(path, fill_rule, paint, &clip_rect, &mut blitter);
+            }
+
+            Ok(())
+        } else {
+            let path = match path.clone().transform(transform) {
+                Some(v) => v,
+                None => {
+                    log::warn!("path transformation failed");
+                    return Err(TinySkiaError::NonFinite);
+                }
+            };
+
+            let mut paint = paint.clone();
+            paint.shader.transform(transform);
+
+            self.try_fill_path(&path, &paint, fill_rule, Transform::identity(), mask)
+        }
+    }
+
+    /// Strokes a path.
+    ///
+    /// Stroking is implemented using two separate algorithms:
+    ///
+    /// 1. If a stroke width is wider than 1px (after applying the transformation),
+    ///    a path will be converted into a stroked path and then filled using `fill_path`.
+    ///    Which means that we have to allocate a separate `Path`, that can be 2-3x larger
+    ///    then the original path.
+    /// 2. If a stroke width is thinner than 1px (after applying the transformation),
+    ///    we will use hairline stroking, which doesn't involve a separate path allocation.
+    ///
+    /// Also, if a `stroke` has a dash array, then path will be converted into
+    /// a dashed path first and then stroked. Which means a yet another allocation.
+    pub fn stroke_path(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        stroke: &Stroke,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let mut stats = StrokeStats::default();
+        self.stroke_path_impl(path, paint, stroke, transform, mask, &mut stats);
+    }
+
+    /// Strokes a path and returns stroking statistics.
+    ///
+    /// The same as [`stroke_path`](Self::stroke_path), but also reports how many joins
+    /// and caps were produced and how many miter joins were beveled because of the miter limit.
+    ///
+    /// Hairline strokes are not converted into a path, therefore zero stats are returned.
+    /// Dashed strokes report stats for all dashes combined.
+    pub fn stroke_path_with_stats(
+        &mut self,
+        path: &Path,
+        paint: &Paint,
+        stroke: &Stroke,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) -> StrokeStats {
+        let mut stats = StrokeStats::default();
+        self.stroke_path_impl(path, paint, stroke, transform, mask, &mut stats);
+        stats
+    }
+
+    /// Fills and then strokes a path.
+    ///
+    /// A shorthand for [`fill_path`](PixmapMut::fill_path) followed by
+    /// [`stroke_path`](PixmapMut::stroke_path), which is how SVG paints shapes.
+    /// The result is exactly the same as calling them separately.
+    ///
+    /// Since the fill and the stroke outline are different shapes,
+    /// they are still rasterized separately.
+    pub fn fill_and_stroke_path(
+        &mut self,
+        path: &Path,
+        fill_paint: &Paint,
+        fill_rule: FillRule,
+        stroke: &Stroke,
+        stroke_paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        self.fill_path(path, fill_paint, fill_rule, transform, mask);
+        self.stroke_path(path, stroke_paint, stroke, transform, mask);
+    }
+
+    /// Fills a prepared path at an integer offset.
+    ///
+    /// Produces exactly the same result as [`fill_path`](PixmapMut::fill_path)
+    /// with the original path, its fill rule and a `Transform::from_translate(x, y)`,
+    /// including the shader transformation.
+    /// But unlike `fill_path`, reuses edges built by [`PreparedPath::new`].
+    ///
+    /// When the moved path is not fully inside the pixmap, its coordinates would be rounded
+    /// by the translation or the `paint` has different anti-aliasing or curve tolerance
+    /// settings than the ones used during preparation, the path will be filled via `fill_path`.
+    pub fn fill_prepared_path(
+        &mut self,
+        path: &PreparedPath,
+        paint: &Paint,
+        x: i32,
+        y: i32,
+        mask: Option<&Mask>,
+    ) {
+        let ts = Transform::from_translate(x as f32, y as f32);
+        let edges = match path.edges {
+            Some(ref edges)
+                if path.is_compatible(paint)
+                    && paint.max_segments.is_none()
+                    && path.is_movable_by(x, y) =>
+            {
+                edges
+            }
+            _ => {
+                self.fill_path(&path.path, paint, path.fill_rule, ts, mask);
+                return;
+            }
+        };
+
+        // Must match the bounds of a translated path.
+        let b = path.path.bounds();
+        let (dx, dy) = (x as f32, y as f32);
+        let bounds = Rect::from_ltrb(b.left() + dx, b.top() + dy, b.right() + dx, b.bottom() + dy);
+        let bounds = match bounds {
+            Some(v) => v,
+            None => {
+                self.fill_path(&path.path, paint, path.fill_rule, ts, mask);
+                return;
+            }
+        };
+
+        if bounds.width().is_nearly_zero() || bounds.height().is_nearly_zero() {
+            log::warn!("empty paths and horizontal/vertical lines cannot be filled");
+            return;
+        }
+
+        if DrawTiler::new(self.width(), self.height()).is_some() {
+            self.fill_path(&path.path, paint, path.fill_rule, ts, mask);
+            return;
+        }
+
+        let mut moved_paint = paint.clone();
+        if !ts.is_identity() {
+            moved_paint.shader.transform(ts);
+        }
+
+        let filled = {
+            let clip = self.size().to_screen_int_rect(0, 0);
+            let submask = mask.map(|mask| mask.as_submask());
+            let mut subpix = self.as_subpixmap();
+            let mut blitter = match RasterPipelineBlitter::new(&moved_paint, submask, &mut subpix) {
                 Some(v) => v,
-                None => {
-                    log::warn!("path transformation failed");
-                    return;
-                }
+                None => return, // nothing to do, all good
             };
 
-            let mut paint = paint.clone();
-            paint.shader.transform(transform);
+            let fill_rule = path.fill_rule;
+            match path.anti_alias_mode {
+                AntiAliasMode::None => scan::path::fill_prepared_path(
+                    edges,
+                    x,
+                    y,
+                    &bounds,
+                    fill_rule,
+                    &clip,
+                    &mut blitter,
+                ),
+                AntiAliasMode::Analytic => scan::path_aa::fill_prepared_path(
+                    edges,
+                    x,
+                    y,
+                    &bounds,
+                    fill_rule,
+                    &clip,
+                    &mut blitter,
+                ),
+                AntiAliasMode::Supersample { .. } => false,
+            }
+        };
 
-            self.fill_path(&path, &paint, fill_rule, Transform::identity(), mask)
+        if !filled {
+            self.fill_path(&path.path, paint, path.fill_rule, ts, mask);
         }
     }
 
-    /// Strokes a path.
+    /// Builds a path using a closure and fills it.
     ///
-    /// Stroking is implemented using two separate algorithms:
+    /// Produces exactly the same result as building a path via [`PathBuilder`]
+    /// and passing it to [`fill_path`](PixmapMut::fill_path).
+    /// But instead of allocating a new path each time, reuses a per-thread `PathBuilder`,
+    /// which is useful when many small, temporary paths are drawn per frame.
     ///
-    /// 1. If a stroke width is wider than 1px (after applying the transformation),
-    ///    a path will be converted into a stroked path and then filled using `fill_path`.
-    ///    Which means that we have to allocate a separate `Path`, that can be 2-3x larger
-    ///    then the original path.
-    /// 2. If a stroke width is thinner than 1px (after applying the transformation),
-    ///    we will use hairline stroking, which doesn't involve a separate path allocation.
+    /// The builder passed to `f` is always empty.
+    /// Without the `std` feature, a new builder is allocated on each call.
+    pub fn fill_with<F: FnOnce(&mut PathBuilder)>(
+        &mut self,
+        paint: &Paint,
+        fill_rule: FillRule,
+        transform: Transform,
+        mask: Option<&Mask>,
+        f: F,
+    ) {
+        let mut pb = take_path_builder();
+        f(&mut pb);
+
+        // An invalid path consumes the builder and its memory will not be reused.
+        if let Some(path) = pb.finish() {
+            self.fill_path(&path, paint, fill_rule, transform, mask);
+            release_path_builder(path.clear());
+        }
+    }
+
+    /// Fills a batch of glyph outlines.
     ///
-    /// Also, if a `stroke` has a dash array, then path will be converted into
-    /// a dashed path first and then stroked. Which means a yet another allocation.
-    pub fn stroke_path(
+    /// Each glyph is placed using its own transform, which is then followed by `transform`,
+    /// usually a text run position. The paint's shader is transformed only by `transform`,
+    /// therefore a gradient spans the whole run instead of being repeated per glyph.
+    ///
+    /// Glyphs are filled one after another using their stored fill rule
+    /// or `Winding` when none is set, so overlapping glyphs are blended the same way as separate
+    /// [`fill_path`](PixmapMut::fill_path) calls would do.
+    /// But unlike `fill_path`, the paint pipeline is built only once for the whole batch.
+    ///
+    /// Empty and non-finite glyphs are skipped.
+    pub fn fill_glyphs(
+        &mut self,
+        glyphs: &[(&Path, Transform)],
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        if !transform.is_finite() {
+            log::warn!("transform must be finite");
+            return;
+        }
+
+        let mut run_paint = paint.clone();
+        run_paint.shader.transform(transform);
+
+        let glyphs = glyphs.iter().filter_map(|(path, ts)| {
+            let fill_rule = path.fill_rule_or(FillRule::Winding);
+            let path = (*path).clone().transform(ts.post_concat(transform))?;
+            let bounds = path.bounds();
+            if bounds.width().is_nearly_zero()
+                || bounds.height().is_nearly_zero()
+                || is_too_big_for_math(&path)
+            {
+                return None;
+            }
+
+            Some((path, fill_rule))
+        });
+
+        // Large pixmaps and segment limits are handled by `fill_path`.
+        if DrawTiler::new(self.width(), self.height()).is_some() || paint.max_segments.is_some() {
+            for (path, fill_rule) in glyphs {
+                self.fill_path(&path, &run_paint, fill_rule, Transform::identity(), mask);
+            }
+
+            return;
+        }
+
+        let clip_rect = self.size().to_screen_int_rect(0, 0);
+        let submask = mask.map(|mask| mask.as_submask());
+        let mut subpix = self.as_subpixmap();
+        let mut blitter = match RasterPipelineBlitter::new(&run_paint, submask, &mut subpix) {
+            Some(v) => v,
+            None => return, // nothing to do, all good
+        };
+
+        for (path, fill_rule) in glyphs {
+            fill_path_impl(&path, fill_rule, &run_paint, &clip_rect, &mut blitter);
+        }
+    }
+
+    fn stroke_path_impl(
         &mut self,
         path: &Path,
         paint: &Paint,
         stroke: &Stroke,
         transform: Transform,
         mask: Option<&Mask>,
+        stats: &mut StrokeStats,
     ) {
-        if stroke.width < 0.0 {
-            log::warn!("negative stroke width isn't allowed");
+        if !(stroke.width >= 0.0 && stroke.width.is_finite()) {
+            log::warn!("stroke width must be finite and non-negative");
+            return;
+        }
+
+        if !transform.is_finite() {
+            log::warn!("transform must be finite");
             return;
         }
 
@@ -381,7 +1095,7 @@ impl PixmapMut<'_> {
                     };
                 }
 
-                for tile in tiler {
+                for tile in tiler.with_bounds(path.bounds()) {
                     let ts = Transform::from_translate(-(tile.x() as f32), -(tile.y() as f32));
                     path = match path.transform(ts) {
                         Some(v) => v,
@@ -431,7 +1145,13 @@ impl PixmapMut<'_> {
                 }
             }
         } else {
-            let path = match path.stroke(stroke, res_scale) {
+            // A stroked path approximates offset curves, therefore its precision
+            // must follow the curve tolerance as well.
+            let res_scale = res_scale * edge::curve_tolerance_scale(paint.curve_tolerance);
+            let mut stroker = PathStroker::new();
+            let path = stroker.stroke(path, stroke, res_scale);
+            *stats = stroker.stats();
+            let path = match path {
                 Some(v) => v,
                 None => {
                     log::warn!("path stroking failed");
@@ -463,9 +1183,65 @@ impl PixmapMut<'_> {
         }
     }
 
+    /// Draws a line segment.
+    ///
+    /// A shorthand for stroking a two-point path with butt caps.
+    ///
+    /// When the line is axis-aligned and covers whole pixels after applying
+    /// a translate-only `transform`, the paint is an opaque solid color and there is no mask,
+    /// pixel rows will be filled directly, without stroking.
+    /// Like 1px lines going through pixel centers.
+    pub fn draw_line(
+        &mut self,
+        p0: Point,
+        p1: Point,
+        paint: &Paint,
+        width: f32,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        if mask.is_none() && !transform.has_scale() && !transform.has_skew() {
+            if let Some(color) = fast_fill_color(paint) {
+                let (tx, ty) = (transform.tx, transform.ty);
+                let rect = line_rect(p0, p1, width)
+                    .and_then(|r| {
+                        Rect::from_ltrb(
+                            r.left() + tx,
+                            r.top() + ty,
+                            r.right() + tx,
+                            r.bottom() + ty,
+                        )
+                    })
+                    .and_then(|r| to_exact_int_rect(&r));
+                if let Some(rect) = rect {
+                    self.fill_int_rect(rect, color);
+                    return;
+                }
+            }
+        }
+
+        let mut pb = PathBuilder::new();
+        pb.move_to(p0.x, p0.y);
+        pb.line_to(p1.x, p1.y);
+        if let Some(path) = pb.finish() {
+            let stroke = Stroke {
+                width,
+                ..Stroke::default()
+            };
+            self.stroke_path(&path, paint, &stroke, transform, mask);
+        }
+    }
+
     /// Draws a `Pixmap` on top of the current `Pixmap`.
     ///
     /// The same as filling a rectangle with a `pixmap` pattern.
+    ///
+    /// The pixmap can end up at a fractional position because of the `transform`.
+    /// With `FilterQuality::Nearest`, each destination pixel takes the source pixel
+    /// under its center, therefore the image is effectively rounded to the pixel grid,
+    /// but the rounding can change between frames during animation.
+    /// With other filters, fractional offsets are filtered.
+    /// Use [`PixmapPaint::snap_to_pixel_grid`] to always align the pixmap to the pixel grid.
     pub fn draw_pixmap(
         &mut self,
         x: i32,
@@ -474,9 +1250,59 @@ impl PixmapMut<'_> {
         paint: &PixmapPaint,
         transform: Transform,
         mask: Option<&Mask>,
+    ) {
+        self.draw_pixmap_impl(x, y, pixmap, paint, None, transform, mask);
+    }
+
+    /// Draws a `Pixmap` recolored by a `tint` color.
+    ///
+    /// With [`TintMode::Alpha`], pixmap's RGB is ignored and its alpha is used
+    /// as a coverage for the `tint` color, like drawing through a single-channel mask.
+    /// With [`TintMode::Multiply`], pixmap's color is modulated by the `tint`.
+    ///
+    /// Tinting is done before the `paint` opacity and blending are applied.
+    pub fn draw_pixmap_tinted(
+        &mut self,
+        x: i32,
+        y: i32,
+        pixmap: PixmapRef,
+        tint: Color,
+        tint_mode: TintMode,
+        paint: &PixmapPaint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        let tint = Some((tint, tint_mode));
+        self.draw_pixmap_impl(x, y, pixmap, paint, tint, transform, mask);
+    }
+
+    fn draw_pixmap_impl(
+        &mut self,
+        x: i32,
+        y: i32,
+        pixmap: PixmapRef,
+        paint: &PixmapPaint,
+        tint: Option<(Color, TintMode)>,
+        transform: Transform,
+        mask: Option<&Mask>,
     ) {
         let rect = pixmap.size().to_int_rect(x, y).to_rect();
 
+        let transform = if paint.snap_to_pixel_grid {
+            let mut origin = Point::from_xy(x as f32, y as f32);
+            transform.map_point(&mut origin);
+            let snapped_x = (origin.x + 0.5).floor();
+            let snapped_y = (origin.y + 0.5).floor();
+            if transform.is_translate() {
+                // Set the translation directly to avoid rounding errors.
+                Transform::from_translate(snapped_x - x as f32, snapped_y - y as f32)
+            } else {
+                transform.post_translate(snapped_x - origin.x, snapped_y - origin.y)
+            }
+        } else {
+            transform
+        };
+
         // TODO: SkSpriteBlitter
         // TODO: partially clipped
         // TODO: clipped out
@@ -484,17 +1310,29 @@ impl PixmapMut<'_> {
         // Translate pattern as well as bounds.
         let patt_transform = Transform::from_translate(x as f32, y as f32);
 
+        let mut shader = Pattern::new(
+            pixmap,
+            SpreadMode::Pad, // Pad, otherwise we will get weird borders overlap.
+            paint.quality,
+            paint.opacity,
+            patt_transform,
+        )
+        .with_color_space(paint.color_space);
+        if let Some((color, mode)) = tint {
+            shader = shader.with_tint(color, mode);
+        }
+
         let paint = Paint {
-            shader: Pattern::new(
-                pixmap,
-                SpreadMode::Pad, // Pad, otherwise we will get weird borders overlap.
-                paint.quality,
-                paint.opacity,
-                patt_transform,
-            ),
+            shader,
             blend_mode: paint.blend_mode,
-            anti_alias: false,        // Skia doesn't use it too.
+            anti_alias: false, // Skia doesn't use it too.
+            anti_alias_mode: AntiAliasMode::default(),
             force_hq_pipeline: false, // Pattern will use hq anyway.
+            blend_in_linear_space: false,
+            dither: false,
+            render_mode: paint.render_mode,
+            curve_tolerance: DEFAULT_CURVE_TOLERANCE,
+            max_segments: None,
         };
 
         self.fill_rect(rect, &paint, transform, mask);
@@ -535,9 +1373,265 @@ impl PixmapMut<'_> {
             &mut self.as_subpixmap(),
         );
     }
+
+    /// Fills the area covered by a coverage mask using the paint.
+    ///
+    /// Each pixel of the `paint` shader, including a solid color, is multiplied
+    /// by the `coverage` in premultiplied space and then blended onto the pixmap.
+    /// This is the same as filling a path that the coverage was rendered from,
+    /// but the coverage can be reused, like for cached glyphs.
+    ///
+    /// The `coverage` occupies the `0, 0, width, height` rect, which is mapped
+    /// onto the pixmap using the `transform`. The shader is transformed as well.
+    /// For an integer translate, coverage pixels are mapped onto pixmap pixels exactly.
+    /// Otherwise, the coverage is resampled using bilinear filtering first.
+    ///
+    /// Unlike the clip `mask`, the `coverage` doesn't have to be the same size as the pixmap.
+    pub fn fill_mask(
+        &mut self,
+        coverage: &Mask,
+        paint: &Paint,
+        transform: Transform,
+        mask: Option<&Mask>,
+    ) {
+        if !transform.is_finite() {
+            log::warn!("transform must be finite");
+            return;
+        }
+
+        if let Some(mask) = mask {
+            if mask.size() != self.size() {
+                log::warn!("Pixmap and Mask are expected to have the same size");
+                return;
+            }
+        }
+
+        let is_int_translate = !transform.has_scale()
+            && !transform.has_skew()
+            && transform.tx.trunc() == transform.tx
+            && transform.ty.trunc() == transform.ty;
+
+        let resampled;
+        let (coverage, coverage_rect) = if is_int_translate {
+            let rect = match coverage
+                .size()
+                .to_int_rect(0, 0)
+                .translate(transform.tx as i32, transform.ty as i32)
+            {
+                Some(v) => v,
+                None => return,
+            };
+
+            (coverage, rect)
+        } else {
+            let rect = match coverage
+                .size()
+                .to_rect(0.0, 0.0)
+                .and_then(|r| r.transform(transform))
+                .and_then(|r| r.round_out())
+                .and_then(|r| r.intersect(&self.size().to_int_rect(0, 0)))
+            {
+                Some(v) => v,
+                None => return,
+            };
+
+            resampled = match resample_mask(coverage, transform, rect) {
+                Some(v) => v,
+                None => return,
+            };
+
+            (&resampled, rect)
+        };
+
+        let clip = match coverage_rect.intersect(&self.size().to_int_rect(0, 0)) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let submask = match clip
+            .translate(-coverage_rect.x(), -coverage_rect.y())
+            .and_then(|r| coverage.submask(r))
+        {
+            Some(v) => v,
+            None => return,
+        };
+
+        // The blitter accepts a single mask, therefore the clip mask is premultiplied.
+        let combined;
+        let submask = match mask.and_then(|mask| mask.submask(clip)) {
+            Some(clip_submask) => {
+                combined = match multiply_submasks(&submask, &clip_submask) {
+                    Some(v) => v,
+                    None => return,
+                };
+                combined.as_submask()
+            }
+            None => submask,
+        };
+
+        // The blitter works in the subpixmap coordinates.
+        let mut paint = paint.clone();
+        paint.shader.transform(transform);
+        paint.shader.transform(Transform::from_translate(
+            -(clip.x() as f32),
+            -(clip.y() as f32),
+        ));
+
+        let mut subpix = match self.subpixmap(clip) {
+            Some(v) => v,
+            None => return,
+        };
+
+        let mut blitter = match RasterPipelineBlitter::new(&paint, Some(submask), &mut subpix) {
+            Some(v) => v,
+            None => return, // nothing to do, all good
+        };
+
+        blitter::Blitter::blit_rect(&mut blitter, &clip.size().to_screen_int_rect(0, 0));
+    }
+}
+
+// Renders a transformed mask into a new mask covering the `rect` in device coordinates.
+fn resample_mask(mask: &Mask, transform: Transform, rect: IntRect) -> Option<Mask> {
+    // Use the mask as an alpha-only pixmap, so it could be drawn as a pattern.
+    let mut data = alloc::vec::Vec::with_capacity(mask.data().len() * BYTES_PER_PIXEL);
+    for a in mask.data() {
+        data.extend_from_slice(&[0, 0, 0, *a]);
+    }
+    let src = Pixmap::from_vec(data, mask.size())?;
+
+    // Unlike `draw_pixmap`, edges are anti-aliased, since the mask is a coverage itself.
+    let paint = Paint {
+        shader: Pattern::new(
+            src.as_ref(),
+            SpreadMode::Pad,
+            FilterQuality::Bilinear,
+            1.0,
+            Transform::identity(),
+        ),
+        anti_alias: true,
+        ..Paint::default()
+    };
+
+    let mut pixmap = Pixmap::new(rect.width(), rect.height())?;
+    let ts = transform.post_translate(-(rect.x() as f32), -(rect.y() as f32));
+    pixmap.fill_rect(mask.size().to_rect(0.0, 0.0)?, &paint, ts, None);
+
+    Some(Mask::from_pixmap(pixmap.as_ref(), MaskType::Alpha))
+}
+
+// Multiplies two masks of the same size.
+fn multiply_submasks(a: &SubMaskRef, b: &SubMaskRef) -> Option<Mask> {
+    let width = a.size.width() as usize;
+    let mut data = alloc::vec::Vec::with_capacity(width * a.size.height() as usize);
+    for y in 0..a.size.height() as usize {
+        let row_a = &a.data[y * a.real_width as usize..][..width];
+        let row_b = &b.data[y * b.real_width as usize..][..width];
+        data.extend(
+            row_a
+                .iter()
+                .zip(row_b)
+                .map(|(a, b)| crate::color::premultiply_u8(*a, *b)),
+        );
+    }
+
+    Mask::from_vec(data, a.size)
+}
+
+/// Fills a device-space path using the paint's anti-aliasing mode.
+fn fill_path_impl(
+    path: &Path,
+    fill_rule: FillRule,
+    paint: &Paint,
+    clip: &ScreenIntRect,
+    blitter: &mut dyn blitter::Blitter,
+) {
+    let mode = if paint.anti_alias {
+        paint.anti_alias_mode
+    } else {
+        AntiAliasMode::None
+    };
+
+    let tolerance = paint.curve_tolerance;
+    match mode {
+        AntiAliasMode::None => scan::path::fill_path(path, fill_rule, tolerance, clip, blitter),
+        AntiAliasMode::Analytic => {
+            scan::path_aa::fill_path(path, fill_rule, tolerance, clip, blitter)
+        }
+        AntiAliasMode::Supersample { factor } => {
+            scan::path_ss::fill_path(path, fill_rule, tolerance, factor, clip, blitter)
+        }
+    }
+}
+
+/// Returns the number of line segments a device-space path will be rasterized with.
+fn flattened_segments_count(path: &Path, paint: &Paint) -> usize {
+    let mode = if paint.anti_alias {
+        paint.anti_alias_mode
+    } else {
+        AntiAliasMode::None
+    };
+
+    let shift = match mode {
+        AntiAliasMode::None => 0,
+        // Supersampling scales the path by `factor`, which is similar to shifting.
+        AntiAliasMode::Supersample { factor } if factor > 1 => {
+            let factor = factor.min(scan::path_ss::MAX_FACTOR);
+            factor.next_power_of_two().trailing_zeros() as i32
+        }
+        _ => scan::path_aa::SHIFT as i32,
+    };
+
+    edge_builder::count_segments(path, shift, paint.curve_tolerance)
+}
+
+/// Returns a color that can be written directly, without blending.
+fn fast_fill_color(paint: &Paint) -> Option<PremultipliedColorU8> {
+    let color = match paint.shader {
+        Shader::SolidColor(ref c) => *c,
+        _ => return None,
+    };
+
+    match paint.blend_mode {
+        BlendMode::Source => {}
+        BlendMode::SourceOver if color.is_opaque() => {}
+        _ => return None,
+    }
+
+    Some(color.premultiply().to_color_u8())
+}
+
+/// Returns a rect covered by an axis-aligned line with butt caps.
+fn line_rect(p0: Point, p1: Point, width: f32) -> Option<Rect> {
+    let half = width.half();
+    if p0.y == p1.y {
+        Rect::from_ltrb(p0.x.min(p1.x), p0.y - half, p0.x.max(p1.x), p0.y + half)
+    } else if p0.x == p1.x {
+        Rect::from_ltrb(p0.x - half, p0.y.min(p1.y), p0.x + half, p0.y.max(p1.y))
+    } else {
+        None
+    }
+}
+
+/// Converts a `Rect` into an `IntRect` only when all its edges are integers.
+fn to_exact_int_rect(rect: &Rect) -> Option<IntRect> {
+    fn to_i32(n: f32) -> Option<i32> {
+        if n.trunc() == n && n >= i32::MIN as f32 && n <= i32::MAX as f32 {
+            Some(n as i32)
+        } else {
+            None
+        }
+    }
+
+    IntRect::from_ltrb(
+        to_i32(rect.left())?,
+        to_i32(rect.top())?,
+        to_i32(rect.right())?,
+        to_i32(rect.bottom())?,
+    )
 }
 
-fn treat_as_hairline(paint: &Paint, stroke: &Stroke, mut ts: Transform) -> Option<f32> {
+pub(crate) fn treat_as_hairline(paint: &Paint, stroke: &Stroke, mut ts: Transform) -> Option<f32> {
     fn fast_len(p: Point) -> f32 {
         let mut x = p.x.abs();
         let mut y = p.y.abs();
@@ -597,6 +1691,39 @@ pub(crate) fn is_too_big_for_math(path: &Path) -> bool {
     !(b.left() >= -MAX && b.top() >= -MAX && b.right() <= MAX && b.bottom() <= MAX)
 }
 
+#[cfg(feature = "std")]
+std::thread_local! {
+    // `const` thread locals require Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static PATH_BUILDER_POOL: core::cell::RefCell<Option<PathBuilder>> =
+        core::cell::RefCell::new(None);
+}
+
+// The builder is moved out of the pool, therefore nested calls will simply allocate a new one.
+#[cfg(feature = "std")]
+fn take_path_builder() -> PathBuilder {
+    match PATH_BUILDER_POOL.with(|pool| pool.borrow_mut().take()) {
+        Some(pb) => pb,
+        // Not `PathBuilder::default()`, which doesn't inject an initial move_to.
+        None => PathBuilder::new(),
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn take_path_builder() -> PathBuilder {
+    PathBuilder::new()
+}
+
+// `pb` must be empty.
+#[cfg(feature = "std")]
+fn release_path_builder(pb: PathBuilder) {
+    debug_assert!(pb.is_empty());
+    PATH_BUILDER_POOL.with(|pool| *pool.borrow_mut() = Some(pb));
+}
+
+#[cfg(not(feature = "std"))]
+fn release_path_builder(_: PathBuilder) {}
+
 /// Splits the target pixmap into a list of tiles.
 ///
 /// Skia/tiny-skia uses a lot of fixed-point math during path rendering.
@@ -611,6 +1738,8 @@ pub(crate) struct DrawTiler {
     x_offset: u32,
     y_offset: u32,
     finished: bool,
+    // Tiles outside of these bounds are skipped.
+    bounds: Option<IntRect>,
 }
 
 impl DrawTiler {
@@ -629,11 +1758,21 @@ impl DrawTiler {
                 x_offset: 0,
                 y_offset: 0,
                 finished: false,
+                bounds: None,
             })
         } else {
             None
         }
     }
+
+    /// Skips tiles that do not intersect the shape `bounds` in pixmap coordinates.
+    ///
+    /// Bounds are outset, so tiles with anti-aliased edges or hairline caps are never skipped.
+    pub(crate) fn with_bounds(mut self, bounds: Rect) -> Self {
+        // When bounds are too big, nothing is skipped.
+        self.bounds = bounds.outset(2.0, 2.0).and_then(|r| r.round_out());
+        self
+    }
 }
 
 impl Iterator for DrawTiler {
@@ -644,9 +1783,7 @@ impl Iterator for DrawTiler {
             return None;
         }
 
-        // TODO: iterate only over tiles that actually affected by the shape
-
-        if self.x_offset < self.image_width && self.y_offset < self.image_height {
+        while self.x_offset < self.image_width && self.y_offset < self.image_height {
             let h = if self.y_offset < self.image_height {
                 (self.image_height - self.y_offset).min(Self::MAX_DIMENSIONS)
             } else {
@@ -666,6 +1803,12 @@ impl Iterator for DrawTiler {
                 self.y_offset += Self::MAX_DIMENSIONS;
             }
 
+            if let (Some(r), Some(bounds)) = (r, self.bounds) {
+                if r.to_int_rect().intersect(&bounds).is_none() {
+                    continue;
+                }
+            }
+
             return r;
         }
 
@@ -676,6 +1819,10 @@ impl Iterator for DrawTiler {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::alpha_runs::AlphaRun;
+    use crate::color::AlphaU8;
+    use alloc::vec::Vec;
+
     const MAX_DIM: u32 = DrawTiler::MAX_DIMENSIONS;
 
     #[test]
@@ -728,4 +1875,101 @@ mod tests {
         );
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn bounds() {
+        let rect = |x, y, w, h| Rect::from_xywh(x, y, w, h).unwrap();
+
+        // Only the first tile.
+        let mut iter = DrawTiler::new(10000, 10000)
+            .unwrap()
+            .with_bounds(rect(20.0, 20.0, 10.0, 10.0));
+        assert_eq!(
+            iter.next(),
+            ScreenIntRect::from_xywh(0, 0, MAX_DIM, MAX_DIM)
+        );
+        assert_eq!(iter.next(), None);
+
+        // Only the last tile.
+        let mut iter = DrawTiler::new(10000, 10000)
+            .unwrap()
+            .with_bounds(rect(9000.0, 9000.0, 10.0, 10.0));
+        assert_eq!(
+            iter.next(),
+            ScreenIntRect::from_xywh(MAX_DIM, MAX_DIM, 10000 - MAX_DIM, 10000 - MAX_DIM)
+        );
+        assert_eq!(iter.next(), None);
+
+        // Close to the edge of the next tile.
+        let x = MAX_DIM as f32 - 11.5;
+        let mut iter = DrawTiler::new(10000, 500)
+            .unwrap()
+            .with_bounds(rect(x, 20.0, 10.0, 10.0));
+        assert_eq!(iter.next(), ScreenIntRect::from_xywh(0, 0, MAX_DIM, 500));
+        assert_eq!(
+            iter.next(),
+            ScreenIntRect::from_xywh(MAX_DIM, 0, 10000 - MAX_DIM, 500)
+        );
+        assert_eq!(iter.next(), None);
+
+        // Outside of the pixmap.
+        let mut iter = DrawTiler::new(10000, 500)
+            .unwrap()
+            .with_bounds(rect(20.0, 600.0, 10.0, 10.0));
+        assert_eq!(iter.next(), None);
+    }
+
+    // Records rows touched by the rasterizer.
+    struct RowsBlitter(Vec<u32>);
+
+    impl blitter::Blitter for RowsBlitter {
+        fn blit_h(&mut self, _: u32, y: u32, _: LengthU32) {
+            self.0.push(y);
+        }
+
+        fn blit_anti_h(&mut self, _: u32, y: u32, _: &mut [AlphaU8], _: &mut [AlphaRun]) {
+            self.0.push(y);
+        }
+
+        fn blit_v(&mut self, _: u32, y: u32, height: LengthU32, _: AlphaU8) {
+            self.0.extend(y..y + height.get());
+        }
+
+        fn blit_anti_h2(&mut self, _: u32, y: u32, _: AlphaU8, _: AlphaU8) {
+            self.0.push(y);
+        }
+
+        fn blit_anti_v2(&mut self, _: u32, y: u32, _: AlphaU8, _: AlphaU8) {
+            self.0.extend(y..y + 2);
+        }
+
+        fn blit_rect(&mut self, rect: &ScreenIntRect) {
+            self.0.extend(rect.y()..rect.bottom());
+        }
+    }
+
+    #[test]
+    fn scanlines_are_limited_by_path_bounds() {
+        let path = PathBuilder::from_circle(5020.0, 5020.0, 5.0).unwrap();
+        let clip = ScreenIntRect::from_xywh(0, 0, MAX_DIM, MAX_DIM).unwrap();
+
+        for anti_alias_mode in [
+            None,
+            Some(AntiAliasMode::Analytic),
+            Some(AntiAliasMode::Supersample { factor: 4 }),
+        ] {
+            let paint = Paint {
+                anti_alias: anti_alias_mode.is_some(),
+                anti_alias_mode: anti_alias_mode.unwrap_or_default(),
+                ..Paint::default()
+            };
+
+            let mut blitter = RowsBlitter(Vec::new());
+            fill_path_impl(&path, FillRule::Winding, &paint, &clip, &mut blitter);
+
+            let rows = blitter.0;
+            assert!(!rows.is_empty());
+            assert!(rows.iter().all(|y| (5014..5026).contains(y)), "{:?}", rows);
+        }
+    }
 }
@@ -4,7 +4,9 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
-use crate::{BlendMode, Color, LengthU32, Paint, PixmapRef, PremultipliedColorU8, Shader};
+use crate::{
+    BlendMode, Color, LengthU32, Paint, PixmapRef, PremultipliedColorU8, RenderMode, Shader,
+};
 use crate::{ALPHA_U8_OPAQUE, ALPHA_U8_TRANSPARENT};
 
 use crate::alpha_runs::AlphaRun;
@@ -76,25 +78,43 @@ impl<'a, 'b: 'a> RasterPipelineBlitter<'a, 'b> {
             memset2d_color = Some(PremultipliedColorU8::TRANSPARENT);
         }
 
+        let linear = paint.blend_in_linear_space;
+        let dither = paint.dither && !paint.is_solid_color();
+
         let blit_anti_h_rp = {
             let mut p = RasterPipelineBuilder::new();
             p.set_force_hq_pipeline(paint.force_hq_pipeline);
+            p.set_deterministic(paint.render_mode == RenderMode::Deterministic);
             if !paint.shader.push_stages(&mut p) {
                 return None;
             }
 
+            if dither {
+                p.push(pipeline::Stage::Dither);
+            }
+
             if mask.is_some() {
                 p.push(pipeline::Stage::MaskU8);
             }
 
+            if linear {
+                p.push(pipeline::Stage::SourceToLinear);
+            }
+
             if blend_mode.should_pre_scale_coverage() {
                 p.push(pipeline::Stage::Scale1Float);
                 p.push(pipeline::Stage::LoadDestination);
+                if linear {
+                    p.push(pipeline::Stage::DestinationToLinear);
+                }
                 if let Some(blend_stage) = blend_mode.to_stage() {
                     p.push(blend_stage);
                 }
             } else {
                 p.push(pipeline::Stage::LoadDestination);
+                if linear {
+                    p.push(pipeline::Stage::DestinationToLinear);
+                }
                 if let Some(blend_stage) = blend_mode.to_stage() {
                     p.push(blend_stage);
                 }
@@ -102,6 +122,10 @@ impl<'a, 'b: 'a> RasterPipelineBlitter<'a, 'b> {
                 p.push(pipeline::Stage::Lerp1Float);
             }
 
+            if linear {
+                p.push(pipeline::Stage::LinearToSrgb);
+            }
+
             p.push(pipeline::Stage::Store);
 
             p.compile()
@@ -110,23 +134,42 @@ impl<'a, 'b: 'a> RasterPipelineBlitter<'a, 'b> {
         let blit_rect_rp = {
             let mut p = RasterPipelineBuilder::new();
             p.set_force_hq_pipeline(paint.force_hq_pipeline);
+            p.set_deterministic(paint.render_mode == RenderMode::Deterministic);
             if !paint.shader.push_stages(&mut p) {
                 return None;
             }
 
+            if dither {
+                p.push(pipeline::Stage::Dither);
+            }
+
             if mask.is_some() {
                 p.push(pipeline::Stage::MaskU8);
             }
 
-            if blend_mode == BlendMode::SourceOver && mask.is_none() {
-                // TODO: ignore when dither_rate is non-zero
+            if blend_mode == BlendMode::SourceOver && mask.is_none() && !linear {
                 p.push(pipeline::Stage::SourceOverRgba);
             } else {
-                if blend_mode != BlendMode::Source {
+                // `Source` and `Clear` don't read the destination,
+                // therefore there is no need to load it and convert into a linear space.
+                if !blend_mode.uses_dst() {
+                    if let Some(blend_stage) = blend_mode.to_stage() {
+                        p.push(blend_stage);
+                    }
+                } else {
+                    if linear {
+                        p.push(pipeline::Stage::SourceToLinear);
+                    }
                     p.push(pipeline::Stage::LoadDestination);
+                    if linear {
+                        p.push(pipeline::Stage::DestinationToLinear);
+                    }
                     if let Some(blend_stage) = blend_mode.to_stage() {
                         p.push(blend_stage);
                     }
+                    if linear {
+                        p.push(pipeline::Stage::LinearToSrgb);
+                    }
                 }
 
                 p.push(pipeline::Stage::Store);
@@ -138,22 +181,37 @@ impl<'a, 'b: 'a> RasterPipelineBlitter<'a, 'b> {
         let blit_mask_rp = {
             let mut p = RasterPipelineBuilder::new();
             p.set_force_hq_pipeline(paint.force_hq_pipeline);
+            p.set_deterministic(paint.render_mode == RenderMode::Deterministic);
             if !paint.shader.push_stages(&mut p) {
                 return None;
             }
 
+            if dither {
+                p.push(pipeline::Stage::Dither);
+            }
+
             if mask.is_some() {
                 p.push(pipeline::Stage::MaskU8);
             }
 
+            if linear {
+                p.push(pipeline::Stage::SourceToLinear);
+            }
+
             if blend_mode.should_pre_scale_coverage() {
                 p.push(pipeline::Stage::ScaleU8);
                 p.push(pipeline::Stage::LoadDestination);
+                if linear {
+                    p.push(pipeline::Stage::DestinationToLinear);
+                }
                 if let Some(blend_stage) = blend_mode.to_stage() {
                     p.push(blend_stage);
                 }
             } else {
                 p.push(pipeline::Stage::LoadDestination);
+                if linear {
+                    p.push(pipeline::Stage::DestinationToLinear);
+                }
                 if let Some(blend_stage) = blend_mode.to_stage() {
                     p.push(blend_stage);
                 }
@@ -161,6 +219,10 @@ impl<'a, 'b: 'a> RasterPipelineBlitter<'a, 'b> {
                 p.push(pipeline::Stage::LerpU8);
             }
 
+            if linear {
+                p.push(pipeline::Stage::LinearToSrgb);
+            }
+
             p.push(pipeline::Stage::Store);
 
             p.compile()
@@ -322,9 +384,7 @@ impl Blitter for RasterPipelineBlitter<'_, '_> {
                         .pixmap
                         .offset(rect.x() as usize, (rect.y() + y) as usize);
                     let end = start + rect.width() as usize;
-                    self.pixmap.pixels_mut()[start..end]
-                        .iter_mut()
-                        .for_each(|p| *p = c);
+                    crate::cpu::fill_span(&mut self.pixmap.pixels_mut()[start..end], c);
                 }
             }
 
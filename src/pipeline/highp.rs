@@ -15,7 +15,7 @@ For some reason, we are almost 2x slower. Maybe because Skia uses clang's vector
 and we're using a manual implementation.
 */
 
-use crate::{PremultipliedColorU8, SpreadMode, PixmapRef};
+use crate::{ColorSpace, PremultipliedColorU8, SpreadMode, PixmapRef};
 
 use crate::geom::ScreenIntRect;
 use crate::pixmap::SubPixmapMut;
@@ -104,6 +104,8 @@ pub const STAGES: &[StageFn; super::STAGES_COUNT] = &[
     luminosity,
     source_over_rgba,
     transform,
+    scale_translate,
+    gather_scale_translate,
     reflect,
     repeat,
     bilinear,
@@ -119,12 +121,35 @@ pub const STAGES: &[StageFn; super::STAGES_COUNT] = &[
     xy_to_2pt_conical_greater,
     mask_2pt_conical_degenerates,
     apply_vector_mask,
+    source_to_linear,
+    destination_to_linear,
+    linear_to_srgb,
+    dither,
+    tint_alpha,
+    tint_multiply,
 ];
 
 pub fn fn_ptr(f: StageFn) -> *const () {
     f as *const ()
 }
 
+/// Returns a stage that produces the same results on all platforms.
+///
+/// Most stages are already portable and are returned as is.
+pub fn to_portable(f: StageFn) -> StageFn {
+    if fn_ptr(f) == fn_ptr(color_burn) {
+        color_burn_portable
+    } else if fn_ptr(f) == fn_ptr(color_dodge) {
+        color_dodge_portable
+    } else if fn_ptr(f) == fn_ptr(source_to_linear) {
+        source_to_linear_portable
+    } else if fn_ptr(f) == fn_ptr(linear_to_srgb) {
+        linear_to_srgb_portable
+    } else {
+        f
+    }
+}
+
 #[inline(never)]
 pub fn start(
     functions: &[StageFn],
@@ -218,10 +243,12 @@ fn clamp_0(p: &mut Pipeline) {
 }
 
 fn clamp_a(p: &mut Pipeline) {
-    p.r = p.r.min(f32x8::splat(1.0));
-    p.g = p.g.min(f32x8::splat(1.0));
-    p.b = p.b.min(f32x8::splat(1.0));
+    // Just like in Skia, color channels are clamped to alpha
+    // and not to 1, to keep the color premultiplied.
     p.a = p.a.min(f32x8::splat(1.0));
+    p.r = p.r.min(p.a);
+    p.g = p.g.min(p.a);
+    p.b = p.b.min(p.a);
 
     p.next_stage();
 }
@@ -304,7 +331,7 @@ fn gather_ix(pixmap: PixmapRef, mut x: f32x8, mut y: f32x8) -> u32x8 {
     x = x.max(f32x8::default()).min(f32x8::splat(w));
     y = y.max(f32x8::default()).min(f32x8::splat(h));
 
-    (y.trunc_int() * i32x8::splat(pixmap.width() as i32) + x.trunc_int()).to_u32x8_bitcast()
+    (y.trunc_int() * i32x8::splat(pixmap.real_width() as i32) + x.trunc_int()).to_u32x8_bitcast()
 }
 
 #[inline(always)]
@@ -454,6 +481,28 @@ blend_fn2!(color_dodge, |s: f32x8, d: f32x8, sa: f32x8, da: f32x8|
     )
 );
 
+// `recip_fast` is approximated differently on each platform.
+// Use a slower, but exact division instead.
+blend_fn2!(color_burn_portable, |s: f32x8, d: f32x8, sa: f32x8, da: f32x8|
+    d.cmp_eq(da).blend(
+        d + s * inv(da),
+        s.cmp_eq(f32x8::default()).blend(
+            d * inv(sa),
+            sa * (da - da.min((da - d) * sa / s)) + s * inv(da) + d * inv(sa)
+        )
+    )
+);
+
+blend_fn2!(color_dodge_portable, |s: f32x8, d: f32x8, sa: f32x8, da: f32x8|
+    d.cmp_eq(f32x8::default()).blend(
+        s * inv(da),
+        s.cmp_eq(sa).blend(
+            s + d * inv(sa),
+            sa * da.min((d * sa) / (sa - s)) + s * inv(da) + d * inv(sa)
+        )
+    )
+);
+
 blend_fn2!(hard_light, |s: f32x8, d: f32x8, sa, da|
     s * inv(da) + d * inv(sa) + two(s).cmp_le(sa).blend(
         two(s * d),
@@ -684,6 +733,111 @@ fn transform(p: &mut Pipeline) {
     p.next_stage();
 }
 
+fn scale_translate(p: &mut Pipeline) {
+    let ts = &p.ctx.transform;
+
+    p.r = mad(p.r, f32x8::splat(ts.sx), f32x8::splat(ts.tx));
+    p.g = mad(p.g, f32x8::splat(ts.sy), f32x8::splat(ts.ty));
+
+    p.next_stage();
+}
+
+// A nearest neighbor sampler for scale-translate transforms.
+// Replaces `seed_shader`, `scale_translate` and `gather`.
+//
+// Source coordinates are stepped in 32.32 fixed point, one integer add per pixel.
+// Fixed point doesn't round like `f32` does, therefore coordinates that are
+// too close to a pixel boundary are recomputed the way `scale_translate` does it.
+// This way the result is bit-identical to the general path.
+fn gather_scale_translate(p: &mut Pipeline) {
+    let ctx = &p.ctx.fixed_transform;
+    let ts = &p.ctx.transform;
+    let w = p.pixmap_src.width();
+    let h = p.pixmap_src.height();
+
+    let iy = match fixed_origin(p.dy, 1, ctx.sy, ctx.ty) {
+        Some((y, bound)) if !near_pixel_boundary(y, bound) => fixed_to_index(y, h),
+        _ => f32_to_index((p.dy as f32 + 0.5) * ts.sy + ts.ty, h),
+    };
+    let row = iy * p.pixmap_src.real_width() as u32;
+
+    let mut ix = [0u32; STAGE_WIDTH];
+    match fixed_origin(p.dx, STAGE_WIDTH, ctx.sx, ctx.tx) {
+        Some((mut x, bound)) => {
+            for (i, ix) in ix.iter_mut().enumerate() {
+                *ix = if near_pixel_boundary(x, bound) {
+                    f32_to_index((p.dx as f32 + (i as f32 + 0.5)) * ts.sx + ts.tx, w)
+                } else {
+                    fixed_to_index(x, w)
+                };
+
+                x += ctx.sx;
+            }
+        }
+        None => {
+            for (i, ix) in ix.iter_mut().enumerate() {
+                *ix = f32_to_index((p.dx as f32 + (i as f32 + 0.5)) * ts.sx + ts.tx, w);
+            }
+        }
+    }
+
+    for ix in &mut ix {
+        *ix += row;
+    }
+
+    load_8888(&p.pixmap_src.gather(bytemuck::cast(ix)), &mut p.r, &mut p.g, &mut p.b, &mut p.a);
+
+    p.dr = f32x8::default();
+    p.dg = f32x8::default();
+    p.db = f32x8::default();
+    p.da = f32x8::default();
+
+    p.next_stage();
+}
+
+// Returns a fixed point coordinate of the `d` pixel center and a maximum distance
+// between fixed point and `f32` coordinates of the next `count` pixels.
+#[inline(always)]
+fn fixed_origin(d: usize, count: usize, s: i64, t: i64) -> Option<(i64, u64)> {
+    // Pixel centers are exact in `f32` only up to 2^22.
+    if d >= 1 << 22 {
+        return None;
+    }
+
+    let scaled = (2 * d as i64 + 1).checked_mul(s)? >> 1;
+    let scaled_last = scaled.checked_add(s.checked_mul(count as i64 - 1)?)?;
+    let v = scaled.checked_add(t)?;
+    scaled_last.checked_add(t)?;
+
+    // `f32` rounds both the multiplication and the addition,
+    // with an error up to 2^-24 of a result each. Fixed point loses up to 1/2^32
+    // per coefficient, multiplied by a pixel center.
+    let scaled_max = scaled.unsigned_abs().max(scaled_last.unsigned_abs());
+    let f32_error = scaled_max
+        .saturating_mul(2)
+        .saturating_add(t.unsigned_abs())
+        >> 23;
+    let fixed_error = 2 * (d + count) as u64 + 8;
+    Some((v, f32_error.saturating_add(fixed_error)))
+}
+
+#[inline(always)]
+fn near_pixel_boundary(v: i64, bound: u64) -> bool {
+    let fract = v as u64 & 0xFFFF_FFFF;
+    fract < bound || fract > (1 << 32) - bound.min(1 << 32)
+}
+
+#[inline(always)]
+fn fixed_to_index(v: i64, limit: u32) -> u32 {
+    (v >> 32).max(0).min(limit as i64 - 1) as u32
+}
+
+#[inline(always)]
+fn f32_to_index(v: f32, limit: u32) -> u32 {
+    // Matches `gather_ix`.
+    v.max(0.0).min(ulp_sub(limit as f32)) as u32
+}
+
 // Tile x or y to [0,limit) == [0,limit - 1 ulp] (think, sampling from images).
 // The gather stages will hard clamp the output of these stages to [0,limit)...
 // we just need to do the basic repeat or mirroring.
@@ -863,6 +1017,17 @@ fn sample(
 
     let ix = gather_ix(pixmap, x, y);
     load_8888(&pixmap.gather(ix), r, g, b, a);
+
+    if ctx.color_space == ColorSpace::Linear {
+        // Pixels are always 8-bit, therefore we can use a LUT.
+        fn lut(c: f32) -> f32 {
+            crate::color::SRGB_TO_LINEAR_LUT[((c * 255.0 + 0.5) as usize).min(255)]
+        }
+
+        map_unpremultiplied(r, *a, lut);
+        map_unpremultiplied(g, *a, lut);
+        map_unpremultiplied(b, *a, lut);
+    }
 }
 
 #[inline(always)]
@@ -1042,6 +1207,123 @@ fn apply_vector_mask(p: &mut Pipeline) {
     p.next_stage();
 }
 
+fn source_to_linear(p: &mut Pipeline) {
+    let a = p.a;
+    map_unpremultiplied(&mut p.r, a, crate::color::srgb_to_linear);
+    map_unpremultiplied(&mut p.g, a, crate::color::srgb_to_linear);
+    map_unpremultiplied(&mut p.b, a, crate::color::srgb_to_linear);
+
+    p.next_stage();
+}
+
+fn destination_to_linear(p: &mut Pipeline) {
+    // Destination is always 8-bit, therefore we can use a LUT.
+    fn lut(c: f32) -> f32 {
+        crate::color::SRGB_TO_LINEAR_LUT[((c * 255.0 + 0.5) as usize).min(255)]
+    }
+
+    let a = p.da;
+    map_unpremultiplied(&mut p.dr, a, lut);
+    map_unpremultiplied(&mut p.dg, a, lut);
+    map_unpremultiplied(&mut p.db, a, lut);
+
+    p.next_stage();
+}
+
+fn linear_to_srgb(p: &mut Pipeline) {
+    let a = p.a;
+    map_unpremultiplied(&mut p.r, a, crate::color::linear_to_srgb);
+    map_unpremultiplied(&mut p.g, a, crate::color::linear_to_srgb);
+    map_unpremultiplied(&mut p.b, a, crate::color::linear_to_srgb);
+
+    p.next_stage();
+}
+
+fn source_to_linear_portable(p: &mut Pipeline) {
+    let a = p.a;
+    map_unpremultiplied(&mut p.r, a, crate::color::srgb_to_linear_portable);
+    map_unpremultiplied(&mut p.g, a, crate::color::srgb_to_linear_portable);
+    map_unpremultiplied(&mut p.b, a, crate::color::srgb_to_linear_portable);
+
+    p.next_stage();
+}
+
+fn linear_to_srgb_portable(p: &mut Pipeline) {
+    let a = p.a;
+    map_unpremultiplied(&mut p.r, a, crate::color::linear_to_srgb_portable);
+    map_unpremultiplied(&mut p.g, a, crate::color::linear_to_srgb_portable);
+    map_unpremultiplied(&mut p.b, a, crate::color::linear_to_srgb_portable);
+
+    p.next_stage();
+}
+
+fn dither(p: &mut Pipeline) {
+    // An 8x8 ordered dither (Bayer) matrix.
+    const MATRIX: [u8; 64] = [
+         0, 32,  8, 40,  2, 34, 10, 42,
+        48, 16, 56, 24, 50, 18, 58, 26,
+        12, 44,  4, 36, 14, 46,  6, 38,
+        60, 28, 52, 20, 62, 30, 54, 22,
+         3, 35, 11, 43,  1, 33,  9, 41,
+        51, 19, 59, 27, 49, 17, 57, 25,
+        15, 47,  7, 39, 13, 45,  5, 37,
+        63, 31, 55, 23, 61, 29, 53, 21,
+    ];
+
+    let row = (p.dy & 7) * 8;
+    let mut d = [0.0; STAGE_WIDTH];
+    for (i, v) in d.iter_mut().enumerate() {
+        let m = MATRIX[row + ((p.dx + i) & 7)] as f32;
+        // Maps 0..63 into -0.5..0.5 LSB.
+        *v = ((m + 0.5) / 64.0 - 0.5) / 255.0;
+    }
+    let d = f32x8::from(d);
+
+    // Keep premultiplied colors in a 0..alpha range.
+    let zero = f32x8::default();
+    p.r = (p.r + d).max(zero).min(p.a);
+    p.g = (p.g + d).max(zero).min(p.a);
+    p.b = (p.b + d).max(zero).min(p.a);
+
+    p.next_stage();
+}
+
+fn tint_alpha(p: &mut Pipeline) {
+    let ctx = &p.ctx.tint;
+    p.r = p.a * f32x8::splat(ctx.r);
+    p.g = p.a * f32x8::splat(ctx.g);
+    p.b = p.a * f32x8::splat(ctx.b);
+    p.a *= f32x8::splat(ctx.a);
+
+    p.next_stage();
+}
+
+fn tint_multiply(p: &mut Pipeline) {
+    let ctx = &p.ctx.tint;
+    p.r *= f32x8::splat(ctx.r);
+    p.g *= f32x8::splat(ctx.g);
+    p.b *= f32x8::splat(ctx.b);
+    p.a *= f32x8::splat(ctx.a);
+
+    p.next_stage();
+}
+
+// Applies a transfer function to a premultiplied color channel.
+//
+// There is no SIMD `powf`, so we have to process each lane separately.
+#[inline(always)]
+fn map_unpremultiplied(c: &mut f32x8, a: f32x8, f: fn(f32) -> f32) {
+    let mut cc: [f32; STAGE_WIDTH] = bytemuck::cast(*c);
+    let aa: [f32; STAGE_WIDTH] = bytemuck::cast(a);
+    for (c, a) in cc.iter_mut().zip(aa.iter()) {
+        if *a > 0.0 {
+            *c = f((*c / *a).clamp(0.0, 1.0)) * *a;
+        }
+    }
+
+    *c = f32x8::from(cc);
+}
+
 pub fn just_return(_: &mut Pipeline) {
     // Ends the loop.
 }
@@ -1101,9 +1383,11 @@ fn store_8888(
     r: &f32x8, g: &f32x8, b: &f32x8, a: &f32x8,
     data: &mut [PremultipliedColorU8; STAGE_WIDTH],
 ) {
-    let r: [i32; 8] = unnorm(r).into();
-    let g: [i32; 8] = unnorm(g).into();
-    let b: [i32; 8] = unnorm(b).into();
+    // Color channels are clamped to alpha, so an out of range value
+    // would not produce an invalid premultiplied color.
+    let r: [i32; 8] = unnorm(&r.min(*a)).into();
+    let g: [i32; 8] = unnorm(&g.min(*a)).into();
+    let b: [i32; 8] = unnorm(&b.min(*a)).into();
     let a: [i32; 8] = unnorm(a).into();
 
     let conv = |rr, gg, bb, aa|
@@ -1124,9 +1408,11 @@ fn store_8888_tail(
     r: &f32x8, g: &f32x8, b: &f32x8, a: &f32x8,
     tail: usize, data: &mut [PremultipliedColorU8],
 ) {
-    let r: [i32; 8] = unnorm(r).into();
-    let g: [i32; 8] = unnorm(g).into();
-    let b: [i32; 8] = unnorm(b).into();
+    // Color channels are clamped to alpha, so an out of range value
+    // would not produce an invalid premultiplied color.
+    let r: [i32; 8] = unnorm(&r.min(*a)).into();
+    let g: [i32; 8] = unnorm(&g.min(*a)).into();
+    let b: [i32; 8] = unnorm(&b.min(*a)).into();
     let a: [i32; 8] = unnorm(a).into();
 
     // This is better than `for i in 0..tail`, because this way the compiler
@@ -117,6 +117,8 @@ pub const STAGES: &[StageFn; super::STAGES_COUNT] = &[
     null_fn, // Luminosity
     source_over_rgba,
     transform,
+    scale_translate,
+    null_fn, // GatherScaleTranslate
     null_fn, // Reflect
     null_fn, // Repeat
     null_fn, // Bilinear
@@ -132,6 +134,12 @@ pub const STAGES: &[StageFn; super::STAGES_COUNT] = &[
     null_fn, // XYTo2PtConicalGreater
     null_fn, // Mask2PtConicalDegenerates
     null_fn, // ApplyVectorMask
+    null_fn, // SourceToLinear
+    null_fn, // DestinationToLinear
+    null_fn, // LinearToSrgb
+    null_fn, // Dither
+    null_fn, // TintAlpha
+    null_fn, // TintMultiply
 ];
 
 pub fn fn_ptr(f: StageFn) -> *const () {
@@ -551,6 +559,21 @@ fn transform(p: &mut Pipeline) {
     p.next_stage();
 }
 
+fn scale_translate(p: &mut Pipeline) {
+    let ts = &p.ctx.transform;
+
+    let x = join(&p.r, &p.g);
+    let y = join(&p.b, &p.a);
+
+    let nx = mad(x, f32x16::splat(ts.sx), f32x16::splat(ts.tx));
+    let ny = mad(y, f32x16::splat(ts.sy), f32x16::splat(ts.ty));
+
+    split(&nx, &mut p.r, &mut p.g);
+    split(&ny, &mut p.b, &mut p.a);
+
+    p.next_stage();
+}
+
 fn pad_x1(p: &mut Pipeline) {
     let x = join(&p.r, &p.g);
     let x = x.normalize();
@@ -769,6 +792,11 @@ fn store_8888(
     r: &u16x16, g: &u16x16, b: &u16x16, a: &u16x16,
     data: &mut [PremultipliedColorU8; STAGE_WIDTH],
 ) {
+    // Color channels are clamped to alpha, so an out of range value
+    // would not produce an invalid premultiplied color.
+    let r = r.min(a);
+    let g = g.min(a);
+    let b = b.min(a);
     let r = r.as_slice();
     let g = g.as_slice();
     let b = b.as_slice();
@@ -797,6 +825,11 @@ fn store_8888_tail(
     r: &u16x16, g: &u16x16, b: &u16x16, a: &u16x16,
     tail: usize, data: &mut [PremultipliedColorU8],
 ) {
+    // Color channels are clamped to alpha, so an out of range value
+    // would not produce an invalid premultiplied color.
+    let r = r.min(a);
+    let g = g.min(a);
+    let b = b.min(a);
     let r = r.as_slice();
     let g = g.as_slice();
     let b = b.as_slice();
@@ -50,7 +50,7 @@ use arrayvec::ArrayVec;
 
 use tiny_skia_path::NormalizedF32;
 
-use crate::{Color, PremultipliedColor, PremultipliedColorU8, SpreadMode};
+use crate::{Color, ColorSpace, PremultipliedColor, PremultipliedColorU8, SpreadMode};
 use crate::{PixmapRef, Transform};
 
 pub use blitter::RasterPipelineBlitter;
@@ -115,6 +115,8 @@ pub enum Stage {
     Luminosity,
     SourceOverRgba,
     Transform,
+    ScaleTranslate,
+    GatherScaleTranslate,
     Reflect,
     Repeat,
     Bilinear,
@@ -130,9 +132,15 @@ pub enum Stage {
     XYTo2PtConicalGreater,
     Mask2PtConicalDegenerates,
     ApplyVectorMask,
+    SourceToLinear,
+    DestinationToLinear,
+    LinearToSrgb,
+    Dither,
+    TintAlpha,
+    TintMultiply,
 }
 
-pub const STAGES_COUNT: usize = Stage::ApplyVectorMask as usize + 1;
+pub const STAGES_COUNT: usize = Stage::TintMultiply as usize + 1;
 
 impl<'a> PixmapRef<'a> {
     #[inline(always)]
@@ -246,12 +254,14 @@ pub struct Context {
     pub current_coverage: f32,
     pub sampler: SamplerCtx,
     pub uniform_color: UniformColorCtx,
+    pub tint: UniformColorCtx,
     pub evenly_spaced_2_stop_gradient: EvenlySpaced2StopGradientCtx,
     pub gradient: GradientCtx,
     pub two_point_conical_gradient: TwoPointConicalGradientCtx,
     pub limit_x: TileCtx,
     pub limit_y: TileCtx,
     pub transform: Transform,
+    pub fixed_transform: FixedTransformCtx,
 }
 
 #[derive(Copy, Clone, Default, Debug)]
@@ -259,6 +269,7 @@ pub struct SamplerCtx {
     pub spread_mode: SpreadMode,
     pub inv_width: f32,
     pub inv_height: f32,
+    pub color_space: ColorSpace,
 }
 
 #[derive(Copy, Clone, Default, Debug)]
@@ -335,9 +346,19 @@ pub struct TileCtx {
     pub inv_scale: f32, // cache of 1/scale
 }
 
+// A scale-translate transform in 32.32 fixed point.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct FixedTransformCtx {
+    pub sx: i64,
+    pub sy: i64,
+    pub tx: i64,
+    pub ty: i64,
+}
+
 pub struct RasterPipelineBuilder {
     stages: ArrayVec<Stage, MAX_STAGES>,
     force_hq_pipeline: bool,
+    deterministic: bool,
     pub ctx: Context,
 }
 
@@ -346,6 +367,7 @@ impl RasterPipelineBuilder {
         RasterPipelineBuilder {
             stages: ArrayVec::new(),
             force_hq_pipeline: false,
+            deterministic: false,
             ctx: Context::default(),
         }
     }
@@ -354,17 +376,51 @@ impl RasterPipelineBuilder {
         self.force_hq_pipeline = hq;
     }
 
+    pub fn set_deterministic(&mut self, deterministic: bool) {
+        self.deterministic = deterministic;
+    }
+
     pub fn push(&mut self, stage: Stage) {
         self.stages.push(stage);
     }
 
     pub fn push_transform(&mut self, ts: Transform) {
         if ts.is_finite() && !ts.is_identity() {
-            self.stages.push(Stage::Transform);
+            // Most images and gradients are only scaled and moved.
+            // Skipping multiplications by zero produces bit-identical results.
+            if ts.has_skew() {
+                self.stages.push(Stage::Transform);
+            } else {
+                self.stages.push(Stage::ScaleTranslate);
+            }
             self.ctx.transform = ts;
         }
     }
 
+    /// Pushes a nearest neighbor sampler with fixed point stepping,
+    /// which replaces `SeedShader`, `push_transform` and `Gather`.
+    ///
+    /// Returns `false` when `ts` has a skew or doesn't fit into 32.32 fixed point.
+    pub fn push_gather_scale_translate(&mut self, ts: Transform) -> bool {
+        const LIMIT: f32 = (1u32 << 30) as f32;
+
+        let coeffs = [ts.sx, ts.sy, ts.tx, ts.ty];
+        if ts.has_skew() || !coeffs.iter().all(|v| *v > -LIMIT && *v < LIMIT) {
+            return false;
+        }
+
+        let to_fixed = |v: f32| (v as f64 * (1u64 << 32) as f64) as i64;
+        self.ctx.fixed_transform = FixedTransformCtx {
+            sx: to_fixed(ts.sx),
+            sy: to_fixed(ts.sy),
+            tx: to_fixed(ts.tx),
+            ty: to_fixed(ts.ty),
+        };
+        self.ctx.transform = ts;
+        self.stages.push(Stage::GatherScaleTranslate);
+        true
+    }
+
     pub fn push_uniform_color(&mut self, c: PremultipliedColor) {
         let r = c.red();
         let g = c.green();
@@ -407,6 +463,14 @@ impl RasterPipelineBuilder {
                 .collect();
             functions.push(highp::just_return as highp::StageFn);
 
+            // Replace stages that produce platform-dependent results.
+            // lowp relies only on integer and basic float arithmetic, which is exact everywhere.
+            if self.deterministic {
+                for fun in &mut functions {
+                    *fun = highp::to_portable(*fun);
+                }
+            }
+
             // I wasn't able to reproduce Skia's load_8888_/store_8888_ performance.
             // Skia uses fallthrough switch, which is probably the reason.
             // In Rust, any branching in load/store code drastically affects the performance.
@@ -648,3 +712,127 @@ mod blend_tests {
     test_blend_highp!(color_highp,            BlendMode::Color,               139, 100,  60, 239);
     test_blend_highp!(luminosity_highp,       BlendMode::Luminosity,          100, 149, 157, 239);
 }
+
+#[cfg(test)]
+mod transform_tests {
+    use super::*;
+    use crate::geom::IntSizeExt;
+    use crate::Pixmap;
+    use alloc::vec;
+
+    // Samples a pixmap with unique pixels using the provided transform stage.
+    fn sample(stage: Stage, ts: Transform) -> Pixmap {
+        let mut src = Pixmap::new(16, 16).unwrap();
+        for (i, p) in src.pixels_mut().iter_mut().enumerate() {
+            *p = PremultipliedColorU8::from_rgba((i % 16) as u8 * 16, (i / 16) as u8 * 16, 0, 255)
+                .unwrap();
+        }
+
+        let mut p = RasterPipelineBuilder::new();
+        p.push(Stage::SeedShader);
+        p.push(stage);
+        p.ctx.transform = ts;
+        p.ctx.limit_x = TileCtx {
+            scale: 16.0,
+            inv_scale: 1.0 / 16.0,
+        };
+        p.ctx.limit_y = p.ctx.limit_x;
+        p.push(Stage::Repeat);
+        p.push(Stage::Gather);
+        p.push(Stage::Store);
+        let mut p = p.compile();
+
+        let mut dst = Pixmap::new(61, 37).unwrap();
+        let rect = dst.size().to_screen_int_rect(0, 0);
+        p.run(
+            &rect,
+            AAMaskCtx::default(),
+            MaskCtx::default(),
+            src.as_ref(),
+            &mut dst.as_mut().as_subpixmap(),
+        );
+        dst
+    }
+
+    #[test]
+    fn scale_translate_matches_transform() {
+        let transforms = [
+            Transform::from_translate(0.3, -7.6),
+            Transform::from_scale(0.37, 3.1),
+            Transform::from_row(-1.7, 0.0, 0.0, 0.6, 100.25, -3.5),
+            Transform::from_row(1.0 / 3.0, 0.0, 0.0, 1.0 / 3.0, 1.0 / 7.0, 5.0 / 7.0),
+        ];
+
+        for ts in transforms {
+            let pixmap1 = sample(Stage::Transform, ts);
+            let pixmap2 = sample(Stage::ScaleTranslate, ts);
+            assert_eq!(pixmap1, pixmap2, "{:?}", ts);
+        }
+    }
+
+    fn gather(fixed: bool, ts: Transform) -> Pixmap {
+        let mut src = Pixmap::new(16, 16).unwrap();
+        for (i, p) in src.pixels_mut().iter_mut().enumerate() {
+            *p = PremultipliedColorU8::from_rgba((i % 16) as u8 * 16, (i / 16) as u8 * 16, 0, 255)
+                .unwrap();
+        }
+
+        let mut p = RasterPipelineBuilder::new();
+        if fixed {
+            assert!(p.push_gather_scale_translate(ts));
+        } else {
+            p.push(Stage::SeedShader);
+            p.push(Stage::Transform);
+            p.ctx.transform = ts;
+            p.push(Stage::Gather);
+        }
+        p.push(Stage::Store);
+        let mut p = p.compile();
+
+        let mut dst = Pixmap::new(203, 37).unwrap();
+        let rect = dst.size().to_screen_int_rect(0, 0);
+        p.run(
+            &rect,
+            AAMaskCtx::default(),
+            MaskCtx::default(),
+            src.as_ref(),
+            &mut dst.as_mut().as_subpixmap(),
+        );
+        dst
+    }
+
+    #[test]
+    fn gather_scale_translate_matches_gather() {
+        let mut transforms = vec![
+            Transform::identity(),
+            Transform::from_translate(0.5, -7.5),
+            Transform::from_scale(0.1, 0.1),
+            Transform::from_scale(1.0 / 3.0, 3.0),
+            Transform::from_row(0.1, 0.0, 0.0, 0.1, 0.05, 0.05),
+            Transform::from_row(-0.37, 0.0, 0.0, -0.2, 16.0, 7.3),
+            Transform::from_row(1e-7, 0.0, 0.0, 1e7, 3.0, -2e8),
+        ];
+
+        // Scales that put many pixel centers right at pixel boundaries.
+        let mut seed = 0x2545_f491u32;
+        for _ in 0..200 {
+            let mut next = || {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed
+            };
+            let sx = 1.0 / (next() % 40 + 1) as f32;
+            let sy = (next() % 100) as f32 / 30.0 - 1.5;
+            let tx = (next() % 200) as f32 / 40.0 - 2.0;
+            let ty = (next() % 200) as f32 / 7.0 - 10.0;
+            transforms.push(Transform::from_row(sx, 0.0, 0.0, sy, tx, ty));
+        }
+
+        for ts in transforms {
+            let pixmap1 = gather(false, ts);
+            let pixmap2 = gather(true, ts);
+            assert_eq!(pixmap1, pixmap2, "{:?}", ts);
+        }
+    }
+}
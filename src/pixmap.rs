@@ -7,19 +7,17 @@
 use alloc::vec;
 use alloc::vec::Vec;
 
-use core::convert::TryFrom;
+use core::convert::{TryFrom, TryInto};
 use core::num::NonZeroUsize;
 
 use tiny_skia_path::IntSize;
 
-use crate::{Color, IntRect};
+use crate::{Color, FilterQuality, IntRect, Pattern, Shader, SpreadMode, TinySkiaError, Transform};
+use crate::{Mask, MaskType};
 
-use crate::color::PremultipliedColorU8;
+use crate::color::{premultiply_u8, PremultipliedColorU8, ALPHA_U8_OPAQUE};
 use crate::geom::{IntSizeExt, ScreenIntRect};
 
-#[cfg(feature = "png-format")]
-use crate::color::{premultiply_u8, ALPHA_U8_OPAQUE};
-
 /// Number of bytes per pixel.
 pub const BYTES_PER_PIXEL: usize = 4;
 
@@ -40,8 +38,44 @@ impl Pixmap {
     /// Zero size in an error.
     ///
     /// Pixmap's width is limited by i32::MAX/4.
+    ///
+    /// See [`Pixmap::try_new`] for error details.
     pub fn new(width: u32, height: u32) -> Option<Self> {
-        let size = IntSize::from_wh(width, height)?;
+        Pixmap::try_new(width, height).ok()
+    }
+
+    /// Allocates a new pixmap.
+    ///
+    /// The same as [`Pixmap::new`], but reports why the allocation is not possible.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::ZeroSize`] when `width` or `height` is zero.
+    /// - [`TinySkiaError::SizeOverflow`] when the pixmap is too large.
+    pub fn try_new(width: u32, height: u32) -> Result<Self, TinySkiaError> {
+        let size = IntSize::from_wh(width, height).ok_or(TinySkiaError::ZeroSize)?;
+        let data_len = data_len_for_size(size).ok_or(TinySkiaError::SizeOverflow)?;
+
+        Ok(Pixmap {
+            data: vec![0; data_len],
+            size,
+        })
+    }
+
+    /// Allocates a new pixmap filled with a specified color.
+    ///
+    /// The same as [`Pixmap::new`] followed by [`Pixmap::fill`].
+    /// Therefore, the color is premultiplied and stored as is, even when translucent.
+    pub fn new_filled(width: u32, height: u32, color: Color) -> Option<Self> {
+        let mut pixmap = Pixmap::new(width, height)?;
+        pixmap.fill(color);
+        Some(pixmap)
+    }
+
+    /// Allocates a new pixmap using an `IntSize`.
+    ///
+    /// The same as [`Pixmap::new`].
+    pub fn from_size(size: IntSize) -> Option<Self> {
         let data_len = data_len_for_size(size)?;
 
         // We cannot check that allocation was successful yet.
@@ -68,6 +102,25 @@ impl Pixmap {
         Some(Pixmap { data, size })
     }
 
+    /// Creates a new pixmap from premultiplied BGRA data.
+    ///
+    /// Red and blue channels are swapped in place, therefore no allocation is performed.
+    /// Alpha is not affected.
+    ///
+    /// Returns `None` when `data.len() != size.width() * size.height() * BYTES_PER_PIXEL`.
+    pub fn from_bgra(mut data: Vec<u8>, size: IntSize) -> Option<Self> {
+        let data_len = data_len_for_size(size)?;
+        if data.len() != data_len {
+            return None;
+        }
+
+        for pixel in data.chunks_exact_mut(BYTES_PER_PIXEL) {
+            pixel.swap(0, 2);
+        }
+
+        Some(Pixmap { data, size })
+    }
+
     /// Decodes a PNG data into a `Pixmap`.
     ///
     /// Only 8-bit images are supported.
@@ -179,11 +232,40 @@ impl Pixmap {
         self.as_ref().save_png(path)
     }
 
+    /// Encodes pixmap into an uncompressed 32-bit BMP data.
+    ///
+    /// See [`PixmapRef::encode_bmp`] for details.
+    pub fn encode_bmp(&self) -> Option<Vec<u8>> {
+        self.as_ref().encode_bmp()
+    }
+
+    /// Encodes pixmap into an uncompressed 32-bit TGA data.
+    ///
+    /// See [`PixmapRef::encode_tga`] for details.
+    pub fn encode_tga(&self) -> Option<Vec<u8>> {
+        self.as_ref().encode_tga()
+    }
+
+    /// Returns pixmap's data as premultiplied BGRA.
+    ///
+    /// See [`PixmapRef::to_bgra`] for details.
+    pub fn to_bgra(&self) -> Vec<u8> {
+        self.as_ref().to_bgra()
+    }
+
+    /// Copies pixmap's data into `dst` as premultiplied BGRA.
+    ///
+    /// See [`PixmapRef::copy_to_bgra`] for details.
+    pub fn copy_to_bgra(&self, dst: &mut [u8]) -> bool {
+        self.as_ref().copy_to_bgra(dst)
+    }
+
     /// Returns a container that references Pixmap's data.
     pub fn as_ref(&self) -> PixmapRef {
         PixmapRef {
             data: &self.data,
             size: self.size,
+            real_width: self.size.width() as usize,
         }
     }
 
@@ -192,6 +274,7 @@ impl Pixmap {
         PixmapMut {
             data: &mut self.data,
             size: self.size,
+            real_width: self.size.width() as usize,
         }
     }
 
@@ -214,11 +297,17 @@ impl Pixmap {
     }
 
     /// Fills the entire pixmap with a specified color.
+    ///
+    /// See [`PixmapMut::fill`](struct.PixmapMut.html#method.fill) for details.
     pub fn fill(&mut self, color: Color) {
-        let c = color.premultiply().to_color_u8();
-        for p in self.as_mut().pixels_mut() {
-            *p = c;
-        }
+        self.as_mut().fill(color);
+    }
+
+    /// Clears the pixmap.
+    ///
+    /// Zero-fills the internal data buffer, aka sets all pixels to transparent black.
+    pub fn clear(&mut self) {
+        self.data.fill(0);
     }
 
     /// Returns the internal data.
@@ -239,20 +328,55 @@ impl Pixmap {
     ///
     /// Returns `None` when position is out of bounds.
     pub fn pixel(&self, x: u32, y: u32) -> Option<PremultipliedColorU8> {
-        let idx = self.width().checked_mul(y)?.checked_add(x)?;
-        self.pixels().get(idx as usize).cloned()
+        self.as_ref().pixel(x, y)
+    }
+
+    /// Sets a pixel color.
+    ///
+    /// The color is premultiplied already, therefore it's stored as is.
+    ///
+    /// Does nothing when position is out of bounds.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: PremultipliedColorU8) {
+        self.as_mut().set_pixel(x, y, color);
     }
 
     /// Returns a mutable slice of pixels.
+    ///
+    /// A zero-copy view of [`data_mut`](Pixmap::data_mut), one item per 4 bytes.
+    ///
+    /// Pixels written through this slice are stored as is, therefore keeping
+    /// color components <= alpha is the caller's responsibility.
+    /// Pixels created via [`PremultipliedColorU8`] constructors are always valid,
+    /// use [`PremultipliedColorU8::clamp`] to fix others.
     pub fn pixels_mut(&mut self) -> &mut [PremultipliedColorU8] {
         bytemuck::cast_slice_mut(self.data_mut())
     }
 
     /// Returns a slice of pixels.
+    ///
+    /// A zero-copy view of [`data`](Pixmap::data), one item per 4 bytes.
     pub fn pixels(&self) -> &[PremultipliedColorU8] {
         bytemuck::cast_slice(self.data())
     }
 
+    /// Returns an iterator over pixel rows.
+    ///
+    /// Each row is exactly `width` pixels long.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[PremultipliedColorU8]> {
+        self.pixels().chunks(self.width() as usize)
+    }
+
+    /// Returns an iterator over mutable pixel rows.
+    ///
+    /// Each row is exactly `width` pixels long.
+    ///
+    /// Pixels can only be set to valid premultiplied colors,
+    /// since `PremultipliedColorU8` checks that RGB components are <= alpha on creation.
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [PremultipliedColorU8]> {
+        let width = self.width() as usize;
+        self.pixels_mut().chunks_mut(width)
+    }
+
     /// Consumes the internal data.
     ///
     /// Byteorder: RGBA
@@ -266,6 +390,46 @@ impl Pixmap {
     pub fn clone_rect(&self, rect: IntRect) -> Option<Pixmap> {
         self.as_ref().clone_rect(rect)
     }
+
+    /// Creates a pattern shader that borrows this pixmap.
+    ///
+    /// See [`PixmapRef::to_shader`] for details.
+    pub fn to_shader(
+        &self,
+        spread_mode: SpreadMode,
+        quality: FilterQuality,
+        transform: Transform,
+    ) -> Shader {
+        self.as_ref().to_shader(spread_mode, quality, transform)
+    }
+
+    /// Composites the pixmap over a solid background.
+    ///
+    /// See [`PixmapRef::flatten_onto`] for details.
+    pub fn flatten_onto(&self, background: Color) -> Pixmap {
+        self.as_ref().flatten_onto(background)
+    }
+
+    /// Extracts the alpha channel into a mask.
+    ///
+    /// See [`PixmapRef::to_alpha_mask`] for details.
+    pub fn to_alpha_mask(&self) -> Mask {
+        self.as_ref().to_alpha_mask()
+    }
+
+    /// Returns a hash of the pixmap content.
+    ///
+    /// See [`PixmapRef::hash`] for details.
+    pub fn hash(&self) -> u64 {
+        self.as_ref().hash()
+    }
+
+    /// Compares two pixmaps.
+    ///
+    /// See [`PixmapRef::diff`] for details.
+    pub fn diff(&self, other: PixmapRef, threshold: u8) -> Option<DiffStats> {
+        self.as_ref().diff(other, threshold)
+    }
 }
 
 impl core::fmt::Debug for Pixmap {
@@ -278,15 +442,31 @@ impl core::fmt::Debug for Pixmap {
     }
 }
 
+/// Pixmaps difference statistics.
+///
+/// Can be created via [`PixmapRef::diff`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DiffStats {
+    /// The maximum absolute difference of a single channel.
+    pub max: u8,
+    /// The mean absolute difference of all channels.
+    pub mean: f32,
+    /// The number of pixels with any channel difference larger than a threshold.
+    pub pixels_over_threshold: u64,
+}
+
 /// A container that references premultiplied RGBA pixels.
 ///
 /// Can be created from `Pixmap` or from a user provided data.
 ///
-/// The data is not aligned, therefore width == stride.
+/// The data is not aligned, therefore width == stride,
+/// unless created via [`from_bytes_with_stride`](PixmapRef::from_bytes_with_stride).
 #[derive(Clone, Copy, PartialEq)]
 pub struct PixmapRef<'a> {
     data: &'a [u8],
     size: IntSize,
+    // Row stride in pixels.
+    real_width: usize,
 }
 
 impl<'a> PixmapRef<'a> {
@@ -303,15 +483,58 @@ impl<'a> PixmapRef<'a> {
             return None;
         }
 
-        Some(PixmapRef { data, size })
+        Some(PixmapRef {
+            data,
+            size,
+            real_width: size.width() as usize,
+        })
+    }
+
+    /// Creates a new `PixmapRef` from bytes with an explicit row stride.
+    ///
+    /// Allows referencing a row-padded buffer or a subregion of a larger image
+    /// without copying.
+    ///
+    /// `stride` is in bytes. It must be a multiple of `BYTES_PER_PIXEL`,
+    /// at least `width * BYTES_PER_PIXEL` and no larger than `i32::MAX`.
+    /// The size must be at least `stride * (height - 1) + width * BYTES_PER_PIXEL`,
+    /// aka the last row doesn't require padding.
+    ///
+    /// The `data` is assumed to have premultiplied RGBA pixels (byteorder: RGBA).
+    pub fn from_bytes_with_stride(
+        data: &'a [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Option<Self> {
+        let size = IntSize::from_wh(width, height)?;
+        let data_len = data_len_for_size_and_stride(size, stride)?;
+        Some(PixmapRef {
+            data: data.get(..data_len)?,
+            size,
+            real_width: stride / BYTES_PER_PIXEL,
+        })
     }
 
     /// Creates a new `Pixmap` from the current data.
     ///
-    /// Clones the underlying data.
+    /// Clones the underlying data. Row padding is not preserved.
     pub fn to_owned(&self) -> Pixmap {
+        if self.is_packed() {
+            return Pixmap {
+                data: self.data.to_vec(),
+                size: self.size,
+            };
+        }
+
+        let len = self.size.width() as usize * self.size.height() as usize * BYTES_PER_PIXEL;
+        let mut data = Vec::with_capacity(len);
+        for row in self.rows() {
+            data.extend_from_slice(bytemuck::cast_slice(row));
+        }
+
         Pixmap {
-            data: self.data.to_vec(),
+            data,
             size: self.size,
         }
     }
@@ -328,18 +551,36 @@ impl<'a> PixmapRef<'a> {
         self.size.height()
     }
 
+    /// Returns pixmap's row stride in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.real_width * BYTES_PER_PIXEL
+    }
+
     /// Returns pixmap's size.
     pub(crate) fn size(&self) -> IntSize {
         self.size
     }
 
+    /// Returns pixmap's row stride in pixels.
+    #[inline]
+    pub(crate) fn real_width(&self) -> usize {
+        self.real_width
+    }
+
     /// Returns pixmap's rect.
     pub(crate) fn rect(&self) -> ScreenIntRect {
         self.size.to_screen_int_rect(0, 0)
     }
 
+    fn is_packed(&self) -> bool {
+        self.real_width == self.size.width() as usize
+    }
+
     /// Returns the internal data.
     ///
+    /// Includes the row padding when the stride is larger than the width.
+    ///
     /// Byteorder: RGBA
     pub fn data(&self) -> &'a [u8] {
         self.data
@@ -349,16 +590,32 @@ impl<'a> PixmapRef<'a> {
     ///
     /// Returns `None` when position is out of bounds.
     pub fn pixel(&self, x: u32, y: u32) -> Option<PremultipliedColorU8> {
-        let idx = self.width().checked_mul(y)?.checked_add(x)?;
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let idx = (self.real_width as u32).checked_mul(y)?.checked_add(x)?;
         self.pixels().get(idx as usize).cloned()
     }
 
     /// Returns a slice of pixels.
+    ///
+    /// Includes the row padding when the stride is larger than the width.
+    /// Use [`rows`](PixmapRef::rows) to skip it.
     pub fn pixels(&self) -> &'a [PremultipliedColorU8] {
         bytemuck::cast_slice(self.data())
     }
 
-    // TODO: add rows() iterator
+    /// Returns an iterator over pixel rows.
+    ///
+    /// Row padding is excluded.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [PremultipliedColorU8]> {
+        let width = self.size.width() as usize;
+        self.pixels()
+            .chunks(self.real_width)
+            .take(self.size.height() as usize)
+            .map(move |row| &row[..width])
+    }
 
     /// Returns a copy of the pixmap that intersects the `rect`.
     ///
@@ -376,7 +633,8 @@ impl<'a> PixmapRef<'a> {
             // TODO: optimize
             for y in 0..rect.height() {
                 for x in 0..rect.width() {
-                    let old_idx = (y + rect.y() as u32) * self.width() + (x + rect.x() as u32);
+                    let old_idx =
+                        (y + rect.y() as u32) * self.real_width as u32 + (x + rect.x() as u32);
                     let new_idx = y * rect.width() + x;
                     new_pixels[new_idx as usize] = old_pixels[old_idx as usize];
                 }
@@ -386,6 +644,143 @@ impl<'a> PixmapRef<'a> {
         Some(new)
     }
 
+    /// Creates a pattern shader that borrows this pixmap.
+    ///
+    /// A shorthand for `Pattern::new` with a full opacity.
+    /// No pixels are copied.
+    ///
+    /// `transform` maps pixmap pixels into the user space.
+    /// With an identity transform, the pixmap's top-left corner
+    /// is at the origin and a single pixel matches a single user space unit.
+    pub fn to_shader(
+        &self,
+        spread_mode: SpreadMode,
+        quality: FilterQuality,
+        transform: Transform,
+    ) -> Shader<'a> {
+        Pattern::new(*self, spread_mode, quality, 1.0, transform)
+    }
+
+    /// Extracts the alpha channel into a mask.
+    ///
+    /// Values are the raw premultiplied alpha, which is equal to coverage
+    /// when a solid opaque color was drawn.
+    /// The mask has the same size as the pixmap. Row padding is not preserved.
+    ///
+    /// The same as `Mask::from_pixmap(pixmap, MaskType::Alpha)`.
+    pub fn to_alpha_mask(&self) -> Mask {
+        Mask::from_pixmap(*self, MaskType::Alpha)
+    }
+
+    /// Composites the pixmap over a solid background.
+    ///
+    /// Returns an opaque pixmap, which is useful for encoding into formats
+    /// without an alpha channel.
+    ///
+    /// The background alpha is ignored, so the resulting alpha is always 255.
+    pub fn flatten_onto(&self, background: Color) -> Pixmap {
+        let mut background = background;
+        background.set_alpha(1.0);
+        let bg = background.premultiply().to_color_u8();
+
+        let len = self.size.width() as usize * self.size.height() as usize * BYTES_PER_PIXEL;
+        let mut data = Vec::with_capacity(len);
+        for row in self.rows() {
+            for p in row {
+                // Source-over in premultiplied space: `src + bg * (1 - src_alpha)`.
+                let inv_a = 255 - p.alpha();
+                data.extend_from_slice(&[
+                    p.red().saturating_add(premultiply_u8(bg.red(), inv_a)),
+                    p.green().saturating_add(premultiply_u8(bg.green(), inv_a)),
+                    p.blue().saturating_add(premultiply_u8(bg.blue(), inv_a)),
+                    ALPHA_U8_OPAQUE,
+                ]);
+            }
+        }
+
+        Pixmap {
+            data,
+            size: self.size,
+        }
+    }
+
+    /// Returns a hash of the pixmap content.
+    ///
+    /// A fast, non-cryptographic hash of the size and pixels.
+    /// Row padding is ignored, therefore the same image always produces the same hash,
+    /// regardless of the stride and the platform.
+    pub fn hash(&self) -> u64 {
+        // A multiply-rotate hash, similar to FxHash.
+        const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+        fn add(hash: u64, word: u64) -> u64 {
+            (hash.rotate_left(5) ^ word).wrapping_mul(SEED)
+        }
+
+        let mut hash = add(0, u64::from(self.width()) << 32 | u64::from(self.height()));
+        for row in self.rows() {
+            let bytes: &[u8] = bytemuck::cast_slice(row);
+            let mut words = bytes.chunks_exact(8);
+            for word in &mut words {
+                // Cannot fail, since the chunk is 8 bytes.
+                let word: [u8; 8] = word.try_into().unwrap_or_default();
+                hash = add(hash, u64::from_le_bytes(word));
+            }
+
+            for byte in words.remainder() {
+                hash = add(hash, u64::from(*byte));
+            }
+        }
+
+        hash
+    }
+
+    /// Compares two pixmaps.
+    ///
+    /// Useful for testing, where an exact match is too strict, since results
+    /// can slightly vary between platforms and SIMD implementations.
+    /// Premultiplied values are compared.
+    ///
+    /// Pixels with any channel difference larger than `threshold` are counted
+    /// in [`DiffStats::pixels_over_threshold`].
+    ///
+    /// Returns `None` when pixmaps have different sizes.
+    pub fn diff(&self, other: PixmapRef, threshold: u8) -> Option<DiffStats> {
+        if self.size != other.size {
+            return None;
+        }
+
+        let mut max = 0;
+        let mut sum = 0u64;
+        let mut pixels_over_threshold = 0;
+        for (row1, row2) in self.rows().zip(other.rows()) {
+            let row1: &[u8] = bytemuck::cast_slice(row1);
+            let row2: &[u8] = bytemuck::cast_slice(row2);
+            for (p1, p2) in row1
+                .chunks_exact(BYTES_PER_PIXEL)
+                .zip(row2.chunks_exact(BYTES_PER_PIXEL))
+            {
+                let mut pixel_max = 0;
+                for (c1, c2) in p1.iter().zip(p2) {
+                    let d = (i16::from(*c1) - i16::from(*c2)).unsigned_abs() as u8;
+                    pixel_max = pixel_max.max(d);
+                    sum += u64::from(d);
+                }
+
+                max = max.max(pixel_max);
+                if pixel_max > threshold {
+                    pixels_over_threshold += 1;
+                }
+            }
+        }
+
+        let channels = self.size.width() as u64 * self.size.height() as u64 * 4;
+        Some(DiffStats {
+            max,
+            mean: (sum as f64 / channels as f64) as f32,
+            pixels_over_threshold,
+        })
+    }
+
     /// Encodes pixmap into a PNG data.
     #[cfg(feature = "png-format")]
     pub fn encode_png(&self) -> Result<Vec<u8>, png::EncodingError> {
@@ -394,16 +789,23 @@ impl<'a> PixmapRef<'a> {
         // Sadly, we have to copy the pixmap here, because of demultiplication.
         // Not sure how to avoid this.
         // TODO: remove allocation
-        let mut tmp_pixmap = self.to_owned();
+        //
+        // Demultiplied colors are not valid premultiplied ones,
+        // therefore they are stored as raw bytes.
+        let mut tmp_data =
+            Vec::with_capacity(self.width() as usize * self.height() as usize * BYTES_PER_PIXEL);
 
         // Demultiply alpha.
         //
         // RasterPipeline is 15% faster here, but produces slightly different results
         // due to rounding. So we stick with this method for now.
-        for pixel in tmp_pixmap.pixels_mut() {
-            let c = pixel.demultiply();
-            *pixel =
-                PremultipliedColorU8::from_rgba_unchecked(c.red(), c.green(), c.blue(), c.alpha());
+        //
+        // Rows are used instead of pixels to skip the stride padding.
+        for row in self.rows() {
+            for pixel in row {
+                let c = pixel.demultiply();
+                tmp_data.extend_from_slice(&[c.red(), c.green(), c.blue(), c.alpha()]);
+            }
         }
 
         let mut data = Vec::new();
@@ -412,7 +814,7 @@ impl<'a> PixmapRef<'a> {
             encoder.set_color(png::ColorType::Rgba);
             encoder.set_depth(png::BitDepth::Eight);
             let mut writer = encoder.write_header()?;
-            writer.write_image_data(&tmp_pixmap.data)?;
+            writer.write_image_data(&tmp_data)?;
         }
 
         Ok(data)
@@ -425,6 +827,156 @@ impl<'a> PixmapRef<'a> {
         std::fs::write(path, data)?;
         Ok(())
     }
+
+    /// Encodes pixmap into an uncompressed 32-bit BMP data.
+    ///
+    /// Pixels are demultiplied and stored as bottom-up BGRA rows.
+    /// A `BITMAPV4HEADER` with explicit channel masks is used,
+    /// so the alpha channel is preserved.
+    ///
+    /// Returns `None` when the file would be larger than `u32::MAX` bytes,
+    /// which BMP cannot represent.
+    pub fn encode_bmp(&self) -> Option<Vec<u8>> {
+        let (image_size, file_size) = bmp_sizes(self.width(), self.height())?;
+        let data_offset = BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE;
+
+        let mut data = Vec::with_capacity(file_size as usize);
+
+        // BITMAPFILEHEADER
+        data.extend_from_slice(b"BM");
+        data.extend_from_slice(&file_size.to_le_bytes());
+        data.extend_from_slice(&[0; 4]); // reserved
+        data.extend_from_slice(&data_offset.to_le_bytes());
+
+        // BITMAPV4HEADER
+        data.extend_from_slice(&BMP_INFO_HEADER_SIZE.to_le_bytes());
+        data.extend_from_slice(&(self.width() as i32).to_le_bytes());
+        // A positive height indicates bottom-up rows.
+        data.extend_from_slice(&(self.height() as i32).to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes()); // planes
+        data.extend_from_slice(&32u16.to_le_bytes()); // bits per pixel
+        data.extend_from_slice(&3u32.to_le_bytes()); // BI_BITFIELDS
+        data.extend_from_slice(&image_size.to_le_bytes());
+        data.extend_from_slice(&2835i32.to_le_bytes()); // 72 DPI
+        data.extend_from_slice(&2835i32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes()); // colors used
+        data.extend_from_slice(&0u32.to_le_bytes()); // important colors
+        data.extend_from_slice(&0x00FF0000u32.to_le_bytes()); // red mask
+        data.extend_from_slice(&0x0000FF00u32.to_le_bytes()); // green mask
+        data.extend_from_slice(&0x000000FFu32.to_le_bytes()); // blue mask
+        data.extend_from_slice(&0xFF000000u32.to_le_bytes()); // alpha mask
+        data.extend_from_slice(b"BGRs"); // LCS_sRGB
+        data.extend_from_slice(&[0; 36]); // endpoints
+        data.extend_from_slice(&[0; 12]); // gamma
+
+        for row in self.rows().rev() {
+            push_bgra_row(row, &mut data);
+        }
+
+        Some(data)
+    }
+
+    /// Encodes pixmap into an uncompressed 32-bit TGA data.
+    ///
+    /// Pixels are demultiplied and stored as top-down BGRA rows.
+    ///
+    /// Returns `None` when the width or height is larger than 65535,
+    /// which TGA cannot represent.
+    pub fn encode_tga(&self) -> Option<Vec<u8>> {
+        const HEADER_SIZE: usize = 18;
+        const FOOTER: &[u8] = b"\0\0\0\0\0\0\0\0TRUEVISION-XFILE.\0";
+
+        let width = u16::try_from(self.width()).ok()?;
+        let height = u16::try_from(self.height()).ok()?;
+
+        let image_size = self.width() as usize * self.height() as usize * BYTES_PER_PIXEL;
+        let mut data = Vec::with_capacity(HEADER_SIZE + image_size + FOOTER.len());
+
+        data.push(0); // ID length
+        data.push(0); // no color map
+        data.push(2); // uncompressed true-color
+        data.extend_from_slice(&[0; 5]); // color map specification
+        data.extend_from_slice(&0u16.to_le_bytes()); // X origin
+        data.extend_from_slice(&0u16.to_le_bytes()); // Y origin
+        data.extend_from_slice(&width.to_le_bytes());
+        data.extend_from_slice(&height.to_le_bytes());
+        data.push(32); // bits per pixel
+        data.push(0x28); // 8 alpha bits and a top-left origin
+
+        for row in self.rows() {
+            push_bgra_row(row, &mut data);
+        }
+
+        data.extend_from_slice(FOOTER);
+        Some(data)
+    }
+
+    /// Returns pixmap's data as premultiplied BGRA.
+    ///
+    /// Unlike [`encode_bmp`](Self::encode_bmp), pixels are not demultiplied,
+    /// only red and blue channels are swapped. Alpha stays in place.
+    /// Rows are tightly packed, aka row padding is not preserved.
+    pub fn to_bgra(&self) -> Vec<u8> {
+        let len = self.size.width() as usize * self.size.height() as usize * BYTES_PER_PIXEL;
+        let mut data = vec![0; len];
+        self.copy_to_bgra(&mut data);
+        data
+    }
+
+    /// Copies pixmap's data into `dst` as premultiplied BGRA.
+    ///
+    /// Same as [`to_bgra`](Self::to_bgra), but writes into an existing buffer,
+    /// which allows reusing it between frames.
+    ///
+    /// Returns `false` and does nothing when
+    /// `dst.len() != width * height * BYTES_PER_PIXEL`.
+    pub fn copy_to_bgra(&self, dst: &mut [u8]) -> bool {
+        let row_len = self.size.width() as usize * BYTES_PER_PIXEL;
+        if dst.len() != row_len * self.size.height() as usize {
+            return false;
+        }
+
+        for (row, dst_row) in self.rows().zip(dst.chunks_exact_mut(row_len)) {
+            copy_bgra_row(row, dst_row);
+        }
+
+        true
+    }
+}
+
+fn copy_bgra_row(row: &[PremultipliedColorU8], dst: &mut [u8]) {
+    // A plain per-byte shuffle, which compilers are able to vectorize.
+    let src: &[u8] = bytemuck::cast_slice(row);
+    for (s, d) in src
+        .chunks_exact(BYTES_PER_PIXEL)
+        .zip(dst.chunks_exact_mut(BYTES_PER_PIXEL))
+    {
+        d[0] = s[2];
+        d[1] = s[1];
+        d[2] = s[0];
+        d[3] = s[3];
+    }
+}
+
+const BMP_FILE_HEADER_SIZE: u32 = 14;
+const BMP_INFO_HEADER_SIZE: u32 = 108;
+
+/// Returns BMP image and file sizes in bytes.
+///
+/// Returns `None` when they do not fit into the header's `u32`.
+fn bmp_sizes(width: u32, height: u32) -> Option<(u32, u32)> {
+    // 32-bit rows are always 4-byte aligned, so no padding is needed.
+    let row_size = width.checked_mul(BYTES_PER_PIXEL as u32)?;
+    let image_size = row_size.checked_mul(height)?;
+    let file_size = image_size.checked_add(BMP_FILE_HEADER_SIZE + BMP_INFO_HEADER_SIZE)?;
+    Some((image_size, file_size))
+}
+
+fn push_bgra_row(row: &[PremultipliedColorU8], data: &mut Vec<u8>) {
+    for pixel in row {
+        let c = pixel.demultiply();
+        data.extend_from_slice(&[c.blue(), c.green(), c.red(), c.alpha()]);
+    }
 }
 
 impl core::fmt::Debug for PixmapRef<'_> {
@@ -441,11 +993,14 @@ impl core::fmt::Debug for PixmapRef<'_> {
 ///
 /// Can be created from `Pixmap` or from a user provided data.
 ///
-/// The data is not aligned, therefore width == stride.
+/// The data is not aligned, therefore width == stride,
+/// unless created via [`from_bytes_with_stride`](PixmapMut::from_bytes_with_stride).
 #[derive(PartialEq)]
 pub struct PixmapMut<'a> {
     data: &'a mut [u8],
     size: IntSize,
+    // Row stride in pixels.
+    real_width: usize,
 }
 
 impl<'a> PixmapMut<'a> {
@@ -462,17 +1017,65 @@ impl<'a> PixmapMut<'a> {
             return None;
         }
 
-        Some(PixmapMut { data, size })
+        Some(PixmapMut {
+            data,
+            size,
+            real_width: size.width() as usize,
+        })
+    }
+
+    /// Creates a new `PixmapMut` from pixels.
+    ///
+    /// A typed alternative to [`from_bytes`](Self::from_bytes) for buffers owned by the caller.
+    /// No data is copied, so drawing goes directly into `pixels`,
+    /// exactly like into a [`Pixmap`].
+    ///
+    /// Pixels are stored row by row, top to bottom, without any padding.
+    /// Therefore, `pixels.len()` must be exactly `width * height`.
+    /// Zero size in an error.
+    pub fn from_pixels(
+        pixels: &'a mut [PremultipliedColorU8],
+        width: u32,
+        height: u32,
+    ) -> Option<Self> {
+        let size = IntSize::from_wh(width, height)?;
+        let data_len = data_len_for_size(size)?;
+        if pixels.len() * BYTES_PER_PIXEL != data_len {
+            return None;
+        }
+
+        Some(PixmapMut {
+            data: bytemuck::cast_slice_mut(pixels),
+            size,
+            real_width: size.width() as usize,
+        })
+    }
+
+    /// Creates a new `PixmapMut` from bytes with an explicit row stride.
+    ///
+    /// See [`PixmapRef::from_bytes_with_stride`] for details.
+    ///
+    /// Drawing will not touch the row padding.
+    pub fn from_bytes_with_stride(
+        data: &'a mut [u8],
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> Option<Self> {
+        let size = IntSize::from_wh(width, height)?;
+        let data_len = data_len_for_size_and_stride(size, stride)?;
+        Some(PixmapMut {
+            data: data.get_mut(..data_len)?,
+            size,
+            real_width: stride / BYTES_PER_PIXEL,
+        })
     }
 
     /// Creates a new `Pixmap` from the current data.
     ///
-    /// Clones the underlying data.
+    /// Clones the underlying data. Row padding is not preserved.
     pub fn to_owned(&self) -> Pixmap {
-        Pixmap {
-            data: self.data.to_vec(),
-            size: self.size,
-        }
+        self.as_ref().to_owned()
     }
 
     /// Returns a container that references Pixmap's data.
@@ -480,6 +1083,16 @@ impl<'a> PixmapMut<'a> {
         PixmapRef {
             data: self.data,
             size: self.size,
+            real_width: self.real_width,
+        }
+    }
+
+    /// Returns a container that mutably references the same data for a shorter lifetime.
+    pub(crate) fn reborrow(&mut self) -> PixmapMut {
+        PixmapMut {
+            data: self.data,
+            size: self.size,
+            real_width: self.real_width,
         }
     }
 
@@ -495,36 +1108,108 @@ impl<'a> PixmapMut<'a> {
         self.size.height()
     }
 
+    /// Returns pixmap's row stride in bytes.
+    #[inline]
+    pub fn stride(&self) -> usize {
+        self.real_width * BYTES_PER_PIXEL
+    }
+
     /// Returns pixmap's size.
     pub(crate) fn size(&self) -> IntSize {
         self.size
     }
 
+    fn is_packed(&self) -> bool {
+        self.real_width == self.size.width() as usize
+    }
+
     /// Fills the entire pixmap with a specified color.
+    ///
+    /// The color will be premultiplied once and then copied into each pixel.
+    /// Unlike painting, this method always overwrites the existing content,
+    /// even when the color is translucent. Aka `BlendMode::Source`.
     pub fn fill(&mut self, color: Color) {
         let c = color.premultiply().to_color_u8();
-        for p in self.pixels_mut() {
-            *p = c;
+        if c == PremultipliedColorU8::TRANSPARENT {
+            self.clear();
+        } else if self.is_packed() {
+            crate::cpu::fill_span(self.pixels_mut(), c);
+        } else {
+            for row in self.rows_mut() {
+                crate::cpu::fill_span(row, c);
+            }
+        }
+    }
+
+    /// Clears the pixmap.
+    ///
+    /// Zero-fills the internal data buffer, aka sets all pixels to transparent black.
+    /// Row padding is left untouched.
+    pub fn clear(&mut self) {
+        if self.is_packed() {
+            self.data.fill(0);
+        } else {
+            for row in self.rows_mut() {
+                row.fill(PremultipliedColorU8::TRANSPARENT);
+            }
         }
     }
 
     /// Returns the mutable internal data.
     ///
+    /// Includes the row padding when the stride is larger than the width.
+    ///
     /// Byteorder: RGBA
     pub fn data_mut(&mut self) -> &mut [u8] {
         self.data
     }
 
+    /// Sets a pixel color.
+    ///
+    /// The color is premultiplied already, therefore it's stored as is.
+    ///
+    /// Does nothing when position is out of bounds.
+    pub fn set_pixel(&mut self, x: u32, y: u32, color: PremultipliedColorU8) {
+        if x >= self.width() || y >= self.height() {
+            return;
+        }
+
+        // Cannot overflow, since the data length fits into `isize`.
+        let idx = self.real_width * y as usize + x as usize;
+        if let Some(pixel) = self.pixels_mut().get_mut(idx) {
+            *pixel = color;
+        }
+    }
+
     /// Returns a mutable slice of pixels.
+    ///
+    /// Includes the row padding when the stride is larger than the width.
+    /// Use [`rows_mut`](PixmapMut::rows_mut) to skip it.
+    ///
+    /// Keeping color components <= alpha is the caller's responsibility.
+    /// See [`Pixmap::pixels_mut`] for details.
     pub fn pixels_mut(&mut self) -> &mut [PremultipliedColorU8] {
         bytemuck::cast_slice_mut(self.data_mut())
     }
 
+    /// Returns an iterator over mutable pixel rows.
+    ///
+    /// Row padding is excluded.
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [PremultipliedColorU8]> {
+        let width = self.size.width() as usize;
+        let height = self.size.height() as usize;
+        let real_width = self.real_width;
+        self.pixels_mut()
+            .chunks_mut(real_width)
+            .take(height)
+            .map(move |row| &mut row[..width])
+    }
+
     /// Creates `SubPixmapMut` that contains the whole `PixmapMut`.
     pub(crate) fn as_subpixmap(&mut self) -> SubPixmapMut {
         SubPixmapMut {
             size: self.size(),
-            real_width: self.width() as usize,
+            real_width: self.real_width,
             data: &mut self.data,
         }
     }
@@ -534,12 +1219,12 @@ impl<'a> PixmapMut<'a> {
     /// Returns `None` when `Pixmap`'s rect doesn't contain `rect`.
     pub(crate) fn subpixmap(&mut self, rect: IntRect) -> Option<SubPixmapMut> {
         let rect = self.size.to_int_rect(0, 0).intersect(&rect)?;
-        let row_bytes = self.width() as usize * BYTES_PER_PIXEL;
+        let row_bytes = self.real_width * BYTES_PER_PIXEL;
         let offset = rect.top() as usize * row_bytes + rect.left() as usize * BYTES_PER_PIXEL;
 
         Some(SubPixmapMut {
             size: rect.size(),
-            real_width: self.width() as usize,
+            real_width: self.real_width,
             data: &mut self.data[offset..],
         })
     }
@@ -581,23 +1266,65 @@ impl<'a> SubPixmapMut<'a> {
 /// Returns minimum bytes per row as usize.
 ///
 /// Pixmap's maximum value for row bytes must fit in 31 bits.
-fn min_row_bytes(size: IntSize) -> Option<NonZeroUsize> {
+fn min_row_bytes(size: IntSize, bytes_per_pixel: usize) -> Option<NonZeroUsize> {
     let w = i32::try_from(size.width()).ok()?;
-    let w = w.checked_mul(BYTES_PER_PIXEL as i32)?;
+    let w = w.checked_mul(bytes_per_pixel as i32)?;
     NonZeroUsize::new(w as usize)
 }
 
 /// Returns storage size required by pixel array.
-fn compute_data_len(size: IntSize, row_bytes: usize) -> Option<usize> {
+fn compute_data_len(size: IntSize, row_bytes: usize, bytes_per_pixel: usize) -> Option<usize> {
     let h = size.height().checked_sub(1)?;
     let h = (h as usize).checked_mul(row_bytes)?;
 
-    let w = (size.width() as usize).checked_mul(BYTES_PER_PIXEL)?;
+    let w = (size.width() as usize).checked_mul(bytes_per_pixel)?;
+
+    let len = h.checked_add(w)?;
+
+    // `Vec` cannot be larger than `isize::MAX` bytes.
+    if len > isize::MAX as usize {
+        return None;
+    }
+
+    Some(len)
+}
+
+/// Returns storage size required by an RGBA image with the specified row stride in bytes.
+fn data_len_for_size_and_stride(size: IntSize, stride: usize) -> Option<usize> {
+    let row_bytes = min_row_bytes(size, BYTES_PER_PIXEL)?;
+    // Just like row bytes, stride must fit in 31 bits.
+    if stride < row_bytes.get() || stride > i32::MAX as usize || stride % BYTES_PER_PIXEL != 0 {
+        return None;
+    }
 
-    h.checked_add(w)
+    compute_data_len(size, stride, BYTES_PER_PIXEL)
 }
 
 fn data_len_for_size(size: IntSize) -> Option<usize> {
-    let row_bytes = min_row_bytes(size)?;
-    compute_data_len(size, row_bytes.get())
+    data_len_for_size_and_bpp(size, BYTES_PER_PIXEL)
+}
+
+/// Returns storage size required by an image with the specified bytes per pixel.
+///
+/// All image allocations must be validated by this function,
+/// so an absurd size would be rejected instead of overflowing.
+pub(crate) fn data_len_for_size_and_bpp(size: IntSize, bytes_per_pixel: usize) -> Option<usize> {
+    let row_bytes = min_row_bytes(size, bytes_per_pixel)?;
+    compute_data_len(size, row_bytes.get(), bytes_per_pixel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bmp_sizes_overflow() {
+        assert_eq!(bmp_sizes(3, 2), Some((24, 146)));
+        // Allocatable on 64-bit targets, but too large for BMP.
+        assert_eq!(bmp_sizes(32768, 32768), None);
+        assert!(bmp_sizes(32768, 32767).is_some());
+        // Only the headers overflow.
+        assert_eq!(bmp_sizes(1, 1_073_741_799), None);
+        assert_eq!(bmp_sizes(u32::MAX, 1), None);
+    }
 }
@@ -0,0 +1,195 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use tiny_skia_path::IntSize;
+
+use crate::{FillRule, IntRect, Path, Pixmap, PixmapRef, PremultipliedColorF16, Transform};
+
+use crate::pixmap::{data_len_for_size_and_bpp, BYTES_PER_PIXEL};
+
+/// Number of bytes per pixel in `PixmapF16`.
+const BYTES_PER_PIXEL_F16: usize = 8;
+
+/// A container that owns half-float premultiplied RGBA pixels.
+///
+/// Unlike [`Pixmap`], stores 16 bits per channel, which prevents banding
+/// and allows color values larger than 1.0.
+/// The intended usage is to render into a `PixmapF16`
+/// and then tone map it into an 8-bit `Pixmap`.
+///
+/// Only solid color fills with the `SourceOver` blending are supported for now.
+#[derive(Clone, PartialEq)]
+pub struct PixmapF16 {
+    data: Vec<PremultipliedColorF16>,
+    size: IntSize,
+}
+
+impl PixmapF16 {
+    /// Allocates a new pixmap.
+    ///
+    /// A pixmap is filled with transparent black by default.
+    ///
+    /// Zero size in an error.
+    pub fn new(width: u32, height: u32) -> Option<Self> {
+        Self::from_size(IntSize::from_wh(width, height)?)
+    }
+
+    /// Allocates a new pixmap using an `IntSize`.
+    ///
+    /// The same as [`PixmapF16::new`].
+    pub fn from_size(size: IntSize) -> Option<Self> {
+        data_len_for_size_and_bpp(size, BYTES_PER_PIXEL_F16)?;
+
+        let len = size.width() as usize * size.height() as usize;
+        Some(PixmapF16 {
+            data: vec![PremultipliedColorF16::TRANSPARENT; len],
+            size,
+        })
+    }
+
+    /// Converts an 8-bit pixmap.
+    ///
+    /// The conversion is lossless, aka [`to_pixmap`](Self::to_pixmap)
+    /// will return the original pixels.
+    pub fn from_pixmap(pixmap: PixmapRef) -> Option<Self> {
+        let mut f16 = Self::from_size(pixmap.size())?;
+        let dst_rows = f16.data.chunks_exact_mut(pixmap.width() as usize);
+        for (row, dst_row) in pixmap.rows().zip(dst_rows) {
+            for (p, d) in row.iter().zip(dst_row) {
+                *d = PremultipliedColorF16::from(*p);
+            }
+        }
+
+        Some(f16)
+    }
+
+    /// Converts into an 8-bit pixmap.
+    ///
+    /// Color values larger than alpha are clamped,
+    /// therefore a tone mapping should be applied beforehand.
+    pub fn to_pixmap(&self) -> Pixmap {
+        let mut data = Vec::with_capacity(self.data.len() * BYTES_PER_PIXEL);
+        for p in &self.data {
+            let c = p.to_color_u8();
+            data.extend_from_slice(&[c.red(), c.green(), c.blue(), c.alpha()]);
+        }
+
+        // Cannot fail, since an F16 pixmap of the same size was already allocated.
+        Pixmap::from_vec(data, self.size).unwrap()
+    }
+
+    /// Returns pixmap's width.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.size.width()
+    }
+
+    /// Returns pixmap's height.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.size.height()
+    }
+
+    /// Returns a slice of pixels.
+    pub fn pixels(&self) -> &[PremultipliedColorF16] {
+        &self.data
+    }
+
+    /// Returns a mutable slice of pixels.
+    pub fn pixels_mut(&mut self) -> &mut [PremultipliedColorF16] {
+        &mut self.data
+    }
+
+    /// Returns a pixel color.
+    ///
+    /// Returns `None` when position is out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<PremultipliedColorF16> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let idx = self.width() as usize * y as usize + x as usize;
+        self.data.get(idx).cloned()
+    }
+
+    /// Fills the entire pixmap with a specified color.
+    pub fn fill(&mut self, color: PremultipliedColorF16) {
+        self.data.fill(color);
+    }
+
+    /// Clears the pixmap, aka sets all pixels to transparent black.
+    pub fn clear(&mut self) {
+        self.fill(PremultipliedColorF16::TRANSPARENT);
+    }
+
+    /// Draws a filled path onto the pixmap using a solid color.
+    ///
+    /// Uses the `SourceOver` blending, which is calculated in `f32`.
+    pub fn fill_path(
+        &mut self,
+        path: &Path,
+        color: PremultipliedColorF16,
+        fill_rule: FillRule,
+        anti_alias: bool,
+        transform: Transform,
+    ) {
+        // `rasterize_path` is limited to 8191x8191.
+        const TILE_SIZE: u32 = 8191;
+
+        let path = match path.clone().transform(transform) {
+            Some(v) => v,
+            None => {
+                log::warn!("path transformation failed");
+                return;
+            }
+        };
+
+        let src = [color.red(), color.green(), color.blue(), color.alpha()];
+        let width = self.width() as usize;
+        let data = &mut self.data;
+        let mut blend = |y: i32, x: i32, len: u32, coverage: &[u8]| {
+            let start = y as usize * width + x as usize;
+            let pixels = &mut data[start..start + len as usize];
+            for (d, c) in pixels.iter_mut().zip(coverage) {
+                *d = source_over(src, *d, *c as f32 / 255.0);
+            }
+        };
+
+        for y in (0..self.size.height()).step_by(TILE_SIZE as usize) {
+            for x in (0..self.size.width()).step_by(TILE_SIZE as usize) {
+                let w = TILE_SIZE.min(self.size.width() - x);
+                let h = TILE_SIZE.min(self.size.height() - y);
+                if let Some(tile) = IntRect::from_xywh(x as i32, y as i32, w, h) {
+                    crate::rasterize_path(&path, fill_rule, anti_alias, tile, &mut blend);
+                }
+            }
+        }
+    }
+}
+
+fn source_over(src: [f32; 4], dst: PremultipliedColorF16, coverage: f32) -> PremultipliedColorF16 {
+    let inv_sa = 1.0 - src[3] * coverage;
+    PremultipliedColorF16::from_rgba_clamped(
+        src[0] * coverage + dst.red() * inv_sa,
+        src[1] * coverage + dst.green() * inv_sa,
+        src[2] * coverage + dst.blue() * inv_sa,
+        src[3] * coverage + dst.alpha() * inv_sa,
+    )
+}
+
+impl core::fmt::Debug for PixmapF16 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PixmapF16")
+            .field("data", &"...")
+            .field("width", &self.size.width())
+            .field("height", &self.size.height())
+            .finish()
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec::Vec;
+
+use crate::{AntiAliasMode, FillRule, Paint, Path, PathSegment, Point};
+
+use crate::edge::Edge;
+use crate::path_geometry;
+use crate::scan;
+
+/// A fill path pre-tessellated into scan converter edges.
+///
+/// Flattening curves and building edges is a noticeable part of path filling.
+/// For static geometry that is only moved between frames, like UI elements,
+/// this work can be done once and reused via [`PixmapMut::fill_prepared_path`].
+///
+/// Drawing a prepared path at an integer offset produces exactly the same pixels
+/// as filling the path with a translate transform.
+/// Edges are moved in fixed-point coordinates only when this matches
+/// the translated path exactly. Otherwise, the path is filled as is.
+///
+/// Strokes can be prepared as well by caching the outline produced by [`Path::stroke`]:
+///
+/// ```
+/// use tiny_skia::*;
+///
+/// let path = PathBuilder::from_circle(20.0, 20.0, 10.0).unwrap();
+/// let stroke = Stroke { width: 4.0, ..Stroke::default() };
+/// let outline = path.stroke(&stroke, 1.0).unwrap();
+///
+/// let paint = Paint::default();
+/// let prepared = PreparedPath::new(&outline, FillRule::Winding, &paint);
+///
+/// let mut pixmap = Pixmap::new(200, 200).unwrap();
+/// for i in 0..5 {
+///     pixmap.fill_prepared_path(&prepared, &paint, i * 30, 0, None);
+/// }
+/// ```
+///
+/// [`PixmapMut::fill_prepared_path`]: crate::PixmapMut::fill_prepared_path
+#[derive(Clone, Debug)]
+pub struct PreparedPath {
+    pub(crate) path: Path,
+    pub(crate) fill_rule: FillRule,
+    pub(crate) anti_alias_mode: AntiAliasMode,
+    pub(crate) curve_tolerance: f32,
+    // Sorted edges at the original path position.
+    // `None` when edges cannot be reused and the path will be filled as is.
+    pub(crate) edges: Option<Vec<Edge>>,
+}
+
+impl PreparedPath {
+    /// Prepares a path for filling.
+    ///
+    /// The `path` must be in device coordinates.
+    /// Only anti-aliasing and curve tolerance settings of the `paint` are used.
+    ///
+    /// Edges are reused only for paths with non-negative coordinates smaller than 8192
+    /// and curves monotonic in Y, filled using `AntiAliasMode::None`
+    /// or `AntiAliasMode::Analytic`.
+    /// Other paths are still supported, but will be fully rasterized on each draw.
+    pub fn new(path: &Path, fill_rule: FillRule, paint: &Paint) -> Self {
+        let anti_alias_mode = prepared_anti_alias_mode(paint);
+
+        // Our fixed-point math is limited and truncation of negative coordinates
+        // cannot be translated, therefore edges are built only for "safe" paths.
+        const MAX_COORD: f32 = 8191.0;
+        let b = path.bounds();
+        let in_range =
+            b.left() >= 0.0 && b.top() >= 0.0 && b.right() <= MAX_COORD && b.bottom() <= MAX_COORD;
+        // Chopped curves depend on the path position.
+        let in_range = in_range && !has_chopped_curves(path, 0.0, 0.0);
+
+        let shift = match anti_alias_mode {
+            AntiAliasMode::None => Some(0),
+            AntiAliasMode::Analytic => Some(scan::path_aa::SHIFT as i32),
+            AntiAliasMode::Supersample { .. } => None,
+        };
+
+        let edges = match shift {
+            Some(shift) if in_range => Some(
+                scan::path::build_sorted_edges(path, shift, paint.curve_tolerance)
+                    .unwrap_or_default(),
+            ),
+            _ => None,
+        };
+
+        PreparedPath {
+            path: path.clone(),
+            fill_rule,
+            anti_alias_mode,
+            curve_tolerance: paint.curve_tolerance,
+            edges,
+        }
+    }
+
+    /// Returns the original path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the fill rule.
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    /// Checks that edges moved by an integer offset are the same as edges
+    /// of the path translated by this offset.
+    ///
+    /// Translation in floats can round coordinates, which would affect
+    /// conversion into fixed-point and curves chopping.
+    pub(crate) fn is_movable_by(&self, x: i32, y: i32) -> bool {
+        let shift = match self.anti_alias_mode {
+            AntiAliasMode::None => 0,
+            AntiAliasMode::Analytic => scan::path_aa::SHIFT as i32,
+            AntiAliasMode::Supersample { .. } => return false,
+        };
+
+        // Must match `LineEdge::new` and friends.
+        let scale = (1 << (shift + 6)) as f32;
+        let to_fixed = |n: f32| i64::from((n * scale) as i32);
+        let (dx, dy) = (x as f32, y as f32);
+        let (fixed_dx, fixed_dy) = (i64::from(x) << (shift + 6), i64::from(y) << (shift + 6));
+        let is_exact = self.path.points().iter().all(|p| {
+            to_fixed(p.x + dx) == to_fixed(p.x) + fixed_dx
+                && to_fixed(p.y + dy) == to_fixed(p.y) + fixed_dy
+        });
+
+        is_exact && !has_chopped_curves(&self.path, dx, dy)
+    }
+
+    /// Checks that the paint has the same rasterization settings as the prepared path.
+    pub(crate) fn is_compatible(&self, paint: &Paint) -> bool {
+        prepared_anti_alias_mode(paint) == self.anti_alias_mode
+            && paint.curve_tolerance == self.curve_tolerance
+    }
+}
+
+// Checks that the edge builder will chop any curve of a translated path at Y extrema.
+fn has_chopped_curves(path: &Path, dx: f32, dy: f32) -> bool {
+    // Must match `Transform::map_points`.
+    let moved = |p: Point| Point::from_xy(p.x + dx, p.y + dy);
+
+    let mut last = Point::zero();
+    let mut last_move_to = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => {
+                last = p;
+                last_move_to = p;
+            }
+            PathSegment::LineTo(p) => last = p,
+            PathSegment::QuadTo(p1, p2) => {
+                let points = [moved(last), moved(p1), moved(p2)];
+                let mut dst = [Point::zero(); 5];
+                if path_geometry::chop_quad_at_y_extrema(&points, &mut dst) != 0 {
+                    return true;
+                }
+
+                last = p2;
+            }
+            PathSegment::CubicTo(p1, p2, p3) => {
+                let points = [moved(last), moved(p1), moved(p2), moved(p3)];
+                let mut dst = [Point::zero(); 10];
+                if path_geometry::chop_cubic_at_y_extrema(&points, &mut dst) != 0 {
+                    return true;
+                }
+
+                last = p3;
+            }
+            PathSegment::Close => last = last_move_to,
+        }
+    }
+
+    false
+}
+
+fn prepared_anti_alias_mode(paint: &Paint) -> AntiAliasMode {
+    if paint.anti_alias {
+        paint.anti_alias_mode
+    } else {
+        AntiAliasMode::None
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use tiny_skia_path::Scalar;
+
+use crate::{FillRule, IntRect, LengthU32, Path, Transform};
+
+use crate::alpha_runs::AlphaRun;
+use crate::blitter::Blitter;
+use crate::color::{AlphaU8, ALPHA_U8_OPAQUE, ALPHA_U8_TRANSPARENT};
+use crate::edge::DEFAULT_CURVE_TOLERANCE;
+use crate::geom::{IntSizeExt, ScreenIntRect};
+use crate::scan;
+
+/// A coverage callback.
+///
+/// Arguments are: `y`, `x`, `len` and a `coverage` slice of `len` values.
+pub type CoverageFn<'a> = dyn FnMut(i32, i32, u32, &[u8]) + 'a;
+
+/// Rasterizes a path without a pixmap.
+///
+/// Instead of compositing pixels, calls `callback` for each horizontal run of covered pixels.
+/// Runs are emitted in scanline order. When `anti_alias` is set, edge pixels will have
+/// a fractional coverage.
+///
+/// The `path` must be in device coordinates,
+/// and only the area inside `clip_bounds` will be rasterized.
+///
+/// `clip_bounds` cannot be larger than 8191x8191, since our fixed-point math is limited.
+pub fn rasterize_path(
+    path: &Path,
+    fill_rule: FillRule,
+    anti_alias: bool,
+    clip_bounds: IntRect,
+    callback: &mut CoverageFn,
+) {
+    const MAX_DIMENSIONS: u32 = 8192 - 1;
+
+    if clip_bounds.width() > MAX_DIMENSIONS || clip_bounds.height() > MAX_DIMENSIONS {
+        log::warn!("clip bounds are too big");
+        return;
+    }
+
+    let path_bounds = path.bounds();
+    if path_bounds.width().is_nearly_zero() || path_bounds.height().is_nearly_zero() {
+        log::warn!("empty paths and horizontal/vertical lines cannot be filled");
+        return;
+    }
+
+    if crate::painter::is_too_big_for_math(path) {
+        log::warn!("path coordinates are too big");
+        return;
+    }
+
+    // Scan converters operate only on non-negative coordinates,
+    // therefore we have to move the clip to the origin.
+    let shifted_path;
+    let path = if clip_bounds.x() != 0 || clip_bounds.y() != 0 {
+        let ts = Transform::from_translate(-clip_bounds.x() as f32, -clip_bounds.y() as f32);
+        shifted_path = match path.clone().transform(ts) {
+            Some(v) => v,
+            None => {
+                log::warn!("path transformation failed");
+                return;
+            }
+        };
+        &shifted_path
+    } else {
+        path
+    };
+
+    let clip = clip_bounds.size().to_screen_int_rect(0, 0);
+    let mut blitter = CoverageBlitter {
+        offset_x: clip_bounds.x(),
+        offset_y: clip_bounds.y(),
+        row: vec![0; clip.width() as usize + 1],
+        callback,
+    };
+
+    if anti_alias {
+        scan::path_aa::fill_path(
+            path,
+            fill_rule,
+            DEFAULT_CURVE_TOLERANCE,
+            &clip,
+            &mut blitter,
+        );
+    } else {
+        scan::path::fill_path(
+            path,
+            fill_rule,
+            DEFAULT_CURVE_TOLERANCE,
+            &clip,
+            &mut blitter,
+        );
+    }
+}
+
+struct CoverageBlitter<'a, 'b> {
+    offset_x: i32,
+    offset_y: i32,
+    row: Vec<u8>,
+    callback: &'a mut CoverageFn<'b>,
+}
+
+impl CoverageBlitter<'_, '_> {
+    fn emit(&mut self, x: u32, y: u32, coverage: &[u8]) {
+        (self.callback)(
+            y as i32 + self.offset_y,
+            x as i32 + self.offset_x,
+            coverage.len() as u32,
+            coverage,
+        );
+    }
+
+    fn emit_solid(&mut self, x: u32, y: u32, width: u32, alpha: AlphaU8) {
+        let mut row = core::mem::take(&mut self.row);
+        if row.len() < width as usize {
+            row.resize(width as usize, 0);
+        }
+
+        row[..width as usize].iter_mut().for_each(|a| *a = alpha);
+        self.emit(x, y, &row[..width as usize]);
+        self.row = row;
+    }
+}
+
+impl Blitter for CoverageBlitter<'_, '_> {
+    fn blit_h(&mut self, x: u32, y: u32, width: LengthU32) {
+        self.emit_solid(x, y, width.get(), ALPHA_U8_OPAQUE);
+    }
+
+    fn blit_anti_h(&mut self, mut x: u32, y: u32, aa: &mut [AlphaU8], runs: &mut [AlphaRun]) {
+        let mut row = core::mem::take(&mut self.row);
+
+        // Merge neighbour runs with a non-zero coverage into a single span.
+        let mut span_x = x;
+        let mut span_len = 0;
+        let mut offset = 0;
+        while let Some(run) = runs[offset] {
+            let width = usize::from(run.get());
+            let alpha = aa[offset];
+            if alpha == ALPHA_U8_TRANSPARENT {
+                if span_len != 0 {
+                    self.emit(span_x, y, &row[..span_len]);
+                    span_len = 0;
+                }
+
+                span_x = x + width as u32;
+            } else {
+                if row.len() < span_len + width {
+                    row.resize(span_len + width, 0);
+                }
+
+                row[span_len..span_len + width]
+                    .iter_mut()
+                    .for_each(|a| *a = alpha);
+                span_len += width;
+            }
+
+            x += width as u32;
+            offset += width;
+        }
+
+        if span_len != 0 {
+            self.emit(span_x, y, &row[..span_len]);
+        }
+
+        self.row = row;
+    }
+
+    fn blit_v(&mut self, x: u32, y: u32, height: LengthU32, alpha: AlphaU8) {
+        for i in 0..height.get() {
+            self.emit_solid(x, y + i, 1, alpha);
+        }
+    }
+
+    fn blit_anti_h2(&mut self, x: u32, y: u32, alpha0: AlphaU8, alpha1: AlphaU8) {
+        self.emit(x, y, &[alpha0, alpha1]);
+    }
+
+    fn blit_anti_v2(&mut self, x: u32, y: u32, alpha0: AlphaU8, alpha1: AlphaU8) {
+        self.emit(x, y, &[alpha0]);
+        self.emit(x, y + 1, &[alpha1]);
+    }
+
+    fn blit_rect(&mut self, rect: &ScreenIntRect) {
+        for y in rect.top()..rect.bottom() {
+            self.emit_solid(rect.x(), y, rect.width(), ALPHA_U8_OPAQUE);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::IntRect;
+
+/// A set of integer rectangles.
+///
+/// Similar to `SkRegion`, a region is stored as a list of horizontal bands,
+/// each with a list of spans. The representation is always canonical:
+/// bands and spans are sorted and do not overlap, touching spans are merged
+/// and touching bands with the same spans are merged as well.
+/// Therefore, two regions covering the same area are always equal.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Region {
+    bands: Vec<Band>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Band {
+    top: i32,
+    bottom: i32,
+    // A list of `(left, right)` pairs.
+    spans: Vec<(i32, i32)>,
+}
+
+impl Region {
+    /// Creates a new, empty region.
+    pub fn new() -> Self {
+        Region::default()
+    }
+
+    /// Creates a new region from a rectangle.
+    pub fn from_rect(rect: IntRect) -> Self {
+        Region {
+            bands: vec![Band {
+                top: rect.y(),
+                bottom: rect.bottom(),
+                spans: vec![(rect.x(), rect.right())],
+            }],
+        }
+    }
+
+    /// Checks that the region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    /// Checks that the region is a single rectangle.
+    pub fn is_rect(&self) -> bool {
+        self.bands.len() == 1 && self.bands[0].spans.len() == 1
+    }
+
+    /// Returns region's bounds.
+    ///
+    /// Returns `None` when the region is empty.
+    pub fn bounds(&self) -> Option<IntRect> {
+        let first = self.bands.first()?;
+        let last = self.bands.last()?;
+
+        let mut left = i32::MAX;
+        let mut right = i32::MIN;
+        for band in &self.bands {
+            // Spans are never empty.
+            left = left.min(band.spans[0].0);
+            right = right.max(band.spans[band.spans.len() - 1].1);
+        }
+
+        IntRect::from_ltrb(left, first.top, right, last.bottom)
+    }
+
+    /// Returns an iterator over region's rectangles.
+    ///
+    /// Rectangles are non-overlapping and are returned in the top-to-bottom,
+    /// left-to-right order. Rectangles from the same band have the same top and bottom.
+    pub fn rects(&self) -> RegionRectsIter<'_> {
+        RegionRectsIter {
+            bands: &self.bands,
+            band_index: 0,
+            span_index: 0,
+        }
+    }
+
+    /// Checks that the region contains a point.
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        let idx = self.bands.partition_point(|band| band.bottom <= y);
+        let band = match self.bands.get(idx) {
+            Some(v) if v.top <= y => v,
+            _ => return false,
+        };
+
+        let idx = band.spans.partition_point(|span| span.1 <= x);
+        match band.spans.get(idx) {
+            Some(span) => span.0 <= x,
+            None => false,
+        }
+    }
+
+    /// Adds a rectangle to the region.
+    pub fn add_rect(&mut self, rect: IntRect) {
+        self.union(&Region::from_rect(rect));
+    }
+
+    /// Removes a rectangle from the region.
+    pub fn subtract_rect(&mut self, rect: IntRect) {
+        self.subtract(&Region::from_rect(rect));
+    }
+
+    /// Intersects the region with a rectangle.
+    pub fn intersect_rect(&mut self, rect: IntRect) {
+        self.intersect(&Region::from_rect(rect));
+    }
+
+    /// Adds another region to the region.
+    pub fn union(&mut self, other: &Region) {
+        self.bands = combine(&self.bands, &other.bands, |a, b| a || b);
+    }
+
+    /// Removes another region from the region.
+    pub fn subtract(&mut self, other: &Region) {
+        self.bands = combine(&self.bands, &other.bands, |a, b| a && !b);
+    }
+
+    /// Intersects the region with another region.
+    pub fn intersect(&mut self, other: &Region) {
+        self.bands = combine(&self.bands, &other.bands, |a, b| a && b);
+    }
+}
+
+impl From<IntRect> for Region {
+    fn from(rect: IntRect) -> Self {
+        Region::from_rect(rect)
+    }
+}
+
+/// An iterator over region's rectangles.
+///
+/// Created by [`Region::rects`].
+#[derive(Clone, Debug)]
+pub struct RegionRectsIter<'a> {
+    bands: &'a [Band],
+    band_index: usize,
+    span_index: usize,
+}
+
+impl Iterator for RegionRectsIter<'_> {
+    type Item = IntRect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let band = self.bands.get(self.band_index)?;
+        let (left, right) = band.spans[self.span_index];
+
+        self.span_index += 1;
+        if self.span_index == band.spans.len() {
+            self.span_index = 0;
+            self.band_index += 1;
+        }
+
+        // Cannot fail, since spans were created from valid rects.
+        IntRect::from_ltrb(left, band.top, right, band.bottom)
+    }
+}
+
+// Combines two band lists using a boolean operation.
+//
+// Both lists are split at all band edges, therefore each resulting band
+// is either fully inside or fully outside of any source band.
+fn combine(a: &[Band], b: &[Band], op: fn(bool, bool) -> bool) -> Vec<Band> {
+    let mut ys: Vec<i32> = a
+        .iter()
+        .chain(b)
+        .flat_map(|band| [band.top, band.bottom])
+        .collect();
+    ys.sort_unstable();
+    ys.dedup();
+
+    let mut bands: Vec<Band> = Vec::new();
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+    for y in ys.windows(2) {
+        let (top, bottom) = (y[0], y[1]);
+        let spans_a = band_spans_at(a, &mut idx_a, top);
+        let spans_b = band_spans_at(b, &mut idx_b, top);
+        let spans = combine_spans(spans_a, spans_b, op);
+        if spans.is_empty() {
+            continue;
+        }
+
+        // Merge with the previous band to keep the representation canonical.
+        if let Some(last) = bands.last_mut() {
+            if last.bottom == top && last.spans == spans {
+                last.bottom = bottom;
+                continue;
+            }
+        }
+
+        bands.push(Band { top, bottom, spans });
+    }
+
+    bands
+}
+
+// Returns spans of a band containing `y`.
+//
+// `y` must not decrease between calls.
+fn band_spans_at<'a>(bands: &'a [Band], idx: &mut usize, y: i32) -> &'a [(i32, i32)] {
+    while *idx < bands.len() && bands[*idx].bottom <= y {
+        *idx += 1;
+    }
+
+    match bands.get(*idx) {
+        Some(band) if band.top <= y => &band.spans,
+        _ => &[],
+    }
+}
+
+fn combine_spans(
+    a: &[(i32, i32)],
+    b: &[(i32, i32)],
+    op: fn(bool, bool) -> bool,
+) -> Vec<(i32, i32)> {
+    let mut xs: Vec<i32> = a
+        .iter()
+        .chain(b)
+        .flat_map(|span| [span.0, span.1])
+        .collect();
+    xs.sort_unstable();
+    xs.dedup();
+
+    let mut spans: Vec<(i32, i32)> = Vec::new();
+    let mut idx_a = 0;
+    let mut idx_b = 0;
+    for x in xs.windows(2) {
+        let (left, right) = (x[0], x[1]);
+        let in_a = span_contains(a, &mut idx_a, left);
+        let in_b = span_contains(b, &mut idx_b, left);
+        if !op(in_a, in_b) {
+            continue;
+        }
+
+        // Merge touching spans.
+        match spans.last_mut() {
+            Some(last) if last.1 == left => last.1 = right,
+            _ => spans.push((left, right)),
+        }
+    }
+
+    spans
+}
+
+// Checks that `x` is inside one of the spans.
+//
+// `x` must not decrease between calls.
+fn span_contains(spans: &[(i32, i32)], idx: &mut usize, x: i32) -> bool {
+    while *idx < spans.len() && spans[*idx].1 <= x {
+        *idx += 1;
+    }
+
+    match spans.get(*idx) {
+        Some(span) => span.0 <= x,
+        None => false,
+    }
+}
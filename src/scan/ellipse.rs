@@ -0,0 +1,178 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroU16;
+
+use crate::Rect;
+
+use crate::alpha_runs::AlphaRun;
+use crate::blitter::Blitter;
+use crate::color::{AlphaU8, ALPHA_U8_OPAQUE};
+use crate::geom::ScreenIntRect;
+
+#[cfg(all(not(feature = "std"), feature = "no-std-float"))]
+use tiny_skia_path::NoStdFloat;
+
+/// Fills an axis-aligned ellipse inscribed into `rect`.
+///
+/// Unlike path filling, coverage is computed analytically, using an approximate
+/// distance from a pixel center to the ellipse edge.
+/// Without anti-aliasing, only pixels with centers inside the ellipse are filled.
+///
+/// The clip width must fit into `u16`, since it's used for runs.
+pub fn fill_ellipse(
+    rect: &Rect,
+    anti_alias: bool,
+    clip: &ScreenIntRect,
+    blitter: &mut dyn Blitter,
+) {
+    debug_assert!(clip.width() <= u32::from(u16::MAX));
+
+    let rx = rect.width() * 0.5;
+    let ry = rect.height() * 0.5;
+    let cx = rect.left() + rx;
+    let cy = rect.top() + ry;
+
+    // Anti-aliasing extends edges by half a pixel.
+    let outset = if anti_alias { 0.5 } else { 0.0 };
+    let left = (rect.left() - outset).floor().max(clip.left() as f32) as u32;
+    let top = (rect.top() - outset).floor().max(clip.top() as f32) as u32;
+    let right = (rect.right() + outset).ceil().min(clip.right() as f32) as u32;
+    let bottom = (rect.bottom() + outset).ceil().min(clip.bottom() as f32) as u32;
+    if left >= right || top >= bottom {
+        return; // clipped out, nothing to do
+    }
+
+    let ellipse = Ellipse {
+        cx,
+        rx,
+        ry,
+        anti_alias,
+    };
+
+    let width = (right - left) as usize;
+    let mut aa: Vec<AlphaU8> = vec![0; width + 1];
+    let mut runs: Vec<AlphaRun> = vec![None; width + 1];
+    for y in top..bottom {
+        let dy = y as f32 + 0.5 - cy;
+        let row = &mut aa[..width];
+
+        // An ellipse is convex, so everything between
+        // the first and the last opaque pixels is opaque as well.
+        let mut start = 0;
+        while start < width {
+            row[start] = ellipse.coverage(left as usize + start, dy);
+            if row[start] == ALPHA_U8_OPAQUE {
+                break;
+            }
+
+            start += 1;
+        }
+
+        if start < width {
+            let mut end = width - 1;
+            while end > start {
+                row[end] = ellipse.coverage(left as usize + end, dy);
+                if row[end] == ALPHA_U8_OPAQUE {
+                    break;
+                }
+
+                end -= 1;
+            }
+
+            row[start..end].fill(ALPHA_U8_OPAQUE);
+        }
+
+        let first = match row.iter().position(|a| *a != 0) {
+            Some(v) => v,
+            None => continue,
+        };
+        // Cannot fail, since `first` exists.
+        let last = row.iter().rposition(|a| *a != 0).unwrap_or(first);
+
+        // Merge pixels with the same coverage into runs.
+        let mut i = first;
+        while i <= last {
+            let mut j = i + 1;
+            while j <= last && aa[j] == aa[i] {
+                j += 1;
+            }
+
+            runs[i] = NonZeroU16::new((j - i) as u16);
+            i = j;
+        }
+        runs[last + 1] = None;
+
+        blitter.blit_anti_h(
+            left + first as u32,
+            y,
+            &mut aa[first..=last + 1],
+            &mut runs[first..=last + 1],
+        );
+    }
+}
+
+struct Ellipse {
+    cx: f32,
+    rx: f32,
+    ry: f32,
+    anti_alias: bool,
+}
+
+impl Ellipse {
+    fn coverage(&self, x: usize, dy: f32) -> AlphaU8 {
+        let nx = (x as f32 + 0.5 - self.cx) / self.rx;
+        let ny = dy / self.ry;
+        let f = nx * nx + ny * ny - 1.0;
+        if !self.anti_alias {
+            return if f < 0.0 { ALPHA_U8_OPAQUE } else { 0 };
+        }
+
+        // The implicit function divided by its gradient length gives
+        // a distance to the edge, which is precise enough near the edge.
+        let gx = (nx / self.rx).abs();
+        let gy = (ny / self.ry).abs();
+        let g = (gx * gx + gy * gy).sqrt();
+        if g == 0.0 {
+            // The center.
+            return ALPHA_U8_OPAQUE;
+        }
+
+        let distance = -f / (2.0 * g);
+        let (a, b) = if gx > gy {
+            (gx / g, gy / g)
+        } else {
+            (gy / g, gx / g)
+        };
+        let coverage = box_coverage(distance, a, b);
+        (coverage * 255.0 + 0.5) as AlphaU8
+    }
+}
+
+// Returns an area of a pixel covered by a straight edge at the specified distance
+// from the pixel center. Positive distances are inside.
+//
+// `a` and `b` are components of the edge normal, where `a >= b >= 0`.
+fn box_coverage(d: f32, a: f32, b: f32) -> f32 {
+    // Half of the pixel projection onto the normal
+    // and half of its part with a constant density.
+    let w = (a + b) * 0.5;
+    let e = (a - b) * 0.5;
+    if d >= w {
+        1.0
+    } else if d <= -w {
+        0.0
+    } else if d < -e {
+        (d + w) * (d + w) / (2.0 * a * b)
+    } else if d <= e {
+        (b * 0.5 + d + e) / a
+    } else {
+        1.0 - (w - d) * (w - d) / (2.0 * a * b)
+    }
+}
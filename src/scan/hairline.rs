@@ -421,9 +421,9 @@ fn hair_quad(
 }
 
 fn compute_nocheck_quad_bounds(points: &[Point; 3]) -> Option<Rect> {
-    debug_assert!(points[0].is_finite());
-    debug_assert!(points[1].is_finite());
-    debug_assert!(points[2].is_finite());
+    hot_debug_assert!(points[0].is_finite());
+    hot_debug_assert!(points[1].is_finite());
+    hot_debug_assert!(points[2].is_finite());
 
     let mut min = points[0].to_f32x2();
     let mut max = min;
@@ -555,10 +555,10 @@ fn hair_cubic(
 }
 
 fn compute_nocheck_cubic_bounds(points: &[Point; 4]) -> Option<Rect> {
-    debug_assert!(points[0].is_finite());
-    debug_assert!(points[1].is_finite());
-    debug_assert!(points[2].is_finite());
-    debug_assert!(points[3].is_finite());
+    hot_debug_assert!(points[0].is_finite());
+    hot_debug_assert!(points[1].is_finite());
+    hot_debug_assert!(points[2].is_finite());
+    hot_debug_assert!(points[3].is_finite());
 
     let mut min = points[0].to_f32x2();
     let mut max = min;
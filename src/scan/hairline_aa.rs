@@ -40,7 +40,7 @@ impl FixedRect {
 /// (i.e. return (value * alpha256) >> 8)
 fn alpha_mul(value: AlphaU8, alpha256: i32) -> u8 {
     let a = (i32::from(value) * alpha256) >> 8;
-    debug_assert!(a >= 0 && a <= 255);
+    hot_debug_assert!(a >= 0 && a <= 255);
     a as u8
 }
 
@@ -67,7 +67,7 @@ fn fill_fixed_rect(rect: &FixedRect, blitter: &mut dyn Blitter) {
 
 fn fill_dot8(l: FDot8, t: FDot8, r: FDot8, b: FDot8, fill_inner: bool, blitter: &mut dyn Blitter) {
     fn to_alpha(a: i32) -> u8 {
-        debug_assert!(a >= 0 && a <= 255);
+        hot_debug_assert!(a >= 0 && a <= 255);
         a as u8
     }
 
@@ -380,7 +380,7 @@ fn do_anti_hairline(
             blitter_kind = Some(BlitterKind::HLine);
         } else {
             slope = fdot16::fast_div(y1 - y0, x1 - x0);
-            debug_assert!(slope >= -fdot16::ONE && slope <= fdot16::ONE);
+            hot_debug_assert!(slope >= -fdot16::ONE && slope <= fdot16::ONE);
             fstart += (slope * (32 - (x0 & 63)) + 32) >> 6;
             blitter_kind = Some(BlitterKind::Horish);
         }
@@ -389,7 +389,7 @@ fn do_anti_hairline(
         if istop - istart == 1 {
             // we are within a single pixel
             scale_start = x1 - x0;
-            debug_assert!(scale_start >= 0 && scale_start <= 64);
+            hot_debug_assert!(scale_start >= 0 && scale_start <= 64);
             scale_stop = 0;
         } else {
             scale_start = 64 - (x0 & 63);
@@ -472,7 +472,7 @@ fn do_anti_hairline(
             blitter_kind = Some(BlitterKind::VLine);
         } else {
             slope = fdot16::fast_div(x1 - x0, y1 - y0);
-            debug_assert!(slope <= fdot16::ONE && slope >= -fdot16::ONE);
+            hot_debug_assert!(slope <= fdot16::ONE && slope >= -fdot16::ONE);
             fstart += (slope * (32 - (y0 & 63)) + 32) >> 6;
             blitter_kind = Some(BlitterKind::Vertish);
         }
@@ -481,7 +481,7 @@ fn do_anti_hairline(
         if istop - istart == 1 {
             // we are within a single pixel
             scale_start = y1 - y0;
-            debug_assert!(scale_start >= 0 && scale_start <= 64);
+            hot_debug_assert!(scale_start >= 0 && scale_start <= 64);
             scale_stop = 0;
         } else {
             scale_start = 64 - (y0 & 63);
@@ -613,7 +613,7 @@ fn any_bad_ints(a: i32, b: i32, c: i32, d: i32) -> i32 {
 // We can do that w/o a branch with an extra sub and add.
 fn contribution_64(ordinate: FDot6) -> i32 {
     let result = ((ordinate - 1) & 63) + 1;
-    debug_assert!(result > 0 && result <= 64);
+    hot_debug_assert!(result > 0 && result <= 64);
     result
 }
 
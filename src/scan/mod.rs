@@ -4,10 +4,12 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+pub mod ellipse;
 pub mod hairline;
 pub mod hairline_aa;
 pub mod path;
 pub mod path_aa;
+pub mod path_ss;
 
 use crate::{IntRect, Rect};
 
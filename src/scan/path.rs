@@ -4,6 +4,7 @@
 // Use of this source code is governed by a BSD-style license that can be
 // found in the LICENSE file.
 
+use alloc::vec::Vec;
 use core::convert::TryFrom;
 
 use tiny_skia_path::SaturateCast;
@@ -22,6 +23,7 @@ use tiny_skia_path::NoStdFloat;
 pub fn fill_path(
     path: &Path,
     fill_rule: FillRule,
+    curve_tolerance: f32,
     clip: &ScreenIntRect,
     blitter: &mut dyn Blitter,
 ) {
@@ -30,19 +32,14 @@ pub fn fill_path(
         None => return,
     };
 
-    let path_contained_in_clip = if let Some(bounds) = ir.to_screen_int_rect() {
-        clip.contains(&bounds)
-    } else {
-        // If bounds cannot be converted into ScreenIntRect,
-        // the path is out of clip.
-        false
-    };
+    let path_contained_in_clip = is_contained_in_clip(&ir, clip);
 
     // TODO: SkScanClipper
 
     fill_path_impl(
         path,
         fill_rule,
+        curve_tolerance,
         clip,
         ir.y(),
         ir.bottom(),
@@ -100,9 +97,10 @@ fn round_up_to_int(x: f32) -> i32 {
 pub fn fill_path_impl(
     path: &Path,
     fill_rule: FillRule,
+    curve_tolerance: f32,
     clip_rect: &ScreenIntRect,
-    mut start_y: i32,
-    mut stop_y: i32,
+    start_y: i32,
+    stop_y: i32,
     shift_edges_up: i32,
     path_contained_in_clip: bool,
     blitter: &mut dyn Blitter,
@@ -117,11 +115,36 @@ pub fn fill_path_impl(
     } else {
         Some(&shifted_clip)
     };
-    let mut edges = match BasicEdgeBuilder::build_edges(path, clip, shift_edges_up) {
+    let edges = BasicEdgeBuilder::build_edges(path, clip, shift_edges_up, curve_tolerance);
+    let mut edges = match edges {
         Some(v) => v,
         None => return, // no edges to render, just return
     };
 
+    sort_edges(&mut edges);
+
+    fill_sorted_edges(
+        edges,
+        fill_rule,
+        &shifted_clip,
+        start_y,
+        stop_y,
+        shift_edges_up,
+        path_contained_in_clip,
+        blitter,
+    );
+}
+
+/// Builds edges for a path that will be rendered without clipping.
+///
+/// The result can be moved via `Edge::translate` and rendered via `fill_prepared_path`.
+pub fn build_sorted_edges(path: &Path, shift: i32, curve_tolerance: f32) -> Option<Vec<Edge>> {
+    let mut edges = BasicEdgeBuilder::build_edges(path, None, shift, curve_tolerance)?;
+    sort_edges(&mut edges);
+    Some(edges)
+}
+
+fn sort_edges(edges: &mut [Edge]) {
     edges.sort_by(|a, b| {
         let mut value_a = a.as_line().first_y;
         let mut value_b = b.as_line().first_y;
@@ -133,7 +156,80 @@ pub fn fill_path_impl(
 
         value_a.cmp(&value_b)
     });
+}
 
+/// Fills edges built by `build_sorted_edges` moved by an integer offset.
+///
+/// `bounds` are the bounds of the moved path.
+///
+/// Returns `false` when the moved path is not contained in the clip,
+/// in which case it must be filled via `fill_path` instead.
+pub fn fill_prepared_path(
+    edges: &[Edge],
+    dx: i32,
+    dy: i32,
+    bounds: &Rect,
+    fill_rule: FillRule,
+    clip: &ScreenIntRect,
+    blitter: &mut dyn Blitter,
+) -> bool {
+    let ir = match conservative_round_to_int(bounds) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    if !is_contained_in_clip(&ir, clip) {
+        return false;
+    }
+
+    let shifted_clip = match ShiftedIntRect::new(clip, 0) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    fill_sorted_edges(
+        translate_edges(edges, dx, dy),
+        fill_rule,
+        &shifted_clip,
+        ir.y(),
+        ir.bottom(),
+        0,
+        true,
+        blitter,
+    );
+
+    true
+}
+
+pub fn translate_edges(edges: &[Edge], dx: i32, dy: i32) -> Vec<Edge> {
+    let mut edges = edges.to_vec();
+    for edge in &mut edges {
+        edge.translate(dx, dy);
+    }
+
+    edges
+}
+
+pub fn is_contained_in_clip(bounds: &IntRect, clip: &ScreenIntRect) -> bool {
+    if let Some(bounds) = bounds.to_screen_int_rect() {
+        clip.contains(&bounds)
+    } else {
+        // If bounds cannot be converted into ScreenIntRect,
+        // the path is out of clip.
+        false
+    }
+}
+
+pub fn fill_sorted_edges(
+    mut edges: Vec<Edge>,
+    fill_rule: FillRule,
+    shifted_clip: &ShiftedIntRect,
+    mut start_y: i32,
+    mut stop_y: i32,
+    shift_edges_up: i32,
+    path_contained_in_clip: bool,
+    blitter: &mut dyn Blitter,
+) {
     for i in 0..edges.len() {
         // 0 will be set later, so start with 1.
         edges[i].prev = Some(i as u32 + 0);
@@ -11,6 +11,8 @@ use crate::{FillRule, IntRect, LengthU32, Path, Rect};
 use crate::alpha_runs::AlphaRuns;
 use crate::blitter::Blitter;
 use crate::color::AlphaU8;
+use crate::edge::Edge;
+use crate::edge_builder::ShiftedIntRect;
 use crate::geom::{IntRectExt, ScreenIntRect};
 use crate::math::left_shift;
 
@@ -20,26 +22,18 @@ use tiny_skia_path::NoStdFloat;
 /// controls how much we super-sample (when we use that scan conversion)
 const SUPERSAMPLE_SHIFT: u32 = 2;
 
-const SHIFT: u32 = SUPERSAMPLE_SHIFT;
+pub const SHIFT: u32 = SUPERSAMPLE_SHIFT;
 const SCALE: u32 = 1 << SHIFT;
 const MASK: u32 = SCALE - 1;
 
 pub fn fill_path(
     path: &Path,
     fill_rule: FillRule,
+    curve_tolerance: f32,
     clip: &ScreenIntRect,
     blitter: &mut dyn Blitter,
 ) {
-    // Unlike `path.bounds.to_rect()?.round_out()`,
-    // this method rounds out first and then converts into a Rect.
-    let ir = Rect::from_ltrb(
-        path.bounds().left().floor(),
-        path.bounds().top().floor(),
-        path.bounds().right().ceil(),
-        path.bounds().bottom().ceil(),
-    )
-    .and_then(|r| r.round_out());
-    let ir = match ir {
+    let ir = match round_out_bounds(&path.bounds()) {
         Some(v) => v,
         None => return,
     };
@@ -53,7 +47,7 @@ pub fn fill_path(
         None => return,
     };
     if rect_overflows_short_shift(&clipped_ir, SHIFT as i32) != 0 {
-        super::path::fill_path(path, fill_rule, clip, blitter);
+        super::path::fill_path(path, fill_rule, curve_tolerance, clip, blitter);
         return;
     }
 
@@ -70,7 +64,81 @@ pub fn fill_path(
     // TODO: SkScanClipper
     // TODO: AAA
 
-    fill_path_impl(path, fill_rule, &ir, clip, blitter)
+    fill_path_impl(path, fill_rule, curve_tolerance, &ir, clip, blitter)
+}
+
+/// Fills edges built by `path::build_sorted_edges` with `SHIFT`
+/// and moved by an integer offset in pixels.
+///
+/// `bounds` are the bounds of the moved path.
+///
+/// Returns `false` when the moved path is not contained in the clip
+/// or cannot be supersampled, in which case it must be filled via `fill_path` instead.
+pub fn fill_prepared_path(
+    edges: &[Edge],
+    dx: i32,
+    dy: i32,
+    bounds: &Rect,
+    fill_rule: FillRule,
+    clip: &ScreenIntRect,
+    blitter: &mut dyn Blitter,
+) -> bool {
+    let ir = match round_out_bounds(bounds) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    if !super::path::is_contained_in_clip(&ir, clip) {
+        return false;
+    }
+
+    // `fill_path` will fallback to a non-AA rendering in this case.
+    if rect_overflows_short_shift(&ir, SHIFT as i32) != 0 {
+        return false;
+    }
+
+    {
+        const MAX_CLIP_COORD: u32 = 32767;
+        if clip.right() > MAX_CLIP_COORD || clip.bottom() > MAX_CLIP_COORD {
+            return true;
+        }
+    }
+
+    let mut blitter = match SuperBlitter::new(&ir, clip, blitter) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    let shifted_clip = match ShiftedIntRect::new(clip, SHIFT as i32) {
+        Some(v) => v,
+        None => return true,
+    };
+
+    let shift = SHIFT as i32;
+    super::path::fill_sorted_edges(
+        super::path::translate_edges(edges, left_shift(dx, shift), left_shift(dy, shift)),
+        fill_rule,
+        &shifted_clip,
+        ir.top(),
+        ir.bottom(),
+        shift,
+        true,
+        &mut blitter,
+    );
+
+    true
+}
+
+// Unlike `path.bounds.to_rect()?.round_out()`,
+// this method rounds out first and then converts into a Rect.
+fn round_out_bounds(bounds: &Rect) -> Option<IntRect> {
+    Rect::from_ltrb(
+        bounds.left().floor(),
+        bounds.top().floor(),
+        bounds.right().ceil(),
+        bounds.bottom().ceil(),
+    )
+    .and_then(|r| r.round_out())
 }
 
 // Would any of the coordinates of this rectangle not fit in a short,
@@ -97,6 +165,7 @@ fn overflows_short_shift(value: i32, shift: i32) -> i32 {
 fn fill_path_impl(
     path: &Path,
     fill_rule: FillRule,
+    curve_tolerance: f32,
     bounds: &IntRect,
     clip: &ScreenIntRect,
     blitter: &mut dyn Blitter,
@@ -109,17 +178,12 @@ fn fill_path_impl(
         None => return, // clipped out, nothing else to do
     };
 
-    let path_contained_in_clip = if let Some(bounds) = bounds.to_screen_int_rect() {
-        clip.contains(&bounds)
-    } else {
-        // If bounds cannot be converted into ScreenIntRect,
-        // the path is out of clip.
-        false
-    };
+    let path_contained_in_clip = super::path::is_contained_in_clip(bounds, clip);
 
     super::path::fill_path_impl(
         path,
         fill_rule,
+        curve_tolerance,
         clip,
         bounds.top(),
         bounds.bottom(),
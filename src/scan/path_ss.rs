@@ -0,0 +1,140 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::num::NonZeroU16;
+
+use crate::{FillRule, LengthU32, Path, Transform};
+
+use crate::alpha_runs::AlphaRun;
+use crate::blitter::Blitter;
+use crate::color::AlphaU8;
+use crate::geom::{IntRectExt, ScreenIntRect};
+
+/// The maximum number of samples per pixel side.
+///
+/// 16x16 samples is already an overkill and the sample count still fits into `u16`.
+pub const MAX_FACTOR: u8 = 16;
+
+// Our fixed-point math is limited by 8192.
+const MAX_DIMENSIONS: u32 = 8192 - 1;
+
+/// Fills a path using NxN supersampling.
+///
+/// The path is rasterized without anti-aliasing at `factor` times the resolution
+/// and then box-downsampled into a per-pixel coverage.
+///
+/// Falls back to the analytic anti-aliasing when `factor` is 1
+/// or when the supersampled area is too big.
+pub fn fill_path(
+    path: &Path,
+    fill_rule: FillRule,
+    curve_tolerance: f32,
+    factor: u8,
+    clip: &ScreenIntRect,
+    blitter: &mut dyn Blitter,
+) {
+    let factor = factor.min(MAX_FACTOR);
+    if factor <= 1 {
+        super::path_aa::fill_path(path, fill_rule, curve_tolerance, clip, blitter);
+        return;
+    }
+
+    let ir = match path
+        .bounds()
+        .round_out()
+        .and_then(|r| r.intersect(&clip.to_int_rect()))
+        .and_then(|r| r.to_screen_int_rect())
+    {
+        Some(v) => v,
+        None => return, // clipped out, nothing to do
+    };
+
+    let factor = u32::from(factor);
+    let (ss_width, ss_height) = match (
+        ir.width().checked_mul(factor),
+        ir.height().checked_mul(factor),
+    ) {
+        (Some(w), Some(h)) if w <= MAX_DIMENSIONS && h <= MAX_DIMENSIONS => (w, h),
+        _ => {
+            log::warn!("path is too big for supersampling, fallback to analytic anti-aliasing");
+            super::path_aa::fill_path(path, fill_rule, curve_tolerance, clip, blitter);
+            return;
+        }
+    };
+
+    let ts = Transform::from_translate(-(ir.x() as f32), -(ir.y() as f32))
+        .post_scale(factor as f32, factor as f32);
+    let path = match path.clone().transform(ts) {
+        Some(v) => v,
+        None => {
+            log::warn!("path transformation failed");
+            return;
+        }
+    };
+
+    let mut ss_blitter = SupersampleBlitter {
+        factor,
+        width: ir.width(),
+        samples: vec![0; (ir.width() * ir.height()) as usize],
+    };
+
+    let ss_clip = match ScreenIntRect::from_xywh(0, 0, ss_width, ss_height) {
+        Some(v) => v,
+        None => return,
+    };
+    super::path::fill_path(&path, fill_rule, curve_tolerance, &ss_clip, &mut ss_blitter);
+
+    let samples_per_pixel = factor * factor;
+    let width = ir.width() as usize;
+    let mut aa: Vec<AlphaU8> = vec![0; width + 1];
+    let mut runs: Vec<AlphaRun> = vec![None; width + 1];
+    for (y, row) in ss_blitter.samples.chunks_exact(width).enumerate() {
+        if row.iter().all(|n| *n == 0) {
+            continue;
+        }
+
+        // Merge neighbour pixels with the same coverage into a single run.
+        let mut start = 0;
+        while start < width {
+            let alpha = to_alpha(row[start], samples_per_pixel);
+            let mut end = start + 1;
+            while end < width && to_alpha(row[end], samples_per_pixel) == alpha {
+                end += 1;
+            }
+
+            aa[start] = alpha;
+            runs[start] = NonZeroU16::new((end - start) as u16);
+            start = end;
+        }
+        runs[width] = None;
+
+        blitter.blit_anti_h(ir.x(), ir.y() + y as u32, &mut aa, &mut runs);
+    }
+}
+
+/// Converts a sample count into a box-averaged coverage.
+fn to_alpha(samples: u16, samples_per_pixel: u32) -> AlphaU8 {
+    ((u32::from(samples) * 255 + samples_per_pixel / 2) / samples_per_pixel) as AlphaU8
+}
+
+/// Accumulates a number of covered samples per destination pixel.
+struct SupersampleBlitter {
+    factor: u32,
+    width: u32,
+    samples: Vec<u16>,
+}
+
+impl Blitter for SupersampleBlitter {
+    fn blit_h(&mut self, x: u32, y: u32, width: LengthU32) {
+        let row = (y / self.factor * self.width) as usize;
+        for x in x..x + width.get() {
+            self.samples[row + (x / self.factor) as usize] += 1;
+        }
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::tiled::render_commands;
+use crate::{
+    Canvas, DrawCommand, FillRule, Paint, Path, Pixmap, PixmapMut, PixmapPaint, Stroke, Transform,
+};
+
+/// A recorded list of drawing commands.
+///
+/// Allows rendering the same content multiple times without re-issuing drawing calls.
+/// Rendering a scene is equivalent to issuing the recorded calls directly.
+///
+/// Scene owns all the data, therefore paints must be `'static`.
+/// Which means that a [`Pattern`](crate::Pattern) shader can only reference a `'static` pixmap.
+///
+/// Clips and transforms form a single stack, therefore pushes and pops must be properly nested.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Scene {
+    commands: Vec<DrawCommand<'static>>,
+    // Stack of pushed commands. `true` for clips and `false` for transforms.
+    stack: Vec<bool>,
+}
+
+impl Scene {
+    /// Creates a new, empty scene.
+    pub fn new() -> Self {
+        Scene::default()
+    }
+
+    /// Returns recorded commands.
+    pub fn commands(&self) -> &[DrawCommand<'static>] {
+        &self.commands
+    }
+
+    /// Checks that the scene has no commands.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes all recorded commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.stack.clear();
+    }
+
+    /// Records a path filling.
+    pub fn fill_path(
+        &mut self,
+        path: Path,
+        paint: Paint<'static>,
+        fill_rule: FillRule,
+        transform: Transform,
+    ) {
+        self.commands.push(DrawCommand::FillPath {
+            path,
+            paint,
+            fill_rule,
+            transform,
+        });
+    }
+
+    /// Records a path stroking.
+    pub fn stroke_path(
+        &mut self,
+        path: Path,
+        paint: Paint<'static>,
+        stroke: Stroke,
+        transform: Transform,
+    ) {
+        self.commands.push(DrawCommand::StrokePath {
+            path,
+            paint,
+            stroke,
+            transform,
+        });
+    }
+
+    /// Records a pixmap drawing.
+    pub fn draw_pixmap(
+        &mut self,
+        x: i32,
+        y: i32,
+        pixmap: Arc<Pixmap>,
+        paint: PixmapPaint,
+        transform: Transform,
+    ) {
+        self.commands.push(DrawCommand::DrawPixmap {
+            x,
+            y,
+            pixmap,
+            paint,
+            transform,
+        });
+    }
+
+    /// Records a clip push.
+    ///
+    /// Must be balanced by a [`pop_clip`](Scene::pop_clip) call.
+    pub fn push_clip(&mut self, path: Path, fill_rule: FillRule, anti_alias: bool) {
+        self.stack.push(true);
+        self.commands.push(DrawCommand::PushClip {
+            path,
+            fill_rule,
+            anti_alias,
+        });
+    }
+
+    /// Records a clip pop.
+    ///
+    /// Does nothing and triggers a debug assertion when the last push was not a clip.
+    pub fn pop_clip(&mut self) {
+        if self.stack.last() != Some(&true) {
+            debug_assert!(false, "pop_clip without a matching push_clip");
+            return;
+        }
+
+        self.stack.pop();
+        self.commands.push(DrawCommand::PopClip);
+    }
+
+    /// Records a transform push.
+    ///
+    /// Must be balanced by a [`pop_transform`](Scene::pop_transform) call.
+    pub fn push_transform(&mut self, transform: Transform) {
+        self.stack.push(false);
+        self.commands.push(DrawCommand::PushTransform(transform));
+    }
+
+    /// Records a transform pop.
+    ///
+    /// Does nothing and triggers a debug assertion when the last push was not a transform.
+    pub fn pop_transform(&mut self) {
+        if self.stack.last() != Some(&false) {
+            debug_assert!(false, "pop_transform without a matching push_transform");
+            return;
+        }
+
+        self.stack.pop();
+        self.commands.push(DrawCommand::PopTransform);
+    }
+
+    /// Renders the scene onto a pixmap.
+    pub fn render_to(&self, pixmap: &mut PixmapMut) {
+        let mut canvas = Canvas::new(pixmap.reborrow());
+        self.render_to_canvas(&mut canvas);
+    }
+
+    /// Renders the scene onto a canvas.
+    ///
+    /// All commands are affected by the current canvas transform and clip.
+    /// Which allows rendering the same scene at different viewports.
+    ///
+    /// Pushes that were not popped are popped automatically,
+    /// therefore the canvas state is preserved.
+    pub fn render_to_canvas(&self, canvas: &mut Canvas) {
+        render_commands(&self.commands, canvas);
+    }
+}
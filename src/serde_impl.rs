@@ -0,0 +1,74 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+//! `serde` support.
+//!
+//! Geometry types are implemented in `tiny-skia-path`.
+
+use serde_crate::de::Error;
+use serde_crate::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Color;
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "serde_crate", rename = "Color")]
+struct ColorData {
+    r: f32,
+    g: f32,
+    b: f32,
+    a: f32,
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ColorData {
+            r: self.red(),
+            g: self.green(),
+            b: self.blue(),
+            a: self.alpha(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ColorData::deserialize(deserializer)?;
+        Color::from_rgba(data.r, data.g, data.b, data.a)
+            .ok_or_else(|| D::Error::custom("color components must be in 0..=1 range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn color() {
+        let tokens = [
+            Token::Struct {
+                name: "Color",
+                len: 4,
+            },
+            Token::Str("r"),
+            Token::F32(0.0),
+            Token::Str("g"),
+            Token::F32(0.25),
+            Token::Str("b"),
+            Token::F32(0.5),
+            Token::Str("a"),
+            Token::F32(1.0),
+            Token::StructEnd,
+        ];
+        assert_tokens(&Color::from_rgba(0.0, 0.25, 0.5, 1.0).unwrap(), &tokens);
+
+        let mut tokens = tokens;
+        tokens[8] = Token::F32(1.5);
+        assert_de_tokens_error::<Color>(&tokens, "color components must be in 0..=1 range");
+    }
+}
@@ -8,7 +8,7 @@ use alloc::vec::Vec;
 
 use tiny_skia_path::{NormalizedF32, Scalar};
 
-use crate::{Color, SpreadMode, Transform};
+use crate::{Color, SpreadMode, TinySkiaError, Transform};
 
 use crate::pipeline::RasterPipelineBuilder;
 use crate::pipeline::{self, EvenlySpaced2StopGradientCtx, GradientColor, GradientCtx};
@@ -17,11 +17,39 @@ use crate::pipeline::{self, EvenlySpaced2StopGradientCtx, GradientColor, Gradien
 // gradients defined in the wild.
 pub const DEGENERATE_THRESHOLD: f32 = 1.0 / (1 << 15) as f32;
 
+/// A color space in which gradient stops are interpolated.
+///
+/// Doesn't affect opaque gradients.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GradientInterpolation {
+    /// Interpolates straight-alpha colors and premultiplies the result.
+    ///
+    /// Color channels are interpolated independently of alpha,
+    /// which is what Skia does by default.
+    /// Therefore, a fade into a transparent version of the same color keeps the color constant,
+    /// but a fade into transparent black will darken midway.
+    Unpremultiplied,
+
+    /// Interpolates premultiplied colors.
+    ///
+    /// Color channels are weighted by alpha, therefore a fade into any transparent color
+    /// looks the same and doesn't darken.
+    /// This is what CSS and SVG 2 require.
+    Premultiplied,
+}
+
+impl Default for GradientInterpolation {
+    fn default() -> Self {
+        GradientInterpolation::Unpremultiplied
+    }
+}
+
 /// A gradient point.
 #[allow(missing_docs)]
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct GradientStop {
-    pub(crate) position: NormalizedF32,
+    // Can be NaN. Will be validated by gradient constructors.
+    pub(crate) position: f32,
     pub(crate) color: Color,
 }
 
@@ -29,12 +57,53 @@ impl GradientStop {
     /// Creates a new gradient point.
     ///
     /// `position` will be clamped to a 0..=1 range.
+    ///
+    /// A NaN `position` is preserved and will be rejected by gradient constructors.
     pub fn new(position: f32, color: Color) -> Self {
         GradientStop {
-            position: NormalizedF32::new_clamped(position),
+            position: position.clamp(0.0, 1.0),
             color,
         }
     }
+
+    /// Creates a new gradient point without clamping the `position`.
+    ///
+    /// Useful when positions are already known to be normalized and sorted,
+    /// in which case gradient constructors will not reorder stops.
+    /// Positions outside the 0..=1 range will still be clamped during gradient creation.
+    pub fn new_unchecked(position: f32, color: Color) -> Self {
+        GradientStop { position, color }
+    }
+}
+
+/// Validates and sorts gradient stops.
+///
+/// Returns `None` when there are less than two stops or when any position is NaN.
+pub(crate) fn prepare_stops(
+    mut stops: Vec<GradientStop>,
+) -> Result<Vec<GradientStop>, TinySkiaError> {
+    if stops.len() < 2 {
+        log::warn!("a gradient must have at least two stops");
+        return Err(TinySkiaError::TooFewGradientStops);
+    }
+
+    if stops.iter().any(|s| s.position.is_nan()) {
+        log::warn!("gradient stop positions must not be NaN");
+        return Err(TinySkiaError::InvalidGradientStopPosition);
+    }
+
+    for stop in &mut stops {
+        stop.position = stop.position.clamp(0.0, 1.0);
+    }
+
+    // Positions are not NaN, so they can be compared.
+    // A stable sort is required to preserve an order of stops with the same position,
+    // which is used to define hard color transitions.
+    if !stops.windows(2).all(|w| w[0].position <= w[1].position) {
+        stops.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+    }
+
+    Ok(stops)
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -45,6 +114,7 @@ pub struct Gradient {
     points_to_unit: Transform,
     pub(crate) colors_are_opaque: bool,
     has_uniform_stops: bool,
+    pub(crate) interpolation: GradientInterpolation,
 }
 
 impl Gradient {
@@ -55,14 +125,15 @@ impl Gradient {
         points_to_unit: Transform,
     ) -> Self {
         debug_assert!(stops.len() > 1);
+        debug_assert!(stops.iter().all(|s| s.position.is_finite()));
 
         // Note: we let the caller skip the first and/or last position.
         // i.e. pos[0] = 0.3, pos[1] = 0.7
         // In these cases, we insert dummy entries to ensure that the final data
         // will be bracketed by [0, 1].
         // i.e. our_pos[0] = 0, our_pos[1] = 0.3, our_pos[2] = 0.7, our_pos[3] = 1
-        let dummy_first = stops[0].position.get() != 0.0;
-        let dummy_last = stops[stops.len() - 1].position.get() != 1.0;
+        let dummy_first = stops[0].position != 0.0;
+        let dummy_last = stops[stops.len() - 1].position != 1.0;
 
         // Now copy over the colors, adding the dummies as needed.
         if dummy_first {
@@ -79,17 +150,17 @@ impl Gradient {
         let start_index = if dummy_first { 0 } else { 1 };
         let mut prev = 0.0;
         let mut has_uniform_stops = true;
-        let uniform_step = stops[start_index].position.get() - prev;
+        let uniform_step = stops[start_index].position - prev;
         for i in start_index..stops.len() {
             let curr = if i + 1 == stops.len() {
                 // The last one must be zero.
                 1.0
             } else {
-                stops[i].position.get().bound(prev, 1.0)
+                stops[i].position.bound(prev, 1.0)
             };
 
             has_uniform_stops &= uniform_step.is_nearly_equal(curr - prev);
-            stops[i].position = NormalizedF32::new_clamped(curr);
+            stops[i].position = curr;
             prev = curr;
         }
 
@@ -100,6 +171,7 @@ impl Gradient {
             points_to_unit,
             colors_are_opaque,
             has_uniform_stops,
+            interpolation: GradientInterpolation::default(),
         }
     }
 
@@ -145,17 +217,12 @@ impl Gradient {
         if self.stops.len() == 2 {
             debug_assert!(self.has_uniform_stops);
 
-            let c0 = self.stops[0].color;
-            let c1 = self.stops[1].color;
+            let c0 = self.stop_color(0);
+            let c1 = self.stop_color(1);
 
             p.ctx.evenly_spaced_2_stop_gradient = EvenlySpaced2StopGradientCtx {
-                factor: GradientColor::new(
-                    c1.red() - c0.red(),
-                    c1.green() - c0.green(),
-                    c1.blue() - c0.blue(),
-                    c1.alpha() - c0.alpha(),
-                ),
-                bias: GradientColor::from(c0),
+                factor: GradientColor::new(c1.r - c0.r, c1.g - c0.g, c1.b - c0.b, c1.a - c0.a),
+                bias: c0,
             };
 
             p.push(pipeline::Stage::EvenlySpaced2StopGradient);
@@ -195,14 +262,14 @@ impl Gradient {
                 (0, 1)
             };
 
-            let mut t_l = self.stops[first_stop].position.get();
-            let mut c_l = GradientColor::from(self.stops[first_stop].color);
+            let mut t_l = self.stops[first_stop].position;
+            let mut c_l = self.stop_color(first_stop);
             ctx.push_const_color(c_l);
             ctx.t_values.push(NormalizedF32::ZERO);
             // N.B. lastStop is the index of the last stop, not one after.
             for i in first_stop..last_stop {
-                let t_r = self.stops[i + 1].position.get();
-                let c_r = GradientColor::from(self.stops[i + 1].color);
+                let t_r = self.stops[i + 1].position;
+                let c_r = self.stop_color(i + 1);
                 debug_assert!(t_l <= t_r);
                 if t_l < t_r {
                     // For each stop we calculate a bias B and a scale factor F, such that
@@ -248,7 +315,8 @@ impl Gradient {
             p.ctx.gradient = ctx;
         }
 
-        if !self.colors_are_opaque {
+        // Premultiplied colors are already interpolated as premultiplied.
+        if !self.colors_are_opaque && self.interpolation == GradientInterpolation::Unpremultiplied {
             p.push(pipeline::Stage::Premultiply);
         }
 
@@ -257,6 +325,17 @@ impl Gradient {
         true
     }
 
+    fn stop_color(&self, index: usize) -> GradientColor {
+        let c = self.stops[index].color;
+        match self.interpolation {
+            GradientInterpolation::Unpremultiplied => GradientColor::from(c),
+            GradientInterpolation::Premultiplied => {
+                let c = c.premultiply();
+                GradientColor::new(c.red(), c.green(), c.blue(), c.alpha())
+            }
+        }
+    }
+
     pub fn apply_opacity(&mut self, opacity: f32) {
         for stop in &mut self.stops {
             stop.color.apply_opacity(opacity);
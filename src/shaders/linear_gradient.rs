@@ -8,9 +8,9 @@ use alloc::vec::Vec;
 
 use tiny_skia_path::Scalar;
 
-use crate::{Color, GradientStop, Point, Shader, SpreadMode, Transform};
+use crate::{Color, GradientStop, Point, Shader, SpreadMode, TinySkiaError, Transform};
 
-use super::gradient::{Gradient, DEGENERATE_THRESHOLD};
+use super::gradient::{prepare_stops, Gradient, DEGENERATE_THRESHOLD};
 use crate::pipeline::RasterPipelineBuilder;
 
 /// A linear gradient shader.
@@ -22,15 +22,19 @@ pub struct LinearGradient {
 impl LinearGradient {
     /// Creates a new linear gradient shader.
     ///
+    /// Stops will be sorted by position.
+    /// Stops with the same position create a hard color transition.
+    ///
     /// Returns `Shader::SolidColor` when:
-    /// - `stops.len()` == 1
     /// - `start` and `end` are very close
     ///
     /// Returns `None` when:
     ///
-    /// - `stops` is empty
-    /// - `start` == `end`
+    /// - `stops.len()` < 2
+    /// - any stop position is NaN
     /// - `transform` is not invertible
+    ///
+    /// See [`LinearGradient::try_new`] for error details.
     #[allow(clippy::new_ret_no_self)]
     pub fn new(
         start: Point,
@@ -39,17 +43,31 @@ impl LinearGradient {
         mode: SpreadMode,
         transform: Transform,
     ) -> Option<Shader<'static>> {
-        if stops.is_empty() {
-            return None;
-        }
+        LinearGradient::try_new(start, end, stops, mode, transform).ok()
+    }
 
-        if stops.len() == 1 {
-            return Some(Shader::SolidColor(stops[0].color));
-        }
+    /// Creates a new linear gradient shader.
+    ///
+    /// The same as [`LinearGradient::new`], but reports why the gradient is invalid.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::TooFewGradientStops`] when `stops.len()` < 2.
+    /// - [`TinySkiaError::InvalidGradientStopPosition`] when any stop position is NaN.
+    /// - [`TinySkiaError::NonFinite`] when `start`, `end` or `transform` are not finite.
+    /// - [`TinySkiaError::NotInvertible`] when `transform` is not invertible.
+    pub fn try_new(
+        start: Point,
+        end: Point,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+    ) -> Result<Shader<'static>, TinySkiaError> {
+        let stops = prepare_stops(stops)?;
 
         let length = (end - start).length();
         if !length.is_finite() {
-            return None;
+            return Err(TinySkiaError::NonFinite);
         }
 
         if length.is_nearly_zero_within_tolerance(DEGENERATE_THRESHOLD) {
@@ -68,21 +86,21 @@ impl LinearGradient {
                     // Depending on how the gradient shape degenerates,
                     // there may be a more specialized fallback representation
                     // for the factories to use, but this is a reasonable default.
-                    return Some(Shader::SolidColor(stops.last().unwrap().color));
+                    return Ok(Shader::SolidColor(stops.last().unwrap().color));
                 }
                 SpreadMode::Reflect | SpreadMode::Repeat => {
                     // repeat and mirror are treated the same: the border colors are never visible,
                     // but approximate the final color as infinite repetitions of the colors, so
                     // it can be represented as the average color of the gradient.
-                    return Some(Shader::SolidColor(average_gradient_color(&stops)));
+                    return Ok(Shader::SolidColor(average_gradient_color(&stops)));
                 }
             }
         }
 
-        transform.invert()?;
+        transform.try_invert()?;
 
-        let unit_ts = points_to_unit_ts(start, end)?;
-        Some(Shader::LinearGradient(LinearGradient {
+        let unit_ts = points_to_unit_ts(start, end).ok_or(TinySkiaError::NonFinite)?;
+        Ok(Shader::LinearGradient(LinearGradient {
             base: Gradient::new(stops, mode, transform, unit_ts),
         }))
     }
@@ -138,24 +156,24 @@ fn average_gradient_color(points: &[GradientStop]) -> Color {
         let c1 = load_color(points[i + 1].color);
         // when pos == null, there are colorCount uniformly distributed stops, going from 0 to 1,
         // so pos[i + 1] - pos[i] = 1/(colorCount-1)
-        let w = points[i + 1].position.get() - points[i].position.get();
+        let w = points[i + 1].position - points[i].position;
         blend += w_scale * f32x4::splat(w) * (c1 + c0);
     }
 
     // Now account for any implicit intervals at the start or end of the stop definitions
-    if points[0].position.get() > 0.0 {
+    if points[0].position > 0.0 {
         // The first color is fixed between p = 0 to pos[0], so 0.5 * (ci + cj) * (pj - pi)
         // becomes 0.5 * (c + c) * (pj - 0) = c * pj
         let c = load_color(points[0].color);
-        blend += f32x4::splat(points[0].position.get()) * c;
+        blend += f32x4::splat(points[0].position) * c;
     }
 
     let last_idx = points.len() - 1;
-    if points[last_idx].position.get() < 1.0 {
+    if points[last_idx].position < 1.0 {
         // The last color is fixed between pos[n-1] to p = 1, so 0.5 * (ci + cj) * (pj - pi)
         // becomes 0.5 * (c + c) * (1 - pi) = c * (1 - pi)
         let c = load_color(points[last_idx].color);
-        blend += (f32x4::splat(1.0) - f32x4::splat(points[last_idx].position.get())) * c;
+        blend += (f32x4::splat(1.0) - f32x4::splat(points[last_idx].position)) * c;
     }
 
     store_color(blend)
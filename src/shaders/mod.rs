@@ -11,9 +11,9 @@ mod radial_gradient;
 
 use tiny_skia_path::{NormalizedF32, Scalar};
 
-pub use gradient::GradientStop;
+pub use gradient::{GradientInterpolation, GradientStop};
 pub use linear_gradient::LinearGradient;
-pub use pattern::{FilterQuality, Pattern, PixmapPaint};
+pub use pattern::{ColorSpace, FilterQuality, Pattern, PixmapPaint, TintMode};
 pub use radial_gradient::RadialGradient;
 
 use crate::{Color, Transform};
@@ -25,6 +25,8 @@ use crate::pipeline::RasterPipelineBuilder;
 pub enum SpreadMode {
     /// Replicate the edge color if the shader draws outside of its
     /// original bounds.
+    ///
+    /// Filtering near the edge uses the edge pixels as well, including their alpha.
     Pad,
 
     /// Repeat the shader's image horizontally and vertically, alternating
@@ -49,6 +51,11 @@ impl Default for SpreadMode {
 /// once (e.g. bitmap tiling or gradient) and then change its transparency
 /// without having to modify the original shader. Only the paint's alpha needs
 /// to be modified.
+///
+/// Gradients do not bake a color lookup table. Colors are computed per pixel
+/// from per-stop factors, which are prepared in linear time when drawing starts.
+/// Therefore, creating many gradients with the same stops is cheap, and a single gradient
+/// can be placed differently via [`with_local_matrix`](Shader::with_local_matrix).
 #[derive(Clone, PartialEq, Debug)]
 pub enum Shader<'a> {
     /// A solid color shader.
@@ -104,6 +111,84 @@ impl<'a> Shader<'a> {
         }
     }
 
+    /// Returns the shader with a local matrix applied.
+    ///
+    /// The local matrix is pre-concatenated with the shader's own transform,
+    /// therefore it's applied first, followed by the shader's transform
+    /// and then by the transform the shader is drawn with.
+    /// This allows placing the same shader differently without rebuilding it.
+    ///
+    /// The matrix is merged into the shader's transform, so it adds no per-pixel work.
+    #[must_use]
+    pub fn with_local_matrix(mut self, ts: Transform) -> Self {
+        if ts.is_identity() {
+            return self;
+        }
+
+        match self {
+            Shader::SolidColor(_) => {}
+            Shader::LinearGradient(ref mut g) => {
+                g.base.transform = g.base.transform.pre_concat(ts);
+            }
+            Shader::RadialGradient(ref mut g) => {
+                g.base.transform = g.base.transform.pre_concat(ts);
+            }
+            Shader::Pattern(ref mut p) => {
+                p.transform = p.transform.pre_concat(ts);
+            }
+        }
+
+        self
+    }
+
+    /// Returns the shader with a specified gradient interpolation.
+    ///
+    /// The default one is [`GradientInterpolation::Unpremultiplied`].
+    ///
+    /// Does nothing for non-gradient shaders.
+    #[must_use]
+    pub fn with_gradient_interpolation(mut self, interpolation: GradientInterpolation) -> Self {
+        match self {
+            Shader::SolidColor(_) | Shader::Pattern(_) => {}
+            Shader::LinearGradient(ref mut g) => {
+                g.base.interpolation = interpolation;
+            }
+            Shader::RadialGradient(ref mut g) => {
+                g.base.interpolation = interpolation;
+            }
+        }
+
+        self
+    }
+
+    /// Returns the shader with a specified pattern filtering color space.
+    ///
+    /// The default one is [`ColorSpace::Srgb`].
+    ///
+    /// Does nothing for non-pattern shaders.
+    #[must_use]
+    pub fn with_color_space(mut self, color_space: ColorSpace) -> Self {
+        if let Shader::Pattern(ref mut p) = self {
+            p.color_space = color_space;
+        }
+
+        self
+    }
+
+    /// Returns the shader with a specified pattern tint.
+    ///
+    /// See [`TintMode`] for details.
+    ///
+    /// Does nothing for non-pattern shaders.
+    #[must_use]
+    pub fn with_tint(mut self, color: Color, mode: TintMode) -> Self {
+        if let Shader::Pattern(ref mut p) = self {
+            p.tint = Some((color, mode));
+        }
+
+        self
+    }
+
     /// Shifts shader's opacity.
     ///
     /// `opacity` will be clamped to the 0..=1 range.
@@ -6,7 +6,7 @@
 
 use tiny_skia_path::NormalizedF32;
 
-use crate::{BlendMode, PixmapRef, Shader, SpreadMode, Transform};
+use crate::{BlendMode, Color, PixmapRef, RenderMode, Shader, SpreadMode, Transform};
 
 use crate::pipeline;
 use crate::pipeline::RasterPipelineBuilder;
@@ -20,11 +20,49 @@ pub enum FilterQuality {
     /// Nearest-neighbor. Low quality, but fastest.
     Nearest,
     /// Bilinear.
+    ///
+    /// Neighbouring pixels are interpolated as premultiplied colors,
+    /// so fully transparent pixels do not bleed their color into opaque ones.
     Bilinear,
     /// Bicubic. High quality, but slow.
     Bicubic,
 }
 
+/// A color space in which pattern pixels are filtered.
+///
+/// Pixels are always stored as sRGB-encoded, this only affects filtering.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ColorSpace {
+    /// Pixels are filtered as is.
+    ///
+    /// The fastest option, but darkens edges between contrasting pixels
+    /// when a pixmap is scaled or transformed.
+    Srgb,
+    /// Pixels are converted into a linear space before filtering and back after.
+    ///
+    /// Has no effect on `FilterQuality::Nearest`, since no pixels are mixed.
+    Linear,
+}
+
+impl Default for ColorSpace {
+    fn default() -> Self {
+        ColorSpace::Srgb
+    }
+}
+
+/// Controls how a pattern is recolored by a tint color.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TintMode {
+    /// Pixmap's alpha is used as a coverage and the color is taken from the tint.
+    ///
+    /// Pixmap's RGB is ignored. Useful for alpha-only icons and glyphs.
+    Alpha,
+    /// Pixmap's color is multiplied by the tint.
+    ///
+    /// A white tint doesn't change the pixmap.
+    Multiply,
+}
+
 /// Controls how a pixmap should be blended.
 ///
 /// Like `Paint`, but for `Pixmap`.
@@ -46,6 +84,28 @@ pub struct PixmapPaint {
     ///
     /// Default: Nearest
     pub quality: FilterQuality,
+
+    /// A rendering mode.
+    ///
+    /// Default: Fast
+    pub render_mode: RenderMode,
+
+    /// A color space in which pixmap's pixels are filtered.
+    ///
+    /// Default: Srgb
+    pub color_space: ColorSpace,
+
+    /// Snaps the pixmap's origin to the pixel grid.
+    ///
+    /// After applying the transform, the pixmap's top-left corner will be moved
+    /// to the nearest pixel corner, with halves rounded up.
+    /// Produces crisp results for UI and pixel-art, especially with `FilterQuality::Nearest`.
+    ///
+    /// When disabled, fractional offsets are preserved and will be filtered
+    /// according to `quality`, which is better for smooth animations.
+    ///
+    /// Default: false
+    pub snap_to_pixel_grid: bool,
 }
 
 impl Default for PixmapPaint {
@@ -54,6 +114,9 @@ impl Default for PixmapPaint {
             opacity: 1.0,
             blend_mode: BlendMode::default(),
             quality: FilterQuality::Nearest,
+            render_mode: RenderMode::default(),
+            color_space: ColorSpace::default(),
+            snap_to_pixel_grid: false,
         }
     }
 }
@@ -69,6 +132,8 @@ pub struct Pattern<'a> {
     pub(crate) pixmap: PixmapRef<'a>,
     quality: FilterQuality,
     spread_mode: SpreadMode,
+    pub(crate) color_space: ColorSpace,
+    pub(crate) tint: Option<(Color, TintMode)>,
     pub(crate) opacity: NormalizedF32,
     pub(crate) transform: Transform,
 }
@@ -89,6 +154,8 @@ impl<'a> Pattern<'a> {
             pixmap,
             spread_mode,
             quality,
+            color_space: ColorSpace::default(),
+            tint: None,
             opacity: NormalizedF32::new_clamped(opacity),
             transform,
         })
@@ -103,28 +170,32 @@ impl<'a> Pattern<'a> {
             }
         };
 
-        p.push(pipeline::Stage::SeedShader);
-
-        p.push_transform(ts);
-
         let mut quality = self.quality;
 
-        if ts.is_identity() || ts.is_translate() {
+        if ts.is_identity() {
             quality = FilterQuality::Nearest;
         }
 
-        if quality == FilterQuality::Bilinear {
-            if ts.is_translate() {
-                if ts.tx == ts.tx.trunc() && ts.ty == ts.ty.trunc() {
-                    // When the matrix is just an integer translate, bilerp == nearest neighbor.
-                    quality = FilterQuality::Nearest;
-                }
-            }
+        if ts.is_translate() && ts.tx == ts.tx.trunc() && ts.ty == ts.ty.trunc() {
+            // When the matrix is just an integer translate, filtering == nearest neighbor.
+            quality = FilterQuality::Nearest;
         }
 
         // TODO: minimizing scale via mipmap
 
+        // Most images are only scaled and moved.
+        // Such images are sampled with a fixed point stepping, which is bit-identical.
+        let fast_path = quality == FilterQuality::Nearest
+            && self.spread_mode == SpreadMode::Pad
+            && p.push_gather_scale_translate(ts);
+
+        if !fast_path {
+            p.push(pipeline::Stage::SeedShader);
+            p.push_transform(ts);
+        }
+
         match quality {
+            FilterQuality::Nearest if fast_path => {}
             FilterQuality::Nearest => {
                 p.ctx.limit_x = pipeline::TileCtx {
                     scale: self.pixmap.width() as f32,
@@ -149,6 +220,7 @@ impl<'a> Pattern<'a> {
                     spread_mode: self.spread_mode,
                     inv_width: 1.0 / self.pixmap.width() as f32,
                     inv_height: 1.0 / self.pixmap.height() as f32,
+                    color_space: self.color_space,
                 };
                 p.push(pipeline::Stage::Bilinear);
             }
@@ -157,6 +229,7 @@ impl<'a> Pattern<'a> {
                     spread_mode: self.spread_mode,
                     inv_width: 1.0 / self.pixmap.width() as f32,
                     inv_height: 1.0 / self.pixmap.height() as f32,
+                    color_space: self.color_space,
                 };
                 p.push(pipeline::Stage::Bicubic);
 
@@ -166,6 +239,23 @@ impl<'a> Pattern<'a> {
             }
         }
 
+        // Samplers have already converted pixels into a linear space.
+        if quality != FilterQuality::Nearest && self.color_space == ColorSpace::Linear {
+            p.push(pipeline::Stage::LinearToSrgb);
+        }
+
+        if let Some((color, mode)) = self.tint {
+            let c = color.premultiply();
+            p.ctx.tint.r = c.red();
+            p.ctx.tint.g = c.green();
+            p.ctx.tint.b = c.blue();
+            p.ctx.tint.a = c.alpha();
+            p.push(match mode {
+                TintMode::Alpha => pipeline::Stage::TintAlpha,
+                TintMode::Multiply => pipeline::Stage::TintMultiply,
+            });
+        }
+
         // Unlike Skia, we do not support global opacity and only Pattern allows it.
         if self.opacity != NormalizedF32::ONE {
             debug_assert_eq!(
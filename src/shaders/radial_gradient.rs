@@ -8,9 +8,9 @@ use alloc::vec::Vec;
 
 use tiny_skia_path::Scalar;
 
-use crate::{GradientStop, Point, Shader, SpreadMode, Transform};
+use crate::{GradientStop, Point, Shader, SpreadMode, TinySkiaError, Transform};
 
-use super::gradient::{Gradient, DEGENERATE_THRESHOLD};
+use super::gradient::{prepare_stops, Gradient, DEGENERATE_THRESHOLD};
 use crate::pipeline;
 use crate::pipeline::RasterPipelineBuilder;
 use crate::wide::u32x8;
@@ -52,14 +52,17 @@ pub struct RadialGradient {
 impl RadialGradient {
     /// Creates a new radial gradient shader.
     ///
-    /// Returns `Shader::SolidColor` when:
-    /// - `stops.len()` == 1
+    /// Stops will be sorted by position.
+    /// Stops with the same position create a hard color transition.
     ///
     /// Returns `None` when:
     ///
-    /// - `stops` is empty
+    /// - `stops.len()` < 2
+    /// - any stop position is NaN
     /// - `radius` <= 0
     /// - `transform` is not invertible
+    ///
+    /// See [`RadialGradient::try_new`] for error details.
     #[allow(clippy::new_ret_no_self)]
     pub fn new(
         start: Point,
@@ -69,25 +72,45 @@ impl RadialGradient {
         mode: SpreadMode,
         transform: Transform,
     ) -> Option<Shader<'static>> {
+        RadialGradient::try_new(start, end, radius, stops, mode, transform).ok()
+    }
+
+    /// Creates a new radial gradient shader.
+    ///
+    /// The same as [`RadialGradient::new`], but reports why the gradient is invalid.
+    ///
+    /// # Errors
+    ///
+    /// - [`TinySkiaError::InvalidGradientRadius`] when `radius` <= 0.
+    /// - [`TinySkiaError::TooFewGradientStops`] when `stops.len()` < 2.
+    /// - [`TinySkiaError::InvalidGradientStopPosition`] when any stop position is NaN.
+    /// - [`TinySkiaError::NonFinite`] when `start`, `end`, `radius` or `transform` are not finite.
+    /// - [`TinySkiaError::NotInvertible`] when `transform` is not invertible.
+    pub fn try_new(
+        start: Point,
+        end: Point,
+        radius: f32,
+        stops: Vec<GradientStop>,
+        mode: SpreadMode,
+        transform: Transform,
+    ) -> Result<Shader<'static>, TinySkiaError> {
         // From SkGradientShader::MakeTwoPointConical
 
-        if radius < 0.0 || radius.is_nearly_zero() {
-            return None;
+        if !radius.is_finite() {
+            return Err(TinySkiaError::NonFinite);
         }
 
-        if stops.is_empty() {
-            return None;
+        if radius < 0.0 || radius.is_nearly_zero() {
+            return Err(TinySkiaError::InvalidGradientRadius);
         }
 
-        if stops.len() == 1 {
-            return Some(Shader::SolidColor(stops[0].color));
-        }
+        let stops = prepare_stops(stops)?;
 
-        transform.invert()?;
+        transform.try_invert()?;
 
         let length = (end - start).length();
         if !length.is_finite() {
-            return None;
+            return Err(TinySkiaError::NonFinite);
         }
 
         if length.is_nearly_zero_within_tolerance(DEGENERATE_THRESHOLD) {
@@ -102,7 +125,7 @@ impl RadialGradient {
 
             // We can treat this gradient as radial, which is faster. If we got here, we know
             // that endRadius is not equal to 0, so this produces a meaningful gradient
-            Some(Shader::RadialGradient(RadialGradient {
+            Ok(Shader::RadialGradient(RadialGradient {
                 base: Gradient::new(stops, mode, transform, ts),
                 focal_data: None,
             }))
@@ -113,7 +136,8 @@ impl RadialGradient {
                 end,
                 Point::from_xy(0.0, 0.0),
                 Point::from_xy(1.0, 0.0),
-            )?;
+            )
+            .ok_or(TinySkiaError::NonFinite)?;
 
             let d_center = (start - end).length();
             let r1 = radius / d_center;
@@ -127,7 +151,7 @@ impl RadialGradient {
                 ts = ts.post_scale(r1 / (r1 * r1 - 1.0), 1.0 / ((r1 * r1 - 1.0).abs()).sqrt());
             }
 
-            Some(Shader::RadialGradient(RadialGradient {
+            Ok(Shader::RadialGradient(RadialGradient {
                 base: Gradient::new(stops, mode, transform, ts),
                 focal_data: Some(focal_data),
             }))
@@ -0,0 +1,252 @@
+// Copyright 2020 Yevhenii Reizner
+//
+// Use of this source code is governed by a BSD-style license that can be
+// found in the LICENSE file.
+
+// This module was written from scratch, therefore there is no Google copyright.
+
+use alloc::sync::Arc;
+
+use tiny_skia_path::IntSize;
+
+use crate::{
+    Canvas, FillRule, IntRect, Paint, Path, Pixmap, PixmapMut, PixmapPaint, PixmapRef, Stroke,
+    Transform,
+};
+
+use crate::pixmap::{data_len_for_size_and_bpp, BYTES_PER_PIXEL};
+
+/// A drawing command used by [`TiledRenderer`] and [`Scene`](crate::Scene).
+///
+/// Clip and transform pushes and pops form a single stack, therefore they must be properly nested.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DrawCommand<'a> {
+    /// Fills a path.
+    ///
+    /// See [`PixmapMut::fill_path`](struct.PixmapMut.html#method.fill_path) for details.
+    FillPath {
+        /// A path to fill.
+        path: Path,
+        /// A paint.
+        paint: Paint<'a>,
+        /// A fill rule.
+        fill_rule: FillRule,
+        /// A path transform.
+        transform: Transform,
+    },
+    /// Strokes a path.
+    ///
+    /// See [`PixmapMut::stroke_path`](struct.PixmapMut.html#method.stroke_path) for details.
+    StrokePath {
+        /// A path to stroke.
+        path: Path,
+        /// A paint.
+        paint: Paint<'a>,
+        /// A stroke properties.
+        stroke: Stroke,
+        /// A path transform.
+        transform: Transform,
+    },
+    /// Draws a pixmap.
+    ///
+    /// See [`PixmapMut::draw_pixmap`](struct.PixmapMut.html#method.draw_pixmap) for details.
+    DrawPixmap {
+        /// Pixmap position.
+        x: i32,
+        /// Pixmap position.
+        y: i32,
+        /// A pixmap to draw.
+        pixmap: Arc<Pixmap>,
+        /// A pixmap paint.
+        paint: PixmapPaint,
+        /// A pixmap transform.
+        transform: Transform,
+    },
+    /// Intersects the current clip with a path, until the matching `PopClip`.
+    ///
+    /// The path is affected by the current transform.
+    PushClip {
+        /// A clip path.
+        path: Path,
+        /// A fill rule.
+        fill_rule: FillRule,
+        /// Enables anti-aliasing.
+        anti_alias: bool,
+    },
+    /// Restores the clip to the state before the matching `PushClip`.
+    PopClip,
+    /// Pre-concats the current transform with the provided one,
+    /// until the matching `PopTransform`.
+    PushTransform(Transform),
+    /// Restores the transform to the state before the matching `PushTransform`.
+    PopTransform,
+}
+
+/// Renders an image tile-by-tile.
+///
+/// Allows rendering images that are too large to fit into memory at once,
+/// since only a single tile is allocated.
+///
+/// Each tile is rendered by translating all commands by the tile origin and clipping
+/// to the tile bounds. Since tiles are offset by whole pixels, shapes spanning multiple
+/// tiles have no seams. But since paths are clipped by each tile separately, curves
+/// can be approximated slightly differently, which affects anti-aliased edges.
+/// Just like when rendering onto a pixmap larger than 8191x8191.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TiledRenderer {
+    width: u32,
+    height: u32,
+    tile_size: u32,
+}
+
+impl TiledRenderer {
+    /// Creates a new renderer for an image of the specified size.
+    ///
+    /// Tiles are `tile_size` x `tile_size` pixels, except the ones
+    /// on the right and bottom edges, which can be smaller.
+    ///
+    /// Returns `None` when any value is zero, the image size is bigger than `i32::MAX`
+    /// or a tile is too big to be allocated.
+    pub fn new(width: u32, height: u32, tile_size: u32) -> Option<Self> {
+        // Tiles must be representable as `IntRect`.
+        IntRect::from_xywh(0, 0, width, height)?;
+
+        let tile = IntSize::from_wh(tile_size.min(width), tile_size.min(height))?;
+        data_len_for_size_and_bpp(tile, BYTES_PER_PIXEL)?;
+
+        Some(TiledRenderer {
+            width,
+            height,
+            tile_size,
+        })
+    }
+
+    /// Returns the image width.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the image height.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the tile size.
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size
+    }
+
+    /// Renders commands tile-by-tile.
+    ///
+    /// `f` is called for each tile in a row-major order, with tile bounds in the image space
+    /// and a rendered tile. Tiles are transparent before rendering.
+    pub fn render<F: FnMut(IntRect, PixmapRef)>(&self, commands: &[DrawCommand], mut f: F) {
+        let max_width = self.tile_size.min(self.width);
+        let max_height = self.tile_size.min(self.height);
+        let mut buffer = match Pixmap::new(max_width, max_height) {
+            Some(v) => v,
+            None => return, // technically unreachable, checked in `new`
+        };
+
+        for y in (0..self.height).step_by(self.tile_size as usize) {
+            for x in (0..self.width).step_by(self.tile_size as usize) {
+                let width = max_width.min(self.width - x);
+                let height = max_height.min(self.height - y);
+                let len = width as usize * height as usize * BYTES_PER_PIXEL;
+                let data = &mut buffer.data_mut()[..len];
+                let mut tile = match PixmapMut::from_bytes(data, width, height) {
+                    Some(v) => v,
+                    None => continue, // technically unreachable
+                };
+
+                tile.clear();
+                render_tile(commands, x, y, &mut tile);
+
+                let rect = match IntRect::from_xywh(x as i32, y as i32, width, height) {
+                    Some(v) => v,
+                    None => continue, // technically unreachable, checked in `new`
+                };
+
+                f(rect, tile.as_ref());
+            }
+        }
+    }
+}
+
+fn render_tile(commands: &[DrawCommand], x: u32, y: u32, tile: &mut PixmapMut) {
+    let mut canvas = Canvas::new(tile.reborrow());
+    canvas.translate(-(x as f32), -(y as f32));
+    render_commands(commands, &mut canvas);
+}
+
+/// Renders commands onto a canvas.
+///
+/// All commands are affected by the current canvas transform and clip.
+///
+/// Pushes that were not popped are popped automatically and pops without
+/// a matching push are ignored, therefore the canvas state is preserved.
+pub(crate) fn render_commands(commands: &[DrawCommand], canvas: &mut Canvas) {
+    let mut depth = 0;
+    for command in commands {
+        match command {
+            DrawCommand::FillPath {
+                path,
+                paint,
+                fill_rule,
+                transform,
+            } => {
+                let ts = canvas.transform();
+                canvas.set_transform(ts.pre_concat(*transform));
+                canvas.fill_path(path, paint, *fill_rule);
+                canvas.set_transform(ts);
+            }
+            DrawCommand::StrokePath {
+                path,
+                paint,
+                stroke,
+                transform,
+            } => {
+                let ts = canvas.transform();
+                canvas.set_transform(ts.pre_concat(*transform));
+                canvas.stroke_path(path, paint, stroke);
+                canvas.set_transform(ts);
+            }
+            DrawCommand::DrawPixmap {
+                x,
+                y,
+                pixmap,
+                paint,
+                transform,
+            } => {
+                let ts = canvas.transform();
+                canvas.set_transform(ts.pre_concat(*transform));
+                canvas.draw_pixmap(*x, *y, pixmap.as_ref().as_ref(), paint);
+                canvas.set_transform(ts);
+            }
+            DrawCommand::PushClip {
+                path,
+                fill_rule,
+                anti_alias,
+            } => {
+                canvas.save();
+                canvas.clip_path(path, *fill_rule, *anti_alias);
+                depth += 1;
+            }
+            DrawCommand::PushTransform(ts) => {
+                canvas.save();
+                canvas.concat(*ts);
+                depth += 1;
+            }
+            DrawCommand::PopClip | DrawCommand::PopTransform => {
+                if depth > 0 {
+                    canvas.restore();
+                    depth -= 1;
+                }
+            }
+        }
+    }
+
+    for _ in 0..depth {
+        canvas.restore();
+    }
+}
@@ -17,6 +17,8 @@ use bytemuck::cast;
 #[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))]
 use core::arch::aarch64::uint16x8_t;
 
+// Arithmetic wraps, just like SIMD instructions do. Some blend modes compute
+// both branches and discard an overflowed one, which must not panic in debug builds.
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, PartialEq, Default, Debug)]
 pub struct u16x16(pub [u16; 16]);
@@ -139,7 +141,7 @@ impl core::ops::Add<u16x16> for u16x16 {
             if #[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))] {
                 impl_aarch64_call!(vaddq_u16, self, rhs)
             } else {
-                impl_u16x16_op!(self, add, rhs)
+                impl_u16x16_op!(self, wrapping_add, rhs)
             }
         }
     }
@@ -154,7 +156,7 @@ impl core::ops::Sub<u16x16> for u16x16 {
             if #[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))] {
                 impl_aarch64_call!(vsubq_u16, self, rhs)
             } else {
-                impl_u16x16_op!(self, sub, rhs)
+                impl_u16x16_op!(self, wrapping_sub, rhs)
             }
         }
     }
@@ -169,7 +171,7 @@ impl core::ops::Mul<u16x16> for u16x16 {
             if #[cfg(all(feature = "simd", target_arch = "aarch64", target_feature = "neon"))] {
                 impl_aarch64_call!(vmulq_u16, self, rhs)
             } else {
-                impl_u16x16_op!(self, mul, rhs)
+                impl_u16x16_op!(self, wrapping_mul, rhs)
             }
         }
     }
@@ -0,0 +1,267 @@
+use tiny_skia::*;
+
+fn paint() -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint
+}
+
+#[test]
+fn transform_stack() {
+    let rect = Rect::from_xywh(10.0, 10.0, 30.0, 20.0).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.translate(20.0, 10.0);
+    canvas.save();
+    canvas.scale(2.0, 1.5);
+    canvas.rotate(15.0);
+    assert_eq!(canvas.save_count(), 1);
+    canvas.fill_rect(rect, &paint());
+    canvas.restore();
+    assert_eq!(canvas.save_count(), 0);
+    assert_eq!(canvas.transform(), Transform::from_translate(20.0, 10.0));
+    canvas.fill_rect(rect, &paint());
+    drop(canvas);
+
+    let ts = Transform::from_translate(20.0, 10.0);
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_rect(rect, &paint(), ts.pre_scale(2.0, 1.5).pre_concat(Transform::from_rotate(15.0)), None);
+    expected.fill_rect(rect, &paint(), ts, None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn clip_stack() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    let clip1 = Rect::from_xywh(0.0, 0.0, 60.0, 100.0).unwrap();
+    let clip2 = Rect::from_xywh(0.0, 40.0, 100.0, 60.0).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.save();
+    canvas.clip_rect(clip1, false);
+    canvas.save();
+    canvas.clip_rect(clip2, false);
+    canvas.fill_path(&path, &paint(), FillRule::Winding);
+    canvas.restore();
+    canvas.stroke_path(&path, &paint(), &Stroke::default());
+    canvas.restore();
+    assert!(canvas.clip_mask().is_none());
+    canvas.fill_rect(Rect::from_xywh(90.0, 0.0, 10.0, 10.0).unwrap(), &paint());
+    drop(canvas);
+
+    let mut mask1 = Mask::new(100, 100).unwrap();
+    mask1.fill_path(&PathBuilder::from_rect(clip1), FillRule::Winding, false, Transform::identity());
+    let mut mask2 = mask1.clone();
+    mask2.intersect_path(&PathBuilder::from_rect(clip2), FillRule::Winding, false, Transform::identity());
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_path(&path, &paint(), FillRule::Winding, Transform::identity(), Some(&mask2));
+    expected.stroke_path(&path, &paint(), &Stroke::default(), Transform::identity(), Some(&mask1));
+    expected.fill_rect(Rect::from_xywh(90.0, 0.0, 10.0, 10.0).unwrap(), &paint(), Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn clip_uses_transform() {
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.translate(50.0, 50.0);
+    canvas.clip_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap(), false);
+    canvas.set_transform(Transform::identity());
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap(), &paint());
+    drop(canvas);
+
+    let color = pixmap.pixel(55, 55).unwrap();
+    assert_ne!(color.alpha(), 0);
+    assert_eq!(pixmap.pixel(45, 45).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(65, 65).unwrap().alpha(), 0);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "restore without a matching save")]
+fn unbalanced_restore() {
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.restore();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "save without a matching restore")]
+fn unbalanced_save() {
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.save();
+}
+
+fn opaque(r: u8, g: u8, b: u8) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, 255);
+    paint
+}
+
+#[test]
+fn layer_opacity() {
+    let rect1 = Rect::from_xywh(10.0, 10.0, 50.0, 50.0).unwrap();
+    let rect2 = Rect::from_xywh(30.0, 30.0, 50.0, 50.0).unwrap();
+    let layer_paint = PixmapPaint { opacity: 0.5, ..PixmapPaint::default() };
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 20.0).unwrap(), &opaque(0, 0, 255));
+    canvas.save_layer(None, &layer_paint);
+    canvas.fill_rect(rect1, &opaque(255, 0, 0));
+    canvas.fill_rect(rect2, &opaque(0, 255, 0));
+    canvas.restore();
+    drop(canvas);
+
+    let mut group = Pixmap::new(100, 100).unwrap();
+    group.fill_rect(rect1, &opaque(255, 0, 0), Transform::identity(), None);
+    group.fill_rect(rect2, &opaque(0, 255, 0), Transform::identity(), None);
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 20.0).unwrap(), &opaque(0, 0, 255), Transform::identity(), None);
+    expected.draw_pixmap(0, 0, group.as_ref(), &layer_paint, Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+
+    // The overlapping area is not darker, unlike when drawing each rect with half opacity.
+    assert_eq!(pixmap.pixel(40, 40), pixmap.pixel(70, 70));
+}
+
+#[test]
+fn layer_bounds() {
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.translate(10.0, 10.0);
+    canvas.save_layer(Some(Rect::from_xywh(10.0, 10.0, 20.5, 20.0).unwrap()), &PixmapPaint::default());
+    // Coordinates are still in the canvas space.
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap(), &opaque(255, 0, 0));
+    canvas.restore();
+    drop(canvas);
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_rect(Rect::from_xywh(20.0, 20.0, 21.0, 20.0).unwrap(), &opaque(255, 0, 0), Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn layer_clip() {
+    let circle = PathBuilder::from_circle(50.0, 50.0, 30.0).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.clip_path(&circle, FillRule::Winding, true);
+    canvas.save_layer(None, &PixmapPaint { blend_mode: BlendMode::Source, ..PixmapPaint::default() });
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 50.0).unwrap(), &opaque(255, 0, 0));
+    canvas.restore();
+    drop(canvas);
+
+    // Only the clip area is affected, even with the `Source` blend mode.
+    assert_eq!(pixmap.pixel(50, 60).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(10, 10).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(50, 30).unwrap().alpha(), 255);
+}
+
+#[test]
+fn nested_layers() {
+    let rect = Rect::from_xywh(20.0, 20.0, 60.0, 60.0).unwrap();
+    let half = PixmapPaint { opacity: 0.5, ..PixmapPaint::default() };
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.save_layer(Some(Rect::from_xywh(10.0, 10.0, 80.0, 80.0).unwrap()), &half);
+    canvas.fill_rect(rect, &opaque(255, 0, 0));
+    canvas.save_layer(Some(Rect::from_xywh(40.0, 40.0, 50.0, 50.0).unwrap()), &half);
+    canvas.fill_rect(rect, &opaque(0, 0, 255));
+    assert_eq!(canvas.save_count(), 2);
+    canvas.restore();
+    canvas.restore();
+    drop(canvas);
+
+    // Inner layer is composited onto the outer one first.
+    let mut inner = Pixmap::new(100, 100).unwrap();
+    inner.fill_rect(Rect::from_xywh(40.0, 40.0, 40.0, 40.0).unwrap(), &opaque(0, 0, 255), Transform::identity(), None);
+    let mut outer = Pixmap::new(100, 100).unwrap();
+    outer.fill_rect(rect, &opaque(255, 0, 0), Transform::identity(), None);
+    outer.draw_pixmap(0, 0, inner.as_ref(), &half, Transform::identity(), None);
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.draw_pixmap(0, 0, outer.as_ref(), &half, Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn empty_layer() {
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    canvas.save_layer(Some(Rect::from_xywh(200.0, 200.0, 10.0, 10.0).unwrap()), &PixmapPaint::default());
+    canvas.clip_rect(Rect::from_xywh(0.0, 0.0, 50.0, 50.0).unwrap(), false);
+    canvas.save_layer(None, &PixmapPaint::default());
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 100.0).unwrap(), &opaque(255, 0, 0));
+    canvas.restore();
+    canvas.restore();
+    canvas.fill_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap(), &opaque(255, 0, 0));
+    drop(canvas);
+
+    assert_eq!(pixmap.pixel(5, 5).unwrap().alpha(), 255);
+    assert_eq!(pixmap.pixel(20, 20).unwrap().alpha(), 0);
+}
+
+#[test]
+fn y_up_coordinate_system() {
+    let rect = Rect::from_xywh(10.0, 10.0, 30.0, 20.0).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 80).unwrap();
+    let mut canvas = Canvas::with_coordinate_system(pixmap.as_mut(), CoordinateSystem::YUp);
+    assert_eq!(canvas.coordinate_system(), CoordinateSystem::YUp);
+    // The flip is not a part of the canvas transform.
+    canvas.set_transform(Transform::from_translate(5.0, 0.0));
+    canvas.clip_rect(Rect::from_xywh(0.0, 0.0, 100.0, 25.0).unwrap(), false);
+    canvas.fill_rect(rect, &paint());
+    drop(canvas);
+
+    // Bottom-left origin, so the rect is 10px above the bottom edge
+    // and the clip cuts its top.
+    let mut expected = Pixmap::new(100, 80).unwrap();
+    expected.fill_rect(Rect::from_xywh(15.0, 55.0, 30.0, 15.0).unwrap(), &paint(), Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn y_up_pixmap_orientation() {
+    // Red top row and blue bottom row.
+    let mut image = Pixmap::new(2, 2).unwrap();
+    image.fill_rect(Rect::from_xywh(0.0, 0.0, 2.0, 1.0).unwrap(), &opaque(255, 0, 0), Transform::identity(), None);
+    image.fill_rect(Rect::from_xywh(0.0, 1.0, 2.0, 1.0).unwrap(), &opaque(0, 0, 255), Transform::identity(), None);
+
+    let mut pixmap = Pixmap::new(20, 20).unwrap();
+    let mut canvas = Canvas::with_coordinate_system(pixmap.as_mut(), CoordinateSystem::YUp);
+    canvas.draw_pixmap(3, 4, image.as_ref(), &PixmapPaint::default());
+    canvas.save_layer(None, &PixmapPaint::default());
+    canvas.draw_pixmap(10, 0, image.as_ref(), &PixmapPaint::default());
+    canvas.restore();
+    drop(canvas);
+
+    // The bottom-left corner is at (3, 4), and the image is still upright.
+    let mut expected = Pixmap::new(20, 20).unwrap();
+    expected.draw_pixmap(3, 14, image.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+    expected.draw_pixmap(10, 18, image.as_ref(), &PixmapPaint::default(), Transform::identity(), None);
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn y_down_is_default() {
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    let canvas = Canvas::new(pixmap.as_mut());
+    assert_eq!(canvas.coordinate_system(), CoordinateSystem::YDown);
+    assert_eq!(CoordinateSystem::default(), CoordinateSystem::YDown);
+}
@@ -168,3 +168,101 @@ fn closed() {
     let expected = Pixmap::load_png("tests/images/dash/closed.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+#[test]
+fn zero_len_subpath() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 80.0);
+    pb.close();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(40.0, 10.0);
+    pb.move_to(60.0, 60.0);
+    pb.line_to(60.0, 60.0);
+    let path = pb.finish().unwrap();
+
+    // Zero-length subpaths are preserved as zero-length lines, so caps can be drawn.
+    let dash = StrokeDash::new(vec![10.0, 5.0], 0.0).unwrap();
+    assert_eq!(
+        format!("{:?}", path.dash(&dash, 1.0).unwrap()),
+        "Path { segments: \"M 50 80 L 50 80 M 10 10 L 20 10 M 25 10 L 35 10 M 60 60 L 60 60\", \
+         bounds: Rect { left: 10.0, top: 10.0, right: 60.0, bottom: 80.0 } }"
+    );
+
+    // Unless the dash pattern starts with a gap.
+    let dash = StrokeDash::new(vec![10.0, 5.0], 12.0).unwrap();
+    assert_eq!(
+        format!("{:?}", path.dash(&dash, 1.0).unwrap()),
+        "Path { segments: \"M 13 10 L 23 10 M 28 10 L 38 10\", \
+         bounds: Rect { left: 13.0, top: 10.0, right: 38.0, bottom: 10.0 } }"
+    );
+}
+
+#[test]
+fn zero_len_dashes() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 50.0);
+    pb.line_to(90.0, 50.0);
+    pb.move_to(50.0, 80.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    // Point markers.
+    let mut stroke = Stroke::default();
+    stroke.dash = StrokeDash::new(vec![0.0, 20.0], 0.0);
+    stroke.width = 6.0;
+    stroke.line_cap = LineCap::Round;
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    assert_ne!(pixmap.pixel(10, 50).unwrap().alpha(), 0);
+    assert_ne!(pixmap.pixel(30, 50).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(20, 50).unwrap().alpha(), 0);
+    assert_ne!(pixmap.pixel(50, 80).unwrap().alpha(), 0);
+
+    // Nothing is drawn with butt caps.
+    stroke.line_cap = LineCap::Butt;
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+}
+
+#[test]
+fn apply() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 30.0).unwrap();
+    let dash = StrokeDash::new(vec![15.0, 5.0], 3.0).unwrap();
+
+    let dashed = dash.apply(&path).unwrap();
+    assert_eq!(Some(dashed.clone()), path.dash(&dash, 1.0));
+
+    // Dashes must follow the curve.
+    for segment in dashed.segments() {
+        let p = match segment {
+            PathSegment::MoveTo(p) => p,
+            PathSegment::LineTo(p) => p,
+            PathSegment::QuadTo(_, p) => p,
+            PathSegment::CubicTo(_, _, p) => p,
+            PathSegment::Close => continue,
+        };
+        let d = ((p.x - 50.0).powi(2) + (p.y - 50.0).powi(2)).sqrt();
+        assert!((d - 30.0).abs() < 0.5, "{:?} is not on the circle", p);
+    }
+
+    // Stroking a pre-dashed path is the same as stroking with a dash.
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut stroke = Stroke::default();
+    stroke.width = 4.0;
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.stroke_path(&dashed, &paint, &stroke, Transform::identity(), None);
+
+    stroke.dash = Some(dash);
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    pixmap2.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
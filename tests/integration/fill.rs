@@ -84,6 +84,44 @@ fn float_rect() {
     assert_eq!(pixmap, expected);
 }
 
+#[test]
+fn flipped_rect() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = false;
+
+    // Corners from a drag in the top-left direction.
+    let rect = Rect::from_corners(Point::from_xy(90.0, 85.0), Point::from_xy(10.0, 15.0)).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+
+    let expected = Pixmap::load_png("tests/images/fill/int-rect.png").unwrap();
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn empty_rect() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let p = Point::from_xy(10.5, 15.5);
+    let rect = Rect::from_corners(p, p).unwrap();
+    let horizontal = Rect::from_corners(p, Point::from_xy(50.0, 15.5)).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    for ts in [Transform::identity(), Transform::from_rotate(30.0)] {
+        for anti_alias in [true, false] {
+            paint.anti_alias = anti_alias;
+            pixmap.fill_rect(rect, &paint, ts, None);
+            pixmap.fill_rect(horizontal, &paint, ts, None);
+            pixmap.fill_rect_fast(rect, &paint, ts, None);
+        }
+    }
+
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+}
+
 #[test]
 fn int_rect_aa() {
     let mut paint = Paint::default();
@@ -634,3 +672,925 @@ fn fill_rect() {
     let expected = Pixmap::load_png("tests/images/canvas/fill-rect.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+#[test]
+fn fill_rect_fast() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_rect_fast(
+        Rect::from_ltrb(0.0, 0.0, 80.0, 80.0).unwrap(),
+        &paint,
+        Transform::from_translate(10.0, 10.0),
+        None,
+    );
+
+    let expected = Pixmap::load_png("tests/images/fill/memset2d.png").unwrap();
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn fill_rect_fast_out_of_bounds() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_rect_fast(
+        Rect::from_ltrb(50.0, 50.0, 120.0, 120.0).unwrap(),
+        &paint,
+        Transform::identity(),
+        None,
+    );
+
+    let expected = Pixmap::load_png("tests/images/fill/memset2d-2.png").unwrap();
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn fill_rect_fast_fallback() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = true;
+
+    let rect = Rect::from_xywh(20.3, 10.4, 50.5, 30.2).unwrap();
+    let ts = Transform::from_row(1.2, 0.3, -0.7, 0.8, 12.0, 15.3);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_rect_fast(rect, &paint, ts, None);
+
+    let expected = Pixmap::load_png("tests/images/canvas/fill-rect.png").unwrap();
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn rasterize_path_spans() {
+    let path = PathBuilder::from_rect(Rect::from_ltrb(10.0, 5.0, 20.0, 8.0).unwrap());
+
+    let mut spans = Vec::new();
+    rasterize_path(
+        &path,
+        FillRule::Winding,
+        false,
+        IntRect::from_xywh(0, 0, 100, 100).unwrap(),
+        &mut |y, x, len, coverage| spans.push((y, x, len, coverage.to_vec())),
+    );
+
+    assert_eq!(spans, vec![
+        (5, 10, 10, vec![255; 10]),
+        (6, 10, 10, vec![255; 10]),
+        (7, 10, 10, vec![255; 10]),
+    ]);
+}
+
+#[test]
+fn rasterize_path_negative_clip() {
+    let path = PathBuilder::from_rect(Rect::from_ltrb(-20.0, -8.0, -10.0, -5.0).unwrap());
+
+    let mut spans = Vec::new();
+    rasterize_path(
+        &path,
+        FillRule::Winding,
+        false,
+        IntRect::from_xywh(-50, -50, 100, 100).unwrap(),
+        &mut |y, x, len, _| spans.push((y, x, len)),
+    );
+
+    assert_eq!(spans, vec![(-8, -20, 10), (-7, -20, 10), (-6, -20, 10)]);
+}
+
+#[test]
+fn rasterize_path_aa() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 30.5).unwrap();
+
+    let mut last_y = i32::MIN;
+    let mut partial = 0;
+    rasterize_path(
+        &path,
+        FillRule::Winding,
+        true,
+        IntRect::from_xywh(0, 0, 100, 100).unwrap(),
+        &mut |y, _, len, coverage| {
+            assert!(y >= last_y);
+            assert_eq!(len as usize, coverage.len());
+            last_y = y;
+            partial += coverage.iter().filter(|a| **a != 0 && **a != 255).count();
+        },
+    );
+
+    assert!(partial > 0);
+}
+
+#[test]
+fn blend_in_linear_space() {
+    let mut paint = Paint::default();
+    paint.set_color(Color::WHITE);
+    paint.anti_alias = true;
+
+    let path = PathBuilder::from_rect(Rect::from_ltrb(10.5, 10.0, 20.0, 20.0).unwrap());
+
+    let mut pixmap = Pixmap::new(30, 30).unwrap();
+    pixmap.fill(Color::BLACK);
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+    let srgb_edge = pixmap.pixel(10, 15).unwrap();
+    let srgb_inner = pixmap.pixel(15, 15).unwrap();
+
+    paint.blend_in_linear_space = true;
+    pixmap.fill(Color::BLACK);
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+    let linear_edge = pixmap.pixel(10, 15).unwrap();
+    let linear_inner = pixmap.pixel(15, 15).unwrap();
+
+    // 50% coverage in sRGB is ~128, while in linear space it's ~188.
+    assert!((srgb_edge.red() as i32 - 128).abs() <= 2);
+    assert!((linear_edge.red() as i32 - 188).abs() <= 2);
+    assert_eq!(linear_edge.alpha(), 255);
+
+    // Fully covered pixels are not affected.
+    assert_eq!(srgb_inner, linear_inner);
+}
+
+#[test]
+fn supersample_factor_1() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 30.0).unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    paint.set_anti_alias_mode(AntiAliasMode::Supersample { factor: 1 });
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn supersample() {
+    // Estimates a circle coverage using 32x32 samples.
+    fn circle_coverage(x: u32, y: u32) -> f32 {
+        let mut covered = 0;
+        for i in 0..32 {
+            for j in 0..32 {
+                let dx = x as f32 + (i as f32 + 0.5) / 32.0 - 50.0;
+                let dy = y as f32 + (j as f32 + 0.5) / 32.0 - 50.0;
+                if dx * dx + dy * dy <= 30.0 * 30.0 {
+                    covered += 1;
+                }
+            }
+        }
+
+        covered as f32 / 1024.0
+    }
+
+    let path = PathBuilder::from_circle(50.0, 50.0, 30.0).unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(0, 0, 0, 255);
+    paint.set_anti_alias_mode(AntiAliasMode::Supersample { factor: 8 });
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let mut partial = 0;
+    for y in 15..85 {
+        for x in 15..85 {
+            let alpha = pixmap.pixel(x, y).unwrap().alpha();
+            let expected = circle_coverage(x, y) * 255.0;
+            assert!((alpha as f32 - expected).abs() <= 12.0);
+            if alpha != 0 && alpha != 255 {
+                partial += 1;
+            }
+        }
+    }
+
+    assert!(partial > 0);
+}
+
+#[test]
+fn supersample_half_covered_rect() {
+    let path = PathBuilder::from_rect(Rect::from_ltrb(10.5, 10.0, 20.0, 20.0).unwrap());
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(0, 0, 0, 255);
+    paint.set_anti_alias_mode(AntiAliasMode::Supersample { factor: 4 });
+
+    let mut pixmap = Pixmap::new(30, 30).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    // Box-averaged: 8 out of 16 samples.
+    assert_eq!(pixmap.pixel(10, 15).unwrap().alpha(), 128);
+    assert_eq!(pixmap.pixel(15, 15).unwrap().alpha(), 255);
+    assert_eq!(pixmap.pixel(20, 15).unwrap().alpha(), 0);
+}
+
+fn fill_to_pixmap(path: &Path, fill_rule: FillRule) -> Pixmap {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = false;
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_path(path, &paint, fill_rule, Transform::identity(), None);
+    pixmap
+}
+
+#[test]
+fn to_even_odd_equivalent() {
+    // Two overlapping squares with the same direction.
+    // The overlap is filled by the nonzero rule, but not by the even-odd one.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(10.0, 10.0, 50.0, 50.0).unwrap());
+    pb.push_rect(Rect::from_xywh(40.0, 40.0, 50.0, 50.0).unwrap());
+    let path = pb.finish().unwrap();
+
+    let converted = path.to_even_odd_equivalent().unwrap();
+    assert_eq!(
+        fill_to_pixmap(&converted, FillRule::EvenOdd),
+        fill_to_pixmap(&path, FillRule::Winding)
+    );
+    assert_eq!(
+        fill_to_pixmap(&converted, FillRule::Winding),
+        fill_to_pixmap(&path, FillRule::Winding)
+    );
+}
+
+#[test]
+fn to_even_odd_equivalent_self_intersecting() {
+    // A pentagram. The center has a winding of 2.
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 5.0);
+    pb.line_to(77.0, 90.0);
+    pb.line_to(5.0, 35.0);
+    pb.line_to(95.0, 35.0);
+    pb.line_to(23.0, 90.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+
+    let converted = path.to_even_odd_equivalent().unwrap();
+    let pixmap = fill_to_pixmap(&converted, FillRule::EvenOdd);
+    let expected = fill_to_pixmap(&path, FillRule::Winding);
+    for (x, y) in [(50, 50), (50, 20), (20, 40), (30, 80), (5, 90), (50, 85)] {
+        assert_eq!(pixmap.pixel(x, y), expected.pixel(x, y));
+    }
+    assert_ne!(pixmap.pixel(50, 50).unwrap().alpha(), 0);
+}
+
+#[test]
+fn to_nonzero_equivalent() {
+    // A square with a hole in the same direction.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(10.0, 10.0, 80.0, 80.0).unwrap());
+    pb.push_rect(Rect::from_xywh(30.0, 30.0, 40.0, 40.0).unwrap());
+    let path = pb.finish().unwrap();
+
+    let converted = path.to_nonzero_equivalent().unwrap();
+    assert_eq!(
+        fill_to_pixmap(&converted, FillRule::Winding),
+        fill_to_pixmap(&path, FillRule::EvenOdd)
+    );
+    assert_eq!(converted.bounds(), path.bounds());
+}
+
+#[test]
+fn to_even_odd_equivalent_curves() {
+    let mut pb = PathBuilder::new();
+    pb.push_circle(40.0, 50.0, 30.0);
+    pb.push_circle(60.0, 50.0, 30.0);
+    let path = pb.finish().unwrap();
+
+    let converted = path.to_even_odd_equivalent().unwrap();
+    let pixmap = fill_to_pixmap(&converted, FillRule::EvenOdd);
+    assert_ne!(pixmap.pixel(50, 50).unwrap().alpha(), 0);
+    assert_ne!(pixmap.pixel(15, 50).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(50, 15).unwrap().alpha(), 0);
+}
+
+#[test]
+fn to_even_odd_equivalent_empty() {
+    // Opposite directions cancel each other out.
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(50.0, 10.0);
+    pb.line_to(50.0, 50.0);
+    pb.line_to(10.0, 10.0);
+    pb.line_to(50.0, 50.0);
+    pb.line_to(50.0, 10.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+    assert!(path.to_even_odd_equivalent().is_none());
+}
+
+#[test]
+fn to_even_odd_equivalent_overflow() {
+    // Finite, but flattening overflows.
+    let mut pb = PathBuilder::new();
+    pb.move_to(-1e38, 0.0);
+    pb.cubic_to(1e38, 1e38, -1e38, 1e38, 1e38, 0.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+    assert!(path.to_even_odd_equivalent().is_none());
+    assert!(path.to_nonzero_equivalent().is_none());
+}
+
+fn alpha_stats(a: &Pixmap, b: &Pixmap) -> (u8, usize, u32, u32) {
+    let mut max_diff = 0;
+    let mut diff_count = 0;
+    for (a, b) in a.pixels().iter().zip(b.pixels()) {
+        let diff = (a.alpha() as i16 - b.alpha() as i16).unsigned_abs() as u8;
+        max_diff = max_diff.max(diff);
+        if diff != 0 {
+            diff_count += 1;
+        }
+    }
+
+    let sum = |p: &Pixmap| p.pixels().iter().map(|c| c.alpha() as u32).sum();
+    (max_diff, diff_count, sum(a), sum(b))
+}
+
+#[test]
+fn fill_circle_matches_path() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    for &(cx, cy, r) in &[(50.0, 50.0, 30.0), (20.3, 70.6, 4.2), (90.0, 10.0, 25.5)] {
+        let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+        pixmap1.fill_circle(Point::from_xy(cx, cy), r, &paint, Transform::identity(), None);
+
+        // Supersampling is more precise than the analytic path AA.
+        let mut paint = paint.clone();
+        paint.anti_alias_mode = AntiAliasMode::Supersample { factor: 16 };
+        let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+        let path = PathBuilder::from_circle(cx, cy, r).unwrap();
+        pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+        let (max_diff, _, sum1, sum2) = alpha_stats(&pixmap1, &pixmap2);
+        assert!(max_diff <= 16);
+        assert!((sum1 as f32 / sum2 as f32 - 1.0).abs() < 0.01);
+    }
+}
+
+#[test]
+fn fill_ellipse_no_aa() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = false;
+
+    let rect = Rect::from_xywh(10.3, 20.0, 70.0, 45.5).unwrap();
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.fill_ellipse(rect, &paint, Transform::identity(), None);
+
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    let path = PathBuilder::from_oval(rect).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    // Only some pixels on the edge can be different.
+    let (_, diff_count, _, _) = alpha_stats(&pixmap1, &pixmap2);
+    assert!(diff_count <= 25);
+}
+
+#[test]
+fn fill_ellipse_scaled() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    let ts = Transform::from_row(2.0, 0.0, 0.0, -1.5, 10.0, 90.0);
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.fill_circle(Point::from_xy(20.0, 20.0), 15.0, &paint, ts, None);
+
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    let rect = Rect::from_xywh(20.0, 37.5, 60.0, 45.0).unwrap();
+    pixmap2.fill_ellipse(rect, &paint, Transform::identity(), None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn fill_ellipse_rotated() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    // Falls back to paths.
+    let rect = Rect::from_xywh(-30.0, -15.0, 60.0, 30.0).unwrap();
+    let ts = Transform::from_rotate(30.0).post_translate(50.0, 50.0);
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.fill_ellipse(rect, &paint, ts, None);
+
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    let path = PathBuilder::from_oval(rect).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, ts, None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+
+#[test]
+fn render_mode_deterministic() {
+    assert_eq!(Paint::default().render_mode, RenderMode::Fast);
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(200, 100, 50, 255);
+    paint.blend_mode = BlendMode::ColorBurn;
+    paint.render_mode = RenderMode::Deterministic;
+
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    pixmap.fill(Color::from_rgba8(150, 120, 30, 255));
+    let rect = Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap();
+    pixmap.fill_rect(rect, &paint, Transform::identity(), None);
+
+    // A color burn with exact division. All colors are opaque.
+    let burn = |s: u8, d: u8| {
+        let s = s as f32 / 255.0;
+        let d = d as f32 / 255.0;
+        let v = if d == 1.0 {
+            d
+        } else if s == 0.0 {
+            0.0
+        } else {
+            1.0 - (1.0f32).min((1.0 - d) / s)
+        };
+        (v * 255.0).round() as u8
+    };
+
+    let expected = PremultipliedColorU8::from_rgba(burn(200, 150), burn(100, 120), burn(50, 30), 255);
+    assert!(pixmap.pixels().iter().all(|p| Some(*p) == expected));
+}
+
+#[test]
+fn render_mode_deterministic_linear() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(255, 200, 100, 128);
+    paint.blend_in_linear_space = true;
+
+    let mut pixmap1 = Pixmap::new(10, 10).unwrap();
+    pixmap1.fill(Color::from_rgba8(20, 80, 160, 255));
+    let mut pixmap2 = pixmap1.clone();
+
+    let rect = Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap();
+    pixmap1.fill_rect(rect, &paint, Transform::identity(), None);
+    paint.render_mode = RenderMode::Deterministic;
+    pixmap2.fill_rect(rect, &paint, Transform::identity(), None);
+
+    // A portable transfer function is slightly less precise.
+    assert!(pixmap1.diff(pixmap2.as_ref(), 0).unwrap().max <= 1);
+}
+
+#[test]
+fn stored_fill_rule() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    // Two overlapping rects, where the overlap is a hole using the even-odd rule.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(10.0, 10.0, 50.0, 50.0).unwrap());
+    pb.push_rect(Rect::from_xywh(40.0, 40.0, 50.0, 50.0).unwrap());
+    let mut path = pb.finish().unwrap();
+    path.set_fill_rule(Some(FillRule::EvenOdd));
+
+    let draw = |path: &Path, fill_rule| {
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        pixmap.fill_path(path, &paint, fill_rule, Transform::identity(), None);
+        pixmap
+    };
+
+    let even_odd = draw(&path, FillRule::EvenOdd);
+    let winding = draw(&path, FillRule::Winding);
+    assert_ne!(even_odd, winding);
+
+    // The stored rule is used only when requested.
+    assert_eq!(draw(&path, path.fill_rule_or(FillRule::Winding)), even_odd);
+    path.set_fill_rule(None);
+    assert_eq!(draw(&path, path.fill_rule_or(FillRule::Winding)), winding);
+}
+
+#[test]
+fn curve_tolerance() {
+    // Flattening makes a circle smaller, therefore its area is a good faceting metric.
+    fn area_error(tolerance: f32) -> f32 {
+        let path = PathBuilder::from_circle(250.0, 250.0, 240.0).unwrap();
+
+        let mut paint = Paint::default();
+        paint.curve_tolerance = tolerance;
+
+        let mut pixmap = Pixmap::new(500, 500).unwrap();
+        pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+        let area: u32 = pixmap.pixels().iter().map(|p| p.alpha() as u32).sum();
+        let expected = core::f32::consts::PI * 240.0 * 240.0;
+        (expected - area as f32 / 255.0).abs()
+    }
+
+    let coarse = area_error(16.0);
+    let default = area_error(0.125);
+    let fine = area_error(0.01);
+    assert!(coarse > default * 2.0);
+    assert!(fine < default / 2.0);
+
+    // Out of range values are clamped.
+    assert_eq!(area_error(0.0), area_error(1.0 / 1024.0));
+    assert_eq!(area_error(f32::NAN), area_error(1.0 / 1024.0));
+}
+
+#[test]
+fn max_segments() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+
+    let mut paint = Paint::default();
+    paint.max_segments = Some(10_000);
+    assert_eq!(pixmap.try_fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None), Ok(()));
+    let expected = pixmap.clone();
+
+    // Lines are never split.
+    let rect = PathBuilder::from_rect(Rect::from_xywh(10.0, 10.0, 20.0, 20.0).unwrap());
+    paint.max_segments = Some(4);
+    assert_eq!(pixmap.try_fill_path(&rect, &paint, FillRule::Winding, Transform::identity(), None), Ok(()));
+
+    pixmap.fill(Color::TRANSPARENT);
+    for anti_alias in [false, true] {
+        paint.anti_alias = anti_alias;
+        assert_eq!(
+            pixmap.try_fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None),
+            Err(TinySkiaError::TooComplex)
+        );
+        // The transform is applied before counting.
+        assert_eq!(
+            pixmap.try_fill_path(&path, &paint, FillRule::Winding, Transform::from_scale(0.5, 0.5), None),
+            Err(TinySkiaError::TooComplex)
+        );
+    }
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+
+    // Thick strokes are limited as well.
+    let stroke = Stroke { width: 5.0, ..Stroke::default() };
+    pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+
+    // No limit by default.
+    let paint = Paint::default();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn prepared_path() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.3, 5.7);
+    pb.quad_to(40.1, 0.2, 35.9, 30.4);
+    pb.cubic_to(30.2, 50.6, 5.1, 20.3, 20.7, 45.8);
+    pb.line_to(2.4, 12.9);
+    pb.close();
+    pb.push_circle(25.0, 25.0, 10.0);
+    let path = pb.finish().unwrap();
+
+    let gradient = LinearGradient::new(
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(50.0, 50.0),
+        vec![GradientStop::new(0.0, Color::BLACK), GradientStop::new(1.0, Color::WHITE)],
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap();
+
+    for (anti_alias, fill_rule) in [(false, FillRule::Winding), (true, FillRule::EvenOdd)] {
+        let mut paint = Paint::default();
+        paint.anti_alias = anti_alias;
+        paint.shader = gradient.clone();
+        let prepared = PreparedPath::new(&path, fill_rule, &paint);
+
+        // The last offsets are partially outside of the pixmap.
+        for (x, y) in [(0, 0), (7, 13), (50, 3), (-5, 20), (80, 90)] {
+            let mut expected = Pixmap::new(128, 128).unwrap();
+            let ts = Transform::from_translate(x as f32, y as f32);
+            expected.fill_path(&path, &paint, fill_rule, ts, None);
+
+            let mut pixmap = Pixmap::new(128, 128).unwrap();
+            pixmap.fill_prepared_path(&prepared, &paint, x, y, None);
+            assert_eq!(pixmap, expected);
+        }
+    }
+}
+
+#[test]
+fn prepared_path_fuzz() {
+    // A simple xorshift generator, so the test is reproducible.
+    let mut state = 0x2545_f491_u32;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+    let mut coord = |max: f32| (next() % 1_000_000) as f32 / 1_000_000.0 * max;
+
+    for i in 0..200 {
+        // Edges are reused mostly for lines and curves monotonic in Y, like circle arcs.
+        let path = match i % 3 {
+            0 => PathBuilder::from_circle(10.0 + coord(40.0), 10.0 + coord(40.0), 1.0 + coord(9.0)),
+            1 => {
+                let mut pb = PathBuilder::new();
+                pb.move_to(coord(64.0), coord(64.0));
+                for _ in 0..4 {
+                    pb.line_to(coord(64.0), coord(64.0));
+                }
+                pb.close();
+                pb.finish()
+            }
+            _ => {
+                let mut pb = PathBuilder::new();
+                pb.move_to(coord(64.0), coord(64.0));
+                for j in 0..4 {
+                    match (i + j) % 3 {
+                        0 => pb.line_to(coord(64.0), coord(64.0)),
+                        1 => pb.quad_to(coord(64.0), coord(64.0), coord(64.0), coord(64.0)),
+                        _ => pb.cubic_to(coord(64.0), coord(64.0), coord(64.0), coord(64.0), coord(64.0), coord(64.0)),
+                    }
+                }
+                pb.close();
+                pb.finish()
+            }
+        };
+        let path = match path {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(50, 127, 150, 200);
+        paint.anti_alias = i % 2 == 0;
+        let prepared = PreparedPath::new(&path, FillRule::Winding, &paint);
+
+        let x = coord(900.0) as i32;
+        let y = coord(900.0) as i32;
+
+        let mut expected = Pixmap::new(1000, 1000).unwrap();
+        let ts = Transform::from_translate(x as f32, y as f32);
+        expected.fill_path(&path, &paint, FillRule::Winding, ts, None);
+
+        let mut pixmap = Pixmap::new(1000, 1000).unwrap();
+        pixmap.fill_prepared_path(&prepared, &paint, x, y, None);
+        assert!(pixmap == expected, "path {} at {}x{}", i, x, y);
+    }
+}
+
+#[test]
+fn prepared_path_different_paint() {
+    let path = PathBuilder::from_circle(30.0, 30.0, 20.0).unwrap();
+    let paint = Paint::default();
+    let prepared = PreparedPath::new(&path, FillRule::Winding, &paint);
+
+    // Rasterization settings are taken from the paint used for drawing.
+    let mut paint = Paint::default();
+    paint.anti_alias = false;
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_path(&path, &paint, FillRule::Winding, Transform::from_translate(10.0, 5.0), None);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_prepared_path(&prepared, &paint, 10, 5, None);
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn fill_with() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let ts = Transform::from_row(1.2, 0.3, -0.2, 0.9, 5.0, 3.0);
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    for i in 0..5 {
+        let x = 10.0 + i as f32 * 15.0;
+
+        let mut pb = PathBuilder::new();
+        pb.move_to(x, 10.0);
+        pb.quad_to(x + 20.0, 40.0, x, 80.0);
+        pb.line_to(x + 5.0, 20.0);
+        let path = pb.finish().unwrap();
+        pixmap1.fill_path(&path, &paint, FillRule::Winding, ts, None);
+
+        pixmap2.fill_with(&paint, FillRule::Winding, ts, None, |pb| {
+            // Previous contours must not leak into a new path.
+            assert!(pb.is_empty());
+
+            pb.move_to(x, 10.0);
+            pb.quad_to(x + 20.0, 40.0, x, 80.0);
+            pb.line_to(x + 5.0, 20.0);
+        });
+    }
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn fill_with_implicit_move_to() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+
+    // Leave the pooled builder in a closed state.
+    let rect = Rect::from_xywh(60.0, 60.0, 30.0, 30.0).unwrap();
+    pixmap1.fill_rect(rect, &paint, Transform::identity(), None);
+    pixmap2.fill_with(&paint, FillRule::Winding, Transform::identity(), None, |pb| {
+        pb.push_rect(rect);
+    });
+
+    // A builder without a move_to starts at the origin.
+    let mut pb = PathBuilder::new();
+    pb.line_to(50.0, 10.0);
+    pb.line_to(10.0, 50.0);
+    let path = pb.finish().unwrap();
+    pixmap1.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+    pixmap2.fill_with(&paint, FillRule::Winding, Transform::identity(), None, |pb| {
+        pb.line_to(50.0, 10.0);
+        pb.line_to(10.0, 50.0);
+    });
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn large_pixmap() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    // Only the tiles with the shape are rendered, but the edges crossing
+    // the tile boundary at 8191 must be preserved.
+    let mut pixmap1 = Pixmap::new(10000, 20).unwrap();
+    let path = PathBuilder::from_circle(8191.0, 10.0, 6.0).unwrap();
+    pixmap1.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let mut pixmap2 = Pixmap::new(20, 20).unwrap();
+    let path = PathBuilder::from_circle(10.0, 10.0, 6.0).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    for y in 0..20 {
+        for x in 0..10000 {
+            let expected = if (8181..8201).contains(&x) {
+                pixmap2.pixel(x - 8181, y).unwrap()
+            } else {
+                PremultipliedColorU8::TRANSPARENT
+            };
+            assert_eq!(pixmap1.pixel(x, y).unwrap(), expected, "{}x{}", x, y);
+        }
+    }
+}
+
+#[test]
+fn fill_glyphs() {
+    // A simple "glyph" with a counter-clockwise hole, like in fonts.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(0.0, 0.0, 10.0, 14.0).unwrap());
+    pb.move_to(3.0, 3.0);
+    pb.line_to(3.0, 11.0);
+    pb.line_to(7.0, 11.0);
+    pb.line_to(7.0, 3.0);
+    pb.close();
+    let glyph1 = pb.finish().unwrap();
+    let glyph2 = PathBuilder::from_circle(5.0, 7.0, 6.0).unwrap();
+
+    // The second glyph overlaps the first one.
+    let glyphs = [
+        (&glyph1, Transform::from_translate(2.0, 3.0)),
+        (&glyph2, Transform::from_translate(8.0, 3.0)),
+        (&glyph1, Transform::from_row(1.0, 0.0, -0.25, 1.0, 20.0, 3.0)),
+    ];
+    let base = Transform::from_row(2.0, 0.0, 0.0, 2.0, 4.0, 8.0);
+
+    let mut paint = Paint::default();
+    paint.shader = LinearGradient::new(
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(30.0, 0.0),
+        vec![
+            GradientStop::new(0.0, Color::from_rgba8(50, 127, 150, 200)),
+            GradientStop::new(1.0, Color::from_rgba8(220, 140, 75, 180)),
+        ],
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap();
+
+    let mut pixmap1 = Pixmap::new(80, 50).unwrap();
+    pixmap1.fill_glyphs(&glyphs, &paint, base, None);
+
+    // Must be the same as filling each glyph separately.
+    let mut pixmap2 = Pixmap::new(80, 50).unwrap();
+    for (path, ts) in glyphs.iter() {
+        let path = (*path).clone().transform(*ts).unwrap();
+        pixmap2.fill_path(&path, &paint, FillRule::Winding, base, None);
+    }
+
+    assert_eq!(pixmap1, pixmap2);
+
+    // Overlapping glyphs are blended with each other.
+    let overlap = pixmap1.pixel(32, 24).unwrap();
+    let single = pixmap1.pixel(12, 10).unwrap();
+    assert!(overlap.alpha() > single.alpha());
+
+    // The hole is preserved.
+    assert_eq!(pixmap1.pixel(15, 22).unwrap(), PremultipliedColorU8::TRANSPARENT);
+}
+
+#[test]
+fn fill_glyphs_stored_fill_rule() {
+    // Both contours have the same direction, so only even-odd creates a hole.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(0.0, 0.0, 10.0, 14.0).unwrap());
+    pb.push_rect(Rect::from_ltrb(3.0, 3.0, 7.0, 11.0).unwrap());
+    let mut glyph = pb.finish().unwrap();
+    glyph.set_fill_rule(Some(FillRule::EvenOdd));
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let glyphs = [(&glyph, Transform::from_translate(2.0, 3.0))];
+    let mut pixmap1 = Pixmap::new(20, 20).unwrap();
+    pixmap1.fill_glyphs(&glyphs, &paint, Transform::identity(), None);
+
+    let mut pixmap2 = Pixmap::new(20, 20).unwrap();
+    pixmap2.fill_path(&glyph, &paint, FillRule::EvenOdd, glyphs[0].1, None);
+
+    assert_eq!(pixmap1, pixmap2);
+    assert_eq!(pixmap1.pixel(7, 10).unwrap(), PremultipliedColorU8::TRANSPARENT);
+}
+
+#[test]
+fn fill_glyphs_large_pixmap() {
+    let glyph = PathBuilder::from_circle(5.0, 5.0, 4.0).unwrap();
+    let glyphs = [
+        (&glyph, Transform::from_translate(8180.0, 0.0)),
+        (&glyph, Transform::from_translate(8190.0, 0.0)),
+    ];
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut pixmap1 = Pixmap::new(8300, 10).unwrap();
+    pixmap1.fill_glyphs(&glyphs, &paint, Transform::identity(), None);
+
+    let mut pixmap2 = Pixmap::new(8300, 10).unwrap();
+    for (path, ts) in glyphs.iter() {
+        pixmap2.fill_path(path, &paint, FillRule::Winding, *ts, None);
+    }
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn blend_mode_properties() {
+    let modes = [
+        BlendMode::Clear, BlendMode::Source, BlendMode::Destination, BlendMode::SourceOver,
+        BlendMode::DestinationOver, BlendMode::SourceIn, BlendMode::DestinationIn,
+        BlendMode::SourceOut, BlendMode::DestinationOut, BlendMode::SourceAtop,
+        BlendMode::DestinationAtop, BlendMode::Xor, BlendMode::Plus, BlendMode::Modulate,
+        BlendMode::Screen, BlendMode::Overlay, BlendMode::Darken, BlendMode::Lighten,
+        BlendMode::ColorDodge, BlendMode::ColorBurn, BlendMode::HardLight, BlendMode::SoftLight,
+        BlendMode::Difference, BlendMode::Exclusion, BlendMode::Multiply, BlendMode::Hue,
+        BlendMode::Saturation, BlendMode::Color, BlendMode::Luminosity,
+    ];
+
+    let colors = |opaque: bool| {
+        let alpha = if opaque { 255 } else { 100 };
+        [Color::from_rgba8(200, 30, 90, alpha), Color::from_rgba8(20, 220, 160, alpha)]
+    };
+
+    let draw = |mode: BlendMode, src: Color, dst: Color| {
+        let mut paint = Paint::default();
+        paint.set_color(src);
+        paint.blend_mode = mode;
+        paint.anti_alias = false;
+
+        let mut pixmap = Pixmap::new(4, 4).unwrap();
+        pixmap.fill(dst);
+        pixmap.fill_rect(Rect::from_xywh(0.0, 0.0, 4.0, 4.0).unwrap(), &paint, Transform::identity(), None);
+        pixmap.pixel(1, 1).unwrap()
+    };
+
+    for mode in modes {
+        for src_opaque in [false, true] {
+            for dst_opaque in [false, true] {
+                for src in colors(src_opaque) {
+                    let [dst1, dst2] = colors(dst_opaque);
+                    let c1 = draw(mode, src, dst1);
+                    let c2 = draw(mode, src, dst2);
+
+                    let opaque = mode.is_opaque_preserving(src_opaque, dst_opaque);
+                    assert_eq!(c1.alpha() == 255, opaque, "{:?} {} {}", mode, src_opaque, dst_opaque);
+                    if !mode.uses_dst() {
+                        assert_eq!(c1, c2, "{:?}", mode);
+                    }
+                }
+            }
+        }
+    }
+
+    // Modes that do read the destination.
+    let src = Color::from_rgba8(200, 30, 90, 100);
+    let dst1 = colors(true)[0];
+    let dst2 = colors(false)[1];
+    for mode in modes.iter().filter(|m| m.uses_dst()) {
+        assert_ne!(draw(*mode, src, dst1), draw(*mode, src, dst2), "{:?}", mode);
+    }
+}
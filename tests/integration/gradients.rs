@@ -433,3 +433,210 @@ fn global_opacity() {
     let expected = Pixmap::load_png("tests/images/gradients/global-opacity.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+#[test]
+fn dither() {
+    fn render(dither: bool) -> Pixmap {
+        let mut paint = Paint::default();
+        paint.anti_alias = false;
+        paint.dither = dither;
+        paint.shader = LinearGradient::new(
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(200.0, 0.0),
+            vec![
+                GradientStop::new(0.0, Color::from_rgba8(0, 0, 0, 255)),
+                GradientStop::new(1.0, Color::from_rgba8(20, 20, 20, 255)),
+            ],
+            SpreadMode::Pad,
+            Transform::identity(),
+        ).unwrap();
+
+        let mut pixmap = Pixmap::new(200, 20).unwrap();
+        pixmap.fill_rect(Rect::from_xywh(0.0, 0.0, 200.0, 20.0).unwrap(), &paint, Transform::identity(), None);
+        pixmap
+    }
+
+    let plain = render(false);
+    let dithered = render(true);
+    assert_ne!(plain, dithered);
+    // Must be deterministic.
+    assert_eq!(dithered, render(true));
+
+    for (a, b) in plain.pixels().iter().zip(dithered.pixels()) {
+        assert!((a.red() as i32 - b.red() as i32).abs() <= 1);
+        assert_eq!(b.alpha(), 255);
+    }
+}
+
+#[test]
+fn dither_solid_color() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.dither = true;
+
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    paint.dither = false;
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn invalid_stops() {
+    let start = Point::from_xy(0.0, 0.0);
+    let end = Point::from_xy(100.0, 0.0);
+
+    assert!(LinearGradient::new(start, end, vec![], SpreadMode::Pad, Transform::identity()).is_none());
+
+    let stops = vec![GradientStop::new(0.0, Color::BLACK)];
+    assert!(LinearGradient::new(start, end, stops, SpreadMode::Pad, Transform::identity()).is_none());
+
+    let stops = vec![
+        GradientStop::new(0.0, Color::BLACK),
+        GradientStop::new(f32::NAN, Color::WHITE),
+    ];
+    assert!(LinearGradient::new(start, end, stops.clone(), SpreadMode::Pad, Transform::identity()).is_none());
+    assert!(RadialGradient::new(start, end, 10.0, stops, SpreadMode::Pad, Transform::identity()).is_none());
+}
+
+#[test]
+fn try_new_errors() {
+    let start = Point::from_xy(0.0, 0.0);
+    let end = Point::from_xy(100.0, 0.0);
+    let stops = vec![
+        GradientStop::new(0.0, Color::BLACK),
+        GradientStop::new(1.0, Color::WHITE),
+    ];
+    let ts = Transform::identity();
+
+    let linear = |start, end, stops, ts| LinearGradient::try_new(start, end, stops, SpreadMode::Pad, ts).err();
+    let radial = |radius, stops, ts| RadialGradient::try_new(start, end, radius, stops, SpreadMode::Pad, ts).err();
+
+    assert_eq!(linear(start, end, stops.clone(), ts), None);
+    assert_eq!(radial(10.0, stops.clone(), ts), None);
+
+    let single_stop = vec![GradientStop::new(0.0, Color::BLACK)];
+    assert_eq!(linear(start, end, single_stop.clone(), ts), Some(TinySkiaError::TooFewGradientStops));
+    assert_eq!(radial(10.0, single_stop, ts), Some(TinySkiaError::TooFewGradientStops));
+
+    let nan_stops = vec![
+        GradientStop::new(0.0, Color::BLACK),
+        GradientStop::new(f32::NAN, Color::WHITE),
+    ];
+    assert_eq!(linear(start, end, nan_stops.clone(), ts), Some(TinySkiaError::InvalidGradientStopPosition));
+    assert_eq!(radial(10.0, nan_stops, ts), Some(TinySkiaError::InvalidGradientStopPosition));
+
+    let zero_scale = Transform::from_scale(0.0, 1.0);
+    assert_eq!(linear(start, end, stops.clone(), zero_scale), Some(TinySkiaError::NotInvertible));
+    assert_eq!(radial(10.0, stops.clone(), zero_scale), Some(TinySkiaError::NotInvertible));
+
+    let infinite = Point::from_xy(f32::INFINITY, 0.0);
+    assert_eq!(linear(start, infinite, stops.clone(), ts), Some(TinySkiaError::NonFinite));
+    assert_eq!(radial(f32::NAN, stops.clone(), ts), Some(TinySkiaError::NonFinite));
+
+    assert_eq!(radial(0.0, stops.clone(), ts), Some(TinySkiaError::InvalidGradientRadius));
+    assert_eq!(radial(-1.0, stops, ts), Some(TinySkiaError::InvalidGradientRadius));
+}
+
+fn render_horizontal_gradient(stops: Vec<GradientStop>) -> Pixmap {
+    let mut paint = Paint::default();
+    paint.anti_alias = false;
+    paint.force_hq_pipeline = true;
+    paint.shader = LinearGradient::new(
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(100.0, 0.0),
+        stops,
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap();
+
+    let mut pixmap = Pixmap::new(100, 1).unwrap();
+    pixmap.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 1.0).unwrap(), &paint, Transform::identity(), None);
+    pixmap
+}
+
+#[test]
+fn unsorted_stops() {
+    let sorted = render_horizontal_gradient(vec![
+        GradientStop::new(0.0, Color::from_rgba8(255, 0, 0, 255)),
+        GradientStop::new(0.3, Color::from_rgba8(0, 255, 0, 255)),
+        GradientStop::new(1.0, Color::from_rgba8(0, 0, 255, 255)),
+    ]);
+
+    let unsorted = render_horizontal_gradient(vec![
+        GradientStop::new(1.0, Color::from_rgba8(0, 0, 255, 255)),
+        GradientStop::new(0.0, Color::from_rgba8(255, 0, 0, 255)),
+        GradientStop::new(0.3, Color::from_rgba8(0, 255, 0, 255)),
+    ]);
+
+    assert_eq!(sorted, unsorted);
+
+    let unchecked = render_horizontal_gradient(vec![
+        GradientStop::new_unchecked(0.0, Color::from_rgba8(255, 0, 0, 255)),
+        GradientStop::new_unchecked(0.3, Color::from_rgba8(0, 255, 0, 255)),
+        GradientStop::new_unchecked(1.0, Color::from_rgba8(0, 0, 255, 255)),
+    ]);
+
+    assert_eq!(sorted, unchecked);
+}
+
+#[test]
+fn hard_stop() {
+    let pixmap = render_horizontal_gradient(vec![
+        GradientStop::new(0.5, Color::from_rgba8(255, 0, 0, 255)),
+        GradientStop::new(0.5, Color::from_rgba8(0, 0, 255, 255)),
+    ]);
+
+    for x in 0..100 {
+        let c = pixmap.pixel(x, 0).unwrap();
+        if x < 50 {
+            assert_eq!((c.red(), c.blue()), (255, 0));
+        } else {
+            assert_eq!((c.red(), c.blue()), (0, 255));
+        }
+    }
+}
+
+fn render_fade(end: Color, interpolation: GradientInterpolation) -> PremultipliedColorU8 {
+    let mut paint = Paint::default();
+    paint.anti_alias = false;
+    paint.shader = LinearGradient::new(
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(100.0, 0.0),
+        vec![
+            GradientStop::new(0.0, Color::from_rgba8(255, 0, 0, 255)),
+            GradientStop::new(1.0, end),
+        ],
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap().with_gradient_interpolation(interpolation);
+
+    let mut pixmap = Pixmap::new(100, 1).unwrap();
+    pixmap.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 1.0).unwrap(), &paint, Transform::identity(), None);
+    pixmap.pixel(50, 0).unwrap()
+}
+
+#[test]
+fn interpolation() {
+    let transparent_red = Color::from_rgba8(255, 0, 0, 0);
+    let transparent_black = Color::TRANSPARENT;
+
+    // A fade into the transparent version of the same color keeps the color in both modes.
+    let c = render_fade(transparent_red, GradientInterpolation::Unpremultiplied);
+    assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (126, 0, 0, 126));
+    let c = render_fade(transparent_red, GradientInterpolation::Premultiplied);
+    assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (126, 0, 0, 126));
+
+    // Unpremultiplied is the default one.
+    let c = render_fade(transparent_black, GradientInterpolation::default());
+    assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (63, 0, 0, 126));
+
+    // Premultiplied interpolation doesn't darken.
+    let c = render_fade(transparent_black, GradientInterpolation::Premultiplied);
+    assert_eq!((c.red(), c.green(), c.blue(), c.alpha()), (126, 0, 0, 126));
+}
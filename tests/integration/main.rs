@@ -1,4 +1,6 @@
 #[rustfmt::skip] mod mask;
+#[rustfmt::skip] mod non_finite;
+#[rustfmt::skip] mod canvas;
 #[rustfmt::skip] mod dash;
 #[rustfmt::skip] mod fill;
 #[rustfmt::skip] mod gradients;
@@ -6,6 +8,10 @@
 #[rustfmt::skip] mod path;
 #[rustfmt::skip] mod pattern;
 #[rustfmt::skip] mod pixmap;
+#[rustfmt::skip] mod pixmap_f16;
 #[rustfmt::skip] mod png;
+#[rustfmt::skip] mod region;
+#[rustfmt::skip] mod scene;
 #[rustfmt::skip] mod skia_dash;
 #[rustfmt::skip] mod stroke;
+#[rustfmt::skip] mod tiled;
@@ -245,6 +245,32 @@ fn mask_from_alpha() {
     assert_eq!(mask, expected);
 }
 
+#[test]
+fn to_alpha_mask() {
+    let path = PathBuilder::from_circle(50.0, 40.0, 30.0).unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    let mut pixmap = Pixmap::new(120, 80).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::default(), None);
+
+    let mask = pixmap.to_alpha_mask();
+    assert_eq!(mask.width(), 120);
+    assert_eq!(mask.height(), 80);
+
+    // An opaque fill alpha is the same as path coverage.
+    let mut expected = Mask::new(120, 80).unwrap();
+    expected.fill_path(&path, FillRule::Winding, true, Transform::default());
+    assert_eq!(mask, expected);
+
+    // Row padding is skipped.
+    let mut data = vec![0; 124 * 4 * 80];
+    let mut pixmap2 = PixmapMut::from_bytes_with_stride(&mut data, 120, 80, 124 * 4).unwrap();
+    pixmap2.fill_path(&path, &paint, FillRule::Winding, Transform::default(), None);
+    assert_eq!(pixmap2.as_ref().to_alpha_mask(), expected);
+}
+
 #[test]
 fn mask_from_luma() {
     let path = PathBuilder::from_circle(100.0, 100.0, 50.0).unwrap();
@@ -261,3 +287,203 @@ fn mask_from_luma() {
     let expected = Mask::load_png("tests/images/mask/mask-from-luma.png").unwrap();
     assert_eq!(mask, expected);
 }
+
+#[test]
+fn blur() {
+    let mut mask = Mask::new(100, 100).unwrap();
+    let rect = Rect::from_xywh(30.0, 30.0, 40.0, 40.0).unwrap();
+    mask.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+
+    // Too small to affect anything.
+    let original = mask.clone();
+    mask.blur(0.1);
+    assert_eq!(mask, original);
+    mask.blur(-1.0);
+    mask.blur(f32::NAN);
+    assert_eq!(mask, original);
+
+    mask.blur(5.0);
+    let at = |x: u32, y: u32| mask.data()[(y * 100 + x) as usize];
+    assert_eq!(at(50, 50), 255);
+    assert_eq!(at(0, 0), 0);
+    // The edge is feathered.
+    assert!(at(30, 50) > 0 && at(30, 50) < 255);
+    assert!(at(25, 50) > 0);
+    assert!(at(35, 50) < 255);
+    assert!(at(25, 50) < at(30, 50) && at(30, 50) < at(35, 50));
+}
+
+#[test]
+fn blur_clamps_edges() {
+    // A fully covered edge stays fully covered.
+    let mut mask = Mask::new(50, 50).unwrap();
+    mask.invert();
+    mask.blur(10.0);
+    assert!(mask.data().iter().all(|a| *a == 255));
+
+    let mut mask = Mask::new(50, 50).unwrap();
+    let rect = Rect::from_xywh(0.0, 0.0, 25.0, 50.0).unwrap();
+    mask.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+    mask.blur(3.0);
+    assert_eq!(mask.data()[25 * 50], 255);
+    assert_eq!(mask.data()[25 * 50 + 49], 0);
+}
+
+#[test]
+fn fill_mask() {
+    let mut mask = Mask::new(4, 4).unwrap();
+    mask.data_mut().fill(255);
+    mask.data_mut()[0] = 128;
+    mask.data_mut()[15] = 0;
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(0, 0, 255, 255);
+
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    pixmap.fill_mask(&mask, &paint, Transform::from_translate(3.0, 2.0), None);
+
+    let at = |x: u32, y: u32| pixmap.pixel(x, y).unwrap();
+    // Coverage is applied to premultiplied color.
+    assert_eq!(at(3, 2), PremultipliedColorU8::from_rgba(0, 0, 128, 128).unwrap());
+    assert_eq!(at(4, 2), PremultipliedColorU8::from_rgba(0, 0, 255, 255).unwrap());
+    assert_eq!(at(6, 5), PremultipliedColorU8::TRANSPARENT);
+    // Outside of the mask.
+    assert_eq!(at(2, 2), PremultipliedColorU8::TRANSPARENT);
+    assert_eq!(at(3, 1), PremultipliedColorU8::TRANSPARENT);
+    assert_eq!(at(7, 3), PremultipliedColorU8::TRANSPARENT);
+    assert_eq!(at(3, 6), PremultipliedColorU8::TRANSPARENT);
+}
+
+#[test]
+fn fill_mask_with_shader() {
+    let mut mask = Mask::new(20, 20).unwrap();
+    mask.invert();
+
+    let mut paint = Paint::default();
+    paint.shader = LinearGradient::new(
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(20.0, 0.0),
+        vec![
+            GradientStop::new(0.0, Color::from_rgba8(50, 127, 150, 200)),
+            GradientStop::new(1.0, Color::from_rgba8(220, 140, 75, 180)),
+        ],
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap();
+
+    // A fully covered mask is the same as a rect.
+    // Partially outside the pixmap.
+    let ts = Transform::from_translate(-5.0, 15.0);
+    let mut pixmap1 = Pixmap::new(30, 30).unwrap();
+    pixmap1.fill_mask(&mask, &paint, ts, None);
+
+    let mut pixmap2 = Pixmap::new(30, 30).unwrap();
+    pixmap2.fill_rect(Rect::from_xywh(0.0, 0.0, 20.0, 20.0).unwrap(), &paint, ts, None);
+
+    assert_eq!(pixmap1, pixmap2);
+}
+
+#[test]
+fn fill_mask_scaled() {
+    let mut mask = Mask::new(10, 10).unwrap();
+    mask.invert();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(0, 0, 255, 255);
+
+    let mut pixmap = Pixmap::new(40, 40).unwrap();
+    pixmap.fill_mask(&mask, &paint, Transform::from_row(2.0, 0.0, 0.0, 2.0, 10.5, 10.0), None);
+
+    let at = |x: u32, y: u32| pixmap.pixel(x, y).unwrap().alpha();
+    assert_eq!(at(20, 20), 255);
+    assert_eq!(at(29, 29), 255);
+    // A fractional edge.
+    assert!(at(10, 20) > 0 && at(10, 20) < 255);
+    assert_eq!(at(9, 20), 0);
+    assert_eq!(at(31, 20), 0);
+    assert_eq!(at(20, 30), 0);
+}
+
+#[test]
+fn intersect_mask() {
+    let mut mask1 = Mask::new(100, 100).unwrap();
+    let rect = Rect::from_xywh(0.0, 0.0, 60.0, 100.0).unwrap();
+    mask1.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+
+    let mut mask2 = Mask::new(100, 100).unwrap();
+    mask2.data_mut().fill(128);
+    let rect = Rect::from_xywh(40.0, 0.0, 60.0, 100.0).unwrap();
+    mask2.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+
+    let mut mask = mask1.clone();
+    mask.intersect_mask(&mask2);
+    assert_eq!(mask.data()[10], 128);
+    assert_eq!(mask.data()[50], 255);
+    assert_eq!(mask.data()[70], 0);
+
+    // The same as intersecting a path.
+    let mut mask3 = mask1.clone();
+    mask3.intersect_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+    let mut mask4 = mask1.clone();
+    let mut mask5 = Mask::new(100, 100).unwrap();
+    mask5.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+    mask4.intersect_mask(&mask5);
+    assert_eq!(mask3, mask4);
+
+    // Different sizes are ignored.
+    let mut mask = mask1.clone();
+    mask.intersect_mask(&Mask::new(10, 10).unwrap());
+    assert_eq!(mask, mask1);
+}
+
+#[test]
+fn clip_all_draw_types() {
+    // Left half is fully visible, the right one is half visible.
+    let mut clip = Mask::new(100, 100).unwrap();
+    clip.data_mut().fill(128);
+    let rect = Rect::from_xywh(0.0, 0.0, 50.0, 100.0).unwrap();
+    clip.fill_path(&PathBuilder::from_rect(rect), FillRule::Winding, false, Transform::identity());
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    let stroke = Stroke { width: 6.0, ..Stroke::default() };
+
+    let mut image = Pixmap::new(40, 40).unwrap();
+    image.fill(Color::from_rgba8(220, 140, 75, 180));
+
+    let mut coverage = Mask::new(60, 60).unwrap();
+    coverage.invert();
+
+    let draw = |pixmap: &mut Pixmap, i: usize, mask: Option<&Mask>| {
+        let ts = Transform::identity();
+        match i {
+            0 => pixmap.fill_rect(Rect::from_xywh(20.0, 20.0, 60.0, 60.0).unwrap(), &paint, ts, mask),
+            1 => pixmap.fill_path(&path, &paint, FillRule::Winding, ts, mask),
+            2 => pixmap.stroke_path(&path, &paint, &stroke, ts, mask),
+            3 => pixmap.draw_pixmap(30, 30, image.as_ref(), &PixmapPaint::default(), ts, mask),
+            4 => pixmap.fill_circle(Point::from_xy(50.0, 50.0), 40.0, &paint, ts, mask),
+            5 => pixmap.fill_ellipse(Rect::from_xywh(10.0, 30.0, 80.0, 40.0).unwrap(), &paint, ts, mask),
+            6 => pixmap.draw_line(Point::from_xy(10.0, 50.0), Point::from_xy(90.0, 50.0), &paint, 4.0, ts, mask),
+            7 => pixmap.fill_mask(&coverage, &paint, Transform::from_translate(20.0, 20.0), mask),
+            8 => pixmap.fill_rect_fast(Rect::from_xywh(20.0, 20.0, 60.0, 60.0).unwrap(), &paint, ts, mask),
+            _ => unreachable!(),
+        }
+    };
+
+    for i in 0..9 {
+        let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+        draw(&mut pixmap1, i, None);
+        let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+        draw(&mut pixmap2, i, Some(&clip));
+
+        // Clipping is the same as applying a mask afterwards, up to rounding
+        // of anti-aliased edges.
+        pixmap1.apply_mask(&clip);
+        for (a, b) in pixmap1.data().iter().zip(pixmap2.data()) {
+            assert!((*a as i32 - *b as i32).abs() <= 1, "draw type {}: {} {}", i, a, b);
+        }
+        assert!(pixmap2.data().iter().any(|c| *c != 0), "draw type {}", i);
+    }
+}
@@ -0,0 +1,213 @@
+use tiny_skia::*;
+
+const VALUES: [f32; 3] = [f32::NAN, f32::INFINITY, f32::NEG_INFINITY];
+
+fn test_path() -> Path {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.cubic_to(80.0, 10.0, 10.0, 80.0, 80.0, 80.0);
+    pb.finish().unwrap()
+}
+
+fn test_gradient(start: Point, end: Point, position: f32, transform: Transform) -> Option<Shader<'static>> {
+    LinearGradient::new(
+        start,
+        end,
+        vec![GradientStop::new(position, Color::BLACK), GradientStop::new(1.0, Color::WHITE)],
+        SpreadMode::Pad,
+        transform,
+    )
+}
+
+fn non_finite_transforms(v: f32) -> Vec<Transform> {
+    vec![
+        Transform::from_row(v, 0.0, 0.0, 1.0, 0.0, 0.0),
+        Transform::from_row(1.0, v, 0.0, 1.0, 0.0, 0.0),
+        Transform::from_row(1.0, 0.0, v, 1.0, 0.0, 0.0),
+        Transform::from_row(1.0, 0.0, 0.0, v, 0.0, 0.0),
+        Transform::from_row(1.0, 0.0, 0.0, 1.0, v, 0.0),
+        Transform::from_row(1.0, 0.0, 0.0, 1.0, 0.0, v),
+        Transform::from_rotate(v),
+    ]
+}
+
+#[test]
+fn path_builder() {
+    for v in VALUES {
+        let mut pb = PathBuilder::new();
+        pb.move_to(0.0, 0.0);
+        pb.line_to(v, 10.0);
+        pb.line_to(10.0, 10.0);
+        assert!(!pb.is_valid());
+        assert_eq!(pb.try_finish(), Err(TinySkiaError::NonFinite));
+
+        let mut pb = PathBuilder::new();
+        pb.push_circle(v, 10.0, 5.0);
+        assert!(pb.finish().is_none());
+
+        let mut pb = PathBuilder::new();
+        pb.move_to(0.0, 0.0);
+        pb.quad_to(10.0, 10.0, 20.0, 0.0);
+        pb.cubic_to(10.0, 10.0, 20.0, v, 10.0, 0.0);
+        assert!(pb.finish().is_none());
+
+        assert!(PathBuilder::from_circle(10.0, 10.0, v).is_none());
+        assert!(Rect::from_xywh(10.0, 10.0, 10.0, v).is_none());
+    }
+}
+
+#[test]
+fn path_transform() {
+    let path = test_path();
+    for v in VALUES {
+        for ts in non_finite_transforms(v) {
+            assert!(path.clone().transform(ts).is_none());
+            assert!(ts.invert().is_none());
+        }
+    }
+}
+
+#[test]
+fn gradients() {
+    for v in VALUES {
+        let p0 = Point::from_xy(0.0, 0.0);
+        let p1 = Point::from_xy(50.0, 50.0);
+        let ts = Transform::identity();
+        assert!(test_gradient(Point::from_xy(v, 0.0), p1, 0.0, ts).is_none());
+        assert!(test_gradient(p0, Point::from_xy(50.0, v), 0.0, ts).is_none());
+        assert!(test_gradient(p0, p1, 0.0, Transform::from_translate(v, 0.0)).is_none());
+
+        assert!(RadialGradient::new(
+            p0,
+            p1,
+            v,
+            vec![GradientStop::new(0.0, Color::BLACK), GradientStop::new(1.0, Color::WHITE)],
+            SpreadMode::Pad,
+            ts,
+        ).is_none());
+    }
+
+    // Infinite positions are clamped, NaN is an error.
+    let p0 = Point::from_xy(0.0, 0.0);
+    let p1 = Point::from_xy(50.0, 50.0);
+    let ts = Transform::identity();
+    assert!(test_gradient(p0, p1, f32::NAN, ts).is_none());
+    assert!(test_gradient(p0, p1, f32::INFINITY, ts).is_some());
+    assert!(test_gradient(p0, p1, f32::NEG_INFINITY, ts).is_some());
+}
+
+#[test]
+fn stroke() {
+    let path = test_path();
+    for v in VALUES {
+        let mut stroke = Stroke::default();
+        stroke.width = v;
+        assert!(path.stroke(&stroke, 1.0).is_none());
+
+        let stroke = Stroke::default();
+        assert!(path.stroke(&stroke, v).is_none());
+
+        let dash = StrokeDash::new(vec![5.0, 5.0], 0.0).unwrap();
+        assert!(path.dash(&dash, v).is_none());
+        assert!(StrokeDash::new(vec![v, 5.0], 0.0).is_none());
+        assert!(StrokeDash::new(vec![5.0, 5.0], v).is_none());
+    }
+
+    let mut stroke = Stroke::default();
+    stroke.line_join = LineJoin::Miter;
+    stroke.miter_limit = f32::NAN;
+    assert!(path.stroke(&stroke, 1.0).is_none());
+
+    stroke.miter_limit = f32::INFINITY;
+    assert!(path.stroke(&stroke, 1.0).is_some());
+}
+
+#[test]
+fn draw_with_non_finite_transform() {
+    let path = test_path();
+    let rect = Rect::from_xywh(10.0, 10.0, 50.0, 50.0).unwrap();
+    let mut src = Pixmap::new(10, 10).unwrap();
+    src.fill(Color::BLACK);
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut stroke = Stroke::default();
+    stroke.width = 5.0;
+
+    let mut dashed_stroke = stroke.clone();
+    dashed_stroke.dash = StrokeDash::new(vec![5.0, 5.0], 0.0);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut mask = Mask::new(100, 100).unwrap();
+    for v in VALUES {
+        for ts in non_finite_transforms(v) {
+            for anti_alias in [true, false] {
+                paint.anti_alias = anti_alias;
+                assert_eq!(
+                    pixmap.try_fill_path(&path, &paint, FillRule::Winding, ts, None),
+                    Err(TinySkiaError::NonFinite)
+                );
+                pixmap.stroke_path(&path, &paint, &Stroke::default(), ts, None);
+                pixmap.stroke_path(&path, &paint, &stroke, ts, None);
+                pixmap.stroke_path(&path, &paint, &dashed_stroke, ts, None);
+                pixmap.fill_rect(rect, &paint, ts, None);
+                pixmap.fill_rect_fast(rect, &paint, ts, None);
+                pixmap.fill_circle(Point::from_xy(50.0, 50.0), 10.0, &paint, ts, None);
+                pixmap.fill_ellipse(rect, &paint, ts, None);
+                pixmap.draw_line(Point::from_xy(1.0, 1.0), Point::from_xy(50.0, 60.0), &paint, 3.0, ts, None);
+                pixmap.draw_pixmap(0, 0, src.as_ref(), &PixmapPaint::default(), ts, None);
+                mask.fill_path(&path, FillRule::Winding, anti_alias, ts);
+            }
+        }
+    }
+
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+    assert!(mask.data().iter().all(|a| *a == 0));
+}
+
+#[test]
+fn draw_with_non_finite_values() {
+    let path = test_path();
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut src = Pixmap::new(10, 10).unwrap();
+    src.fill(Color::BLACK);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    for v in VALUES {
+        let mut stroke = Stroke::default();
+        stroke.width = v;
+        pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+
+        let p = Point::from_xy(50.0, 60.0);
+        pixmap.draw_line(Point::from_xy(1.0, 1.0), p, &paint, v, Transform::identity(), None);
+        pixmap.draw_line(Point::from_xy(v, 1.0), p, &paint, 1.0, Transform::identity(), None);
+        pixmap.fill_circle(Point::from_xy(50.0, v), 10.0, &paint, Transform::identity(), None);
+        pixmap.fill_circle(Point::from_xy(50.0, 50.0), v, &paint, Transform::identity(), None);
+        pixmap.fill_with(&paint, FillRule::Winding, Transform::identity(), None, |pb| {
+            pb.move_to(v, 1.0);
+            pb.line_to(20.0, 20.0);
+            pb.line_to(20.0, 40.0);
+        });
+
+        let mut pixmap_paint = PixmapPaint::default();
+        pixmap_paint.snap_to_pixel_grid = true;
+        let ts = Transform::from_translate(v, 0.0);
+        pixmap.draw_pixmap(0, 0, src.as_ref(), &pixmap_paint, ts, None);
+        pixmap.draw_pixmap_tinted(0, 0, src.as_ref(), Color::BLACK, TintMode::Alpha, &pixmap_paint, ts, None);
+
+        let mut canvas = Canvas::new(pixmap.as_mut());
+        canvas.translate(v, 1.0);
+        canvas.clip_path(&path, FillRule::Winding, true);
+        canvas.fill_path(&path, &paint, FillRule::Winding);
+        canvas.stroke_path(&path, &paint, &Stroke::default());
+        canvas.draw_pixmap(1, 1, src.as_ref(), &PixmapPaint::default());
+
+        assert_eq!(path.winding_number(Point::from_xy(v, 20.0)), 0);
+        assert!(!path.contains(Point::from_xy(20.0, v), FillRule::EvenOdd));
+    }
+
+    assert!(pixmap.pixels().iter().all(|p| p.alpha() == 0));
+}
@@ -264,3 +264,1218 @@ fn circle() {
 fn large_circle() {
     assert!(PathBuilder::from_circle(250.0, 250.0, 2000.0).is_some()); // Must not panic.
 }
+
+#[test]
+fn with_capacity() {
+    fn build(mut pb: PathBuilder) -> Path {
+        pb.move_to(10.0, 20.0);
+        pb.line_to(30.0, 40.0);
+        pb.cubic_to(50.0, 60.0, 70.0, 80.0, 90.0, 100.0);
+        pb.close();
+        pb.finish().unwrap()
+    }
+
+    assert_eq!(build(PathBuilder::new()), build(PathBuilder::with_capacity(4, 5)));
+    assert_eq!(build(PathBuilder::new()), build(PathBuilder::with_capacity(0, 0)));
+
+    let mut pb = PathBuilder::new();
+    pb.reserve(4, 5);
+    assert_eq!(build(PathBuilder::new()), build(pb));
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut pb = PathBuilder::with_capacity(100, 100);
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.shrink_to_fit();
+    pb.line_to(50.0, 10.0);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.points(), &[
+        Point::from_xy(10.0, 20.0),
+        Point::from_xy(30.0, 40.0),
+        Point::from_xy(50.0, 10.0),
+    ]);
+}
+
+#[test]
+fn segments_last_point() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.quad_to(50.0, 60.0, 70.0, 80.0);
+    pb.close();
+    pb.move_to(100.0, 100.0);
+    pb.cubic_to(110.0, 100.0, 120.0, 110.0, 120.0, 120.0);
+    let path = pb.finish().unwrap();
+
+    let mut iter = path.segments();
+    let mut pens = Vec::new();
+    while let Some(segment) = iter.next() {
+        pens.push((segment, iter.last_move_to(), iter.last_point()));
+    }
+
+    assert_eq!(pens, &[
+        (PathSegment::MoveTo(Point::from_xy(10.0, 20.0)), Point::from_xy(10.0, 20.0), Point::from_xy(10.0, 20.0)),
+        (PathSegment::LineTo(Point::from_xy(30.0, 40.0)), Point::from_xy(10.0, 20.0), Point::from_xy(30.0, 40.0)),
+        (PathSegment::QuadTo(Point::from_xy(50.0, 60.0), Point::from_xy(70.0, 80.0)), Point::from_xy(10.0, 20.0), Point::from_xy(70.0, 80.0)),
+        // Close returns the pen to the contour start.
+        (PathSegment::Close, Point::from_xy(10.0, 20.0), Point::from_xy(10.0, 20.0)),
+        (PathSegment::MoveTo(Point::from_xy(100.0, 100.0)), Point::from_xy(100.0, 100.0), Point::from_xy(100.0, 100.0)),
+        (PathSegment::CubicTo(Point::from_xy(110.0, 100.0), Point::from_xy(120.0, 110.0), Point::from_xy(120.0, 120.0)), Point::from_xy(100.0, 100.0), Point::from_xy(120.0, 120.0)),
+    ]);
+}
+
+#[test]
+fn is_convex() {
+    let rect = PathBuilder::from_rect(Rect::from_ltrb(10.0, 20.0, 30.0, 40.0).unwrap());
+    assert!(rect.is_convex());
+
+    let circle = PathBuilder::from_circle(50.0, 50.0, 20.0).unwrap();
+    assert!(circle.is_convex());
+
+    // Counter-clockwise triangle.
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(0.0, 10.0);
+    pb.line_to(10.0, 10.0);
+    assert!(pb.finish().unwrap().is_convex());
+
+    // Arrow.
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(10.0, 5.0);
+    pb.line_to(0.0, 10.0);
+    pb.line_to(3.0, 5.0);
+    pb.close();
+    assert!(!pb.finish().unwrap().is_convex());
+
+    // Self-intersecting star with a consistent turn direction.
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 0.0);
+    pb.line_to(79.0, 90.0);
+    pb.line_to(2.0, 35.0);
+    pb.line_to(98.0, 35.0);
+    pb.line_to(21.0, 90.0);
+    pb.close();
+    assert!(!pb.finish().unwrap().is_convex());
+
+    // Two contours.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_ltrb(10.0, 20.0, 30.0, 40.0).unwrap());
+    pb.push_rect(Rect::from_ltrb(50.0, 20.0, 70.0, 40.0).unwrap());
+    assert!(!pb.finish().unwrap().is_convex());
+
+    // A line is convex.
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(10.0, 10.0);
+    assert!(pb.finish().unwrap().is_convex());
+}
+
+#[test]
+fn as_rect() {
+    let rect = Rect::from_ltrb(10.0, 20.0, 30.0, 40.0).unwrap();
+    assert_eq!(PathBuilder::from_rect(rect).as_rect(), Some(rect));
+
+    // Counter-clockwise, not closed and with an explicit closing line.
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(10.0, 40.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(30.0, 20.0);
+    pb.line_to(10.0, 20.0);
+    assert_eq!(pb.finish().unwrap().as_rect(), Some(rect));
+
+    // Not a rect.
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(15.0, 40.0);
+    pb.close();
+    assert_eq!(pb.finish().unwrap().as_rect(), None);
+
+    // Self-intersecting "bow tie" with axis-aligned edges.
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(40.0, 40.0);
+    pb.close();
+    assert_eq!(pb.finish().unwrap().as_rect(), None);
+
+    assert_eq!(PathBuilder::from_circle(50.0, 50.0, 20.0).unwrap().as_rect(), None);
+}
+
+#[test]
+fn as_rect_within_tolerance() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0001, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(10.0, 40.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.as_rect(), None);
+    assert_eq!(path.as_rect_within_tolerance(0.001), Some(path.bounds()));
+}
+
+#[test]
+fn contour_directions() {
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_ltrb(10.0, 20.0, 30.0, 40.0).unwrap());
+    pb.move_to(0.0, 0.0);
+    pb.line_to(0.0, 10.0);
+    pb.line_to(10.0, 10.0);
+    pb.close();
+    // Zero area.
+    pb.move_to(50.0, 50.0);
+    pb.line_to(60.0, 60.0);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(
+        path.contour_directions(),
+        &[PathDirection::CW, PathDirection::CCW, PathDirection::CW]
+    );
+}
+
+#[test]
+fn oriented() {
+    // Outer rect and a hole, both clockwise, plus a separate counter-clockwise triangle.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_ltrb(10.0, 10.0, 90.0, 90.0).unwrap());
+    pb.push_rect(Rect::from_ltrb(30.0, 30.0, 70.0, 70.0).unwrap());
+    pb.move_to(100.0, 0.0);
+    pb.line_to(100.0, 10.0);
+    pb.quad_to(105.0, 15.0, 110.0, 10.0);
+    pb.close();
+    pb.move_to(120.0, 0.0);
+    pb.line_to(130.0, 10.0);
+    let path = pb.finish().unwrap();
+
+    let oriented = path.oriented(PathDirection::CW);
+    assert_eq!(
+        oriented.contour_directions(),
+        &[PathDirection::CW, PathDirection::CCW, PathDirection::CW, PathDirection::CW]
+    );
+    assert_eq!(oriented.bounds(), path.bounds());
+    assert_eq!(oriented.len(), path.len());
+
+    // The reversed triangle.
+    let segments: Vec<_> = oriented.segments().skip(10).collect();
+    assert_eq!(
+        segments,
+        &[
+            PathSegment::MoveTo(Point::from_xy(110.0, 10.0)),
+            PathSegment::QuadTo(Point::from_xy(105.0, 15.0), Point::from_xy(100.0, 10.0)),
+            PathSegment::LineTo(Point::from_xy(100.0, 0.0)),
+            PathSegment::Close,
+            PathSegment::MoveTo(Point::from_xy(120.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(130.0, 10.0)),
+        ]
+    );
+
+    let oriented = path.oriented(PathDirection::CCW);
+    assert_eq!(
+        oriented.contour_directions(),
+        &[PathDirection::CCW, PathDirection::CW, PathDirection::CCW, PathDirection::CW]
+    );
+}
+
+#[test]
+fn area_and_centroid() {
+    let rect = PathBuilder::from_rect(Rect::from_ltrb(10.0, 20.0, 30.0, 60.0).unwrap());
+    assert_eq!(rect.area(), 800.0);
+    assert_eq!(rect.centroid(), Some(Point::from_xy(20.0, 40.0)));
+
+    // Counter-clockwise contours have a negative area.
+    let ccw = rect.oriented(PathDirection::CCW);
+    assert_eq!(ccw.area(), -800.0);
+    assert_eq!(ccw.centroid(), Some(Point::from_xy(20.0, 40.0)));
+
+    let circle = PathBuilder::from_circle(50.0, 50.0, 20.0).unwrap();
+    let expected = core::f32::consts::PI * 400.0;
+    assert!((circle.area() - expected).abs() < expected * 0.01);
+    let center = circle.centroid().unwrap();
+    assert!((center.x - 50.0).abs() < 0.01 && (center.y - 50.0).abs() < 0.01);
+
+    // A hole with an opposite direction is subtracted.
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_ltrb(0.0, 0.0, 40.0, 40.0).unwrap());
+    pb.push_rect(Rect::from_ltrb(20.0, 0.0, 40.0, 40.0).unwrap());
+    let path = pb.finish().unwrap().oriented(PathDirection::CW);
+    assert_eq!(path.area(), 800.0);
+    assert_eq!(path.centroid(), Some(Point::from_xy(10.0, 20.0)));
+
+    // Open contours are closed implicitly.
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(30.0, 0.0);
+    pb.line_to(30.0, 30.0);
+    let triangle = pb.finish().unwrap();
+    assert_eq!(triangle.area(), 450.0);
+    assert_eq!(triangle.centroid(), Some(Point::from_xy(20.0, 10.0)));
+
+    // Degenerate.
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(30.0, 30.0);
+    let line = pb.finish().unwrap();
+    assert_eq!(line.area(), 0.0);
+    assert_eq!(line.centroid(), None);
+}
+
+#[test]
+fn winding_number() {
+    let rect = PathBuilder::from_rect(Rect::from_ltrb(10.0, 20.0, 30.0, 60.0).unwrap());
+    assert_eq!(rect.winding_number(Point::from_xy(20.0, 40.0)), 1);
+    assert_eq!(rect.oriented(PathDirection::CCW).winding_number(Point::from_xy(20.0, 40.0)), -1);
+    assert_eq!(rect.winding_number(Point::from_xy(5.0, 40.0)), 0);
+    // Left and top edges are inclusive, right and bottom ones are not.
+    assert_eq!(rect.winding_number(Point::from_xy(10.0, 20.0)), 1);
+    assert_eq!(rect.winding_number(Point::from_xy(30.0, 40.0)), 0);
+    assert_eq!(rect.winding_number(Point::from_xy(20.0, 60.0)), 0);
+
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_ltrb(0.0, 0.0, 40.0, 40.0).unwrap());
+    pb.push_rect(Rect::from_ltrb(20.0, 0.0, 60.0, 40.0).unwrap());
+    let path = pb.finish().unwrap();
+    assert_eq!(path.winding_number(Point::from_xy(30.0, 20.0)), 2);
+    assert!(path.contains(Point::from_xy(30.0, 20.0), FillRule::Winding));
+    assert!(!path.contains(Point::from_xy(30.0, 20.0), FillRule::EvenOdd));
+}
+
+#[test]
+fn winding_number_matches_rasterization() {
+    // A self-intersecting star with a curve.
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 5.0);
+    pb.line_to(77.0, 90.0);
+    pb.line_to(5.0, 35.0);
+    pb.quad_to(50.0, 20.0, 95.0, 35.0);
+    pb.line_to(23.0, 90.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.winding_number(Point::from_xy(50.0, 50.0)), 2);
+
+    for fill_rule in [FillRule::Winding, FillRule::EvenOdd] {
+        let mut paint = Paint::default();
+        paint.anti_alias = false;
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        pixmap.fill_path(&path, &paint, fill_rule, Transform::identity(), None);
+
+        let mut mismatches = 0;
+        for y in 0..100 {
+            for x in 0..100 {
+                let center = Point::from_xy(x as f32 + 0.5, y as f32 + 0.5);
+                let filled = pixmap.pixel(x, y).unwrap().alpha() != 0;
+                if filled != path.contains(center, fill_rule) {
+                    mismatches += 1;
+                }
+            }
+        }
+
+        // Edges are rasterized using fixed-point math, so a few boundary pixels can differ.
+        assert!(mismatches < 10, "{}", mismatches);
+    }
+}
+
+#[test]
+fn measure_pos_tan() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(110.0, 10.0);
+    pb.move_to(0.0, 0.0);
+    pb.line_to(0.0, 50.0);
+    let path = pb.finish().unwrap();
+
+    let measure = PathMeasure::new(&path, 1.0);
+    assert_eq!(measure.length(), 150.0);
+    assert_eq!(measure.pos_tan(0.0), Some((Point::from_xy(10.0, 10.0), Point::from_xy(1.0, 0.0))));
+    assert_eq!(measure.pos_tan(50.0), Some((Point::from_xy(60.0, 10.0), Point::from_xy(1.0, 0.0))));
+    assert_eq!(measure.pos_tan(120.0), Some((Point::from_xy(0.0, 20.0), Point::from_xy(0.0, 1.0))));
+    assert_eq!(measure.pos_tan(150.0), Some((Point::from_xy(0.0, 50.0), Point::from_xy(0.0, 1.0))));
+    assert_eq!(measure.pos_tan(150.1), None);
+    assert_eq!(measure.pos_tan(-1.0), None);
+    assert_eq!(measure.pos_tan(f32::NAN), None);
+}
+
+#[test]
+fn measure_matrix_at() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(110.0, 10.0);
+    pb.line_to(110.0, 110.0);
+    let path = pb.finish().unwrap();
+
+    let measure = PathMeasure::new(&path, 1.0);
+
+    // Left-to-right, so a positive offset moves up.
+    let ts = measure.matrix_at(50.0, 5.0).unwrap();
+    assert_eq!(ts, Transform::from_translate(60.0, 5.0));
+
+    // Top-to-bottom, so the X axis points down and a positive offset moves right.
+    let ts = measure.matrix_at(150.0, 5.0).unwrap();
+    assert_eq!(ts, Transform::from_row(0.0, 1.0, -1.0, 0.0, 115.0, 60.0));
+    let mut p = Point::from_xy(10.0, 0.0);
+    ts.map_point(&mut p);
+    assert_eq!(p, Point::from_xy(115.0, 70.0));
+
+    assert_eq!(measure.matrix_at(200.1, 0.0), None);
+}
+
+#[test]
+fn measure_curve() {
+    let path = PathBuilder::from_circle(0.0, 0.0, 10.0).unwrap();
+    let measure = PathMeasure::new(&path, 10.0);
+    assert!((measure.length() - 2.0 * core::f32::consts::PI * 10.0).abs() < 0.1);
+
+    let (pos, tan) = measure.pos_tan(measure.length() * 0.25).unwrap();
+    assert!((pos.x - 0.0).abs() < 0.1 && (pos.y - 10.0).abs() < 0.1);
+    assert!((tan.x + 1.0).abs() < 0.01 && tan.y.abs() < 0.1);
+}
+
+#[test]
+fn trim_full() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    assert_eq!(path.trim(0.0, 1.0, 0.0).unwrap(), path);
+    assert_eq!(path.trim(0.0, 1.0, 0.3).unwrap(), path);
+    assert_eq!(path.trim(0.5, 0.5, 0.0), None);
+    assert_eq!(path.trim(f32::NAN, 1.0, 0.0), None);
+}
+
+#[test]
+fn trim_open() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(100.0, 0.0);
+    pb.line_to(100.0, 100.0);
+    let path = pb.finish().unwrap();
+
+    let trimmed = path.trim(0.25, 0.75, 0.0).unwrap();
+    assert_eq!(
+        trimmed.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(50.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(100.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(100.0, 50.0)),
+        ]
+    );
+
+    // Wraps around the end, but an open path must not be connected.
+    let trimmed = path.trim(0.75, 0.25, 0.0).unwrap();
+    assert_eq!(
+        trimmed.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(100.0, 50.0)),
+            PathSegment::LineTo(Point::from_xy(100.0, 100.0)),
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(50.0, 0.0)),
+        ]
+    );
+
+    // The same window, but via an offset.
+    assert_eq!(path.trim(0.0, 0.5, 0.75).unwrap(), trimmed);
+    assert_eq!(path.trim(0.0, 0.5, -0.25).unwrap(), trimmed);
+}
+
+#[test]
+fn trim_closed() {
+    let path = PathBuilder::from_rect(Rect::from_ltrb(0.0, 0.0, 100.0, 100.0).unwrap());
+
+    // Continues across the close.
+    let trimmed = path.trim(0.875, 0.125, 0.0).unwrap();
+    assert_eq!(
+        trimmed.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 50.0)),
+            PathSegment::LineTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(50.0, 0.0)),
+        ]
+    );
+}
+
+#[test]
+fn trim_multiple_contours() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(100.0, 0.0);
+    pb.move_to(0.0, 50.0);
+    pb.line_to(100.0, 50.0);
+    let path = pb.finish().unwrap();
+
+    let trimmed = path.trim(0.25, 0.75, 0.0).unwrap();
+    assert_eq!(
+        trimmed.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(50.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(100.0, 0.0)),
+            PathSegment::MoveTo(Point::from_xy(0.0, 50.0)),
+            PathSegment::LineTo(Point::from_xy(50.0, 50.0)),
+        ]
+    );
+}
+
+#[test]
+fn push_polygon() {
+    let mut pb = PathBuilder::new();
+    pb.push_polygon(50.0, 50.0, 20.0, 4, 0.0);
+    let path = pb.finish().unwrap();
+
+    let segments: Vec<_> = path.segments().collect();
+    assert_eq!(segments.len(), 5);
+    assert_eq!(segments[4], PathSegment::Close);
+
+    let expected = [
+        Point::from_xy(70.0, 50.0),
+        Point::from_xy(50.0, 70.0),
+        Point::from_xy(30.0, 50.0),
+        Point::from_xy(50.0, 30.0),
+    ];
+    for (p, e) in path.points().iter().zip(expected.iter()) {
+        assert!((p.x - e.x).abs() < 1e-4 && (p.y - e.y).abs() < 1e-4);
+    }
+
+    assert_eq!(path.contour_directions(), &[PathDirection::CW]);
+}
+
+#[test]
+fn push_polygon_rotation() {
+    let mut pb = PathBuilder::new();
+    pb.push_polygon(0.0, 0.0, 10.0, 6, 90.0);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.points().len(), 6);
+    let p = path.points()[0];
+    assert!(p.x.abs() < 1e-4 && (p.y - 10.0).abs() < 1e-4);
+}
+
+#[test]
+fn push_polygon_invalid() {
+    let mut pb = PathBuilder::new();
+    pb.push_polygon(50.0, 50.0, 20.0, 2, 0.0);
+    pb.push_polygon(50.0, 50.0, 0.0, 5, 0.0);
+    pb.push_polygon(50.0, 50.0, f32::NAN, 5, 0.0);
+    pb.push_polygon(f32::INFINITY, 50.0, 20.0, 5, 0.0);
+    assert!(pb.is_empty());
+}
+
+#[test]
+fn push_star() {
+    let mut pb = PathBuilder::new();
+    pb.push_star(50.0, 50.0, 20.0, 10.0, 5, -90.0);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.points().len(), 10);
+    assert_eq!(path.segments().last(), Some(PathSegment::Close));
+    assert_eq!(path.contour_directions(), &[PathDirection::CW]);
+
+    for (i, p) in path.points().iter().enumerate() {
+        let r = if i % 2 == 0 { 20.0 } else { 10.0 };
+        assert!((p.distance(Point::from_xy(50.0, 50.0)) - r).abs() < 1e-4);
+    }
+
+    // The first point is at the top.
+    let p = path.points()[0];
+    assert!((p.x - 50.0).abs() < 1e-4 && (p.y - 30.0).abs() < 1e-4);
+}
+
+#[test]
+fn push_star_invalid() {
+    let mut pb = PathBuilder::new();
+    pb.push_star(50.0, 50.0, 20.0, 10.0, 2, 0.0);
+    pb.push_star(50.0, 50.0, 0.0, 10.0, 5, 0.0);
+    pb.push_star(50.0, 50.0, 20.0, -1.0, 5, 0.0);
+    assert!(pb.is_empty());
+}
+
+#[test]
+fn push_catmull_rom_too_few_points() {
+    let mut pb = PathBuilder::new();
+    pb.push_catmull_rom(&[], 0.0, false);
+    pb.push_catmull_rom(&[Point::from_xy(10.0, 20.0)], 0.0, true);
+    assert!(pb.is_empty());
+}
+
+#[test]
+fn push_catmull_rom_two_points() {
+    let mut pb = PathBuilder::new();
+    pb.push_catmull_rom(
+        &[Point::from_xy(10.0, 20.0), Point::from_xy(30.0, 40.0)],
+        0.0,
+        false,
+    );
+    let path = pb.finish().unwrap();
+
+    assert_eq!(
+        path.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+            PathSegment::LineTo(Point::from_xy(30.0, 40.0)),
+        ]
+    );
+}
+
+#[test]
+fn push_catmull_rom_open() {
+    let points = [
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(30.0, 60.0),
+        Point::from_xy(60.0, 0.0),
+    ];
+
+    let mut pb = PathBuilder::new();
+    pb.push_catmull_rom(&points, 0.0, false);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(
+        path.segments().collect::<Vec<_>>(),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::CubicTo(
+                Point::from_xy(5.0, 10.0),
+                Point::from_xy(20.0, 60.0),
+                Point::from_xy(30.0, 60.0)
+            ),
+            PathSegment::CubicTo(
+                Point::from_xy(40.0, 60.0),
+                Point::from_xy(55.0, 10.0),
+                Point::from_xy(60.0, 0.0)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn push_catmull_rom_closed() {
+    let points = [
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(60.0, 0.0),
+        Point::from_xy(60.0, 60.0),
+        Point::from_xy(0.0, 60.0),
+    ];
+
+    let mut pb = PathBuilder::new();
+    pb.push_catmull_rom(&points, 0.0, true);
+    let path = pb.finish().unwrap();
+
+    let segments: Vec<_> = path.segments().collect();
+    assert_eq!(segments.len(), 6);
+    assert_eq!(segments[5], PathSegment::Close);
+
+    // The tangent at the first point wraps around the last one.
+    assert_eq!(
+        segments[1],
+        PathSegment::CubicTo(
+            Point::from_xy(10.0, -10.0),
+            Point::from_xy(50.0, -10.0),
+            Point::from_xy(60.0, 0.0)
+        )
+    );
+    assert_eq!(
+        segments[4],
+        PathSegment::CubicTo(
+            Point::from_xy(-10.0, 50.0),
+            Point::from_xy(-10.0, 10.0),
+            Point::from_xy(0.0, 0.0)
+        )
+    );
+}
+
+#[test]
+fn push_catmull_rom_max_tension() {
+    let points = [
+        Point::from_xy(0.0, 0.0),
+        Point::from_xy(30.0, 60.0),
+        Point::from_xy(60.0, 0.0),
+    ];
+
+    let mut pb = PathBuilder::new();
+    pb.push_catmull_rom(&points, 1.0, false);
+    let path = pb.finish().unwrap();
+
+    // Control points match end points, aka straight lines.
+    assert_eq!(
+        path.segments().nth(1),
+        Some(PathSegment::CubicTo(
+            Point::from_xy(0.0, 0.0),
+            Point::from_xy(30.0, 60.0),
+            Point::from_xy(30.0, 60.0)
+        ))
+    );
+}
+
+fn svg_segments(data: &str) -> Vec<PathSegment> {
+    Path::from_svg(data).unwrap().segments().collect()
+}
+
+#[test]
+fn from_svg_absolute() {
+    assert_eq!(
+        svg_segments("M 10 20 L 30 40 H 50 V 60 Z"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+            PathSegment::LineTo(Point::from_xy(30.0, 40.0)),
+            PathSegment::LineTo(Point::from_xy(50.0, 40.0)),
+            PathSegment::LineTo(Point::from_xy(50.0, 60.0)),
+            PathSegment::Close,
+        ]
+    );
+}
+
+#[test]
+fn from_svg_relative() {
+    assert_eq!(
+        svg_segments("m 10 20 l 10 10 h 5 v -5 z l 1 1 m 5 5 q 1 1 2 0"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+            PathSegment::LineTo(Point::from_xy(20.0, 30.0)),
+            PathSegment::LineTo(Point::from_xy(25.0, 30.0)),
+            PathSegment::LineTo(Point::from_xy(25.0, 25.0)),
+            PathSegment::Close,
+            // The current point is the contour start after a close.
+            PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+            PathSegment::LineTo(Point::from_xy(11.0, 21.0)),
+            PathSegment::MoveTo(Point::from_xy(16.0, 26.0)),
+            PathSegment::QuadTo(Point::from_xy(17.0, 27.0), Point::from_xy(18.0, 26.0)),
+        ]
+    );
+}
+
+#[test]
+fn from_svg_implicit_commands() {
+    assert_eq!(
+        svg_segments("M0,0 10,10 20,0 l5 5 5-5"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(10.0, 10.0)),
+            PathSegment::LineTo(Point::from_xy(20.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(25.0, 5.0)),
+            PathSegment::LineTo(Point::from_xy(30.0, 0.0)),
+        ]
+    );
+
+    // Compact numbers.
+    assert_eq!(
+        svg_segments("M.5.5L-1-2e1"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.5, 0.5)),
+            PathSegment::LineTo(Point::from_xy(-1.0, -20.0)),
+        ]
+    );
+}
+
+#[test]
+fn from_svg_smooth_curves() {
+    assert_eq!(
+        svg_segments("M 0 0 C 10 0 20 10 20 20 S 30 40 40 40 Q 50 40 50 30 T 50 10 T 40 0"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::CubicTo(
+                Point::from_xy(10.0, 0.0),
+                Point::from_xy(20.0, 10.0),
+                Point::from_xy(20.0, 20.0)
+            ),
+            PathSegment::CubicTo(
+                Point::from_xy(20.0, 30.0),
+                Point::from_xy(30.0, 40.0),
+                Point::from_xy(40.0, 40.0)
+            ),
+            PathSegment::QuadTo(Point::from_xy(50.0, 40.0), Point::from_xy(50.0, 30.0)),
+            PathSegment::QuadTo(Point::from_xy(50.0, 20.0), Point::from_xy(50.0, 10.0)),
+            PathSegment::QuadTo(Point::from_xy(50.0, 0.0), Point::from_xy(40.0, 0.0)),
+        ]
+    );
+
+    // Without a previous curve, the current point is used as a control point.
+    assert_eq!(
+        svg_segments("M 0 0 L 10 0 S 20 10 20 20"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(10.0, 0.0)),
+            PathSegment::CubicTo(
+                Point::from_xy(10.0, 0.0),
+                Point::from_xy(20.0, 10.0),
+                Point::from_xy(20.0, 20.0)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn from_svg_arc() {
+    let path = Path::from_svg("M 0 0 A 10 10 0 0 1 20 0").unwrap();
+    assert_eq!(path.len(), 3);
+    assert_eq!(path.points().last(), Some(&Point::from_xy(20.0, 0.0)));
+    // A half circle above the X axis, split into two cubics.
+    let middle = path.points()[3];
+    assert!((middle.x - 10.0).abs() < 0.001);
+    assert!((middle.y + 10.0).abs() < 0.001);
+
+    // Too small radii are scaled up.
+    let scaled = Path::from_svg("M 0 0 A 1 1 0 0 1 20 0").unwrap();
+    for (p1, p2) in scaled.points().iter().zip(path.points()) {
+        assert!((p1.x - p2.x).abs() < 0.001 && (p1.y - p2.y).abs() < 0.001);
+    }
+
+    // Flags can be written without separators.
+    assert_eq!(
+        Path::from_svg("M0 0a10 10 0 1020 0"),
+        Path::from_svg("M 0 0 a 10 10 0 1 0 20 0")
+    );
+
+    // Zero radii produce a line.
+    assert_eq!(
+        svg_segments("M 0 0 A 0 10 0 0 1 20 0"),
+        &[
+            PathSegment::MoveTo(Point::from_xy(0.0, 0.0)),
+            PathSegment::LineTo(Point::from_xy(20.0, 0.0)),
+        ]
+    );
+}
+
+#[test]
+fn from_svg_malformed() {
+    assert!(Path::from_svg("").is_none());
+    assert!(Path::from_svg("M 10 20").is_none());
+    assert!(Path::from_svg("L 10 20").is_none());
+    assert!(Path::from_svg("M 10").is_none());
+    assert!(Path::from_svg("M 10 20 L 30").is_none());
+    assert!(Path::from_svg("M 10 20 X 30 40").is_none());
+    assert!(Path::from_svg("M 10 20 L 30 40 Z 50 60").is_none());
+    assert!(Path::from_svg("M 10 20 L 30 1e40").is_none());
+    assert!(Path::from_svg("M 10 20 A 10 10 0 2 0 30 40").is_none());
+    assert!(Path::from_svg("M 10 20 L 30 40 e").is_none());
+}
+
+#[test]
+fn to_svg() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.5);
+    pb.line_to(-30.0, 40.0);
+    pb.quad_to(1.0, 2.0, 3.0, 4.0);
+    pb.cubic_to(0.1, 0.2, 1e-7, 5.0, 6.0, 7.0);
+    pb.close();
+    pb.line_to(1.0, 1.0);
+    let path = pb.finish().unwrap();
+
+    let data = path.to_svg();
+    assert_eq!(
+        data,
+        "M 10 20.5 L -30 40 Q 1 2 3 4 C 0.1 0.2 0.0000001 5 6 7 Z M 10 20.5 L 1 1"
+    );
+    assert_eq!(Path::from_svg(&data), Some(path));
+}
+
+#[test]
+fn to_pdf_ops() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.cubic_to(50.0, 60.0, 70.0, 80.0, 90.0, 100.0);
+    pb.close();
+    pb.move_to(0.0, 0.0);
+    pb.line_to(5.5, 0.25);
+    let path = pb.finish().unwrap();
+
+    assert_eq!(path.to_pdf_ops(), "10 20 m\n30 40 l\n50 60 70 80 90 100 c\nh\n0 0 m\n5.5 0.25 l\n");
+    assert_eq!(path.to_pdf_ops_flipped(100.0), "10 80 m\n30 60 l\n50 40 70 20 90 0 c\nh\n0 100 m\n5.5 99.75 l\n");
+}
+
+#[test]
+fn to_pdf_ops_quad() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.0, 0.0);
+    pb.quad_to(30.0, 60.0, 60.0, 0.0);
+    let path = pb.finish().unwrap();
+
+    // Control points are at 2/3 of the way to the quad control point.
+    assert_eq!(path.to_pdf_ops(), "0 0 m\n20 40 40 40 60 0 c\n");
+}
+
+#[test]
+fn to_pdf_ops_rect() {
+    let rect = Rect::from_xywh(10.0, 20.0, 30.0, 40.0).unwrap();
+    let path = PathBuilder::from_rect(rect);
+    assert_eq!(path.to_pdf_ops(), "10 20 30 40 re\n");
+    assert_eq!(path.to_pdf_ops_flipped(100.0), "10 40 30 40 re\n");
+
+    // An open rect is written as lines.
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(40.0, 20.0);
+    pb.line_to(40.0, 60.0);
+    pb.line_to(10.0, 60.0);
+    let path = pb.finish().unwrap();
+    assert_eq!(path.to_pdf_ops(), "10 20 m\n40 20 l\n40 60 l\n10 60 l\n");
+}
+
+#[test]
+fn current_bounds() {
+    let mut pb = PathBuilder::new();
+    assert_eq!(pb.current_bounds(), None);
+
+    pb.move_to(10.0, 20.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(10.0, 20.0, 10.0, 20.0));
+
+    pb.line_to(30.0, 5.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(10.0, 5.0, 30.0, 20.0));
+
+    // Control points are included.
+    pb.quad_to(-10.0, 50.0, 20.0, 10.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(-10.0, 5.0, 30.0, 50.0));
+
+    pb.close();
+    pb.cubic_to(0.0, 0.0, 40.0, 0.0, 0.0, 0.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(-10.0, 0.0, 40.0, 50.0));
+
+    let path = pb.clone().finish().unwrap();
+    assert_eq!(Some(path.bounds()), pb.current_bounds());
+
+    pb.clear();
+    assert_eq!(pb.current_bounds(), None);
+}
+
+#[test]
+fn current_bounds_overwritten_move_to() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.move_to(-100.0, -100.0);
+    // The previous move to is overwritten, therefore must not affect bounds.
+    pb.move_to(50.0, 60.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(10.0, 20.0, 50.0, 60.0));
+
+    pb.line_to(0.0, 0.0);
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(0.0, 0.0, 50.0, 60.0));
+    assert_eq!(pb.finish().unwrap().bounds(), Rect::from_ltrb(0.0, 0.0, 50.0, 60.0).unwrap());
+}
+
+#[test]
+fn current_bounds_not_finite() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(f32::NAN, 40.0);
+    pb.line_to(30.0, 40.0);
+    assert_eq!(pb.current_bounds(), None);
+    assert_eq!(pb.finish(), None);
+}
+
+#[test]
+fn current_bounds_push_path() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.push_path(&PathBuilder::from_circle(100.0, 100.0, 10.0).unwrap());
+    assert_eq!(pb.current_bounds(), Rect::from_ltrb(10.0, 20.0, 110.0, 110.0));
+}
+
+// Returns the maximum distance between a circle and a path approximating it.
+fn max_circle_deviation(path: &Path, radius: f32) -> f32 {
+    let mut max = 0.0f32;
+    let mut prev = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p) => prev = p,
+            PathSegment::QuadTo(p1, p2) => {
+                for i in 0..=16 {
+                    let t = i as f32 / 16.0;
+                    let mt = 1.0 - t;
+                    let x = mt * mt * prev.x + 2.0 * mt * t * p1.x + t * t * p2.x;
+                    let y = mt * mt * prev.y + 2.0 * mt * t * p1.y + t * t * p2.y;
+                    max = max.max(((x * x + y * y).sqrt() - radius).abs());
+                }
+                prev = p2;
+            }
+            _ => {}
+        }
+    }
+
+    max
+}
+
+#[test]
+fn tessellation_transform() {
+    let circle = |radius: f32, ts: Transform| {
+        let mut pb = PathBuilder::new();
+        pb.set_tessellation_transform(ts);
+        pb.push_circle(0.0, 0.0, radius);
+        pb.finish().unwrap()
+    };
+
+    let path = circle(10.0, Transform::identity());
+    let scaled_up = circle(10.0, Transform::from_scale(100.0, 100.0));
+    let scaled_down = circle(1000.0, Transform::from_scale(0.01, 0.01));
+    assert_eq!(path.len(), 10);
+    assert_eq!(scaled_up.len(), 66);
+    assert_eq!(scaled_down.len(), 10);
+    assert_eq!(circle(1000.0, Transform::identity()).len(), 66);
+
+    // The error must stay within tolerance after the transform.
+    let ts = Transform::from_scale(100.0, 100.0);
+    assert!(max_circle_deviation(&path.clone().transform(ts).unwrap(), 1000.0) > 0.25);
+    assert!(max_circle_deviation(&scaled_up.transform(ts).unwrap(), 1000.0) <= 0.25);
+
+    // Rotation and translation do not affect tessellation.
+    let ts = Transform::from_rotate(30.0).post_translate(1000.0, 1000.0);
+    assert_eq!(circle(10.0, ts), path);
+}
+
+#[test]
+fn tessellation_transform_is_preserved_by_clear() {
+    let ts = Transform::from_scale(2.0, 3.0);
+    let mut pb = PathBuilder::new();
+    assert_eq!(pb.tessellation_transform(), Transform::identity());
+
+    pb.set_tessellation_transform(ts);
+    pb.clear();
+    assert_eq!(pb.tessellation_transform(), ts);
+}
+
+#[test]
+fn fill_rule() {
+    let mut pb = PathBuilder::new();
+    pb.push_rect(Rect::from_xywh(10.0, 10.0, 20.0, 20.0).unwrap());
+    let mut path = pb.finish().unwrap();
+    assert_eq!(path.fill_rule(), None);
+    assert_eq!(path.fill_rule_or(FillRule::Winding), FillRule::Winding);
+
+    path.set_fill_rule(Some(FillRule::EvenOdd));
+    assert_eq!(path.fill_rule(), Some(FillRule::EvenOdd));
+    assert_eq!(path.fill_rule_or(FillRule::Winding), FillRule::EvenOdd);
+
+    // Preserved by transformation.
+    let path = path.transform(Transform::from_scale(2.0, 2.0)).unwrap();
+    assert_eq!(path.fill_rule(), Some(FillRule::EvenOdd));
+}
+
+#[test]
+fn is_valid() {
+    let mut pb = PathBuilder::new();
+    assert!(!pb.is_valid());
+
+    pb.move_to(10.0, 20.0);
+    assert!(!pb.is_valid());
+
+    pb.line_to(30.0, 40.0);
+    assert!(pb.is_valid());
+
+    // An implicit move to is inserted.
+    let mut pb = PathBuilder::new();
+    pb.cubic_to(40.0, 30.0, 60.0, 75.0, 33.0, 66.0);
+    assert!(pb.is_valid());
+    assert!(pb.finish().is_some());
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, f32::INFINITY);
+    assert!(!pb.is_valid());
+    assert!(pb.finish().is_none());
+}
+
+#[test]
+fn implicit_move_after_close() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(50.0, 20.0);
+    pb.close();
+    pb.quad_to(70.0, 80.0, 90.0, 20.0);
+    let path = pb.finish().unwrap();
+
+    // Just like in Skia, the new contour starts at the previous contour start.
+    assert_eq!(path.segments().collect::<Vec<_>>(), &[
+        PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+        PathSegment::LineTo(Point::from_xy(30.0, 40.0)),
+        PathSegment::LineTo(Point::from_xy(50.0, 20.0)),
+        PathSegment::Close,
+        PathSegment::MoveTo(Point::from_xy(10.0, 20.0)),
+        PathSegment::QuadTo(Point::from_xy(70.0, 80.0), Point::from_xy(90.0, 20.0)),
+    ]);
+}
+
+#[test]
+fn try_finish() {
+    assert_eq!(PathBuilder::new().try_finish(), Err(TinySkiaError::EmptyPath));
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    assert_eq!(pb.try_finish(), Err(TinySkiaError::EmptyPath));
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 20.0);
+    pb.line_to(f32::NAN, 40.0);
+    assert_eq!(pb.try_finish(), Err(TinySkiaError::NonFinite));
+}
+
+#[test]
+fn max_points() {
+    let mut pb = PathBuilder::new();
+    assert_eq!(pb.max_points(), None);
+    pb.set_max_points(Some(3));
+    pb.move_to(10.0, 20.0);
+    pb.line_to(30.0, 40.0);
+    pb.line_to(50.0, 20.0);
+    pb.close();
+    assert!(pb.is_valid());
+    assert!(pb.clone().finish().is_some());
+
+    pb.line_to(70.0, 80.0);
+    assert!(!pb.is_valid());
+    assert_eq!(pb.clone().try_finish(), Err(TinySkiaError::TooComplex));
+    assert!(pb.clone().finish().is_none());
+
+    // Preserved by clear.
+    pb.clear();
+    assert_eq!(pb.max_points(), Some(3));
+}
+
+// Returns the distance from a point to a path, sampling curves densely.
+fn distance_to_path(path: &Path, p: Point) -> f32 {
+    let mut min = f32::MAX;
+    let mut last = Point::zero();
+    for segment in path.segments() {
+        match segment {
+            PathSegment::MoveTo(p0) => last = p0,
+            PathSegment::LineTo(p1) => {
+                for i in 0..=100 {
+                    let t = i as f32 / 100.0;
+                    let q = Point::from_xy(last.x + (p1.x - last.x) * t, last.y + (p1.y - last.y) * t);
+                    min = min.min(q.distance(p));
+                }
+                last = p1;
+            }
+            PathSegment::CubicTo(p1, p2, p3) => {
+                for i in 0..=1000 {
+                    let t = i as f32 / 1000.0;
+                    let mt = 1.0 - t;
+                    let a = mt * mt * mt;
+                    let b = 3.0 * t * mt * mt;
+                    let c = 3.0 * t * t * mt;
+                    let d = t * t * t;
+                    let q = Point::from_xy(
+                        a * last.x + b * p1.x + c * p2.x + d * p3.x,
+                        a * last.y + b * p1.y + c * p2.y + d * p3.y,
+                    );
+                    min = min.min(q.distance(p));
+                }
+                last = p3;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    min
+}
+
+#[test]
+fn fit_cubic_two_points() {
+    let points = [Point::from_xy(10.0, 20.0), Point::from_xy(30.0, 40.0)];
+    let line = vec![PathSegment::MoveTo(points[0]), PathSegment::LineTo(points[1])];
+    let path = PathBuilder::fit_cubic(&points, 0.5).unwrap();
+    assert_eq!(path.segments().collect::<Vec<_>>(), line);
+
+    // Duplicates are ignored.
+    let points = [points[0], points[0], points[1], points[1]];
+    let path = PathBuilder::fit_cubic(&points, 0.5).unwrap();
+    assert_eq!(path.segments().collect::<Vec<_>>(), line);
+
+    assert!(PathBuilder::fit_cubic(&[points[0]], 0.5).is_none());
+    assert!(PathBuilder::fit_cubic(&[points[0], points[0]], 0.5).is_none());
+    assert!(PathBuilder::fit_cubic(&points, -1.0).is_none());
+    assert!(PathBuilder::fit_cubic(&points, f32::NAN).is_none());
+    assert!(PathBuilder::fit_cubic(&[points[0], Point::from_xy(f32::NAN, 1.0)], 0.5).is_none());
+}
+
+#[test]
+fn fit_cubic_noisy_arc() {
+    // A half circle with a deterministic jitter.
+    let mut points = Vec::new();
+    for i in 0..=200 {
+        let angle = i as f32 / 200.0 * core::f32::consts::PI;
+        let noise = ((i * 7919) % 13) as f32 / 13.0 - 0.5;
+        let r = 50.0 + noise * 0.4;
+        points.push(Point::from_xy(60.0 + r * angle.cos(), 60.0 + r * angle.sin()));
+    }
+
+    for &error in &[0.5, 1.0, 4.0] {
+        let path = PathBuilder::fit_cubic(&points, error).unwrap();
+        assert!(path.segments().skip(1).all(|s| matches!(s, PathSegment::CubicTo(..))));
+        // Much more compact than the input.
+        assert!(path.len() < 20, "{} segments", path.len());
+        assert_eq!(path.points()[0], points[0]);
+        assert_eq!(path.points()[path.points().len() - 1], points[200]);
+        for p in &points {
+            assert!(distance_to_path(&path, *p) <= error + 0.01);
+        }
+    }
+}
+
+#[test]
+fn fit_cubic_corner() {
+    // An L-shape with a slightly curved second leg.
+    let mut points = Vec::new();
+    for i in 0..=20 {
+        points.push(Point::from_xy(10.0, 10.0 + i as f32 * 4.0));
+    }
+    for i in 1..=20 {
+        let x = 10.0 + i as f32 * 4.0;
+        points.push(Point::from_xy(x, 90.0 - (x - 10.0) * (x - 10.0) / 200.0));
+    }
+
+    let path = PathBuilder::fit_cubic(&points, 0.25).unwrap();
+    // The corner must be an end point of a segment.
+    assert!(path.points().contains(&Point::from_xy(10.0, 90.0)));
+    for p in &points {
+        assert!(distance_to_path(&path, *p) <= 0.26);
+    }
+
+    // A straight leg is a single segment.
+    assert_eq!(path.points()[1..4].iter().filter(|p| p.x != 10.0).count(), 0);
+}
+
+#[test]
+fn simplify_polyline_basic() {
+    let p = |x, y| Point::from_xy(x, y);
+
+    // A zigzag within the tolerance.
+    let points = [p(0.0, 0.0), p(10.0, 0.4), p(20.0, -0.4), p(30.0, 0.3), p(40.0, 0.0)];
+    assert_eq!(simplify_polyline(&points, 0.5), vec![p(0.0, 0.0), p(40.0, 0.0)]);
+    assert_eq!(simplify_polyline(&points, 0.35), points.to_vec());
+
+    // Corners are preserved.
+    let points = [p(0.0, 0.0), p(5.0, 0.1), p(10.0, 0.0), p(10.0, 5.0), p(10.0, 10.0)];
+    assert_eq!(simplify_polyline(&points, 0.5), vec![p(0.0, 0.0), p(10.0, 0.0), p(10.0, 10.0)]);
+
+    // A closed polyline.
+    let points = [p(0.0, 0.0), p(10.0, 0.0), p(10.0, 10.0), p(0.0, 10.0), p(0.0, 0.0)];
+    assert_eq!(simplify_polyline(&points, 1.0), points.to_vec());
+
+    // Zero tolerance keeps even collinear and duplicated points.
+    let points = [p(0.0, 0.0), p(1.0, 0.0), p(1.0, 0.0), p(2.0, 0.0)];
+    assert_eq!(simplify_polyline(&points, 0.0), points.to_vec());
+    assert_eq!(simplify_polyline(&points, f32::NAN), points.to_vec());
+    assert_eq!(simplify_polyline(&points, 0.1), vec![p(0.0, 0.0), p(2.0, 0.0)]);
+
+    assert_eq!(simplify_polyline(&points[..2], 0.1), points[..2].to_vec());
+    assert_eq!(simplify_polyline(&[], 0.1), vec![]);
+}
+
+#[test]
+fn simplify_polyline_huge() {
+    // Must not overflow the stack.
+    let mut points = Vec::new();
+    for i in 0..200_000 {
+        let x = i as f32;
+        points.push(Point::from_xy(x, x * x * 1e-3));
+    }
+
+    let simplified = simplify_polyline(&points, 0.01);
+    assert!(simplified.len() < points.len());
+    assert_eq!(simplified[0], points[0]);
+    assert_eq!(simplified[simplified.len() - 1], points[points.len() - 1]);
+}
@@ -251,3 +251,288 @@ fn filter_bicubic() {
     let expected = Pixmap::load_png("tests/images/pattern/filter-bicubic.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+#[test]
+fn to_shader() {
+    let triangle = crate_triangle();
+
+    let mut paint = Paint::default();
+    paint.anti_alias = false;
+    paint.shader = triangle.to_shader(
+        SpreadMode::Repeat,
+        FilterQuality::Nearest,
+        Transform::identity(),
+    );
+
+    let path = PathBuilder::from_rect(Rect::from_ltrb(10.0, 10.0, 190.0, 190.0).unwrap());
+
+    let mut pixmap = Pixmap::new(200, 200).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let expected = Pixmap::load_png("tests/images/pattern/repeat-nearest.png").unwrap();
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn to_shader_transform() {
+    let triangle = crate_triangle();
+
+    let mut paint = Paint::default();
+    paint.anti_alias = false;
+    paint.shader = triangle.as_ref().to_shader(
+        SpreadMode::Pad,
+        FilterQuality::Nearest,
+        Transform::from_translate(30.0, 40.0),
+    );
+
+    let path = PathBuilder::from_rect(Rect::from_xywh(30.0, 40.0, 20.0, 20.0).unwrap());
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    // The top-left pixmap pixel is mapped to the translate offset.
+    assert_eq!(
+        pixmap
+            .clone_rect(IntRect::from_xywh(30, 40, 20, 20).unwrap())
+            .unwrap(),
+        triangle
+    );
+}
+
+#[test]
+fn local_matrix() {
+    let triangle = crate_triangle();
+
+    let shader = Pattern::new(
+        triangle.as_ref(),
+        SpreadMode::Repeat,
+        FilterQuality::Bicubic,
+        1.0,
+        Transform::from_translate(10.0, 0.0),
+    );
+
+    assert_eq!(
+        shader.clone().with_local_matrix(Transform::identity()),
+        shader
+    );
+
+    // The local matrix is applied first.
+    let expected = Pattern::new(
+        triangle.as_ref(),
+        SpreadMode::Repeat,
+        FilterQuality::Bicubic,
+        1.0,
+        Transform::from_row(2.0, 0.0, 0.0, 2.0, 10.0, 0.0),
+    );
+    assert_eq!(
+        shader
+            .clone()
+            .with_local_matrix(Transform::from_scale(2.0, 2.0)),
+        expected
+    );
+
+    let draw = |shader: Shader| {
+        let mut paint = Paint::default();
+        paint.shader = shader;
+
+        let path = PathBuilder::from_rect(Rect::from_ltrb(10.0, 10.0, 90.0, 90.0).unwrap());
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        pixmap.fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            Transform::from_rotate(10.0),
+            None,
+        );
+        pixmap
+    };
+
+    assert_eq!(
+        draw(shader.with_local_matrix(Transform::from_scale(2.0, 2.0))),
+        draw(expected)
+    );
+}
+
+#[test]
+fn linear_color_space() {
+    // A black and a white pixel.
+    let mut image = Pixmap::new(2, 1).unwrap();
+    image.fill(Color::WHITE);
+    image.pixels_mut()[0] = PremultipliedColorU8::from_rgba(0, 0, 0, 255).unwrap();
+
+    let render = |quality: FilterQuality, color_space: Option<ColorSpace>| {
+        let mut shader = image.as_ref().to_shader(
+            SpreadMode::Pad,
+            quality,
+            Transform::from_scale(50.0, 1.0),
+        );
+        if let Some(color_space) = color_space {
+            shader = shader.with_color_space(color_space);
+        }
+
+        let mut paint = Paint::default();
+        paint.shader = shader;
+
+        let mut pixmap = Pixmap::new(100, 1).unwrap();
+        pixmap.fill_rect(Rect::from_xywh(0.0, 0.0, 100.0, 1.0).unwrap(), &paint, Transform::identity(), None);
+        pixmap
+    };
+
+    // sRGB is the default one.
+    let default = render(FilterQuality::Bilinear, None);
+    assert_eq!(default, render(FilterQuality::Bilinear, Some(ColorSpace::Srgb)));
+    assert_eq!(default.pixel(50, 0).unwrap().red(), 130);
+
+    // Linear filtering is brighter in the middle.
+    let linear = render(FilterQuality::Bilinear, Some(ColorSpace::Linear));
+    assert_eq!(linear.pixel(50, 0).unwrap().red(), 189);
+    // But the original pixels are preserved.
+    assert_eq!(linear.pixel(0, 0).unwrap().red(), 0);
+    assert_eq!(linear.pixel(99, 0).unwrap().red(), 255);
+
+    let srgb = render(FilterQuality::Bicubic, None);
+    let linear = render(FilterQuality::Bicubic, Some(ColorSpace::Linear));
+    assert!(linear.pixel(50, 0).unwrap().red() > srgb.pixel(50, 0).unwrap().red());
+
+    // Nothing to filter.
+    assert_eq!(
+        render(FilterQuality::Nearest, Some(ColorSpace::Linear)),
+        render(FilterQuality::Nearest, None)
+    );
+}
+
+#[test]
+fn draw_pixmap_linear_color_space() {
+    let mut image = Pixmap::new(2, 1).unwrap();
+    image.fill(Color::WHITE);
+    image.pixels_mut()[0] = PremultipliedColorU8::from_rgba(0, 0, 0, 255).unwrap();
+
+    let paint = PixmapPaint {
+        quality: FilterQuality::Bilinear,
+        color_space: ColorSpace::Linear,
+        ..PixmapPaint::default()
+    };
+
+    let mut pixmap = Pixmap::new(100, 1).unwrap();
+    pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, Transform::from_scale(50.0, 1.0), None);
+    assert_eq!(pixmap.pixel(50, 0).unwrap().red(), 189);
+}
+
+#[test]
+fn draw_pixmap_tinted() {
+    let mut icon = Pixmap::new(2, 1).unwrap();
+    icon.pixels_mut()[0] = PremultipliedColorU8::from_rgba(200, 100, 50, 255).unwrap();
+    icon.pixels_mut()[1] = PremultipliedColorU8::from_rgba(10, 60, 0, 128).unwrap();
+
+    // The same alpha, but a different color.
+    let mut gray_icon = Pixmap::new(2, 1).unwrap();
+    gray_icon.pixels_mut()[0] = PremultipliedColorU8::from_rgba(255, 255, 255, 255).unwrap();
+    gray_icon.pixels_mut()[1] = PremultipliedColorU8::from_rgba(0, 0, 0, 128).unwrap();
+
+    let tint = Color::from_rgba8(255, 128, 0, 255);
+    let paint = PixmapPaint::default();
+
+    let mut pixmap = Pixmap::new(2, 1).unwrap();
+    pixmap.draw_pixmap_tinted(0, 0, icon.as_ref(), tint, TintMode::Alpha, &paint, Transform::identity(), None);
+    assert_eq!(pixmap.pixel(0, 0).unwrap(), PremultipliedColorU8::from_rgba(255, 128, 0, 255).unwrap());
+    assert_eq!(pixmap.pixel(1, 0).unwrap(), PremultipliedColorU8::from_rgba(128, 64, 0, 128).unwrap());
+
+    // Source RGB is ignored.
+    let mut pixmap2 = Pixmap::new(2, 1).unwrap();
+    pixmap2.draw_pixmap_tinted(0, 0, gray_icon.as_ref(), tint, TintMode::Alpha, &paint, Transform::identity(), None);
+    assert_eq!(pixmap, pixmap2);
+
+    pixmap.fill(Color::TRANSPARENT);
+    pixmap.draw_pixmap_tinted(0, 0, icon.as_ref(), tint, TintMode::Multiply, &paint, Transform::identity(), None);
+    assert_eq!(pixmap.pixel(0, 0).unwrap(), PremultipliedColorU8::from_rgba(200, 50, 0, 255).unwrap());
+    assert_eq!(pixmap.pixel(1, 0).unwrap(), PremultipliedColorU8::from_rgba(10, 30, 0, 128).unwrap());
+
+    // A white tint changes nothing.
+    pixmap.fill(Color::TRANSPARENT);
+    pixmap.draw_pixmap_tinted(0, 0, icon.as_ref(), Color::WHITE, TintMode::Multiply, &paint, Transform::identity(), None);
+    assert_eq!(pixmap, icon);
+}
+
+#[test]
+fn draw_pixmap_snap_to_pixel_grid() {
+    let mut image = Pixmap::new(4, 4).unwrap();
+    image.fill(Color::WHITE);
+    image.pixels_mut()[5] = PremultipliedColorU8::from_rgba(0, 0, 0, 255).unwrap();
+
+    for quality in [FilterQuality::Nearest, FilterQuality::Bilinear, FilterQuality::Bicubic] {
+        let mut paint = PixmapPaint { quality, snap_to_pixel_grid: true, ..PixmapPaint::default() };
+
+        let mut expected = Pixmap::new(20, 20).unwrap();
+        expected.draw_pixmap(3, 5, image.as_ref(), &paint, Transform::identity(), None);
+
+        for (tx, ty) in [(0.3, 0.2), (-0.4, 0.49), (-0.5, -0.5), (1.7, 0.6)] {
+            let (x, y) = if tx > 1.0 { (1, 4) } else { (3, 5) };
+            let mut pixmap = Pixmap::new(20, 20).unwrap();
+            pixmap.draw_pixmap(x, y, image.as_ref(), &paint, Transform::from_translate(tx, ty), None);
+            assert_eq!(pixmap, expected);
+        }
+
+        // Pixel-art upscaling.
+        let mut expected = Pixmap::new(20, 20).unwrap();
+        expected.draw_pixmap(0, 0, image.as_ref(), &paint, Transform::from_row(3.0, 0.0, 0.0, 3.0, 2.0, 1.0), None);
+        let mut pixmap = Pixmap::new(20, 20).unwrap();
+        pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, Transform::from_row(3.0, 0.0, 0.0, 3.0, 1.6, 1.4), None);
+        assert_eq!(pixmap, expected);
+
+        // Without snapping, a subpixel offset is filtered.
+        paint.snap_to_pixel_grid = false;
+        let mut pixmap = Pixmap::new(20, 20).unwrap();
+        pixmap.draw_pixmap(3, 5, image.as_ref(), &paint, Transform::from_translate(0.5, 0.0), None);
+        let black = pixmap.pixel(4, 6).unwrap().red();
+        let next = pixmap.pixel(5, 6).unwrap().red();
+        if quality == FilterQuality::Nearest {
+            assert_eq!((black, next), (0, 255));
+        } else {
+            assert!(black > 0 && black < 255);
+            assert!(next > 0 && next < 255);
+        }
+    }
+}
+
+#[test]
+fn bilinear_transparent_edge() {
+    // Transparent left half, opaque red right half.
+    let mut image = Pixmap::new(4, 4).unwrap();
+    for (i, pixel) in image.pixels_mut().iter_mut().enumerate() {
+        *pixel = if i % 4 < 2 {
+            PremultipliedColorU8::TRANSPARENT
+        } else {
+            PremultipliedColorU8::from_rgba(255, 0, 0, 255).unwrap()
+        };
+    }
+
+    let paint = PixmapPaint { quality: FilterQuality::Bilinear, ..PixmapPaint::default() };
+
+    let mut pixmap = Pixmap::new(8, 4).unwrap();
+    pixmap.fill(Color::WHITE);
+    pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, Transform::from_translate(0.5, 0.0), None);
+
+    for y in 0..4 {
+        // The half-covered pixel between transparent and red.
+        let c = pixmap.pixel(2, y).unwrap();
+        assert_eq!(c.red(), 255);
+        assert_eq!(c.green(), c.blue());
+        assert!(c.green() > 0 && c.green() < 255);
+
+        // Pad replicates the right red column, so there is no fade at the image border.
+        assert_eq!(pixmap.pixel(4, y).unwrap(), PremultipliedColorU8::from_rgba(255, 0, 0, 255).unwrap());
+
+        // Outside of the image.
+        assert_eq!(pixmap.pixel(6, y).unwrap(), PremultipliedColorU8::from_rgba(255, 255, 255, 255).unwrap());
+    }
+
+    // Over a transparent background the color must stay pure red once demultiplied.
+    let mut pixmap = Pixmap::new(8, 4).unwrap();
+    pixmap.draw_pixmap(0, 0, image.as_ref(), &paint, Transform::from_translate(0.5, 0.0), None);
+    for x in 0..8 {
+        let c = pixmap.pixel(x, 1).unwrap().demultiply();
+        if c.alpha() != 0 {
+            assert_eq!((c.red(), c.green(), c.blue()), (255, 0, 0));
+        }
+    }
+}
@@ -73,6 +73,42 @@ fn fill() {
     assert_eq!(pixmap.pixel(1, 1).unwrap(), c.premultiply().to_color_u8());
 }
 
+#[test]
+fn fill_overwrites() {
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    pixmap.fill(Color::from_rgba8(255, 0, 0, 255));
+
+    let c = Color::from_rgba8(50, 100, 150, 100);
+    pixmap.fill(c);
+    assert!(pixmap.pixels().iter().all(|p| *p == c.premultiply().to_color_u8()));
+}
+
+#[test]
+fn new_filled() {
+    let c = Color::from_rgba8(50, 100, 150, 100);
+    let pixmap = Pixmap::new_filled(7, 3, c).unwrap();
+    assert_eq!(pixmap.width(), 7);
+    assert_eq!(pixmap.height(), 3);
+    assert!(pixmap.pixels().iter().all(|p| *p == c.premultiply().to_color_u8()));
+
+    let pixmap = Pixmap::new_filled(7, 3, Color::TRANSPARENT).unwrap();
+    assert_eq!(pixmap, Pixmap::new(7, 3).unwrap());
+
+    assert!(Pixmap::new_filled(0, 3, c).is_none());
+}
+
+#[test]
+fn clear() {
+    let mut pixmap = Pixmap::new(10, 10).unwrap();
+    pixmap.fill(Color::from_rgba8(50, 100, 150, 200));
+    pixmap.clear();
+    assert!(pixmap.data().iter().all(|c| *c == 0));
+
+    pixmap.fill(Color::from_rgba8(50, 100, 150, 200));
+    pixmap.fill(Color::TRANSPARENT);
+    assert!(pixmap.data().iter().all(|c| *c == 0));
+}
+
 #[test]
 fn draw_pixmap() {
     // Tests that painting algorithm will switch `Bicubic`/`Bilinear` to `Nearest`.
@@ -171,3 +207,547 @@ fn draw_pixmap_opacity() {
     let expected = Pixmap::load_png("tests/images/canvas/draw-pixmap-opacity.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+// Checks that all pixels outside of the damage are transparent.
+fn is_outside_untouched(pixmap: &Pixmap, damage: IntRect) -> bool {
+    for y in 0..pixmap.height() {
+        for x in 0..pixmap.width() {
+            let inside = x as i32 >= damage.left() && (x as i32) < damage.right() &&
+                         y as i32 >= damage.top() && (y as i32) < damage.bottom();
+            if !inside && pixmap.pixel(x, y).unwrap().alpha() != 0 {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+#[test]
+fn damage_tracking() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = true;
+
+    let mut pixmap = Pixmap::new(200, 200).unwrap();
+    let mut pixmap_mut = pixmap.as_mut();
+    let mut tracker = pixmap_mut.with_damage_tracking();
+    assert_eq!(tracker.damage(), None);
+
+    tracker.fill_path(
+        &PathBuilder::from_circle(50.5, 50.5, 20.0).unwrap(),
+        &paint,
+        FillRule::Winding,
+        Transform::identity(),
+        None,
+    );
+    assert_eq!(tracker.damage(), IntRect::from_ltrb(30, 30, 71, 71));
+
+    tracker.fill_rect(
+        Rect::from_xywh(100.0, 150.0, 20.0, 20.0).unwrap(),
+        &paint,
+        Transform::from_translate(0.5, 0.0),
+        None,
+    );
+    assert_eq!(tracker.damage(), IntRect::from_ltrb(30, 30, 121, 170));
+
+    let damage = tracker.damage().unwrap();
+    assert!(is_outside_untouched(&pixmap, damage));
+}
+
+#[test]
+fn damage_tracking_stroke() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = true;
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(20.0, 20.0);
+    pb.line_to(80.0, 40.0);
+    pb.line_to(20.0, 60.0);
+    let path = pb.finish().unwrap();
+
+    let mut stroke = Stroke::default();
+    stroke.width = 8.0;
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut pixmap_mut = pixmap.as_mut();
+    let mut tracker = pixmap_mut.with_damage_tracking();
+    tracker.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    let damage = tracker.damage().unwrap();
+    assert!(is_outside_untouched(&pixmap, damage));
+
+    // Hairline.
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut pixmap_mut = pixmap.as_mut();
+    let mut tracker = pixmap_mut.with_damage_tracking();
+    stroke.width = 0.0;
+    tracker.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    assert_eq!(tracker.damage(), IntRect::from_ltrb(19, 19, 81, 61));
+    let damage = tracker.damage().unwrap();
+    assert!(is_outside_untouched(&pixmap, damage));
+
+    // Hairline caps can extend the line further.
+    for line_cap in [LineCap::Round, LineCap::Square] {
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        let mut pixmap_mut = pixmap.as_mut();
+        let mut tracker = pixmap_mut.with_damage_tracking();
+        stroke.line_cap = line_cap;
+        tracker.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        assert_eq!(tracker.damage(), IntRect::from_ltrb(18, 18, 82, 62));
+        let damage = tracker.damage().unwrap();
+        assert!(is_outside_untouched(&pixmap, damage));
+    }
+}
+
+#[test]
+fn damage_tracking_clipped() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut pixmap_mut = pixmap.as_mut();
+    let mut tracker = pixmap_mut.with_damage_tracking();
+    tracker.fill_rect(Rect::from_xywh(-20.0, 50.0, 200.0, 20.0).unwrap(), &paint, Transform::identity(), None);
+    assert_eq!(tracker.damage(), IntRect::from_ltrb(0, 50, 100, 70));
+
+    // Outside the pixmap.
+    tracker.reset_damage();
+    tracker.fill_rect(Rect::from_xywh(120.0, 50.0, 20.0, 20.0).unwrap(), &paint, Transform::identity(), None);
+    assert_eq!(tracker.damage(), None);
+}
+
+#[test]
+fn from_size() {
+    let size = IntSize::from_wh(20, 10).unwrap();
+    let pixmap = Pixmap::from_size(size).unwrap();
+    assert_eq!(pixmap, Pixmap::new(20, 10).unwrap());
+}
+
+#[test]
+fn too_big() {
+    assert!(Pixmap::new(u32::MAX, 1).is_none());
+    assert!(Pixmap::new(i32::MAX as u32 / 4 + 1, 1).is_none());
+    assert!(Mask::new(u32::MAX, 1).is_none());
+    assert!(PixmapRef::from_bytes(&[0; 4], u32::MAX, u32::MAX).is_none());
+}
+
+#[test]
+fn try_new() {
+    assert_eq!(Pixmap::try_new(20, 10), Ok(Pixmap::new(20, 10).unwrap()));
+    assert_eq!(Pixmap::try_new(0, 10), Err(TinySkiaError::ZeroSize));
+    assert_eq!(Pixmap::try_new(10, 0), Err(TinySkiaError::ZeroSize));
+    assert_eq!(Pixmap::try_new(u32::MAX, 1), Err(TinySkiaError::SizeOverflow));
+}
+
+#[test]
+fn set_pixel() {
+    let color = ColorU8::from_rgba(50, 100, 150, 200).premultiply();
+
+    let mut pixmap = Pixmap::new(10, 5).unwrap();
+    pixmap.set_pixel(3, 2, color);
+    assert_eq!(pixmap.pixel(3, 2), Some(color));
+    assert_eq!(pixmap.pixels()[2 * 10 + 3], color);
+    assert_eq!(pixmap.pixels().iter().filter(|p| **p == color).count(), 1);
+
+    // Out of bounds.
+    pixmap.set_pixel(10, 0, color);
+    pixmap.set_pixel(0, 5, color);
+    pixmap.set_pixel(u32::MAX, u32::MAX, color);
+    assert_eq!(pixmap.pixels().iter().filter(|p| **p == color).count(), 1);
+    assert_eq!(pixmap.pixel(10, 0), None);
+    assert_eq!(pixmap.pixel(0, 5), None);
+    assert_eq!(pixmap.pixel(u32::MAX, u32::MAX), None);
+}
+
+#[test]
+fn rows() {
+    let mut pixmap = Pixmap::new(3, 4).unwrap();
+    assert_eq!(pixmap.rows().count(), 4);
+    assert!(pixmap.rows().all(|row| row.len() == 3));
+
+    let color = ColorU8::from_rgba(50, 100, 150, 200).premultiply();
+    for (y, row) in pixmap.rows_mut().enumerate() {
+        row[y % 3] = color;
+    }
+
+    assert_eq!(pixmap.pixel(0, 0), Some(color));
+    assert_eq!(pixmap.pixel(1, 1), Some(color));
+    assert_eq!(pixmap.pixel(2, 2), Some(color));
+    assert_eq!(pixmap.pixel(0, 3), Some(color));
+    assert_eq!(pixmap.pixels().iter().filter(|p| **p == color).count(), 4);
+
+    let last = pixmap.rows().next_back().unwrap();
+    assert_eq!(last, &pixmap.pixels()[9..]);
+    assert!(pixmap.rows().eq(pixmap.as_ref().rows()));
+}
+
+#[test]
+fn set_pixel_with_stride() {
+    let color = ColorU8::from_rgba(50, 100, 150, 200).premultiply();
+
+    let mut data = vec![0; 16 * 3];
+    let mut pixmap = PixmapMut::from_bytes_with_stride(&mut data, 3, 3, 16).unwrap();
+    pixmap.set_pixel(2, 1, color);
+    // Row padding is not addressable.
+    pixmap.set_pixel(3, 1, color);
+    assert_eq!(pixmap.as_ref().pixel(2, 1), Some(color));
+    assert_eq!(pixmap.as_ref().pixel(3, 1), None);
+    assert_eq!(pixmap.pixels_mut()[4 + 2], color);
+    assert_eq!(pixmap.pixels_mut()[4 + 3], PremultipliedColorU8::TRANSPARENT);
+}
+
+#[test]
+fn encode_bmp() {
+    let mut pixmap = Pixmap::new(3, 2).unwrap();
+    pixmap.pixels_mut()[0] = ColorU8::from_rgba(255, 0, 0, 255).premultiply();
+    pixmap.pixels_mut()[5] = ColorU8::from_rgba(0, 0, 255, 128).premultiply();
+
+    let data = pixmap.encode_bmp().unwrap();
+    assert_eq!(data.len(), 14 + 108 + 3 * 2 * 4);
+    assert_eq!(&data[0..2], b"BM");
+    assert_eq!(u32::from_le_bytes([data[2], data[3], data[4], data[5]]), data.len() as u32);
+    assert_eq!(u32::from_le_bytes([data[10], data[11], data[12], data[13]]), 122);
+    assert_eq!(i32::from_le_bytes([data[18], data[19], data[20], data[21]]), 3);
+    assert_eq!(i32::from_le_bytes([data[22], data[23], data[24], data[25]]), 2);
+    assert_eq!(u16::from_le_bytes([data[28], data[29]]), 32);
+
+    // Rows are stored bottom-up.
+    let pixels = &data[122..];
+    assert_eq!(&pixels[8..12], &[255, 0, 0, 128]);
+    assert_eq!(&pixels[12..16], &[0, 0, 255, 255]);
+    assert_eq!(&pixels[0..4], &[0, 0, 0, 0]);
+}
+
+#[test]
+fn encode_tga() {
+    let mut pixmap = Pixmap::new(3, 2).unwrap();
+    pixmap.pixels_mut()[0] = ColorU8::from_rgba(255, 0, 0, 255).premultiply();
+    pixmap.pixels_mut()[5] = ColorU8::from_rgba(0, 0, 255, 128).premultiply();
+
+    let data = pixmap.encode_tga().unwrap();
+    assert_eq!(data.len(), 18 + 3 * 2 * 4 + 26);
+    assert_eq!(data[2], 2);
+    assert_eq!(u16::from_le_bytes([data[12], data[13]]), 3);
+    assert_eq!(u16::from_le_bytes([data[14], data[15]]), 2);
+    assert_eq!(data[16], 32);
+    assert_eq!(data[17], 0x28);
+
+    // Rows are stored top-down.
+    let pixels = &data[18..];
+    assert_eq!(&pixels[0..4], &[0, 0, 255, 255]);
+    assert_eq!(&pixels[20..24], &[255, 0, 0, 128]);
+    assert!(data.ends_with(b"TRUEVISION-XFILE.\0"));
+}
+
+#[test]
+fn encode_tga_too_large() {
+    assert!(Pixmap::new(65535, 1).unwrap().encode_tga().is_some());
+    assert!(Pixmap::new(65536, 1).unwrap().encode_tga().is_none());
+    assert!(Pixmap::new(1, 65536).unwrap().encode_tga().is_none());
+}
+
+#[test]
+fn from_bytes_with_stride() {
+    let data = [0; 40];
+    // Stride is smaller than width.
+    assert!(PixmapRef::from_bytes_with_stride(&data, 3, 2, 8).is_none());
+    // Stride is not pixel-aligned.
+    assert!(PixmapRef::from_bytes_with_stride(&data, 3, 2, 14).is_none());
+    // Not enough data.
+    assert!(PixmapRef::from_bytes_with_stride(&data, 3, 3, 16).is_none());
+    assert!(PixmapRef::from_bytes_with_stride(&data[..27], 3, 2, 16).is_none());
+    assert!(PixmapRef::from_bytes_with_stride(&data, 1, 2, usize::MAX - 3).is_none());
+    assert!(PixmapRef::from_bytes_with_stride(&data, 1, 1, i32::MAX as usize + 1).is_none());
+
+    // The last row doesn't require padding.
+    let pixmap = PixmapRef::from_bytes_with_stride(&data[..28], 3, 2, 16).unwrap();
+    assert_eq!(pixmap.stride(), 16);
+    assert_eq!(pixmap.data().len(), 28);
+    assert_eq!(pixmap.rows().count(), 2);
+    assert!(pixmap.rows().all(|row| row.len() == 3));
+    assert_eq!(pixmap.pixel(2, 1), Some(PremultipliedColorU8::TRANSPARENT));
+    assert_eq!(pixmap.pixel(3, 0), None);
+    assert_eq!(pixmap.pixel(0, 2), None);
+}
+
+#[test]
+fn encode_png_with_stride() {
+    let mut data = [0; 40];
+    for (i, pixel) in data.chunks_exact_mut(4).enumerate() {
+        pixel.copy_from_slice(&[i as u8 * 10, 0, 0, 255]);
+    }
+
+    let pixmap = PixmapRef::from_bytes_with_stride(&data, 3, 2, 20).unwrap();
+    let decoded = Pixmap::decode_png(&pixmap.encode_png().unwrap()).unwrap();
+    assert_eq!(decoded.width(), 3);
+    assert_eq!(decoded.height(), 2);
+    assert!(decoded.rows().eq(pixmap.rows()));
+}
+
+#[test]
+fn stride_fill_and_clear() {
+    let mut data = [255; 4 * 4 * 3];
+    {
+        // A 2x2 region at (1, 1).
+        let mut pixmap = PixmapMut::from_bytes_with_stride(&mut data[20..], 2, 2, 16).unwrap();
+        pixmap.clear();
+        assert_eq!(pixmap.to_owned(), Pixmap::new(2, 2).unwrap());
+
+        pixmap.fill(Color::from_rgba8(0, 0, 255, 255));
+    }
+
+    let pixmap = PixmapRef::from_bytes(&data, 4, 3).unwrap();
+    for y in 0..3 {
+        for x in 0..4 {
+            let expected = if (1..3).contains(&x) && (1..3).contains(&y) {
+                ColorU8::from_rgba(0, 0, 255, 255).premultiply()
+            } else {
+                ColorU8::from_rgba(255, 255, 255, 255).premultiply()
+            };
+            assert_eq!(pixmap.pixel(x, y).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn stride_draw() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let path = PathBuilder::from_circle(25.0, 25.0, 30.0).unwrap();
+
+    let mut expected = Pixmap::new(50, 50).unwrap();
+    expected.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    {
+        // A 50x50 region at (20, 30).
+        let offset = (30 * 100 + 20) * BYTES_PER_PIXEL;
+        let mut sub = PixmapMut::from_bytes_with_stride(
+            &mut pixmap.data_mut()[offset..],
+            50,
+            50,
+            100 * BYTES_PER_PIXEL,
+        )
+        .unwrap();
+        sub.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+        sub.fill_rect_fast(
+            Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap(),
+            &paint,
+            Transform::identity(),
+            None,
+        );
+    }
+    expected.fill_rect_fast(
+        Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap(),
+        &paint,
+        Transform::identity(),
+        None,
+    );
+
+    let region = IntRect::from_xywh(20, 30, 50, 50).unwrap();
+    assert_eq!(pixmap.clone_rect(region).unwrap(), expected);
+    assert!(is_outside_untouched(&pixmap, region));
+}
+
+#[test]
+fn stride_draw_pixmap() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut source = Pixmap::new(100, 100).unwrap();
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    source.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    // A 40x30 region at (10, 20).
+    let offset = (20 * 100 + 10) * BYTES_PER_PIXEL;
+    let sub = PixmapRef::from_bytes_with_stride(
+        &source.data()[offset..],
+        40,
+        30,
+        100 * BYTES_PER_PIXEL,
+    )
+    .unwrap();
+    let packed = source.clone_rect(IntRect::from_xywh(10, 20, 40, 30).unwrap()).unwrap();
+    assert_eq!(sub.to_owned(), packed);
+
+    let mut paint = PixmapPaint::default();
+    paint.quality = FilterQuality::Bicubic;
+    let ts = Transform::from_row(1.5, 0.2, 0.1, 1.2, 5.0, 5.0);
+
+    let mut pixmap1 = Pixmap::new(100, 100).unwrap();
+    pixmap1.draw_pixmap(0, 0, sub, &paint, ts, None);
+
+    let mut pixmap2 = Pixmap::new(100, 100).unwrap();
+    pixmap2.draw_pixmap(0, 0, packed.as_ref(), &paint, ts, None);
+
+    assert_eq!(pixmap1, pixmap2);
+    assert_eq!(Mask::from_pixmap(sub, MaskType::Alpha), Mask::from_pixmap(packed.as_ref(), MaskType::Alpha));
+}
+
+#[test]
+fn flatten_onto() {
+    let mut pixmap = Pixmap::new(3, 1).unwrap();
+    let pixels = pixmap.pixels_mut();
+    pixels[0] = ColorU8::from_rgba(0, 0, 0, 0).premultiply();
+    pixels[1] = ColorU8::from_rgba(255, 0, 0, 128).premultiply();
+    pixels[2] = ColorU8::from_rgba(10, 20, 30, 255).premultiply();
+
+    // Background alpha is ignored.
+    let flat = pixmap.flatten_onto(Color::from_rgba8(0, 0, 255, 100));
+    assert_eq!(flat.width(), 3);
+    assert_eq!(flat.height(), 1);
+    assert_eq!(
+        flat.pixels(),
+        &[
+            PremultipliedColorU8::from_rgba(0, 0, 255, 255).unwrap(),
+            PremultipliedColorU8::from_rgba(128, 0, 127, 255).unwrap(),
+            PremultipliedColorU8::from_rgba(10, 20, 30, 255).unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn flatten_onto_with_stride() {
+    let mut data = vec![0u8; 12 * 2];
+    data[12..16].copy_from_slice(&[0, 100, 0, 100]);
+    let pixmap = PixmapRef::from_bytes_with_stride(&data, 2, 2, 12).unwrap();
+
+    let flat = pixmap.flatten_onto(Color::WHITE);
+    assert_eq!(flat.data().len(), 2 * 2 * 4);
+    assert!(flat.pixels().iter().all(|p| p.alpha() == 255));
+    assert_eq!(
+        flat.pixel(0, 1).unwrap(),
+        PremultipliedColorU8::from_rgba(155, 255, 155, 255).unwrap()
+    );
+    assert_eq!(
+        flat.pixel(1, 1).unwrap(),
+        PremultipliedColorU8::from_rgba(255, 255, 255, 255).unwrap()
+    );
+}
+
+#[test]
+fn hash() {
+    let mut pixmap1 = Pixmap::new(3, 2).unwrap();
+    pixmap1.fill(Color::from_rgba8(10, 20, 30, 40));
+    let pixmap2 = pixmap1.clone();
+    assert_eq!(pixmap1.hash(), pixmap2.hash());
+
+    pixmap1.pixels_mut()[5] = PremultipliedColorU8::from_rgba(0, 0, 0, 0).unwrap();
+    assert_ne!(pixmap1.hash(), pixmap2.hash());
+
+    // Same data, different size.
+    let pixmap3 = PixmapRef::from_bytes(pixmap2.data(), 2, 3).unwrap();
+    assert_ne!(pixmap2.hash(), pixmap3.hash());
+}
+
+#[test]
+fn hash_ignores_stride() {
+    let mut data = vec![0xAA; 12 * 2];
+    data[..8].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    data[12..20].copy_from_slice(&[9, 10, 11, 12, 13, 14, 15, 16]);
+    let pixmap1 = PixmapRef::from_bytes_with_stride(&data, 2, 2, 12).unwrap();
+
+    let packed: Vec<u8> = (1..=16).collect();
+    let pixmap2 = PixmapRef::from_bytes(&packed, 2, 2).unwrap();
+    assert_eq!(pixmap1.hash(), pixmap2.hash());
+}
+
+#[test]
+fn diff() {
+    let mut pixmap1 = Pixmap::new(2, 2).unwrap();
+    pixmap1.fill(Color::from_rgba8(100, 100, 100, 255));
+    let mut pixmap2 = pixmap1.clone();
+
+    let stats = pixmap1.diff(pixmap2.as_ref(), 0).unwrap();
+    assert_eq!(stats.max, 0);
+    assert_eq!(stats.mean, 0.0);
+    assert_eq!(stats.pixels_over_threshold, 0);
+
+    pixmap2.pixels_mut()[0] = PremultipliedColorU8::from_rgba(102, 100, 100, 255).unwrap();
+    pixmap2.pixels_mut()[3] = PremultipliedColorU8::from_rgba(100, 90, 100, 255).unwrap();
+    let stats = pixmap1.diff(pixmap2.as_ref(), 2).unwrap();
+    assert_eq!(stats.max, 10);
+    assert_eq!(stats.mean, 12.0 / 16.0);
+    assert_eq!(stats.pixels_over_threshold, 1);
+
+    let pixmap3 = Pixmap::new(2, 3).unwrap();
+    assert_eq!(pixmap1.diff(pixmap3.as_ref(), 0), None);
+}
+
+#[test]
+fn bgra() {
+    let mut pixmap = Pixmap::new(2, 2).unwrap();
+    pixmap.set_pixel(0, 0, ColorU8::from_rgba(50, 100, 150, 200).premultiply());
+    pixmap.set_pixel(1, 1, ColorU8::from_rgba(255, 0, 0, 255).premultiply());
+
+    let data = pixmap.to_bgra();
+    assert_eq!(data.len(), 16);
+    // Values are still premultiplied and alpha stays in place.
+    assert_eq!(&data[0..4], &[118, 78, 39, 200]);
+    assert_eq!(&data[12..16], &[0, 0, 255, 255]);
+
+    let mut dst = vec![0; 16];
+    assert!(pixmap.copy_to_bgra(&mut dst));
+    assert_eq!(dst, data);
+    assert!(!pixmap.copy_to_bgra(&mut [0; 15]));
+
+    let size = IntSize::from_wh(2, 2).unwrap();
+    let pixmap2 = Pixmap::from_bgra(data, size).unwrap();
+    assert_eq!(pixmap2, pixmap);
+    assert!(Pixmap::from_bgra(vec![0; 12], size).is_none());
+}
+
+#[test]
+fn bgra_with_stride() {
+    let mut data = vec![0; 16 * 2];
+    data[0..4].copy_from_slice(&[10, 20, 30, 40]);
+    data[16..20].copy_from_slice(&[1, 2, 3, 4]);
+    let pixmap = PixmapRef::from_bytes_with_stride(&data, 3, 2, 16).unwrap();
+
+    let bgra = pixmap.to_bgra();
+    assert_eq!(bgra.len(), 3 * 2 * 4);
+    assert_eq!(&bgra[0..4], &[30, 20, 10, 40]);
+    assert_eq!(&bgra[12..16], &[3, 2, 1, 4]);
+}
+
+#[test]
+fn from_pixels() {
+    let mut pixels = vec![PremultipliedColorU8::TRANSPARENT; 6];
+    assert!(PixmapMut::from_pixels(&mut pixels, 0, 2).is_none());
+    assert!(PixmapMut::from_pixels(&mut pixels, 3, 3).is_none());
+    // Extra pixels are an error as well.
+    assert!(PixmapMut::from_pixels(&mut pixels, 2, 2).is_none());
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(0.5, 0.0);
+    pb.line_to(3.0, 0.5);
+    pb.line_to(1.0, 2.0);
+    let path = pb.finish().unwrap();
+
+    let mut pixmap = Pixmap::new(3, 2).unwrap();
+    pixmap.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let mut target = PixmapMut::from_pixels(&mut pixels, 3, 2).unwrap();
+    assert_eq!(target.stride(), 12);
+    target.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    assert_eq!(pixels.as_slice(), pixmap.pixels());
+}
+
+#[test]
+fn pixels_share_data() {
+    let mut pixmap = Pixmap::new(3, 2).unwrap();
+    assert_eq!(pixmap.pixels().len(), 6);
+
+    pixmap.pixels_mut()[4] = PremultipliedColorU8::from_rgba(10, 20, 30, 40).unwrap();
+    assert_eq!(&pixmap.data()[16..20], &[10, 20, 30, 40]);
+
+    pixmap.data_mut()[0..4].copy_from_slice(&[50, 60, 70, 80]);
+    assert_eq!(pixmap.pixels()[0], PremultipliedColorU8::from_rgba(50, 60, 70, 80).unwrap());
+    assert_eq!(pixmap.pixel(1, 1).unwrap(), pixmap.pixels()[4]);
+}
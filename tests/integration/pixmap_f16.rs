@@ -0,0 +1,91 @@
+use tiny_skia::*;
+
+#[test]
+fn new() {
+    let pixmap = PixmapF16::new(20, 10).unwrap();
+    assert_eq!(pixmap.width(), 20);
+    assert_eq!(pixmap.height(), 10);
+    assert!(pixmap.pixels().iter().all(|p| *p == PremultipliedColorF16::TRANSPARENT));
+    assert!(PixmapF16::new(0, 10).is_none());
+}
+
+#[test]
+fn fill() {
+    let mut pixmap = PixmapF16::new(2, 2).unwrap();
+    let c = PremultipliedColorF16::from_rgba(4.0, 0.5, 0.0, 1.0).unwrap();
+    pixmap.fill(c);
+    assert_eq!(pixmap.pixel(1, 1), Some(c));
+    assert_eq!(pixmap.pixel(2, 1), None);
+
+    pixmap.clear();
+    assert_eq!(pixmap.pixel(1, 1), Some(PremultipliedColorF16::TRANSPARENT));
+}
+
+#[test]
+fn pixmap_round_trip() {
+    let mut pixmap = Pixmap::new(16, 16).unwrap();
+    for (i, p) in pixmap.pixels_mut().iter_mut().enumerate() {
+        let a = i as u8;
+        *p = PremultipliedColorU8::from_rgba(a, a / 2, a / 3, a).unwrap();
+    }
+
+    let f16 = PixmapF16::from_pixmap(pixmap.as_ref()).unwrap();
+    assert_eq!(f16.to_pixmap(), pixmap);
+}
+
+#[test]
+fn fill_path_source_over() {
+    let path = PathBuilder::from_rect(Rect::from_xywh(1.0, 1.0, 2.0, 2.0).unwrap());
+
+    let mut pixmap = PixmapF16::new(4, 4).unwrap();
+    pixmap.fill(PremultipliedColorF16::from_rgba(0.0, 0.0, 1.0, 1.0).unwrap());
+    let c = PremultipliedColorF16::from_rgba(0.5, 0.0, 0.0, 0.5).unwrap();
+    pixmap.fill_path(&path, c, FillRule::Winding, false, Transform::identity());
+
+    let p = pixmap.pixel(1, 1).unwrap();
+    assert_eq!((p.red(), p.green(), p.blue(), p.alpha()), (0.5, 0.0, 0.5, 1.0));
+    let p = pixmap.pixel(0, 0).unwrap();
+    assert_eq!((p.red(), p.green(), p.blue(), p.alpha()), (0.0, 0.0, 1.0, 1.0));
+}
+
+#[test]
+fn fill_path_hdr() {
+    let path = PathBuilder::from_rect(Rect::from_xywh(0.0, 0.0, 2.0, 2.0).unwrap());
+    let c = PremultipliedColorF16::from_rgba(3.0, 0.0, 0.0, 0.5).unwrap();
+
+    let mut pixmap = PixmapF16::new(2, 2).unwrap();
+    pixmap.fill_path(&path, c, FillRule::Winding, true, Transform::identity());
+    pixmap.fill_path(&path, c, FillRule::Winding, true, Transform::identity());
+
+    // Values larger than 1.0 are preserved and accumulated.
+    let p = pixmap.pixel(0, 0).unwrap();
+    assert_eq!(p.red(), 4.5);
+    assert_eq!(p.alpha(), 0.75);
+
+    // And clamped to alpha during conversion.
+    assert_eq!(
+        pixmap.to_pixmap().pixel(0, 0),
+        PremultipliedColorU8::from_rgba(191, 0, 0, 191)
+    );
+}
+
+#[test]
+fn fill_path_anti_aliased() {
+    let path = PathBuilder::from_circle(50.0, 50.0, 40.0).unwrap();
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+
+    let c = PremultipliedColorF16::from(ColorU8::from_rgba(50, 127, 150, 200).premultiply());
+    let mut pixmap = PixmapF16::new(100, 100).unwrap();
+    pixmap.fill_path(&path, c, FillRule::Winding, true, Transform::identity());
+
+    // Coverage is the same, but blending precision is not.
+    let result = pixmap.to_pixmap();
+    for (a, b) in result.pixels().iter().zip(expected.pixels()) {
+        assert!((a.alpha() as i32 - b.alpha() as i32).abs() <= 1);
+        assert!((a.red() as i32 - b.red() as i32).abs() <= 1);
+    }
+}
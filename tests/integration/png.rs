@@ -29,4 +29,13 @@ fn decode_rgba() {
     assert_eq!(pixmap.pixel(50, 50).unwrap(), ColorU8::from_rgba(33, 190, 47, 252).premultiply());
 }
 
-// TODO: test encoding, somehow
+#[test]
+fn encode_translucent() {
+    let mut pixmap = Pixmap::new(2, 1).unwrap();
+    pixmap.pixels_mut()[0] = ColorU8::from_rgba(200, 100, 50, 10).premultiply();
+    pixmap.pixels_mut()[1] = ColorU8::from_rgba(36, 191, 49, 252).premultiply();
+
+    let data = pixmap.encode_png().unwrap();
+    let pixmap2 = Pixmap::decode_png(&data).unwrap();
+    assert_eq!(pixmap, pixmap2);
+}
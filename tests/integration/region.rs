@@ -0,0 +1,118 @@
+use tiny_skia::*;
+
+fn rect(x: i32, y: i32, w: u32, h: u32) -> IntRect {
+    IntRect::from_xywh(x, y, w, h).unwrap()
+}
+
+#[test]
+fn empty() {
+    let region = Region::new();
+    assert!(region.is_empty());
+    assert!(!region.is_rect());
+    assert_eq!(region.bounds(), None);
+    assert_eq!(region.rects().count(), 0);
+    assert!(!region.contains_point(0, 0));
+}
+
+#[test]
+fn single_rect() {
+    let region = Region::from_rect(rect(10, 20, 30, 40));
+    assert!(region.is_rect());
+    assert_eq!(region.bounds(), Some(rect(10, 20, 30, 40)));
+    assert_eq!(region.rects().collect::<Vec<_>>(), vec![rect(10, 20, 30, 40)]);
+
+    assert!(region.contains_point(10, 20));
+    assert!(region.contains_point(39, 59));
+    assert!(!region.contains_point(40, 59));
+    assert!(!region.contains_point(39, 60));
+    assert!(!region.contains_point(9, 20));
+    assert!(!region.contains_point(10, 19));
+}
+
+#[test]
+fn add_rect() {
+    let mut region = Region::new();
+    region.add_rect(rect(0, 0, 20, 20));
+    region.add_rect(rect(10, 10, 20, 20));
+
+    assert_eq!(region.rects().collect::<Vec<_>>(), vec![
+        rect(0, 0, 20, 10),
+        rect(0, 10, 30, 10),
+        rect(10, 20, 20, 10),
+    ]);
+    assert_eq!(region.bounds(), Some(rect(0, 0, 30, 30)));
+    assert!(region.contains_point(25, 15));
+    assert!(!region.contains_point(25, 5));
+    assert!(!region.contains_point(5, 25));
+}
+
+#[test]
+fn canonical() {
+    // Touching rects are merged.
+    let mut region = Region::from_rect(rect(0, 0, 10, 10));
+    region.add_rect(rect(10, 0, 10, 10));
+    region.add_rect(rect(0, 10, 20, 10));
+    assert_eq!(region, Region::from_rect(rect(0, 0, 20, 20)));
+    assert!(region.is_rect());
+
+    // The order of operations doesn't matter.
+    let mut a = Region::new();
+    a.add_rect(rect(0, 0, 20, 20));
+    a.add_rect(rect(30, 5, 10, 10));
+    a.add_rect(rect(10, 10, 20, 20));
+
+    let mut b = Region::new();
+    b.add_rect(rect(10, 10, 20, 20));
+    b.add_rect(rect(30, 5, 10, 10));
+    b.add_rect(rect(0, 0, 20, 20));
+    assert_eq!(a, b);
+
+    // Adding and removing a rect restores the original region.
+    let original = a.clone();
+    a.add_rect(rect(100, 100, 10, 10));
+    a.subtract_rect(rect(100, 100, 10, 10));
+    assert_eq!(a, original);
+}
+
+#[test]
+fn subtract_rect() {
+    let mut region = Region::from_rect(rect(0, 0, 30, 30));
+    region.subtract_rect(rect(10, 10, 10, 10));
+
+    assert_eq!(region.rects().collect::<Vec<_>>(), vec![
+        rect(0, 0, 30, 10),
+        rect(0, 10, 10, 10),
+        rect(20, 10, 10, 10),
+        rect(0, 20, 30, 10),
+    ]);
+    assert!(!region.contains_point(15, 15));
+    assert!(region.contains_point(25, 15));
+
+    region.subtract_rect(rect(-10, -10, 100, 100));
+    assert!(region.is_empty());
+}
+
+#[test]
+fn intersect() {
+    let mut region = Region::from_rect(rect(0, 0, 30, 30));
+    region.subtract_rect(rect(10, 10, 10, 10));
+    region.intersect_rect(rect(5, 5, 10, 10));
+
+    assert_eq!(region.rects().collect::<Vec<_>>(), vec![
+        rect(5, 5, 10, 5),
+        rect(5, 10, 5, 5),
+    ]);
+
+    let mut other = Region::from_rect(rect(100, 100, 10, 10));
+    other.intersect(&region);
+    assert!(other.is_empty());
+}
+
+#[test]
+fn union() {
+    let mut a = Region::from_rect(rect(0, 0, 10, 10));
+    a.add_rect(rect(20, 0, 10, 10));
+    let b = Region::from_rect(rect(10, 0, 10, 10));
+    a.union(&b);
+    assert_eq!(a, Region::from_rect(rect(0, 0, 30, 10)));
+}
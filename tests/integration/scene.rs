@@ -0,0 +1,112 @@
+use std::sync::Arc;
+
+use tiny_skia::*;
+
+fn paint(r: u8, g: u8, b: u8, a: u8) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, a);
+    paint
+}
+
+fn image() -> Pixmap {
+    let mut pixmap = Pixmap::new(20, 20).unwrap();
+    pixmap.fill(Color::from_rgba8(220, 140, 75, 180));
+    pixmap
+}
+
+#[test]
+fn equivalent_to_direct_calls() {
+    let path = PathBuilder::from_circle(40.0, 40.0, 30.0).unwrap();
+    let clip = PathBuilder::from_rect(Rect::from_xywh(0.0, 0.0, 50.0, 100.0).unwrap());
+    let stroke = Stroke { width: 4.0, ..Stroke::default() };
+    let ts1 = Transform::from_translate(10.0, 5.0);
+    let ts2 = Transform::from_scale(1.5, 1.2);
+
+    let mut scene = Scene::new();
+    scene.fill_path(path.clone(), paint(50, 127, 150, 200), FillRule::Winding, Transform::identity());
+    scene.push_transform(ts1);
+    scene.push_clip(clip.clone(), FillRule::Winding, true);
+    scene.stroke_path(path.clone(), paint(220, 140, 75, 180), stroke.clone(), ts2);
+    scene.draw_pixmap(5, 10, Arc::new(image()), PixmapPaint::default(), Transform::identity());
+    scene.pop_clip();
+    scene.pop_transform();
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    scene.render_to(&mut pixmap.as_mut());
+
+    let mut mask = Mask::new(100, 100).unwrap();
+    mask.fill_path(&clip, FillRule::Winding, true, ts1);
+
+    let mut expected = Pixmap::new(100, 100).unwrap();
+    expected.fill_path(&path, &paint(50, 127, 150, 200), FillRule::Winding, Transform::identity(), None);
+    expected.stroke_path(&path, &paint(220, 140, 75, 180), &stroke, ts1.pre_concat(ts2), Some(&mask));
+    expected.draw_pixmap(5, 10, image().as_ref(), &PixmapPaint::default(), ts1, Some(&mask));
+
+    assert_eq!(pixmap, expected);
+}
+
+#[test]
+fn viewports() {
+    let mut scene = Scene::new();
+    scene.fill_path(
+        PathBuilder::from_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap()),
+        paint(255, 0, 0, 255),
+        FillRule::Winding,
+        Transform::identity(),
+    );
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    for i in 0..3 {
+        canvas.save();
+        canvas.translate(i as f32 * 30.0, 50.0);
+        scene.render_to_canvas(&mut canvas);
+        canvas.restore();
+    }
+    drop(canvas);
+
+    assert_eq!(pixmap.pixel(5, 55).unwrap().alpha(), 255);
+    assert_eq!(pixmap.pixel(35, 55).unwrap().alpha(), 255);
+    assert_eq!(pixmap.pixel(65, 55).unwrap().alpha(), 255);
+    assert_eq!(pixmap.pixel(20, 55).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(5, 5).unwrap().alpha(), 0);
+}
+
+#[test]
+fn unbalanced_push() {
+    let mut scene = Scene::new();
+    scene.push_transform(Transform::from_translate(10.0, 10.0));
+    scene.push_clip(PathBuilder::from_rect(Rect::from_xywh(0.0, 0.0, 10.0, 10.0).unwrap()), FillRule::Winding, false);
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    let mut canvas = Canvas::new(pixmap.as_mut());
+    scene.render_to_canvas(&mut canvas);
+
+    // All pushes are popped after rendering.
+    assert_eq!(canvas.save_count(), 0);
+    assert_eq!(canvas.transform(), Transform::identity());
+    assert!(canvas.clip_mask().is_none());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "pop_clip without a matching push_clip")]
+fn mismatched_pop() {
+    let mut scene = Scene::new();
+    scene.push_transform(Transform::from_translate(10.0, 10.0));
+    scene.pop_clip();
+}
+
+#[test]
+fn is_static() {
+    fn check<T: 'static + Send + Sync + Clone>(_: &T) {}
+
+    let mut scene = Scene::new();
+    scene.draw_pixmap(0, 0, Arc::new(image()), PixmapPaint::default(), Transform::identity());
+    check(&scene);
+    assert_eq!(scene.commands().len(), 1);
+    assert!(!scene.is_empty());
+
+    scene.clear();
+    assert!(scene.is_empty());
+}
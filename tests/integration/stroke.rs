@@ -132,3 +132,196 @@ fn round_cap_join() {
     let expected = Pixmap::load_png("tests/images/stroke/round-cap-join.png").unwrap();
     assert_eq!(pixmap, expected);
 }
+
+fn draw_line_via_stroke(p0: Point, p1: Point, paint: &Paint, width: f32, ts: Transform) -> Pixmap {
+    let mut pb = PathBuilder::new();
+    pb.move_to(p0.x, p0.y);
+    pb.line_to(p1.x, p1.y);
+    let path = pb.finish().unwrap();
+
+    let mut stroke = Stroke::default();
+    stroke.width = width;
+
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.stroke_path(&path, paint, &stroke, ts, None);
+    pixmap
+}
+
+#[test]
+fn draw_line_matches_stroke() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 255);
+
+    let lines = [
+        // Fast path.
+        (Point::from_xy(10.0, 20.5), Point::from_xy(50.0, 20.5), 1.0),
+        (Point::from_xy(30.5, 80.0), Point::from_xy(30.5, 5.0), 1.0),
+        (Point::from_xy(10.0, 20.0), Point::from_xy(50.0, 20.0), 2.0),
+        // Not pixel-aligned.
+        (Point::from_xy(10.0, 20.0), Point::from_xy(50.0, 20.0), 1.0),
+        (Point::from_xy(10.3, 20.5), Point::from_xy(50.0, 20.5), 1.0),
+        // Diagonal.
+        (Point::from_xy(10.0, 20.0), Point::from_xy(50.0, 70.0), 1.0),
+    ];
+
+    for anti_alias in [true, false] {
+        paint.anti_alias = anti_alias;
+        for ts in [Transform::identity(), Transform::from_translate(5.0, -3.0)] {
+            for &(p0, p1, width) in &lines {
+                let mut pixmap = Pixmap::new(100, 100).unwrap();
+                pixmap.draw_line(p0, p1, &paint, width, ts, None);
+                assert_eq!(pixmap, draw_line_via_stroke(p0, p1, &paint, width, ts));
+            }
+        }
+    }
+}
+
+#[test]
+fn draw_line_translucent() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+
+    let p0 = Point::from_xy(10.0, 20.5);
+    let p1 = Point::from_xy(50.0, 20.5);
+    let mut pixmap = Pixmap::new(100, 100).unwrap();
+    pixmap.draw_line(p0, p1, &paint, 1.0, Transform::identity(), None);
+    assert_eq!(pixmap, draw_line_via_stroke(p0, p1, &paint, 1.0, Transform::identity()));
+
+    // Crisp, aka only a single row.
+    assert_eq!(pixmap.pixel(30, 19).unwrap().alpha(), 0);
+    assert_eq!(pixmap.pixel(30, 20).unwrap().alpha(), 200);
+    assert_eq!(pixmap.pixel(30, 21).unwrap().alpha(), 0);
+}
+
+#[test]
+fn zero_len_subpath_close() {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = true;
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 50.0);
+    pb.close();
+    let path = pb.finish().unwrap();
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(50.0, 50.0);
+    pb.line_to(50.0, 50.0);
+    let line_path = pb.finish().unwrap();
+
+    for cap in [LineCap::Butt, LineCap::Round, LineCap::Square] {
+        let mut stroke = Stroke::default();
+        stroke.width = 20.0;
+        stroke.line_cap = cap;
+
+        let mut pixmap = Pixmap::new(100, 100).unwrap();
+        pixmap.stroke_path(&path, &paint, &stroke, Transform::default(), None);
+
+        let mut expected = Pixmap::new(100, 100).unwrap();
+        expected.stroke_path(&line_path, &paint, &stroke, Transform::default(), None);
+
+        assert_eq!(pixmap, expected);
+    }
+}
+
+#[test]
+fn zero_len_segment_cap_direction() {
+    // A leading zero-length segment must not affect the cap direction.
+    let mut pb = PathBuilder::new();
+    pb.move_to(20.0, 20.0);
+    pb.line_to(20.0, 20.0);
+    pb.line_to(80.0, 80.0);
+    let path = pb.finish().unwrap();
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(20.0, 20.0);
+    pb.line_to(80.0, 80.0);
+    let expected = pb.finish().unwrap();
+
+    let mut stroke = Stroke::default();
+    stroke.width = 10.0;
+    stroke.line_cap = LineCap::Square;
+
+    assert_eq!(path.stroke(&stroke, 1.0), expected.stroke(&stroke, 1.0));
+}
+
+#[test]
+fn round_cap_resolution_scale() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(20.0, 10.0);
+    let path = pb.finish().unwrap();
+
+    let mut stroke = Stroke::default();
+    stroke.width = 10.0;
+    stroke.line_cap = LineCap::Round;
+
+    // Caps are tessellated based on the resolution scale.
+    let len1 = path.stroke(&stroke, 1.0).unwrap().len();
+    let len2 = path.stroke(&stroke, 100.0).unwrap().len();
+    let len3 = path.stroke(&stroke, 0.01).unwrap().len();
+    assert!(len2 > len1);
+    assert_eq!(len3, len1);
+}
+
+#[test]
+fn stroke_path_with_stats() {
+    let mut pb = PathBuilder::new();
+    pb.move_to(10.0, 10.0);
+    pb.line_to(190.0, 10.0);
+    pb.line_to(10.0, 30.0);
+    pb.line_to(190.0, 30.0);
+    pb.line_to(100.0, 190.0);
+    let path = pb.finish().unwrap();
+
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(50, 127, 150, 200);
+    paint.anti_alias = true;
+
+    let mut stroke = Stroke::default();
+    stroke.width = 6.0;
+
+    let mut pixmap = Pixmap::new(200, 200).unwrap();
+    let stats = pixmap.stroke_path_with_stats(&path, &paint, &stroke, Transform::identity(), None);
+    assert_eq!(stats, StrokeStats { joins: 3, miter_fallbacks: 2, caps: 2 });
+
+    // Has no effect on rendering.
+    let mut expected = Pixmap::new(200, 200).unwrap();
+    expected.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+    assert_eq!(pixmap, expected);
+
+    // Each dash is a separate contour.
+    stroke.dash = StrokeDash::new(vec![100.0, 20.0], 0.0);
+    let stats = pixmap.stroke_path_with_stats(&path, &paint, &stroke, Transform::identity(), None);
+    assert_eq!(stats, StrokeStats { joins: 3, miter_fallbacks: 2, caps: 14 });
+
+    // Hairlines are not handled by the stroker.
+    stroke.dash = None;
+    stroke.width = 0.0;
+    let stats = pixmap.stroke_path_with_stats(&path, &paint, &stroke, Transform::identity(), None);
+    assert_eq!(stats, StrokeStats::default());
+}
+
+#[test]
+fn fill_and_stroke_path() {
+    let path = PathBuilder::from_circle(100.0, 100.0, 60.0).unwrap();
+
+    let mut fill_paint = Paint::default();
+    fill_paint.set_color_rgba8(50, 127, 150, 200);
+
+    let mut stroke_paint = Paint::default();
+    stroke_paint.set_color_rgba8(220, 140, 75, 180);
+
+    let mut stroke = Stroke::default();
+    stroke.width = 12.0;
+
+    let ts = Transform::from_row(1.2, 0.3, -0.2, 0.9, 10.0, -5.0);
+
+    let mut expected = Pixmap::new(200, 200).unwrap();
+    expected.fill_path(&path, &fill_paint, FillRule::Winding, ts, None);
+    expected.stroke_path(&path, &stroke_paint, &stroke, ts, None);
+
+    let mut pixmap = Pixmap::new(200, 200).unwrap();
+    pixmap.fill_and_stroke_path(&path, &fill_paint, FillRule::Winding, &stroke, &stroke_paint, ts, None);
+    assert_eq!(pixmap, expected);
+}
@@ -0,0 +1,183 @@
+use tiny_skia::*;
+
+fn render_single(commands: &[DrawCommand], width: u32, height: u32) -> Pixmap {
+    let mut pixmap = Pixmap::new(width, height).unwrap();
+    for command in commands {
+        match command {
+            DrawCommand::FillPath { path, paint, fill_rule, transform } => {
+                pixmap.fill_path(path, paint, *fill_rule, *transform, None);
+            }
+            DrawCommand::StrokePath { path, paint, stroke, transform } => {
+                pixmap.stroke_path(path, paint, stroke, *transform, None);
+            }
+            _ => unimplemented!(),
+        }
+    }
+
+    pixmap
+}
+
+fn render_tiled(commands: &[DrawCommand], width: u32, height: u32, tile_size: u32) -> Pixmap {
+    let renderer = TiledRenderer::new(width, height, tile_size).unwrap();
+
+    let mut pixmap = Pixmap::new(width, height).unwrap();
+    let mut tiles = 0;
+    renderer.render(commands, |rect, tile| {
+        assert_eq!(rect.width(), tile.width());
+        assert_eq!(rect.height(), tile.height());
+        assert!(rect.width() <= tile_size && rect.height() <= tile_size);
+
+        pixmap.draw_pixmap(
+            rect.x(),
+            rect.y(),
+            tile,
+            &PixmapPaint { blend_mode: BlendMode::Source, ..PixmapPaint::default() },
+            Transform::identity(),
+            None,
+        );
+        tiles += 1;
+    });
+
+    let per_row = (width + tile_size - 1) / tile_size;
+    let per_column = (height + tile_size - 1) / tile_size;
+    assert_eq!(tiles, per_row * per_column);
+
+    pixmap
+}
+
+#[test]
+fn rects() {
+    let mut paint1 = Paint::default();
+    paint1.set_color_rgba8(50, 127, 150, 200);
+
+    let mut paint2 = Paint::default();
+    paint2.shader = LinearGradient::new(
+        Point::from_xy(20.0, 10.0),
+        Point::from_xy(90.0, 70.0),
+        vec![
+            GradientStop::new(0.0, Color::from_rgba8(50, 127, 150, 200)),
+            GradientStop::new(1.0, Color::from_rgba8(220, 140, 75, 180)),
+        ],
+        SpreadMode::Pad,
+        Transform::identity(),
+    ).unwrap();
+
+    let path1 = PathBuilder::from_rect(Rect::from_ltrb(10.0, 5.0, 90.0, 60.0).unwrap());
+    let path2 = PathBuilder::from_rect(Rect::from_ltrb(5.0, 5.0, 35.0, 40.0).unwrap());
+
+    let commands = [
+        DrawCommand::FillPath {
+            path: path1,
+            paint: paint1,
+            fill_rule: FillRule::Winding,
+            transform: Transform::identity(),
+        },
+        DrawCommand::FillPath {
+            path: path2,
+            paint: paint2,
+            fill_rule: FillRule::Winding,
+            transform: Transform::from_row(2.0, 0.0, 0.0, 2.0, 17.0, 9.0),
+        },
+    ];
+
+    let expected = render_single(&commands, 120, 100);
+    for tile_size in [16, 25, 64, 200] {
+        assert_eq!(render_tiled(&commands, 120, 100, tile_size), expected);
+    }
+}
+
+#[test]
+fn curves() {
+    let circle = PathBuilder::from_circle(53.3, 41.7, 30.2).unwrap();
+
+    let mut pb = PathBuilder::new();
+    pb.move_to(5.0, 90.0);
+    pb.cubic_to(40.0, 10.0, 70.0, 120.0, 115.0, 20.0);
+    let curve = pb.finish().unwrap();
+
+    let mut paint1 = Paint::default();
+    paint1.set_color_rgba8(50, 127, 150, 200);
+
+    let mut paint2 = Paint::default();
+    paint2.set_color_rgba8(20, 30, 200, 220);
+
+    let mut stroke = Stroke::default();
+    stroke.width = 5.5;
+
+    let commands = [
+        DrawCommand::FillPath {
+            path: circle,
+            paint: paint1,
+            fill_rule: FillRule::Winding,
+            transform: Transform::from_row(1.2, 0.1, -0.2, 0.9, 3.3, 5.1),
+        },
+        DrawCommand::StrokePath {
+            path: curve,
+            paint: paint2,
+            stroke,
+            transform: Transform::identity(),
+        },
+    ];
+
+    let expected = render_single(&commands, 120, 100);
+    for tile_size in [16, 25, 64] {
+        let pixmap = render_tiled(&commands, 120, 100, tile_size);
+
+        // Since paths are clipped by each tile, curves are approximated a bit differently,
+        // which affects anti-aliased edges, but not the shape itself.
+        let mut diff_pixels = 0;
+        for (p1, p2) in pixmap.pixels().iter().zip(expected.pixels()) {
+            if p1 != p2 {
+                diff_pixels += 1;
+                assert!((i32::from(p1.alpha()) - i32::from(p2.alpha())).abs() <= 64);
+            }
+        }
+
+        assert!(diff_pixels < 300);
+    }
+}
+
+#[test]
+fn scene() {
+    let mut paint1 = Paint::default();
+    paint1.set_color_rgba8(50, 127, 150, 200);
+
+    let mut paint2 = Paint::default();
+    paint2.set_color_rgba8(220, 140, 75, 180);
+
+    let mut scene = Scene::new();
+    scene.push_clip(
+        PathBuilder::from_rect(Rect::from_ltrb(8.0, 8.0, 100.0, 80.0).unwrap()),
+        FillRule::Winding,
+        false,
+    );
+    scene.push_transform(Transform::from_translate(4.0, 2.0));
+    scene.fill_path(
+        PathBuilder::from_rect(Rect::from_ltrb(0.0, 0.0, 60.0, 50.0).unwrap()),
+        paint1,
+        FillRule::Winding,
+        Transform::identity(),
+    );
+    scene.pop_transform();
+    scene.fill_path(
+        PathBuilder::from_rect(Rect::from_ltrb(40.0, 30.0, 115.0, 95.0).unwrap()),
+        paint2,
+        FillRule::Winding,
+        Transform::identity(),
+    );
+    scene.pop_clip();
+
+    let mut expected = Pixmap::new(120, 100).unwrap();
+    scene.render_to(&mut expected.as_mut());
+    for tile_size in [16, 25, 64, 200] {
+        assert_eq!(render_tiled(scene.commands(), 120, 100, tile_size), expected);
+    }
+}
+
+#[test]
+fn invalid() {
+    assert!(TiledRenderer::new(0, 100, 16).is_none());
+    assert!(TiledRenderer::new(100, 100, 0).is_none());
+    assert!(TiledRenderer::new(u32::MAX, 100, 16).is_none());
+    assert!(TiledRenderer::new(i32::MAX as u32, i32::MAX as u32, 256).is_some());
+}